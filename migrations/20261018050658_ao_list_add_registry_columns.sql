@@ -0,0 +1,7 @@
+-- Add AO meta data columns so ao_list is source of truth for AOs
+ALTER TABLE ao_list
+    ADD COLUMN friendly_name TEXT,
+    ADD COLUMN ao_type       TEXT,
+    ADD COLUMN address       TEXT,
+    ADD COLUMN map_url       TEXT,
+    ADD COLUMN schedule      JSONB;
@@ -0,0 +1,17 @@
+-- Closure calendar for AOs (holidays, convergences, smoke days, construction etc)
+CREATE TABLE ao_closures
+(
+    id         uuid      NOT NULL,
+    PRIMARY KEY (id),
+    -- ao name, null means region-wide closure
+    ao         TEXT,
+    start_date DATE      NOT NULL,
+    end_date   DATE      NOT NULL,
+    -- repeats every year on the same month/day span
+    recurring  BOOLEAN   NOT NULL DEFAULT false,
+    reason     TEXT      NOT NULL,
+    created_by TEXT,
+    created    TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX ao_closures_dates_idx ON ao_closures (start_date, end_date);
@@ -0,0 +1,55 @@
+-- One row per pax per backblast, replacing comma separated q/pax/fngs columns for queries
+CREATE TABLE back_blast_attendance
+(
+    back_blast_id uuid NOT NULL REFERENCES back_blasts (id) ON DELETE CASCADE,
+    -- lowercase f3 name as posted
+    name          TEXT NOT NULL,
+    -- q, coq, pax or fng
+    role          TEXT NOT NULL,
+    -- slack user if name matched a user when saved
+    slack_id      TEXT,
+    PRIMARY KEY (back_blast_id, name)
+);
+
+CREATE INDEX back_blast_attendance_slack_id_idx ON back_blast_attendance (slack_id);
+CREATE INDEX back_blast_attendance_name_idx ON back_blast_attendance (name);
+CREATE INDEX back_blasts_bb_type_date_idx ON back_blasts (bb_type, date);
+
+-- backfill from existing backblasts. First q listed is the q, rest are co-qs.
+WITH attendees AS (
+    SELECT bb.id                                           AS back_blast_id,
+           trim(lower(q.name))                             AS name,
+           CASE WHEN q.ord = 1 THEN 'q' ELSE 'coq' END     AS role,
+           1                                               AS priority
+    FROM back_blasts bb,
+         unnest(string_to_array(bb.q, ',')) WITH ORDINALITY AS q(name, ord)
+    UNION ALL
+    SELECT bb.id,
+           trim(lower(p.name)),
+           CASE
+               WHEN trim(lower(p.name)) = ANY (string_to_array(lower(coalesce(bb.fngs, '')), ','))
+                   THEN 'fng'
+               ELSE 'pax' END,
+           2
+    FROM back_blasts bb,
+         unnest(string_to_array(bb.pax, ',')) AS p(name)
+)
+INSERT
+INTO back_blast_attendance (back_blast_id, name, role, slack_id)
+SELECT DISTINCT ON (a.back_blast_id, a.name) a.back_blast_id, a.name, a.role, u.slack_id
+FROM attendees a
+         LEFT JOIN users u ON lower(u.name) = a.name
+WHERE a.name <> ''
+ORDER BY a.back_blast_id, a.name, a.priority;
+
+-- attendance lists per backblast. Names resolve to current user name when slack id is known.
+CREATE VIEW back_blast_attendance_lists AS
+SELECT a.back_blast_id,
+       array_agg(lower(coalesce(u.name, a.name)) ORDER BY a.role DESC, lower(coalesce(u.name, a.name)))
+       FILTER (WHERE a.role IN ('q', 'coq'))                                     AS q,
+       array_agg(lower(coalesce(u.name, a.name)) ORDER BY lower(coalesce(u.name, a.name))) AS pax,
+       array_agg(lower(coalesce(u.name, a.name)) ORDER BY lower(coalesce(u.name, a.name)))
+       FILTER (WHERE a.role = 'fng')                                             AS fngs
+FROM back_blast_attendance a
+         LEFT JOIN users u ON u.slack_id = a.slack_id
+GROUP BY a.back_blast_id;
@@ -0,0 +1,32 @@
+-- Merges of two pax identities. Changes are kept so merge can be undone.
+CREATE TABLE pax_merges
+(
+    id            uuid      NOT NULL,
+    PRIMARY KEY (id),
+    -- lowercase name merged away
+    from_name     TEXT      NOT NULL,
+    from_slack_id TEXT,
+    to_name       TEXT      NOT NULL,
+    to_slack_id   TEXT      NOT NULL,
+    -- list of column changes applied by merge
+    changes       JSONB     NOT NULL,
+    created_by    TEXT,
+    created       TIMESTAMP NOT NULL DEFAULT NOW(),
+    undone        BOOLEAN   NOT NULL DEFAULT false,
+    -- alias mapping replaced by merge, restored when merge is undone
+    previous_alias_slack_id TEXT,
+    previous_alias_merge_id uuid
+);
+
+-- Other names a slack user has gone by
+CREATE TABLE pax_aliases
+(
+    -- lowercase name
+    alias    TEXT      NOT NULL,
+    PRIMARY KEY (alias),
+    slack_id TEXT      NOT NULL,
+    merge_id uuid REFERENCES pax_merges (id) ON DELETE SET NULL,
+    created  TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX pax_aliases_slack_id_idx ON pax_aliases (slack_id);
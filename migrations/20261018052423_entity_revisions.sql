@@ -0,0 +1,20 @@
+-- Revisions (full snapshots) of backblasts and preblasts, for history and rollback
+CREATE TABLE entity_revisions
+(
+    id          uuid      NOT NULL,
+    PRIMARY KEY (id),
+    -- backblast or preblast
+    entity_type TEXT      NOT NULL,
+    entity_id   uuid      NOT NULL,
+    -- create, edit, deactivate, sync or rollback
+    action      TEXT      NOT NULL,
+    -- modal, sync-via-url, parser, migration or admin
+    source      TEXT      NOT NULL,
+    -- acting slack user, if known
+    slack_user  TEXT,
+    -- full row after the change
+    snapshot    JSONB     NOT NULL,
+    created     TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX entity_revisions_entity_idx ON entity_revisions (entity_type, entity_id, created);
@@ -0,0 +1,6 @@
+-- Soft delete of backblasts. Deleted backblasts are inactive, so they don't count towards stats.
+ALTER TABLE back_blasts
+    ADD COLUMN deleted_at TIMESTAMP,
+    ADD COLUMN deleted_by TEXT;
+
+CREATE INDEX back_blasts_deleted_at_idx ON back_blasts (deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,35 @@
+-- Dictionary of exercises done at workouts
+CREATE TABLE exercises
+(
+    id           uuid      NOT NULL,
+    PRIMARY KEY (id),
+    -- lowercase name, used for lookup
+    name         TEXT      NOT NULL UNIQUE,
+    -- name as first written
+    display_name TEXT      NOT NULL,
+    created      TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- Structured workout content (the thang) of a backblast
+CREATE TABLE back_blast_exercises
+(
+    back_blast_id uuid    NOT NULL REFERENCES back_blasts (id) ON DELETE CASCADE,
+    -- order exercise was done in
+    position      INTEGER NOT NULL,
+    PRIMARY KEY (back_blast_id, position),
+    -- null when exercise isn't in dictionary (yet)
+    exercise_id   uuid REFERENCES exercises (id),
+    -- lowercase name as written, used to link once exercise is added to exicon
+    name          TEXT    NOT NULL,
+    -- name as written
+    display_name  TEXT    NOT NULL,
+    reps          INTEGER,
+    seconds       INTEGER,
+    meters        INTEGER,
+    notes         TEXT,
+    -- whether exercise was extracted from moleskine instead of entered
+    extracted     BOOLEAN NOT NULL DEFAULT false
+);
+
+CREATE INDEX back_blast_exercises_exercise_id_idx ON back_blast_exercises (exercise_id);
+CREATE INDEX back_blast_exercises_name_idx ON back_blast_exercises (name) WHERE exercise_id IS NULL;
@@ -0,0 +1,10 @@
+-- Exicon: descriptions, aliases and tags for exercise dictionary
+ALTER TABLE exercises
+    ADD COLUMN description TEXT,
+    ADD COLUMN category    TEXT,
+    -- lowercase alternative names (ie. pushup for merkin)
+    ADD COLUMN aliases     TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN tags        TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN updated     TIMESTAMP;
+
+CREATE INDEX exercises_aliases_idx ON exercises USING GIN (aliases);
@@ -0,0 +1,21 @@
+-- Black Diamond ratings (grading of the beatdown), linked to the backblast of the day
+CREATE TABLE black_diamond_ratings
+(
+    id            uuid      NOT NULL,
+    PRIMARY KEY (id),
+    back_blast_id uuid      REFERENCES back_blasts (id) ON DELETE SET NULL,
+    -- channel and date of rated beatdown, used to link backblast posted later
+    channel_id    TEXT      NOT NULL,
+    date          DATE      NOT NULL,
+    pax_count     INTEGER   NOT NULL,
+    vests_removed INTEGER   NOT NULL,
+    miles         REAL      NOT NULL,
+    avg_hr        REAL      NOT NULL,
+    total         REAL      NOT NULL,
+    -- slack id of user that rated
+    rated_by      TEXT,
+    created       TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX black_diamond_ratings_date_idx ON black_diamond_ratings (date);
+CREATE INDEX black_diamond_ratings_back_blast_id_idx ON black_diamond_ratings (back_blast_id);
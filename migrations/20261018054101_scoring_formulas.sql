@@ -0,0 +1,37 @@
+-- Scoring formula per AO (input fields and formula over them)
+CREATE TABLE scoring_formulas
+(
+    id               uuid      NOT NULL,
+    PRIMARY KEY (id),
+    channel_id       TEXT      NOT NULL UNIQUE,
+    name             TEXT      NOT NULL,
+    -- list of input fields (key, label, type and options for selects)
+    fields           JSONB     NOT NULL,
+    formula          TEXT      NOT NULL,
+    unit             TEXT,
+    higher_is_better BOOLEAN   NOT NULL DEFAULT TRUE,
+    created          TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated          TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- Scores of beatdowns, linked to the backblast of the day
+CREATE TABLE workout_scores
+(
+    id            uuid             NOT NULL,
+    PRIMARY KEY (id),
+    formula_id    uuid             NOT NULL REFERENCES scoring_formulas (id) ON DELETE CASCADE,
+    back_blast_id uuid             REFERENCES back_blasts (id) ON DELETE SET NULL,
+    channel_id    TEXT             NOT NULL,
+    date          DATE             NOT NULL,
+    -- field key to value entered
+    inputs        JSONB            NOT NULL,
+    -- formula used at time of scoring, so later formula changes don't change history
+    formula       TEXT             NOT NULL,
+    score         DOUBLE PRECISION NOT NULL,
+    -- slack id of user that scored
+    scored_by     TEXT,
+    created       TIMESTAMP        NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX workout_scores_channel_date_idx ON workout_scores (channel_id, date);
+CREATE INDEX workout_scores_back_blast_id_idx ON workout_scores (back_blast_id);
@@ -0,0 +1,4 @@
+-- Ruck distance and weight carried per pax (rucking and heavy AOs)
+ALTER TABLE back_blast_attendance
+    ADD COLUMN ruck_miles  REAL,
+    ADD COLUMN ruck_weight REAL;
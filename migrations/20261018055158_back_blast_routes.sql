@@ -0,0 +1,22 @@
+-- Routes (GPX or FIT files) uploaded with a backblast, parsed to summary and simplified track
+CREATE TABLE back_blast_routes
+(
+    id                uuid             NOT NULL,
+    PRIMARY KEY (id),
+    back_blast_id     uuid             NOT NULL REFERENCES back_blasts (id) ON DELETE CASCADE,
+    -- slack id of uploaded file
+    file_id           TEXT             NOT NULL,
+    file_name         TEXT,
+    file_type         TEXT             NOT NULL,
+    distance_miles    DOUBLE PRECISION NOT NULL,
+    elevation_gain_ft DOUBLE PRECISION NOT NULL,
+    duration_secs     BIGINT,
+    -- simplified track as [lat, lon] pairs
+    track             JSONB            NOT NULL,
+    -- slack user that uploaded
+    uploaded_by       TEXT,
+    created           TIMESTAMP        NOT NULL DEFAULT NOW(),
+    UNIQUE (back_blast_id, file_id)
+);
+
+CREATE INDEX back_blast_routes_back_blast_id_idx ON back_blast_routes (back_blast_id);
@@ -0,0 +1,7 @@
+-- Emojis counted as a hard commit (HC) on preblasts of AO. Default (hc) is used when null
+ALTER TABLE ao_list
+    ADD COLUMN hc_emojis TEXT[];
+
+-- Channel preblast message was posted in (can differ from its AO channel)
+ALTER TABLE pre_blasts
+    ADD COLUMN post_channel_id TEXT;
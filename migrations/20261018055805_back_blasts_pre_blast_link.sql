@@ -0,0 +1,5 @@
+-- Link backblast to the preblast it was written from
+ALTER TABLE back_blasts
+    ADD COLUMN pre_blast_id uuid REFERENCES pre_blasts (id) ON DELETE SET NULL;
+
+CREATE INDEX back_blasts_pre_blast_id_idx ON back_blasts (pre_blast_id);
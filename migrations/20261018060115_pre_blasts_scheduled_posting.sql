@@ -0,0 +1,7 @@
+-- Preblasts scheduled to post later. Pending until posted by scheduler (ts set) or cancelled by Q.
+ALTER TABLE pre_blasts
+    ADD COLUMN post_at      TIMESTAMPTZ,
+    ADD COLUMN scheduled_by TEXT,
+    ADD COLUMN cancelled_at TIMESTAMP;
+
+CREATE INDEX pre_blasts_pending_idx ON pre_blasts (post_at) WHERE ts IS NULL AND cancelled_at IS NULL;
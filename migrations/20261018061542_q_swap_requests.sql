@@ -0,0 +1,25 @@
+-- Q asking for a sub on their Q line up spot. Kept after resolving as history of swaps.
+CREATE TABLE q_swap_requests
+(
+    id                 uuid      NOT NULL,
+    PRIMARY KEY (id),
+    channel_id         TEXT      NOT NULL,
+    date               DATE      NOT NULL,
+    -- slack user that asked for a sub
+    requested_by       TEXT      NOT NULL,
+    -- qs on line up when request was made
+    original_qs        TEXT      NOT NULL,
+    -- slack users the offer was DM'd to
+    offered_to         TEXT[]    NOT NULL,
+    -- pending, accepted or cancelled
+    status             TEXT      NOT NULL DEFAULT 'pending',
+    accepted_by        TEXT,
+    -- q line up message (and row) to update when accepted
+    message_channel_id TEXT,
+    message_ts         TEXT,
+    message_block_id   TEXT,
+    created            TIMESTAMP NOT NULL DEFAULT NOW(),
+    resolved_at        TIMESTAMP
+);
+
+CREATE INDEX q_swap_requests_line_up_idx ON q_swap_requests (channel_id, date);
@@ -0,0 +1,17 @@
+-- Recurring Q reservations of pax at AO, materialized into q_line_up by scheduler
+CREATE TABLE q_recurrences
+(
+    id         uuid      NOT NULL,
+    PRIMARY KEY (id),
+    slack_id   TEXT      NOT NULL,
+    channel_id TEXT      NOT NULL,
+    -- monthly:<nth>:<weekday> (-1 for last) or weekly:<every n weeks>:<weekday>
+    rule       TEXT      NOT NULL,
+    start_date DATE      NOT NULL,
+    -- last day recurrence applies. null for ongoing
+    end_date   DATE,
+    created    TIMESTAMP NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMP
+);
+
+CREATE INDEX q_recurrences_slack_id_idx ON q_recurrences (slack_id) WHERE deleted_at IS NULL;
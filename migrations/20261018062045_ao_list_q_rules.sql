@@ -0,0 +1,3 @@
+-- Q line up sign up rules of AO (double booking, monthly limit, new Q priority). Defaults are used when null
+ALTER TABLE ao_list
+    ADD COLUMN q_rules JSONB;
@@ -0,0 +1,21 @@
+-- Roles of slack users for permissions. Users without roles are regular pax
+CREATE TABLE user_roles
+(
+    id         uuid      NOT NULL,
+    PRIMARY KEY (id),
+    slack_id   TEXT      NOT NULL,
+    -- region_admin, site_q, comz or pax
+    role       TEXT      NOT NULL,
+    -- ao channel for site_q, null otherwise
+    channel_id TEXT,
+    created    TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX user_roles_unique_idx ON user_roles (slack_id, role, coalesce(channel_id, ''));
+
+-- previously hardcoded admins (backslash, stinger, guac, deepdish)
+INSERT INTO user_roles (id, slack_id, role)
+VALUES (gen_random_uuid(), 'U03SR452HL7', 'region_admin'),
+       (gen_random_uuid(), 'U03T87KHRFE', 'region_admin'),
+       (gen_random_uuid(), 'U04SS5FQXQ9', 'region_admin'),
+       (gen_random_uuid(), 'U05CWQ1FSV8', 'region_admin');
@@ -0,0 +1,234 @@
+//! Closure calendar for AOs. Closures can be for a single AO or region-wide,
+//! span one or more days and optionally repeat every year (ie. holidays).
+
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Serialize;
+
+/// single closure entry
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AoClosure {
+    pub id: String,
+    /// ao that is closed. `None` for region-wide closures.
+    pub ao: Option<AO>,
+    pub start_date: NaiveDate,
+    /// last day of closure (inclusive). Same as start date for single day closures.
+    pub end_date: NaiveDate,
+    /// repeats every year on the same month/day span
+    pub recurring: bool,
+    pub reason: String,
+}
+
+impl AoClosure {
+    pub fn is_region_wide(&self) -> bool {
+        self.ao.is_none()
+    }
+
+    /// whether closure applies to the ao (region-wide closures apply to all)
+    pub fn applies_to(&self, ao: &AO) -> bool {
+        match &self.ao {
+            Some(closed_ao) => closed_ao == ao,
+            None => true,
+        }
+    }
+
+    /// whether closure covers the date
+    pub fn covers_date(&self, date: &NaiveDate) -> bool {
+        if !self.recurring {
+            return &self.start_date <= date && date <= &self.end_date;
+        }
+
+        // check span starting last year as well, in case it wraps the new year
+        let span = self.end_date.signed_duration_since(self.start_date);
+        [date.year() - 1, date.year()].iter().any(|year| {
+            let start =
+                NaiveDate::from_ymd_opt(*year, self.start_date.month(), self.start_date.day())
+                    // feb 29th on non leap years
+                    .or_else(|| NaiveDate::from_ymd_opt(*year, 3, 1));
+            match start {
+                Some(start) => &start <= date && date <= &(start + span),
+                None => false,
+            }
+        })
+    }
+
+    /// text to display for closure
+    pub fn display_text(&self, registry: &AoRegistry) -> String {
+        let ao = self
+            .ao
+            .as_ref()
+            .map(|ao| ao.friendly_name(registry))
+            .unwrap_or_else(|| String::from("All AOs"));
+        let dates = if self.start_date == self.end_date {
+            self.start_date.format("%m/%d").to_string()
+        } else {
+            format!(
+                "{} - {}",
+                self.start_date.format("%m/%d"),
+                self.end_date.format("%m/%d")
+            )
+        };
+        let repeats = if self.recurring { " (every year)" } else { "" };
+        format!("{} closed {}{}: {}", ao, dates, repeats, self.reason)
+    }
+}
+
+/// calendar of closures to check AO days against.
+#[derive(Debug, Default, Clone)]
+pub struct ClosureCalendar {
+    closures: Vec<AoClosure>,
+}
+
+impl ClosureCalendar {
+    pub fn new(closures: Vec<AoClosure>) -> Self {
+        ClosureCalendar { closures }
+    }
+
+    pub fn closures(&self) -> &[AoClosure] {
+        &self.closures
+    }
+
+    /// get closure for ao on date if there is one. AO specific closures take priority.
+    pub fn closure_for(&self, ao: &AO, date: &NaiveDate) -> Option<&AoClosure> {
+        let mut matching = self
+            .closures
+            .iter()
+            .filter(|closure| closure.applies_to(ao) && closure.covers_date(date));
+        let first = matching.next()?;
+        if first.is_region_wide() {
+            Some(
+                matching
+                    .find(|closure| !closure.is_region_wide())
+                    .unwrap_or(first),
+            )
+        } else {
+            Some(first)
+        }
+    }
+
+    pub fn is_closed(&self, ao: &AO, date: &NaiveDate) -> bool {
+        self.closure_for(ao, date).is_some()
+    }
+
+    /// whether ao meets on date and isn't closed
+    pub fn is_open(&self, ao: &AO, date: &NaiveDate, registry: &AoRegistry) -> bool {
+        ao.week_days(registry).contains(&date.weekday()) && !self.is_closed(ao, date)
+    }
+
+    /// number of days ao is open between dates (inclusive)
+    pub fn open_days_between(
+        &self,
+        ao: &AO,
+        start: &NaiveDate,
+        end: &NaiveDate,
+        registry: &AoRegistry,
+    ) -> usize {
+        let mut count = 0;
+        let mut date = *start;
+        while &date <= end {
+            if self.is_open(ao, &date, registry) {
+                count += 1;
+            }
+            date += Duration::days(1);
+        }
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn closure(
+        ao: Option<AO>,
+        start: (i32, u32, u32),
+        end: (i32, u32, u32),
+        recurring: bool,
+    ) -> AoClosure {
+        AoClosure {
+            id: String::from("1"),
+            ao,
+            start_date: NaiveDate::from_ymd_opt(start.0, start.1, start.2).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(end.0, end.1, end.2).unwrap(),
+            recurring,
+            reason: String::from("Holiday"),
+        }
+    }
+
+    #[test]
+    fn single_ao_date_range() {
+        let calendar = ClosureCalendar::new(vec![closure(
+            Some(AO::Bleach),
+            (2024, 8, 5),
+            (2024, 8, 9),
+            false,
+        )]);
+        let date = NaiveDate::from_ymd_opt(2024, 8, 7).unwrap();
+        assert!(calendar.is_closed(&AO::Bleach, &date));
+        assert!(!calendar.is_closed(&AO::Gem, &date));
+        assert!(!calendar.is_closed(&AO::Bleach, &NaiveDate::from_ymd_opt(2024, 8, 10).unwrap()));
+    }
+
+    #[test]
+    fn region_wide_closure() {
+        let calendar = ClosureCalendar::new(vec![closure(None, (2024, 9, 7), (2024, 9, 7), false)]);
+        let date = NaiveDate::from_ymd_opt(2024, 9, 7).unwrap();
+        assert!(calendar.is_closed(&AO::Bleach, &date));
+        assert!(calendar.is_closed(&AO::Gem, &date));
+    }
+
+    #[test]
+    fn recurring_holiday() {
+        let calendar = ClosureCalendar::new(vec![
+            closure(None, (2023, 12, 25), (2023, 12, 25), true),
+            closure(None, (2023, 12, 31), (2024, 1, 1), true),
+        ]);
+        assert!(calendar.is_closed(&AO::Bleach, &NaiveDate::from_ymd_opt(2026, 12, 25).unwrap()));
+        assert!(calendar.is_closed(&AO::Bleach, &NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()));
+        assert!(calendar.is_closed(&AO::Bleach, &NaiveDate::from_ymd_opt(2026, 12, 31).unwrap()));
+        assert!(!calendar.is_closed(&AO::Bleach, &NaiveDate::from_ymd_opt(2026, 1, 2).unwrap()));
+    }
+
+    #[test]
+    fn ao_specific_closure_takes_priority() {
+        let mut specific = closure(Some(AO::Bleach), (2024, 7, 4), (2024, 7, 4), false);
+        specific.reason = String::from("Park construction");
+        let calendar = ClosureCalendar::new(vec![
+            closure(None, (2024, 7, 4), (2024, 7, 4), false),
+            specific,
+        ]);
+        let date = NaiveDate::from_ymd_opt(2024, 7, 4).unwrap();
+        assert_eq!(
+            calendar.closure_for(&AO::Bleach, &date).unwrap().reason,
+            "Park construction"
+        );
+        assert_eq!(
+            calendar.closure_for(&AO::Gem, &date).unwrap().reason,
+            "Holiday"
+        );
+    }
+
+    #[test]
+    fn open_days_skip_closures() {
+        // bleach meets mon, wed, sat
+        let start = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 7, 7).unwrap();
+        let registry = AoRegistry::default();
+        let calendar = ClosureCalendar::default();
+        assert_eq!(
+            calendar.open_days_between(&AO::Bleach, &start, &end, &registry),
+            3
+        );
+        let calendar = ClosureCalendar::new(vec![closure(
+            Some(AO::Bleach),
+            (2024, 7, 3),
+            (2024, 7, 3),
+            false,
+        )]);
+        assert_eq!(
+            calendar.open_days_between(&AO::Bleach, &start, &end, &registry),
+            2
+        );
+    }
+}
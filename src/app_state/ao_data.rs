@@ -1,9 +1,12 @@
+use crate::app_state::ao_registry::{AoRegistry, AoRegistryEntry, AoScheduleDay};
 use crate::app_state::equipment::AoEquipment;
+use crate::shared::common_errors::AppError;
 use crate::slack_api::channels::public_channels::PublicChannels;
 use chrono::{Duration, NaiveTime, Weekday};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fmt::Display;
+use std::str::FromStr;
 
 /// Different AO options.
 #[derive(PartialEq, Debug, Deserialize, Serialize, Eq, Hash)]
@@ -32,13 +35,18 @@ pub enum AO {
     Liberty,
     FirstF,
     DR,
+    /// AO launched through the ao_list registry without a dedicated variant
+    Custom {
+        name: String,
+        channel_id: String,
+    },
     Unknown(String),
 }
 
 /// days of the week the ao is open
 pub type AoDays = HashSet<Weekday>;
 
-#[derive(Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
 pub enum AoType {
     Bootcamp,
     Heavy,
@@ -61,7 +69,40 @@ impl Display for AoType {
     }
 }
 
+impl FromStr for AoType {
+    type Err = AppError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.trim().to_lowercase().as_str() {
+            "bootcamp" => Ok(AoType::Bootcamp),
+            "heavy" => Ok(AoType::Heavy),
+            "highintensity" | "high_intensity" => Ok(AoType::HighIntensity),
+            "running" => Ok(AoType::Running),
+            "rucking" => Ok(AoType::Rucking),
+            "wildcard" | "wild_card" => Ok(AoType::WildCard),
+            other => Err(AppError::General(format!("Unknown ao type: {}", other))),
+        }
+    }
+}
+
 impl AoType {
+    /// name of type as stored in db (ao_list.ao_type)
+    pub fn db_name(&self) -> &str {
+        match self {
+            AoType::Bootcamp => "bootcamp",
+            AoType::Heavy => "heavy",
+            AoType::HighIntensity => "high_intensity",
+            AoType::Running => "running",
+            AoType::Rucking => "rucking",
+            AoType::WildCard => "wild_card",
+        }
+    }
+
+    /// whether pax distance and ruck weight are tracked on backblasts
+    pub fn tracks_ruck(&self) -> bool {
+        matches!(self, AoType::Rucking | AoType::Heavy)
+    }
+
     pub fn equipment(&self) -> HashSet<AoEquipment> {
         match self {
             AoType::Bootcamp => HashSet::from([AoEquipment::Coupons]),
@@ -103,7 +144,75 @@ mod ao_times {
 }
 
 impl AO {
-    pub fn week_days(&self) -> AoDays {
+    pub fn week_days(&self, registry: &AoRegistry) -> AoDays {
+        match registry.get(self) {
+            Some(entry) => entry.week_days(),
+            None => self.default_week_days(),
+        }
+    }
+
+    pub fn friendly_name(&self, registry: &AoRegistry) -> String {
+        match registry.get(self) {
+            Some(entry) => entry.friendly_name.to_string(),
+            None => self.default_friendly_name().to_string(),
+        }
+    }
+
+    pub fn workout_length(&self, week_day: &Weekday, registry: &AoRegistry) -> Option<i64> {
+        match registry.get(self) {
+            Some(entry) => entry.schedule_for(week_day).map(|day| day.minutes),
+            None => self.default_workout_length(week_day),
+        }
+    }
+
+    pub fn start_end_times(
+        &self,
+        week_day: &Weekday,
+        registry: &AoRegistry,
+    ) -> Option<(NaiveTime, NaiveTime)> {
+        match (
+            self.default_time(week_day, registry),
+            self.workout_length(week_day, registry),
+        ) {
+            (Some(start), Some(minutes)) => {
+                let end_time = start + Duration::minutes(minutes);
+                Some((start, end_time))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn default_time(&self, week_day: &Weekday, registry: &AoRegistry) -> Option<NaiveTime> {
+        match registry.get(self) {
+            Some(entry) => entry.schedule_for(week_day).map(|day| day.start_time),
+            None => self.default_start_time(week_day),
+        }
+    }
+
+    pub fn ao_type(&self, registry: &AoRegistry) -> AoType {
+        match registry.get(self) {
+            Some(entry) => entry.ao_type.clone(),
+            None => self.default_ao_type(),
+        }
+    }
+
+    pub fn address(&self, registry: &AoRegistry) -> Option<String> {
+        match registry.get(self) {
+            Some(entry) => entry.address.clone(),
+            None => self.default_address().map(|address| address.to_string()),
+        }
+    }
+
+    // optionally return google maps link
+    pub fn real_map_url(&self, registry: &AoRegistry) -> Option<String> {
+        match registry.get(self) {
+            Some(entry) => entry.map_url.clone(),
+            None => self.default_map_url().map(|url| url.to_string()),
+        }
+    }
+
+    /// hardcoded schedule used to seed ao_list and as fallback when registry isn't loaded.
+    fn default_week_days(&self) -> AoDays {
         match self {
             AO::Bleach => HashSet::from([Weekday::Mon, Weekday::Wed, Weekday::Sat]),
             AO::Gem => HashSet::from([Weekday::Tue, Weekday::Thu, Weekday::Sat]),
@@ -128,11 +237,11 @@ impl AO {
             AO::MallardPark => HashSet::from([Weekday::Wed]),
             AO::BlackCanyon => HashSet::from([Weekday::Tue, Weekday::Thu, Weekday::Sat]),
             AO::Liberty => HashSet::from([Weekday::Tue, Weekday::Thu]),
-            AO::DR | AO::Unknown(_) => HashSet::new(),
+            AO::DR | AO::Custom { .. } | AO::Unknown(_) => HashSet::new(),
         }
     }
 
-    pub fn friendly_name(&self) -> &str {
+    fn default_friendly_name(&self) -> &str {
         match self {
             AO::Bleach => "Bleach",
             AO::Gem => "Gem",
@@ -158,11 +267,11 @@ impl AO {
             AO::BlackCanyon => "Black Canyon",
             AO::Liberty => "Liberty",
             AO::DR => "DR",
-            AO::Unknown(_) => "UNKNOWN",
+            AO::Custom { .. } | AO::Unknown(_) => "UNKNOWN",
         }
     }
 
-    pub fn workout_length(&self, week_day: &Weekday) -> Option<i64> {
+    fn default_workout_length(&self, week_day: &Weekday) -> Option<i64> {
         match week_day {
             Weekday::Mon => match self {
                 AO::BlackDiamond => Some(60),
@@ -185,17 +294,7 @@ impl AO {
         }
     }
 
-    pub fn start_end_times(&self, week_day: &Weekday) -> Option<(NaiveTime, NaiveTime)> {
-        match (self.default_time(week_day), self.workout_length(week_day)) {
-            (Some(start), Some(minutes)) => {
-                let end_time = start + Duration::minutes(minutes);
-                Some((start, end_time))
-            }
-            _ => None,
-        }
-    }
-
-    pub fn default_time(&self, week_day: &Weekday) -> Option<NaiveTime> {
+    fn default_start_time(&self, week_day: &Weekday) -> Option<NaiveTime> {
         let five = ao_times::five();
         let five_fifteen = ao_times::five_fifteen();
         let six = ao_times::six();
@@ -206,40 +305,40 @@ impl AO {
             Weekday::Mon => match self {
                 AO::OldGlory => Some(six),
                 AO::BlackDiamond => Some(five),
-                ao if ao.week_days().contains(week_day) => Some(five_fifteen),
+                ao if ao.default_week_days().contains(week_day) => Some(five_fifteen),
                 _ => None,
             },
             Weekday::Tue => match self {
                 AO::BlackCanyon => Some(five_thirty),
-                ao if ao.week_days().contains(week_day) => Some(five_fifteen),
+                ao if ao.default_week_days().contains(week_day) => Some(five_fifteen),
                 _ => None,
             },
             Weekday::Wed => match self {
                 AO::OldGlory => Some(six),
                 AO::BlackDiamond => Some(five),
-                ao if ao.week_days().contains(week_day) => Some(five_fifteen),
+                ao if ao.default_week_days().contains(week_day) => Some(five_fifteen),
                 _ => None,
             },
             Weekday::Thu => match self {
                 AO::BlackCanyon => Some(five_thirty),
-                ao if ao.week_days().contains(week_day) => Some(five_fifteen),
+                ao if ao.default_week_days().contains(week_day) => Some(five_fifteen),
                 _ => None,
             },
             Weekday::Fri => match self {
                 AO::RuckershipWest => Some(five_fifteen),
                 AO::OldGlory => Some(five_forty_five),
-                ao if ao.week_days().contains(week_day) => Some(five_fifteen),
+                ao if ao.default_week_days().contains(week_day) => Some(five_fifteen),
                 _ => None,
             },
             Weekday::Sat => match self {
-                ao if ao.week_days().contains(week_day) => Some(six),
+                ao if ao.default_week_days().contains(week_day) => Some(six),
                 _ => None,
             },
             Weekday::Sun => None,
         }
     }
 
-    pub fn ao_type(&self) -> AoType {
+    fn default_ao_type(&self) -> AoType {
         match self {
             AO::Bleach => AoType::Heavy,
             AO::BlackDiamond => AoType::HighIntensity,
@@ -264,7 +363,7 @@ impl AO {
             AO::BlackCanyon => AoType::Bootcamp,
             AO::Liberty => AoType::WildCard,
             AO::DR => AoType::Bootcamp,
-            AO::Unknown(_) => AoType::Bootcamp,
+            AO::Custom { .. } | AO::Unknown(_) => AoType::Bootcamp,
         }
     }
 
@@ -273,7 +372,16 @@ impl AO {
         matches!(self, AO::Unknown(_) | AO::DR)
     }
 
-    pub fn channel_id(&self) -> &str {
+    /// slack channel id of AO, from registry when loaded
+    pub fn channel_id<'a>(&'a self, registry: &'a AoRegistry) -> &'a str {
+        match registry.get(self) {
+            Some(entry) => entry.channel_id.as_str(),
+            None => self.default_channel_id(),
+        }
+    }
+
+    /// hardcoded channel id used to seed ao_list and as fallback when registry isn't loaded.
+    pub(crate) fn default_channel_id(&self) -> &str {
         match self {
             AO::Bleach => const_names::BLEACH_CHANNEL_ID,
             AO::Gem => const_names::GEM_CHANNEL_ID,
@@ -299,11 +407,12 @@ impl AO {
             AO::BlackCanyon => const_names::BLACK_CANYON_CHANNEL_ID,
             AO::Liberty => const_names::LIBERTY_CHANNEL_ID,
             AO::DR => const_names::DR_CHANNEL_ID,
+            AO::Custom { channel_id, .. } => channel_id.as_str(),
             AO::Unknown(_) => "UNKNOWN",
         }
     }
 
-    pub fn address(&self) -> Option<&str> {
+    fn default_address(&self) -> Option<&str> {
         match self {
             AO::Backyard => Some("2400 S Stoddard Rd, Meridian, ID 83642"),
             AO::BlackDiamond => Some("Kleiner Park Loop, Meridian, ID 83642"),
@@ -329,12 +438,12 @@ impl AO {
             | AO::BlackOps
             | AO::FirstF
             | AO::DR
+            | AO::Custom { .. }
             | AO::Unknown(_) => None,
         }
     }
 
-    // optionally return google maps link
-    pub fn real_map_url(&self) -> Option<&str> {
+    fn default_map_url(&self) -> Option<&str> {
         match self {
             AO::Bleach => Some(const_names::BLEACH_GOOGLE_MAPS),
             AO::Gem => Some(const_names::GEM_GOOGLE_MAPS),
@@ -357,21 +466,29 @@ impl AO {
             AO::Liberty => Some(const_names::LIBERTY_GOOGLE_MAPS),
             AO::RuckershipWest | AO::RuckershipEast => None,
             AO::DR | AO::BlackOps | AO::FirstF => None,
-            AO::Unknown(_) => None,
+            AO::Custom { .. } | AO::Unknown(_) => None,
         }
     }
 
     /// get google maps link for ao (returns generic text if not available)
-    pub fn google_maps_link(&self) -> &str {
-        self.real_map_url().unwrap_or(match self {
-            AO::RuckershipWest | AO::RuckershipEast => "Location Varies",
-            AO::DR | AO::BlackOps | AO::FirstF => "Location Varies",
-            AO::Unknown(_) => "Unknown",
-            _ => "",
+    pub fn google_maps_link(&self, registry: &AoRegistry) -> String {
+        self.real_map_url(registry).unwrap_or_else(|| {
+            match self {
+                AO::RuckershipWest | AO::RuckershipEast => "Location Varies",
+                AO::DR | AO::BlackOps | AO::FirstF | AO::Custom { .. } => "Location Varies",
+                AO::Unknown(_) => "Unknown",
+                _ => "",
+            }
+            .to_string()
         })
     }
 
-    pub fn from_channel_id(channel_id: &str) -> Self {
+    /// AO for slack channel id, from registry when loaded
+    pub fn from_channel_id(channel_id: &str, registry: &AoRegistry) -> Self {
+        if let Some(entry) = registry.get_by_channel_id(channel_id) {
+            return AO::from_registry_entry(entry);
+        }
+
         match channel_id {
             const_names::BLEACH_CHANNEL_ID => AO::Bleach,
             const_names::GEM_CHANNEL_ID => AO::Gem,
@@ -400,6 +517,47 @@ impl AO {
             _ => AO::Unknown("UNKNOWN".to_string()),
         }
     }
+
+    /// AO of registry entry, AOs without a dedicated variant being custom
+    fn from_registry_entry(entry: &AoRegistryEntry) -> Self {
+        match AO::from(entry.name.to_string()) {
+            AO::Unknown(_) => AO::Custom {
+                name: entry.name.to_string(),
+                channel_id: entry.channel_id.to_string(),
+            },
+            ao => ao,
+        }
+    }
+
+    /// AO as saved with a record (name and channel id). AOs only in registry were saved by name,
+    /// so they resolve without the registry.
+    pub fn from_saved(name: &str, channel_id: &str) -> Self {
+        match AO::from_channel_id(channel_id, &AoRegistry::default()) {
+            AO::Unknown(_) if !name.trim().is_empty() => match AO::from(name.to_string()) {
+                AO::Unknown(_) => AO::Custom {
+                    name: name.trim().to_string(),
+                    channel_id: channel_id.to_string(),
+                },
+                ao => ao,
+            },
+            ao => ao,
+        }
+    }
+
+    /// AO from name (same as `AO::from`), including AOs only in registry
+    pub fn from_name(name: &str, registry: &AoRegistry) -> Self {
+        match AO::from(name.to_string()) {
+            AO::Unknown(unknown) => {
+                let cleaned = unknown.trim_start_matches('#');
+                let cleaned = cleaned.strip_prefix("ao-").unwrap_or(cleaned);
+                match registry.get_by_name(cleaned) {
+                    Some(entry) => AO::from_registry_entry(entry),
+                    None => AO::Unknown(unknown),
+                }
+            }
+            ao => ao,
+        }
+    }
 }
 
 impl Clone for AO {
@@ -429,6 +587,10 @@ impl Clone for AO {
             AO::MallardPark => AO::MallardPark,
             AO::BlackCanyon => AO::BlackCanyon,
             AO::Liberty => AO::Liberty,
+            AO::Custom { name, channel_id } => AO::Custom {
+                name: name.to_string(),
+                channel_id: channel_id.to_string(),
+            },
             AO::Unknown(name) => AO::Unknown(name.to_string()),
         }
     }
@@ -436,6 +598,10 @@ impl Clone for AO {
 
 impl Display for AO {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let AO::Custom { name, .. } = self {
+            return write!(f, "{}", name);
+        }
+
         let name = match self {
             AO::Bleach => const_names::BLEACH,
             AO::Gem => const_names::GEM,
@@ -461,7 +627,7 @@ impl Display for AO {
             AO::BlackCanyon => const_names::BLACK_CANYON,
             AO::Liberty => const_names::LIBERTY,
             AO::DR => "",
-            AO::Unknown(_) => "",
+            AO::Custom { .. } | AO::Unknown(_) => "",
         };
         write!(f, "{}", name)
     }
@@ -558,28 +724,33 @@ fn channel_to_ao_mapper(channel: &PublicChannels) -> AO {
     }
 }
 
-/// represents ao for db
-#[derive(Debug, Deserialize, Serialize)]
-pub struct AoData {
-    /// name of AO
-    pub name: String,
-    /// should be comma separated list of days of week this AO meets
-    pub days: String,
-    /// whether it's an official AO or otb (Off the books)
-    pub active: bool,
-}
-
-impl AoData {
-    pub fn from(ao: &AO) -> Self {
-        let days = ao.week_days();
-        let serialized = days
+/// seed registry entry for ao based on hardcoded defaults (used when syncing ao_list).
+impl From<&AO> for AoRegistryEntry {
+    fn from(ao: &AO) -> Self {
+        let mut days = ao.default_week_days().into_iter().collect::<Vec<Weekday>>();
+        days.sort_by_key(|day| day.num_days_from_monday());
+        let schedule = days
             .into_iter()
-            .map(|day| day.to_string())
-            .collect::<Vec<String>>()
-            .join(",");
-        AoData {
+            .filter_map(|day| {
+                match (ao.default_start_time(&day), ao.default_workout_length(&day)) {
+                    (Some(start_time), Some(minutes)) => Some(AoScheduleDay {
+                        day,
+                        start_time,
+                        minutes,
+                    }),
+                    _ => None,
+                }
+            })
+            .collect();
+
+        AoRegistryEntry {
             name: ao.to_string(),
-            days: serialized,
+            friendly_name: ao.default_friendly_name().to_string(),
+            channel_id: ao.default_channel_id().to_string(),
+            ao_type: ao.default_ao_type(),
+            address: ao.default_address().map(|address| address.to_string()),
+            map_url: ao.default_map_url().map(|url| url.to_string()),
+            schedule,
             active: !ao.is_otb(),
         }
     }
@@ -724,7 +895,9 @@ mod tests {
     #[test]
     fn ruckership_start_time() {
         let ao = AO::RuckershipWest;
-        let start_time = ao.default_time(&Weekday::Fri).unwrap();
+        let start_time = ao
+            .default_time(&Weekday::Fri, &AoRegistry::default())
+            .unwrap();
         assert_eq!(start_time, NaiveTime::from_hms_opt(5, 15, 0).unwrap());
     }
 
@@ -0,0 +1,210 @@
+//! Registry of AO meta data loaded from the `ao_list` table.
+//! Loaded into app state at startup (and refreshed after admin changes) and passed to `AO` methods
+//! so they resolve schedule, start times, type and location from the db instead of hardcoded values.
+
+use crate::app_state::ao_data::const_names::AO_LIST;
+use crate::app_state::ao_data::{AoDays, AoType, AO};
+use crate::shared::common_errors::AppError;
+use chrono::{NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// single day an AO meets.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AoScheduleDay {
+    /// day of week (Mon, Tue, etc)
+    pub day: Weekday,
+    /// start time of workout
+    pub start_time: NaiveTime,
+    /// length of workout in minutes
+    pub minutes: i64,
+}
+
+/// ao as stored in registry (ao_list).
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AoRegistryEntry {
+    /// unique slug name of ao (ie. `bleach`)
+    pub name: String,
+    /// display name of ao
+    pub friendly_name: String,
+    /// slack channel id for ao
+    pub channel_id: String,
+    pub ao_type: AoType,
+    pub address: Option<String>,
+    pub map_url: Option<String>,
+    /// days and times this ao meets
+    pub schedule: Vec<AoScheduleDay>,
+    /// whether ao is currently running (retired AOs are inactive)
+    pub active: bool,
+}
+
+impl AoRegistryEntry {
+    pub fn week_days(&self) -> AoDays {
+        self.schedule.iter().map(|item| item.day).collect()
+    }
+
+    pub fn schedule_for(&self, week_day: &Weekday) -> Option<&AoScheduleDay> {
+        self.schedule.iter().find(|item| &item.day == week_day)
+    }
+
+    /// comma separated list of days (ao_list.days)
+    pub fn days_db_value(&self) -> String {
+        self.schedule
+            .iter()
+            .map(|item| item.day.to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    /// schedule serialized for ao_list.schedule
+    pub fn schedule_db_value(&self) -> Result<serde_json::Value, AppError> {
+        let value = serde_json::to_value(&self.schedule)?;
+        Ok(value)
+    }
+
+    /// validate entry before saving to db
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.name.trim().is_empty() || self.name.contains(char::is_whitespace) {
+            return Err(AppError::from("Name must be a slug without spaces"));
+        }
+
+        if self.channel_id.trim().is_empty() {
+            return Err(AppError::from("Missing channel id"));
+        }
+
+        let mut days = HashSet::<Weekday>::new();
+        for item in self.schedule.iter() {
+            if !days.insert(item.day) {
+                return Err(AppError::General(format!(
+                    "{} listed more than once",
+                    item.day
+                )));
+            }
+
+            if item.minutes <= 0 {
+                return Err(AppError::General(format!(
+                    "Workout length for {} must be positive",
+                    item.day
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// all registry entries. key is slack channel id
+#[derive(Debug, Default, Clone)]
+pub struct AoRegistry {
+    entries: HashMap<String, AoRegistryEntry>,
+}
+
+impl AoRegistry {
+    pub fn new(entries: Vec<AoRegistryEntry>) -> Self {
+        let entries = entries
+            .into_iter()
+            .map(|entry| (entry.channel_id.to_string(), entry))
+            .collect();
+        AoRegistry { entries }
+    }
+
+    pub fn get_by_channel_id(&self, channel_id: &str) -> Option<&AoRegistryEntry> {
+        self.entries.get(channel_id)
+    }
+
+    pub fn get_by_name(&self, name: &str) -> Option<&AoRegistryEntry> {
+        self.entries
+            .values()
+            .find(|entry| entry.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// entry for AO (by its name)
+    pub fn get(&self, ao: &AO) -> Option<&AoRegistryEntry> {
+        match ao {
+            AO::Unknown(_) => None,
+            ao => self.get_by_name(ao.to_string().as_str()),
+        }
+    }
+
+    /// full list of active aos (falls back to hardcoded list if registry not loaded yet)
+    pub fn active_aos(&self) -> Vec<AO> {
+        if self.is_empty() {
+            return AO_LIST.to_vec();
+        }
+        let mut entries = self
+            .entries
+            .values()
+            .filter(|entry| entry.active)
+            .collect::<Vec<&AoRegistryEntry>>();
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        entries
+            .into_iter()
+            .map(|entry| AO::from_channel_id(entry.channel_id.as_str(), self))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, channel_id: &str, active: bool) -> AoRegistryEntry {
+        AoRegistryEntry {
+            name: name.to_string(),
+            friendly_name: name.to_string(),
+            channel_id: channel_id.to_string(),
+            ao_type: AoType::Bootcamp,
+            address: None,
+            map_url: None,
+            schedule: vec![AoScheduleDay {
+                day: Weekday::Tue,
+                start_time: NaiveTime::from_hms_opt(5, 15, 0).unwrap(),
+                minutes: 45,
+            }],
+            active,
+        }
+    }
+
+    #[test]
+    fn seed_entry_from_defaults() {
+        let entry = AoRegistryEntry::from(&AO::BlackDiamond);
+        assert_eq!(entry.name, "black-diamond");
+        assert_eq!(entry.days_db_value(), "Wed");
+        let wed = entry.schedule_for(&Weekday::Wed).unwrap();
+        assert_eq!(wed.start_time, NaiveTime::from_hms_opt(5, 0, 0).unwrap());
+        assert_eq!(wed.minutes, 60);
+    }
+
+    #[test]
+    fn lookup_by_name_and_channel() {
+        let registry = AoRegistry::new(vec![
+            entry("new-ao", "C123", true),
+            entry("old-ao", "C456", false),
+        ]);
+        assert_eq!(registry.get_by_name("NEW-AO").unwrap().channel_id, "C123");
+        assert_eq!(registry.get_by_channel_id("C456").unwrap().name, "old-ao");
+        assert!(registry.get_by_name("missing").is_none());
+    }
+
+    #[test]
+    fn invalid_entries() {
+        let mut item = entry("new ao", "C123", true);
+        assert!(item.validate().is_err());
+        item.name = String::from("new-ao");
+        assert!(item.validate().is_ok());
+        item.schedule.push(item.schedule[0].clone());
+        assert!(item.validate().is_err());
+    }
+
+    #[test]
+    fn schedule_serializes_for_db() {
+        let item = entry("new-ao", "C123", true);
+        let value = item.schedule_db_value().unwrap();
+        let parsed: Vec<AoScheduleDay> = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed, item.schedule);
+    }
+}
@@ -1,4 +1,6 @@
 use super::ao_data::AO;
+use super::exercises::ExerciseEntry;
+use super::ruck::RuckEntry;
 use crate::db::db_back_blast::DbBackBlast;
 use crate::db::queries::all_back_blasts::{BackBlastFullJsonData, BackBlastJsonData};
 use crate::db::save_back_blast::BackBlastDbEntry;
@@ -6,14 +8,14 @@ use crate::shared::string_utils::{string_split_hash, string_vec_to_hash};
 use crate::web_api_routes::slack_events::event_times::EventTimes;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 
 pub const BACK_BLAST_TAG: &str = "#backblast";
 pub const SLACK_BLAST_TAG: &str = "*slackblast*:";
 
 /// General data of a backblast
-#[derive(Debug, PartialEq, Serialize, Deserialize, Eq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct BackBlastData {
     /// possible id of backblast if saved in db
     pub id: Option<String>,
@@ -32,6 +34,12 @@ pub struct BackBlastData {
     pub moleskine: Option<String>,
     /// explicit list of fngs
     pub fngs: HashSet<String>,
+    /// structured workout content (the thang)
+    #[serde(default)]
+    pub exercises: Vec<ExerciseEntry>,
+    /// ruck per pax (lowercase name), for rucking AOs
+    #[serde(default)]
+    pub ruck: HashMap<String, RuckEntry>,
 }
 
 impl BackBlastData {
@@ -167,6 +175,8 @@ impl From<&BackBlastDbEntry> for BackBlastData {
             bb_type: BackBlastType::from(db_entry.bb_type.as_str()),
             event_times: None,
             fngs,
+            exercises: Vec::new(),
+            ruck: HashMap::new(),
             title: db_entry.title.clone(),
             moleskine: db_entry.moleskine.clone(),
         }
@@ -191,7 +201,7 @@ impl From<&BackBlastFullJsonData> for BackBlastData {
         let fngs = string_vec_to_hash(&data.fngs.clone().unwrap_or_default());
         BackBlastData {
             id: Some(data.id.to_string()),
-            ao: AO::from_channel_id(data.channel_id.as_str()),
+            ao: AO::from_saved(&data.ao, &data.channel_id),
             qs,
             pax,
             date: data.date,
@@ -200,6 +210,8 @@ impl From<&BackBlastFullJsonData> for BackBlastData {
             title: data.title.clone(),
             moleskine: data.moleskine.clone(),
             fngs,
+            exercises: Vec::new(),
+            ruck: HashMap::new(),
         }
     }
 }
@@ -210,7 +222,7 @@ impl From<&BackBlastJsonData> for BackBlastData {
         let pax = string_vec_to_hash(&data.pax);
         BackBlastData {
             id: Some(data.id.to_string()),
-            ao: AO::from_channel_id(data.channel_id.as_str()),
+            ao: AO::from_saved(&data.ao, &data.channel_id),
             qs,
             pax,
             date: data.date,
@@ -219,6 +231,8 @@ impl From<&BackBlastJsonData> for BackBlastData {
             title: data.title.clone(),
             moleskine: None,
             fngs: HashSet::new(),
+            exercises: Vec::new(),
+            ruck: HashMap::new(),
         }
     }
 }
@@ -236,6 +250,8 @@ impl Default for BackBlastData {
             title: None,
             moleskine: None,
             fngs: HashSet::new(),
+            exercises: Vec::new(),
+            ruck: HashMap::new(),
         }
     }
 }
@@ -0,0 +1,355 @@
+//! Structured workout content ("the Thang") of a backblast.
+//! Exercises are written one per line, with amount before or after the name
+//! (ie. `20 Merkins (IC)`, `Plank 1 min` or `Run x 400m`). Notes go in parenthesis.
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// how much of an exercise was done
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExerciseAmount {
+    Reps(u32),
+    Seconds(u32),
+    Meters(u32),
+}
+
+impl Display for ExerciseAmount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExerciseAmount::Reps(reps) => write!(f, "{}", reps),
+            ExerciseAmount::Seconds(seconds) if seconds % 60 == 0 => {
+                write!(f, "{} min", seconds / 60)
+            }
+            ExerciseAmount::Seconds(seconds) if *seconds > 60 => {
+                write!(f, "{}:{:02}", seconds / 60, seconds % 60)
+            }
+            ExerciseAmount::Seconds(seconds) => write!(f, "{} sec", seconds),
+            ExerciseAmount::Meters(meters) => write!(f, "{}m", meters),
+        }
+    }
+}
+
+/// single exercise of the thang
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExerciseEntry {
+    /// name as written (dictionary lookup uses `exercise_key`)
+    pub name: String,
+    pub amount: Option<ExerciseAmount>,
+    pub notes: Option<String>,
+}
+
+impl ExerciseEntry {
+    pub fn new(name: &str, amount: Option<ExerciseAmount>) -> Self {
+        ExerciseEntry {
+            name: name.to_string(),
+            amount,
+            notes: None,
+        }
+    }
+
+    pub fn reps(&self) -> Option<i32> {
+        match self.amount {
+            Some(ExerciseAmount::Reps(reps)) => i32::try_from(reps).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn seconds(&self) -> Option<i32> {
+        match self.amount {
+            Some(ExerciseAmount::Seconds(seconds)) => i32::try_from(seconds).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn meters(&self) -> Option<i32> {
+        match self.amount {
+            Some(ExerciseAmount::Meters(meters)) => i32::try_from(meters).ok(),
+            _ => None,
+        }
+    }
+
+    /// amount from db columns
+    pub fn amount_from_columns(
+        reps: Option<i32>,
+        seconds: Option<i32>,
+        meters: Option<i32>,
+    ) -> Option<ExerciseAmount> {
+        let to_u32 = |value: i32| u32::try_from(value).ok();
+        reps.and_then(to_u32)
+            .map(ExerciseAmount::Reps)
+            .or_else(|| seconds.and_then(to_u32).map(ExerciseAmount::Seconds))
+            .or_else(|| meters.and_then(to_u32).map(ExerciseAmount::Meters))
+    }
+}
+
+impl Display for ExerciseEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.amount {
+            Some(amount @ ExerciseAmount::Reps(_)) => write!(f, "{} {}", amount, self.name)?,
+            Some(amount) => write!(f, "{} {}", self.name, amount)?,
+            None => write!(f, "{}", self.name)?,
+        }
+        if let Some(notes) = &self.notes {
+            write!(f, " ({})", notes)?;
+        }
+        Ok(())
+    }
+}
+
+/// dictionary key for exercise name (lowercase, single spaces)
+pub fn exercise_key(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// exercises as text, one per line (for modal and slack post)
+pub fn exercise_list_text(exercises: &[ExerciseEntry]) -> String {
+    exercises
+        .iter()
+        .map(|exercise| exercise.to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// parse thang entered in modal, one exercise per line. Amount is optional.
+pub fn parse_exercise_list(text: &str) -> Vec<ExerciseEntry> {
+    text.lines()
+        .filter_map(|line| parse_exercise_line(line, false))
+        .collect()
+}
+
+/// best effort extraction of exercises from free text moleskine.
+/// Only picks up short `amount name` style entries (also comma separated, or after a `Warmup:` like label).
+pub fn extract_exercises(moleskine: &str) -> Vec<ExerciseEntry> {
+    moleskine
+        .lines()
+        .flat_map(|line| {
+            let line = match line.split_once(':') {
+                Some((label, rest)) if !label.chars().any(|c| c.is_ascii_digit()) => rest,
+                _ => line,
+            };
+            line.split([',', ';'])
+                .filter_map(|part| parse_exercise_line(part, true))
+                .collect::<Vec<ExerciseEntry>>()
+        })
+        .collect()
+}
+
+/// parse single exercise. When `strict`, amount is required and name has to be short.
+fn parse_exercise_line(line: &str, strict: bool) -> Option<ExerciseEntry> {
+    let line = strip_list_marker(line);
+    let (line, mut notes) = split_notes(line);
+    let tokens = line.split_whitespace().collect::<Vec<&str>>();
+    if tokens.is_empty() {
+        return None;
+    }
+
+    let (name_tokens, amount, trailing) = match parse_amount(&tokens) {
+        Some((amount, used)) => (&tokens[used..], Some(amount), &tokens[0..0]),
+        None => (1..tokens.len())
+            .find_map(|start| {
+                parse_amount(&tokens[start..])
+                    .map(|(amount, used)| (&tokens[..start], Some(amount), &tokens[start + used..]))
+            })
+            .unwrap_or((&tokens[..], None, &tokens[0..0])),
+    };
+
+    if !trailing.is_empty() {
+        let trailing = trailing.join(" ");
+        notes = Some(match notes {
+            Some(notes) => format!("{} {}", trailing, notes),
+            None => trailing,
+        });
+    }
+
+    let name = name_tokens
+        .join(" ")
+        .trim_matches(|c: char| c == '-' || c == ':' || c == ',' || c.is_whitespace())
+        .to_string();
+    if !name.chars().any(|c| c.is_alphabetic()) {
+        return None;
+    }
+    if strict && (amount.is_none() || name_tokens.len() > 4 || name.len() > 40) {
+        return None;
+    }
+
+    Some(ExerciseEntry {
+        name,
+        amount,
+        notes,
+    })
+}
+
+/// remove bullet (`-`, `*`, `•`) or numbered list (`1.`, `2)`) marker
+fn strip_list_marker(line: &str) -> &str {
+    let line = line.trim().trim_start_matches(['-', '*', '•', '>']).trim();
+    match line.split_once(' ') {
+        Some((marker, rest))
+            if marker.len() > 1
+                && (marker.ends_with('.') || marker.ends_with(')'))
+                && marker[..marker.len() - 1]
+                    .chars()
+                    .all(|c| c.is_ascii_digit()) =>
+        {
+            rest.trim()
+        }
+        _ => line,
+    }
+}
+
+/// split text in parenthesis out as notes
+fn split_notes(line: &str) -> (String, Option<String>) {
+    match (line.find('('), line.rfind(')')) {
+        (Some(start), Some(end)) if start < end => {
+            let notes = line[start + 1..end].trim();
+            let rest = format!("{} {}", &line[..start], &line[end + 1..]);
+            let notes = if notes.is_empty() {
+                None
+            } else {
+                Some(notes.to_string())
+            };
+            (rest, notes)
+        }
+        _ => (line.to_string(), None),
+    }
+}
+
+/// parse amount at start of tokens. Returns amount and number of tokens used.
+fn parse_amount(tokens: &[&str]) -> Option<(ExerciseAmount, usize)> {
+    let first = tokens.first()?.to_lowercase();
+    // `x 20`
+    if first == "x" {
+        let (amount, used) = parse_amount(&tokens[1..])?;
+        return Some((amount, used + 1));
+    }
+    let value = first.trim_start_matches('x').trim_end_matches('x');
+
+    // `1:30`
+    if let Some((minutes, seconds)) = value.split_once(':') {
+        let minutes = minutes.parse::<u32>().ok()?;
+        let seconds = seconds.parse::<u32>().ok()?;
+        let total = whole_number(f64::from(minutes) * 60.0 + f64::from(seconds))?;
+        return Some((ExerciseAmount::Seconds(total), 1));
+    }
+
+    let number_end = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let number = value[..number_end].parse::<f64>().ok()?;
+    let attached_unit = &value[number_end..];
+    let (unit, used) = if !attached_unit.is_empty() {
+        (attached_unit.to_string(), 1)
+    } else {
+        match tokens.get(1).map(|token| token.to_lowercase()) {
+            Some(unit) if unit_amount(&unit, number).is_some() => (unit, 2),
+            _ => (String::new(), 1),
+        }
+    };
+
+    let amount = if unit.is_empty() {
+        (number.fract() == 0.0)
+            .then(|| whole_number(number).map(ExerciseAmount::Reps))
+            .flatten()
+    } else {
+        unit_amount(&unit, number)
+    }?;
+    Some((amount, used))
+}
+
+fn unit_amount(unit: &str, number: f64) -> Option<ExerciseAmount> {
+    let unit = unit.trim_end_matches(['.', ',']);
+    let seconds =
+        |multiplier: f64| whole_number((number * multiplier).round()).map(ExerciseAmount::Seconds);
+    let meters =
+        |multiplier: f64| whole_number((number * multiplier).round()).map(ExerciseAmount::Meters);
+    match unit {
+        "rep" | "reps" | "x" => whole_number(number.trunc()).map(ExerciseAmount::Reps),
+        "s" | "sec" | "secs" | "second" | "seconds" => seconds(1.0),
+        "min" | "mins" | "minute" | "minutes" => seconds(60.0),
+        "m" | "meter" | "meters" => meters(1.0),
+        "km" => meters(1000.0),
+        "mi" | "mile" | "miles" => meters(1609.344),
+        "yd" | "yds" | "yard" | "yards" => meters(0.9144),
+        _ => None,
+    }
+}
+
+/// whole number as u32, `None` when it doesn't fit the INT column it is saved to
+fn whole_number(number: f64) -> Option<u32> {
+    (number.is_finite() && (0.0..=i32::MAX as f64).contains(&number)).then_some(number as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_modal_list() {
+        let text = "20 Merkins (IC)\nPlank 1 min\n- Run x 400m\n1. Dora 1-2-3\nx15 LBCs\n\n";
+        let list = parse_exercise_list(text);
+        assert_eq!(
+            list,
+            vec![
+                ExerciseEntry {
+                    name: String::from("Merkins"),
+                    amount: Some(ExerciseAmount::Reps(20)),
+                    notes: Some(String::from("IC")),
+                },
+                ExerciseEntry::new("Plank", Some(ExerciseAmount::Seconds(60))),
+                ExerciseEntry::new("Run", Some(ExerciseAmount::Meters(400))),
+                ExerciseEntry::new("Dora 1-2-3", None),
+                ExerciseEntry::new("LBCs", Some(ExerciseAmount::Reps(15))),
+            ]
+        );
+    }
+
+    #[test]
+    fn trailing_amount_notes() {
+        let list = parse_exercise_list("Squats x 25 each leg");
+        assert_eq!(list[0].name, "Squats");
+        assert_eq!(list[0].amount, Some(ExerciseAmount::Reps(25)));
+        assert_eq!(list[0].notes, Some(String::from("each leg")));
+    }
+
+    #[test]
+    fn overflowing_amounts() {
+        assert_eq!(parse_amount(&["99999999:00"]), None);
+        assert_eq!(parse_amount(&["99999999999"]), None);
+        assert_eq!(parse_amount(&["9999999999", "miles"]), None);
+        // bigger than INT column
+        assert_eq!(parse_amount(&["3000000000"]), None);
+        assert_eq!(parse_amount(&["40000000:00"]), None);
+        assert_eq!(
+            parse_amount(&["2", "min"]),
+            Some((ExerciseAmount::Seconds(120), 2))
+        );
+    }
+
+    #[test]
+    fn extract_from_moleskine() {
+        let moleskine = "Warmup: 20 SSH, 10 IST, 15 Merkins\nThe thang was brutal, lots of running.\nMosey 0.5 mi to the rock pile\n1:30 Al Gore";
+        let list = extract_exercises(moleskine);
+        let names = list
+            .iter()
+            .map(|item| item.name.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(names, vec!["SSH", "IST", "Merkins", "Mosey", "Al Gore"]);
+        assert_eq!(list[3].amount, Some(ExerciseAmount::Meters(805)));
+        assert_eq!(list[4].amount, Some(ExerciseAmount::Seconds(90)));
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let entries = vec![
+            ExerciseEntry::new("Merkins", Some(ExerciseAmount::Reps(20))),
+            ExerciseEntry::new("Plank", Some(ExerciseAmount::Seconds(90))),
+            ExerciseEntry::new("Run", Some(ExerciseAmount::Meters(400))),
+        ];
+        let text = exercise_list_text(&entries);
+        assert_eq!(text, "20 Merkins\nPlank 1:30\nRun 400m");
+        assert_eq!(parse_exercise_list(&text), entries);
+        assert_eq!(exercise_key("  Big  Boy Situps "), "big boy situps");
+    }
+}
@@ -0,0 +1,175 @@
+//! Exicon: the F3 exercise dictionary (what is a Merkin anyway?).
+//! Entries live in the same `exercises` table the thang of backblasts links to.
+
+use crate::app_state::exercises::exercise_key;
+use crate::shared::common_errors::AppError;
+use crate::shared::string_utils::edit_distance;
+use serde::{Deserialize, Serialize};
+
+/// exercise in the exicon
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExiconEntry {
+    /// display name
+    pub name: String,
+    /// lowercase alternative names
+    pub aliases: Vec<String>,
+    pub description: String,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
+}
+
+impl ExiconEntry {
+    pub fn new(name: &str, description: &str) -> Self {
+        ExiconEntry {
+            name: name.trim().to_string(),
+            aliases: Vec::new(),
+            description: description.trim().to_string(),
+            category: None,
+            tags: Vec::new(),
+        }
+    }
+
+    pub fn with_aliases(mut self, aliases: &str) -> Self {
+        self.aliases = split_list(aliases);
+        self
+    }
+
+    pub fn with_category(mut self, category: Option<String>) -> Self {
+        self.category = category
+            .map(|category| category.trim().to_string())
+            .filter(|category| !category.is_empty());
+        self
+    }
+
+    pub fn with_tags(mut self, tags: &str) -> Self {
+        self.tags = split_list(tags);
+        self
+    }
+
+    /// dictionary key of entry
+    pub fn key(&self) -> String {
+        exercise_key(&self.name)
+    }
+
+    /// validate entry before saving
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.key().is_empty() {
+            return Err(AppError::from("Exercise name is required"));
+        }
+        if self.description.is_empty() {
+            return Err(AppError::from("Exercise description is required"));
+        }
+        Ok(())
+    }
+
+    /// how well entry matches search key (0 is no match)
+    fn match_score(&self, key: &str) -> usize {
+        let name_score = std::iter::once(self.key())
+            .chain(self.aliases.iter().cloned())
+            .map(|name| name_score(&name, key))
+            .max()
+            .unwrap_or_default();
+        let tag_match = self
+            .tags
+            .iter()
+            .chain(self.category.iter())
+            .any(|tag| exercise_key(tag) == key);
+        let tag_score = if tag_match { 30 } else { 0 };
+        let description_match = self
+            .description
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| word == key);
+        let description_score = if description_match { 10 } else { 0 };
+        name_score.max(tag_score).max(description_score)
+    }
+}
+
+/// split comma or semicolon separated list into lowercase items
+fn split_list(text: &str) -> Vec<String> {
+    let mut items = text
+        .split([',', ';'])
+        .map(exercise_key)
+        .filter(|item| !item.is_empty())
+        .collect::<Vec<String>>();
+    items.dedup();
+    items
+}
+
+fn name_score(name: &str, key: &str) -> usize {
+    let singular = |value: &str| value.trim_end_matches('s').to_string();
+    if name == key {
+        100
+    } else if singular(name) == singular(key) {
+        95
+    } else if name.starts_with(key) || key.starts_with(name) {
+        80
+    } else if name.contains(key) {
+        60
+    } else {
+        let distance = edit_distance(name, key);
+        let allowed = (key.chars().count() / 4).max(1);
+        if distance <= allowed {
+            50usize.saturating_sub(distance)
+        } else {
+            0
+        }
+    }
+}
+
+/// fuzzy search of exicon, best matches first
+pub fn search_exicon<'a>(entries: &'a [ExiconEntry], term: &str) -> Vec<&'a ExiconEntry> {
+    let key = exercise_key(term);
+    if key.is_empty() {
+        return Vec::new();
+    }
+    let mut matches = entries
+        .iter()
+        .map(|entry| (entry.match_score(&key), entry))
+        .filter(|(score, _)| *score > 0)
+        .collect::<Vec<(usize, &ExiconEntry)>>();
+    matches.sort_by(|(a_score, a), (b_score, b)| b_score.cmp(a_score).then(a.name.cmp(&b.name)));
+    matches.into_iter().map(|(_, entry)| entry).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<ExiconEntry> {
+        vec![
+            ExiconEntry::new("Merkin", "A pushup. Hands shoulder width apart.")
+                .with_aliases("Pushup, push-up")
+                .with_category(Some(String::from("Upper body")))
+                .with_tags("chest, arms"),
+            ExiconEntry::new("Side Straddle Hop", "Jumping jack, counted in cadence.")
+                .with_aliases("SSH"),
+            ExiconEntry::new("Big Boy Situp", "Full situp with arms crossed.").with_tags("core"),
+        ]
+    }
+
+    #[test]
+    fn exact_alias_and_plural() {
+        let entries = entries();
+        assert_eq!(search_exicon(&entries, "ssh")[0].name, "Side Straddle Hop");
+        assert_eq!(search_exicon(&entries, "Merkins")[0].name, "Merkin");
+        assert_eq!(search_exicon(&entries, "push-up")[0].name, "Merkin");
+    }
+
+    #[test]
+    fn typos_and_tags() {
+        let entries = entries();
+        assert_eq!(search_exicon(&entries, "merkn")[0].name, "Merkin");
+        assert_eq!(search_exicon(&entries, "core")[0].name, "Big Boy Situp");
+        assert!(search_exicon(&entries, "burpee").is_empty());
+        assert!(search_exicon(&entries, "  ").is_empty());
+    }
+
+    #[test]
+    fn validate_entry() {
+        assert!(ExiconEntry::new("Merkin", "").validate().is_err());
+        assert!(ExiconEntry::new(" ", "desc").validate().is_err());
+        let entry = ExiconEntry::new("Merkin", "desc").with_aliases("pushup;; Pushup");
+        assert_eq!(entry.aliases, vec![String::from("pushup")]);
+    }
+}
@@ -0,0 +1,130 @@
+//! Hard commits (HCs) to a preblast, tracked from emoji reactions on the posted preblast message.
+//! Each AO can configure which emojis count as an HC (`:hc:` by default).
+
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// emojis counted as HC when AO has none configured
+pub const DEFAULT_HC_EMOJIS: [&str; 1] = ["hc"];
+
+/// emoji name without colons or skin tone (ie. `:thumbsup::skin-tone-2:` -> `thumbsup`)
+pub fn emoji_name(reaction: &str) -> String {
+    let reaction = reaction.trim().trim_matches(':');
+    reaction
+        .split("::")
+        .next()
+        .unwrap_or(reaction)
+        .to_lowercase()
+}
+
+/// clean list of emojis to save for AO, falling back to default when empty
+pub fn hc_emoji_list(emojis: &[String]) -> Vec<String> {
+    let mut list = Vec::<String>::new();
+    for emoji in emojis.iter().map(|emoji| emoji_name(emoji)) {
+        if !emoji.is_empty() && !list.contains(&emoji) {
+            list.push(emoji);
+        }
+    }
+    if list.is_empty() {
+        DEFAULT_HC_EMOJIS
+            .iter()
+            .map(|emoji| emoji.to_string())
+            .collect()
+    } else {
+        list
+    }
+}
+
+/// whether reaction is one of AO's HC emojis
+pub fn is_hc_reaction(reaction: &str, emojis: &[String]) -> bool {
+    let name = emoji_name(reaction);
+    emojis.iter().any(|emoji| emoji == &name)
+}
+
+/// headcount with names for preblast message (ie. `2 (Stinger, Backslash)`)
+pub fn hc_count_text(names: &[String]) -> String {
+    if names.is_empty() {
+        String::from("0")
+    } else {
+        format!("{} ({})", names.len(), names.join(", "))
+    }
+}
+
+/// projected (HCs) versus actual attendance (backblast pax) of preblast
+#[derive(Debug, PartialEq, Serialize)]
+pub struct HcAttendance {
+    pub projected: usize,
+    pub actual: usize,
+    /// HCs that posted
+    pub showed: Vec<String>,
+    /// HCs missing from backblast
+    pub no_shows: Vec<String>,
+    /// pax that posted without HC
+    pub walk_ins: Vec<String>,
+}
+
+impl HcAttendance {
+    /// compare HC names to backblast pax (case insensitive)
+    pub fn new(hcs: &[String], pax: &HashSet<String>) -> Self {
+        let hc_names = hcs
+            .iter()
+            .map(|name| name.to_lowercase())
+            .collect::<HashSet<String>>();
+        let pax_names = pax
+            .iter()
+            .map(|name| name.to_lowercase())
+            .collect::<HashSet<String>>();
+
+        let (showed, no_shows): (Vec<String>, Vec<String>) = hcs
+            .iter()
+            .cloned()
+            .partition(|name| pax_names.contains(&name.to_lowercase()));
+        let mut walk_ins = pax
+            .iter()
+            .filter(|name| !hc_names.contains(&name.to_lowercase()))
+            .cloned()
+            .collect::<Vec<String>>();
+        walk_ins.sort();
+
+        HcAttendance {
+            projected: hcs.len(),
+            actual: pax.len(),
+            showed,
+            no_shows,
+            walk_ins,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hc_emojis() {
+        assert_eq!(emoji_name(":HC::skin-tone-3:"), "hc");
+        let emojis = hc_emoji_list(&[
+            String::from(":muscle:"),
+            String::from("hc"),
+            String::from("Muscle"),
+        ]);
+        assert_eq!(emojis, vec!["muscle", "hc"]);
+        assert!(is_hc_reaction("muscle::skin-tone-2", &emojis));
+        assert!(!is_hc_reaction("sc", &emojis));
+        assert_eq!(hc_emoji_list(&[]), vec!["hc"]);
+    }
+
+    #[test]
+    fn projected_vs_actual() {
+        let hcs = vec![String::from("Stinger"), String::from("Backslash")];
+        let pax = HashSet::from([String::from("stinger"), String::from("Puff")]);
+        let attendance = HcAttendance::new(&hcs, &pax);
+        assert_eq!(attendance.projected, 2);
+        assert_eq!(attendance.actual, 2);
+        assert_eq!(attendance.showed, vec!["Stinger"]);
+        assert_eq!(attendance.no_shows, vec!["Backslash"]);
+        assert_eq!(attendance.walk_ins, vec!["Puff"]);
+        assert_eq!(hc_count_text(&hcs), "2 (Stinger, Backslash)");
+        assert_eq!(hc_count_text(&[]), "0");
+    }
+}
@@ -1,5 +1,7 @@
+use crate::app_state::ao_registry::AoRegistry;
 use crate::bot_data::{BotUser, BOT_NAME};
 use crate::db::init::sync_users;
+use crate::db::queries::ao_list::get_ao_registry_entries;
 use crate::shared::common_errors::AppError;
 use crate::slack_api::channels::{list::response::ChannelData, public_channels::PublicChannels};
 use crate::users::f3_user::F3User;
@@ -7,11 +9,25 @@ use sqlx::PgPool;
 use std::collections::HashMap;
 use std::sync::Mutex;
 
+pub mod ao_closures;
 pub mod ao_data;
+pub mod ao_registry;
 pub mod backblast_data;
 pub mod double_downs;
 pub mod equipment;
+pub mod exercises;
+pub mod exicon;
+pub mod hard_commits;
+pub mod pax_merge;
 pub mod pre_blast_data;
+pub mod q_line_up_rules;
+pub mod q_recurrence;
+pub mod revisions;
+pub mod roles;
+pub mod route_track;
+pub mod ruck;
+pub mod scoring;
+pub mod streaks;
 
 pub struct MutableAppState {
     pub app: Mutex<AppState>,
@@ -33,6 +49,20 @@ impl MutableAppState {
         Ok(())
     }
 
+    /// load ao registry from ao_list table.
+    pub async fn load_ao_registry(&self, db_pool: &PgPool) -> Result<(), AppError> {
+        let entries = get_ao_registry_entries(db_pool).await?;
+        let mut app = self.app.lock().expect("Could not lock app state");
+        app.ao_registry = AoRegistry::new(entries);
+        Ok(())
+    }
+
+    /// snapshot of ao registry to resolve AO schedule and meta data with.
+    pub fn get_ao_registry(&self) -> AoRegistry {
+        let app = self.app.lock().expect("Could not lock app state");
+        app.ao_registry.clone()
+    }
+
     pub fn insert_bots(&mut self, bots: HashMap<String, BotUser>) {
         let mut app = self.app.lock().expect("Could not lock app state");
         app.bots = bots;
@@ -57,6 +87,10 @@ pub struct AppState {
     pub bots: HashMap<String, BotUser>,
     /// id of the bot this app is.
     pub self_bot_id: Option<String>,
+    /// bot id on messages this app posts.
+    pub self_message_bot_id: Option<String>,
+    /// aos loaded from ao_list.
+    pub ao_registry: AoRegistry,
 }
 
 impl AppState {
@@ -69,12 +103,13 @@ impl AppState {
     pub fn set_self_bot_id(&mut self) {
         let matched_bot = self.bots.iter().find_map(|(id, bot)| {
             if bot.name == BOT_NAME {
-                Some(id.to_string())
+                Some((id.to_string(), bot.bot_id.clone()))
             } else {
                 None
             }
         });
-        self.self_bot_id = matched_bot;
+        self.self_message_bot_id = matched_bot.as_ref().and_then(|(_, bot_id)| bot_id.clone());
+        self.self_bot_id = matched_bot.map(|(id, _)| id);
     }
 
     pub fn get_channel_data(&self, channel: PublicChannels) -> Option<&ChannelData> {
@@ -0,0 +1,332 @@
+//! Merging two pax identities (ie. after an F3 name change or when a non-slack pax joins slack).
+//! A merge is planned first (so it can be previewed), applied as a list of column changes,
+//! and can be undone by applying those changes in reverse.
+
+use crate::shared::string_utils::edit_distance;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use uuid::Uuid;
+
+/// column that a merge changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum MergeColumn {
+    BackBlastQ,
+    BackBlastPax,
+    BackBlastFngs,
+    QLineUpQs,
+    PreBlastQs,
+    ParentPaxName,
+    ParentPaxSlackId,
+    ParentPaxParent,
+    ReactionSlackUser,
+}
+
+/// single column value change for a row
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaxMergeChange {
+    pub column: MergeColumn,
+    pub row_id: Uuid,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl PaxMergeChange {
+    pub fn new(
+        column: MergeColumn,
+        row_id: Uuid,
+        before: Option<String>,
+        after: Option<String>,
+    ) -> Self {
+        PaxMergeChange {
+            column,
+            row_id,
+            before,
+            after,
+        }
+    }
+
+    /// change that reverts this one
+    pub fn reverted(&self) -> Self {
+        PaxMergeChange {
+            column: self.column,
+            row_id: self.row_id,
+            before: self.after.clone(),
+            after: self.before.clone(),
+        }
+    }
+}
+
+/// request to merge `from_name` into existing slack user.
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct PaxMergeRequest {
+    /// name to merge away (as it shows up in backblasts)
+    pub from_name: String,
+    /// slack user to merge into
+    pub to_slack_id: String,
+}
+
+/// planned merge, used for preview and for applying.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PaxMergePlan {
+    /// lowercase name being merged away
+    pub from_name: String,
+    /// slack id of the identity being merged away, if it had one
+    pub from_slack_id: Option<String>,
+    pub to_name: String,
+    pub to_slack_id: String,
+    pub changes: Vec<PaxMergeChange>,
+}
+
+impl PaxMergePlan {
+    pub fn new(
+        from_name: &str,
+        from_slack_id: Option<String>,
+        to_name: &str,
+        to_slack_id: &str,
+    ) -> Self {
+        PaxMergePlan {
+            from_name: from_name.trim().to_lowercase(),
+            from_slack_id,
+            to_name: to_name.to_string(),
+            to_slack_id: to_slack_id.to_string(),
+            changes: Vec::new(),
+        }
+    }
+
+    pub fn add_change(&mut self, change: PaxMergeChange) {
+        self.changes.push(change);
+    }
+
+    /// number of rows changed per column
+    pub fn summary(&self) -> BTreeMap<MergeColumn, usize> {
+        self.changes
+            .iter()
+            .fold(BTreeMap::new(), |mut acc, change| {
+                *acc.entry(change.column).or_insert(0) += 1;
+                acc
+            })
+    }
+
+    /// ids of backblasts whose attendance needs rebuilding
+    pub fn back_blast_ids(&self) -> HashSet<Uuid> {
+        back_blast_ids(&self.changes)
+    }
+
+    /// text summary for slack preview
+    pub fn preview_text(&self) -> String {
+        let mut text = format!(
+            "Merge *{}* into <@{}> ({})",
+            self.from_name, self.to_slack_id, self.to_name
+        );
+        if self.changes.is_empty() {
+            text.push_str("\nNothing to change.");
+        }
+        for (column, count) in self.summary() {
+            text.push_str(format!("\n• {:?}: {} row(s)", column, count).as_str());
+        }
+        text
+    }
+}
+
+/// ids of backblasts changed by list of changes
+pub fn back_blast_ids(changes: &[PaxMergeChange]) -> HashSet<Uuid> {
+    changes
+        .iter()
+        .filter(|change| {
+            matches!(
+                change.column,
+                MergeColumn::BackBlastQ | MergeColumn::BackBlastPax | MergeColumn::BackBlastFngs
+            )
+        })
+        .map(|change| change.row_id)
+        .collect()
+}
+
+/// replace name in comma separated list (case insensitive).
+/// Drops the old name if new name is already in list. Returns `None` when nothing changed.
+pub fn replace_name_in_list(list: &str, from: &str, to: &str) -> Option<String> {
+    let items = list
+        .split(',')
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .collect::<Vec<&str>>();
+    if !items.iter().any(|item| item.eq_ignore_ascii_case(from)) {
+        return None;
+    }
+
+    let has_to = items.iter().any(|item| item.eq_ignore_ascii_case(to));
+    let replaced = items
+        .into_iter()
+        .filter_map(|item| {
+            if item.eq_ignore_ascii_case(from) {
+                if has_to {
+                    None
+                } else {
+                    Some(to)
+                }
+            } else {
+                Some(item)
+            }
+        })
+        .collect::<Vec<&str>>()
+        .join(",");
+    Some(replaced)
+}
+
+/// point pax parent json (`{"type":"pax","name":..,"slackId":..}`) at new identity.
+/// Returns `None` when parent isn't the merged pax.
+pub fn replace_name_in_parent_json(
+    parent: &serde_json::Value,
+    from: &str,
+    to: &str,
+    to_slack_id: &str,
+) -> Option<serde_json::Value> {
+    let is_from = parent.get("type").and_then(|value| value.as_str()) == Some("pax")
+        && parent
+            .get("name")
+            .and_then(|value| value.as_str())
+            .map(|name| name.eq_ignore_ascii_case(from))
+            .unwrap_or(false);
+    if !is_from {
+        return None;
+    }
+
+    let mut updated = parent.clone();
+    updated["name"] = serde_json::Value::String(to.to_string());
+    updated["slackId"] = serde_json::Value::String(to_slack_id.to_string());
+    Some(updated)
+}
+
+/// possible merge of name seen in backblasts with an existing user.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct MergeCandidate {
+    pub name: String,
+    pub suggested_name: String,
+    pub suggested_slack_id: String,
+}
+
+/// suggest users that names without a user might belong to.
+/// `users` is list of (slack id, name).
+pub fn merge_candidates(
+    names: &HashSet<String>,
+    users: &[(String, String)],
+) -> Vec<MergeCandidate> {
+    let mut candidates = names
+        .iter()
+        .flat_map(|name| {
+            let normalized = normalize_name(name);
+            users
+                .iter()
+                .filter(move |(_, user_name)| {
+                    let user_normalized = normalize_name(user_name);
+                    !normalized.is_empty()
+                        && !user_normalized.is_empty()
+                        && (normalized == user_normalized
+                            || (normalized.len() > 3 && user_normalized.contains(&normalized))
+                            || (user_normalized.len() > 3 && normalized.contains(&user_normalized))
+                            || (normalized.len() > 4
+                                && edit_distance(&normalized, &user_normalized) <= 2))
+                })
+                .map(move |(slack_id, user_name)| MergeCandidate {
+                    name: name.to_string(),
+                    suggested_name: user_name.to_string(),
+                    suggested_slack_id: slack_id.to_string(),
+                })
+        })
+        .collect::<Vec<MergeCandidate>>();
+    candidates.sort_by(|a, b| {
+        a.name
+            .cmp(&b.name)
+            .then(a.suggested_name.cmp(&b.suggested_name))
+    });
+    candidates
+}
+
+/// lowercase alphanumeric only (ie. `Mr. T-Bone` => `mrtbone`)
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replace_in_list() {
+        assert_eq!(
+            replace_name_in_list("Backslash,Old Name,Stinger", "old name", "new-name"),
+            Some(String::from("Backslash,new-name,Stinger"))
+        );
+        // already has new name
+        assert_eq!(
+            replace_name_in_list("new-name,old name", "old name", "new-name"),
+            Some(String::from("new-name"))
+        );
+        assert_eq!(
+            replace_name_in_list("Backslash", "old name", "new-name"),
+            None
+        );
+        // partial matches don't count
+        assert_eq!(
+            replace_name_in_list("old names", "old name", "new-name"),
+            None
+        );
+    }
+
+    #[test]
+    fn replace_parent_json() {
+        let parent = serde_json::json!({"type": "pax", "name": "Old Name", "slackId": null});
+        let updated = replace_name_in_parent_json(&parent, "old name", "new-name", "U123").unwrap();
+        assert_eq!(updated["name"], "new-name");
+        assert_eq!(updated["slackId"], "U123");
+
+        let parent = serde_json::json!({"type": "atBd"});
+        assert!(replace_name_in_parent_json(&parent, "old name", "new-name", "U123").is_none());
+    }
+
+    #[test]
+    fn revert_and_summary() {
+        let mut plan = PaxMergePlan::new("Old Name", None, "new-name", "U123");
+        let id = Uuid::new_v4();
+        plan.add_change(PaxMergeChange::new(
+            MergeColumn::BackBlastPax,
+            id,
+            Some(String::from("old name")),
+            Some(String::from("new-name")),
+        ));
+        plan.add_change(PaxMergeChange::new(
+            MergeColumn::BackBlastQ,
+            id,
+            Some(String::from("old name")),
+            Some(String::from("new-name")),
+        ));
+        assert_eq!(plan.from_name, "old name");
+        assert_eq!(plan.summary().get(&MergeColumn::BackBlastPax), Some(&1));
+        assert_eq!(plan.back_blast_ids().len(), 1);
+        let reverted = plan.changes[0].reverted();
+        assert_eq!(reverted.after, Some(String::from("old name")));
+    }
+
+    #[test]
+    fn suggests_candidates() {
+        let users = vec![
+            (String::from("U1"), String::from("T-Bone")),
+            (String::from("U2"), String::from("Stinger")),
+            (String::from("U3"), String::from("Backslash")),
+        ];
+        let names = HashSet::from([
+            String::from("tbone"),
+            String::from("stingr"),
+            String::from("freighter"),
+        ]);
+        let candidates = merge_candidates(&names, &users);
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].name, "stingr");
+        assert_eq!(candidates[0].suggested_slack_id, "U2");
+        assert_eq!(candidates[1].name, "tbone");
+        assert_eq!(candidates[1].suggested_name, "T-Bone");
+    }
+}
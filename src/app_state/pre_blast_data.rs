@@ -105,7 +105,7 @@ impl From<PreBlastJsonFullData> for PreBlastData {
         PreBlastData {
             id: Some(value.id.to_string()),
             qs,
-            ao: AO::from_channel_id(value.channel_id.as_str()),
+            ao: AO::from_saved(&value.ao, &value.channel_id),
             title: value.title.to_string(),
             date: value.date,
             start_time: value.start_time,
@@ -0,0 +1,158 @@
+//! Rules checked when pax sign up on the Q line up, so one pax can't double book a morning
+//! or hoard every spot at an AO. Configured per AO, defaults only block double booking.
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QLineUpRules {
+    /// allow Q'ing another AO on the same date
+    pub allow_double_booking: bool,
+    /// most sign ups per pax at AO within a month
+    pub max_per_month: Option<usize>,
+    /// spots more than this many days out are held for new Qs
+    pub new_q_priority_days: Option<i64>,
+    /// pax with fewer Qs than this count as new Q
+    pub new_q_max_qs: i64,
+}
+
+impl Default for QLineUpRules {
+    fn default() -> Self {
+        QLineUpRules {
+            allow_double_booking: false,
+            max_per_month: None,
+            new_q_priority_days: None,
+            new_q_max_qs: 3,
+        }
+    }
+}
+
+/// what pax already has lined up, to check new sign up against
+#[derive(Debug, Default)]
+pub struct QSignUpCheck {
+    pub date: NaiveDate,
+    pub today: NaiveDate,
+    /// other AOs pax is signed up to Q on same date
+    pub same_day_aos: Vec<String>,
+    /// sign ups of pax at AO in same month (not counting this one)
+    pub month_sign_ups: usize,
+    /// times pax has Q'd. `None` when not looked up.
+    pub total_qs: Option<i64>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum QRuleViolation {
+    DoubleBooked(Vec<String>),
+    MonthlyLimit(usize),
+    NewQPriority(i64),
+}
+
+impl QRuleViolation {
+    /// explanation to show pax
+    pub fn message(&self) -> String {
+        match self {
+            QRuleViolation::DoubleBooked(aos) => format!(
+                "You're already signed up to Q {} that day. One Q per morning!",
+                aos.join(", ")
+            ),
+            QRuleViolation::MonthlyLimit(max) => format!(
+                "You already have {} Q sign ups at this AO this month. Leave some spots for others!",
+                max
+            ),
+            QRuleViolation::NewQPriority(days) => format!(
+                "Spots more than {} days out are held for new Qs. Check back closer to the date!",
+                days
+            ),
+        }
+    }
+}
+
+impl QLineUpRules {
+    /// whether date is far enough out to be held for new Qs
+    pub fn held_for_new_qs(&self, today: &NaiveDate, date: &NaiveDate) -> bool {
+        match self.new_q_priority_days {
+            Some(days) => *date > *today + Duration::days(days),
+            None => false,
+        }
+    }
+
+    pub fn check(&self, sign_up: &QSignUpCheck) -> Result<(), QRuleViolation> {
+        if !self.allow_double_booking && !sign_up.same_day_aos.is_empty() {
+            return Err(QRuleViolation::DoubleBooked(sign_up.same_day_aos.clone()));
+        }
+        if let Some(max) = self.max_per_month {
+            if sign_up.month_sign_ups >= max {
+                return Err(QRuleViolation::MonthlyLimit(max));
+            }
+        }
+        if let (Some(days), Some(total_qs)) = (self.new_q_priority_days, sign_up.total_qs) {
+            if self.held_for_new_qs(&sign_up.today, &sign_up.date) && total_qs >= self.new_q_max_qs
+            {
+                return Err(QRuleViolation::NewQPriority(days));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sign_up(date_day: u32) -> QSignUpCheck {
+        QSignUpCheck {
+            date: NaiveDate::from_ymd_opt(2026, 11, date_day).unwrap(),
+            today: NaiveDate::from_ymd_opt(2026, 11, 1).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn double_booking() {
+        let rules = QLineUpRules::default();
+        let mut check = sign_up(10);
+        assert_eq!(rules.check(&check), Ok(()));
+        check.same_day_aos = vec![String::from("gem")];
+        assert_eq!(
+            rules.check(&check),
+            Err(QRuleViolation::DoubleBooked(vec![String::from("gem")]))
+        );
+        let allowed = QLineUpRules {
+            allow_double_booking: true,
+            ..Default::default()
+        };
+        assert_eq!(allowed.check(&check), Ok(()));
+    }
+
+    #[test]
+    fn monthly_limit_and_new_q_priority() {
+        let rules = QLineUpRules {
+            max_per_month: Some(2),
+            new_q_priority_days: Some(14),
+            ..Default::default()
+        };
+        let mut check = sign_up(20);
+        check.month_sign_ups = 2;
+        assert_eq!(rules.check(&check), Err(QRuleViolation::MonthlyLimit(2)));
+
+        check.month_sign_ups = 1;
+        check.total_qs = Some(10);
+        assert_eq!(rules.check(&check), Err(QRuleViolation::NewQPriority(14)));
+        check.total_qs = Some(1);
+        assert_eq!(rules.check(&check), Ok(()));
+
+        // inside window anyone can sign up
+        let mut check = sign_up(10);
+        check.total_qs = Some(10);
+        assert_eq!(rules.check(&check), Ok(()));
+    }
+
+    #[test]
+    fn partial_rules_json() {
+        let rules: QLineUpRules = serde_json::from_str(r#"{"max_per_month": 2}"#).unwrap();
+        assert_eq!(rules.max_per_month, Some(2));
+        assert!(!rules.allow_double_booking);
+        assert_eq!(rules.new_q_max_qs, 3);
+    }
+}
@@ -0,0 +1,242 @@
+//! Recurring Q commitments, so pax that Q an AO on a fixed cadence (ie. first Tuesday of every
+//! month) don't have to sign up every time. Rules are stored as text like `monthly:1:tue`
+//! (nth weekday of month, `-1` for last) or `weekly:2:tue` (every N weeks from start date).
+
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::fmt::Display;
+
+const MONTHLY: &str = "monthly";
+const WEEKLY: &str = "weekly";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecurrenceRule {
+    /// nth weekday of every month. `-1` for last weekday of month.
+    MonthlyNthWeekday { nth: i8, weekday: Weekday },
+    /// weekday every N weeks, counting from week of start date
+    EveryNWeeks { weeks: u32, weekday: Weekday },
+}
+
+impl RecurrenceRule {
+    pub fn weekday(&self) -> Weekday {
+        match self {
+            RecurrenceRule::MonthlyNthWeekday { weekday, .. } => *weekday,
+            RecurrenceRule::EveryNWeeks { weekday, .. } => *weekday,
+        }
+    }
+
+    /// whether rule lands on date, for recurrence starting on start date
+    pub fn matches(&self, start_date: &NaiveDate, date: &NaiveDate) -> bool {
+        if date < start_date || date.weekday() != self.weekday() {
+            return false;
+        }
+        match self {
+            RecurrenceRule::MonthlyNthWeekday { nth, .. } if *nth < 0 => {
+                (*date + Duration::days(7)).month() != date.month()
+            }
+            RecurrenceRule::MonthlyNthWeekday { nth, .. } => {
+                ((date.day() - 1) / 7 + 1) as i8 == *nth
+            }
+            RecurrenceRule::EveryNWeeks { weeks, .. } => {
+                let start_week = week_start(start_date);
+                let weeks_since = (week_start(date) - start_week).num_weeks();
+                *weeks > 0 && weeks_since % *weeks as i64 == 0
+            }
+        }
+    }
+
+    /// text to display for rule (ie. `1st Tuesday of every month`)
+    pub fn display_text(&self) -> String {
+        match self {
+            RecurrenceRule::MonthlyNthWeekday { nth, weekday } => format!(
+                "{} {} of every month",
+                nth_text(*nth),
+                weekday_name(weekday)
+            ),
+            RecurrenceRule::EveryNWeeks { weeks: 1, weekday } => {
+                format!("Every {}", weekday_name(weekday))
+            }
+            RecurrenceRule::EveryNWeeks { weeks, weekday } => {
+                format!("Every {} weeks on {}", weeks, weekday_name(weekday))
+            }
+        }
+    }
+}
+
+impl Display for RecurrenceRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            RecurrenceRule::MonthlyNthWeekday { nth, weekday } => {
+                format!("{MONTHLY}:{nth}:{}", weekday_key(weekday))
+            }
+            RecurrenceRule::EveryNWeeks { weeks, weekday } => {
+                format!("{WEEKLY}:{weeks}:{}", weekday_key(weekday))
+            }
+        };
+        write!(f, "{}", str)
+    }
+}
+
+impl TryFrom<&str> for RecurrenceRule {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut parts = value.trim().splitn(3, ':');
+        let (kind, count, weekday) = (parts.next(), parts.next(), parts.next());
+        let invalid = || format!("Invalid recurrence rule: {}", value);
+        let weekday = weekday
+            .and_then(|weekday| weekday.parse::<Weekday>().ok())
+            .ok_or_else(invalid)?;
+        match (kind, count) {
+            (Some(MONTHLY), Some(nth)) => match nth.parse::<i8>() {
+                Ok(nth) if nth == -1 || (1..=4).contains(&nth) => {
+                    Ok(RecurrenceRule::MonthlyNthWeekday { nth, weekday })
+                }
+                _ => Err(invalid()),
+            },
+            (Some(WEEKLY), Some(weeks)) => match weeks.parse::<u32>() {
+                Ok(weeks) if weeks > 0 => Ok(RecurrenceRule::EveryNWeeks { weeks, weekday }),
+                _ => Err(invalid()),
+            },
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// recurring Q reservation of pax at AO
+#[derive(Debug, Clone, PartialEq)]
+pub struct QRecurrence {
+    pub id: String,
+    pub slack_id: String,
+    pub ao: AO,
+    pub rule: RecurrenceRule,
+    pub start_date: NaiveDate,
+    /// last day recurrence applies (inclusive). `None` for ongoing.
+    pub end_date: Option<NaiveDate>,
+}
+
+impl QRecurrence {
+    /// dates between from and to (inclusive) the recurrence lands on
+    pub fn dates_between(&self, from: &NaiveDate, to: &NaiveDate) -> Vec<NaiveDate> {
+        let to = match &self.end_date {
+            Some(end_date) if end_date < to => end_date,
+            _ => to,
+        };
+        let mut dates = Vec::<NaiveDate>::new();
+        let mut date = *from.max(&self.start_date);
+        while &date <= to {
+            if self.rule.matches(&self.start_date, &date) {
+                dates.push(date);
+            }
+            date += Duration::days(1);
+        }
+        dates
+    }
+
+    /// text to display for recurrence (ie. `1st Tuesday of every month at Gem`)
+    pub fn display_text(&self, registry: &AoRegistry) -> String {
+        let until = self
+            .end_date
+            .map(|end_date| format!(" until {}", end_date.format("%b %-d, %Y")))
+            .unwrap_or_default();
+        format!(
+            "{} at {}{}",
+            self.rule.display_text(),
+            self.ao.friendly_name(registry),
+            until
+        )
+    }
+}
+
+/// monday of week date is in
+fn week_start(date: &NaiveDate) -> NaiveDate {
+    *date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+fn nth_text(nth: i8) -> &'static str {
+    match nth {
+        1 => "1st",
+        2 => "2nd",
+        3 => "3rd",
+        4 => "4th",
+        _ => "Last",
+    }
+}
+
+/// lowercase short weekday for saving (ie. `tue`)
+pub fn weekday_key(weekday: &Weekday) -> String {
+    weekday.to_string().to_lowercase()
+}
+
+pub fn weekday_name(weekday: &Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    #[test]
+    fn monthly_nth_weekday() {
+        let rule = RecurrenceRule::try_from("monthly:1:tue").unwrap();
+        assert_eq!(rule.to_string(), "monthly:1:tue");
+        assert_eq!(rule.display_text(), "1st Tuesday of every month");
+        let recurrence = QRecurrence {
+            id: String::new(),
+            slack_id: String::from("U1"),
+            ao: AO::Gem,
+            rule,
+            start_date: date(10, 1),
+            end_date: None,
+        };
+        assert_eq!(
+            recurrence.dates_between(&date(10, 1), &date(12, 31)),
+            vec![date(10, 6), date(11, 3), date(12, 1)]
+        );
+
+        let last = RecurrenceRule::try_from("monthly:-1:fri").unwrap();
+        assert!(last.matches(&date(10, 1), &date(10, 30)));
+        assert!(!last.matches(&date(10, 1), &date(10, 23)));
+        assert_eq!(last.display_text(), "Last Friday of every month");
+    }
+
+    #[test]
+    fn every_n_weeks() {
+        let rule = RecurrenceRule::try_from("weekly:2:wed").unwrap();
+        assert_eq!(rule.display_text(), "Every 2 weeks on Wednesday");
+        let recurrence = QRecurrence {
+            id: String::new(),
+            slack_id: String::from("U1"),
+            ao: AO::Bleach,
+            rule,
+            // monday, so first wednesday counts
+            start_date: date(10, 19),
+            end_date: Some(date(11, 18)),
+        };
+        assert_eq!(
+            recurrence.dates_between(&date(10, 1), &date(12, 31)),
+            vec![date(10, 21), date(11, 4), date(11, 18)]
+        );
+    }
+
+    #[test]
+    fn invalid_rules() {
+        assert!(RecurrenceRule::try_from("monthly:5:tue").is_err());
+        assert!(RecurrenceRule::try_from("weekly:0:tue").is_err());
+        assert!(RecurrenceRule::try_from("daily:1:tue").is_err());
+        assert!(RecurrenceRule::try_from("monthly:1:xyz").is_err());
+    }
+}
@@ -0,0 +1,222 @@
+//! Revisions of backblasts and preblasts. Every write stores a full snapshot of the row
+//! so history can be shown, diffed and rolled back.
+
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fmt::Display;
+
+/// type of entity revision is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevisionEntity {
+    BackBlast,
+    PreBlast,
+}
+
+impl Display for RevisionEntity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            RevisionEntity::BackBlast => "backblast",
+            RevisionEntity::PreBlast => "preblast",
+        };
+        write!(f, "{}", str)
+    }
+}
+
+/// what happened to entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevisionAction {
+    Create,
+    Edit,
+    Deactivate,
+    Restore,
+    Sync,
+    Rollback,
+}
+
+impl Display for RevisionAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            RevisionAction::Create => "create",
+            RevisionAction::Edit => "edit",
+            RevisionAction::Deactivate => "deactivate",
+            RevisionAction::Restore => "restore",
+            RevisionAction::Sync => "sync",
+            RevisionAction::Rollback => "rollback",
+        };
+        write!(f, "{}", str)
+    }
+}
+
+impl From<&str> for RevisionAction {
+    fn from(action: &str) -> Self {
+        match action {
+            "create" => RevisionAction::Create,
+            "deactivate" => RevisionAction::Deactivate,
+            "restore" => RevisionAction::Restore,
+            "sync" => RevisionAction::Sync,
+            "rollback" => RevisionAction::Rollback,
+            _ => RevisionAction::Edit,
+        }
+    }
+}
+
+/// where change came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevisionSource {
+    /// slack modal (new or edit)
+    Modal,
+    /// syncing from other db via url
+    SyncViaUrl,
+    /// backblast parsed from channel message
+    Parser,
+    /// action on posted slack message (ie. delete)
+    SlackAction,
+    /// migrating old data
+    Migration,
+    /// admin action through api (ie. rollback)
+    Admin,
+}
+
+impl Display for RevisionSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            RevisionSource::Modal => "modal",
+            RevisionSource::SyncViaUrl => "sync-via-url",
+            RevisionSource::Parser => "parser",
+            RevisionSource::SlackAction => "slack-action",
+            RevisionSource::Migration => "migration",
+            RevisionSource::Admin => "admin",
+        };
+        write!(f, "{}", str)
+    }
+}
+
+/// who made change and from where
+#[derive(Debug, Clone, PartialEq)]
+pub struct RevisionActor {
+    /// slack user that made change, if known
+    pub slack_user: Option<String>,
+    pub source: RevisionSource,
+}
+
+impl RevisionActor {
+    pub fn new(source: RevisionSource, slack_user: Option<&str>) -> Self {
+        RevisionActor {
+            slack_user: slack_user.map(|user| user.to_string()),
+            source,
+        }
+    }
+
+    pub fn modal(slack_user: &str) -> Self {
+        RevisionActor::new(RevisionSource::Modal, Some(slack_user))
+    }
+
+    pub fn parser(slack_user: Option<&str>) -> Self {
+        RevisionActor::new(RevisionSource::Parser, slack_user)
+    }
+
+    pub fn slack_action(slack_user: &str) -> Self {
+        RevisionActor::new(RevisionSource::SlackAction, Some(slack_user))
+    }
+
+    pub fn sync() -> Self {
+        RevisionActor::new(RevisionSource::SyncViaUrl, None)
+    }
+
+    pub fn migration() -> Self {
+        RevisionActor::new(RevisionSource::Migration, None)
+    }
+}
+
+/// single field that differs between two snapshots
+#[derive(Debug, PartialEq, Serialize)]
+pub struct FieldDiff {
+    pub field: String,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
+
+/// fields that changed between two snapshots. `before` is `None` for first revision.
+pub fn diff_snapshots(
+    before: Option<&serde_json::Value>,
+    after: &serde_json::Value,
+) -> Vec<FieldDiff> {
+    let field = |snapshot: Option<&serde_json::Value>, key: &str| {
+        snapshot
+            .and_then(|value| value.get(key))
+            .filter(|value| !value.is_null())
+            .cloned()
+    };
+    let keys = [before, Some(after)]
+        .into_iter()
+        .flatten()
+        .filter_map(|snapshot| snapshot.as_object())
+        .flat_map(|object| object.keys().cloned())
+        .collect::<BTreeSet<String>>();
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let before = field(before, &key);
+            let after = field(Some(after), &key);
+            if before == after {
+                None
+            } else {
+                Some(FieldDiff {
+                    field: key,
+                    before,
+                    after,
+                })
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_changed_fields() {
+        let before = json!({"id": "1", "pax": "bob,stinger", "title": null, "active": true});
+        let after = json!({"id": "1", "pax": "bob", "title": "Merkins", "active": true});
+        let diff = diff_snapshots(Some(&before), &after);
+        assert_eq!(
+            diff,
+            vec![
+                FieldDiff {
+                    field: String::from("pax"),
+                    before: Some(json!("bob,stinger")),
+                    after: Some(json!("bob")),
+                },
+                FieldDiff {
+                    field: String::from("title"),
+                    before: None,
+                    after: Some(json!("Merkins")),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_first_revision() {
+        let after = json!({"id": "1", "title": null});
+        let diff = diff_snapshots(None, &after);
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].field, "id");
+    }
+
+    #[test]
+    fn action_round_trip() {
+        for action in [
+            RevisionAction::Create,
+            RevisionAction::Edit,
+            RevisionAction::Deactivate,
+            RevisionAction::Restore,
+            RevisionAction::Sync,
+            RevisionAction::Rollback,
+        ] {
+            assert_eq!(RevisionAction::from(action.to_string().as_str()), action);
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! Roles of slack users and what they are allowed to do. Region admins can do everything,
+//! site Qs manage their own AO, comz handle messaging. Everyone else is a regular pax.
+
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use std::fmt::Display;
+
+const REGION_ADMIN: &str = "region_admin";
+const SITE_Q: &str = "site_q";
+const COMZ: &str = "comz";
+const PAX: &str = "pax";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Role {
+    RegionAdmin,
+    /// site Q of single AO
+    SiteQ(AO),
+    Comz,
+    Pax,
+}
+
+impl Role {
+    /// parse role saved as text, with AO for site Qs
+    pub fn from_parts(role: &str, ao: Option<AO>) -> Result<Self, String> {
+        match (role.trim().to_lowercase().as_str(), ao) {
+            (REGION_ADMIN | "admin", _) => Ok(Role::RegionAdmin),
+            (SITE_Q | "siteq", Some(AO::Unknown(name))) => Err(format!("Unknown AO: {}", name)),
+            (SITE_Q | "siteq", Some(ao)) => Ok(Role::SiteQ(ao)),
+            (SITE_Q | "siteq", None) => Err(String::from("Site Q role needs an AO")),
+            (COMZ, _) => Ok(Role::Comz),
+            (PAX, _) => Ok(Role::Pax),
+            (role, _) => Err(format!("Unknown role: {}", role)),
+        }
+    }
+
+    /// AO role is scoped to, if any
+    pub fn ao(&self) -> Option<&AO> {
+        match self {
+            Role::SiteQ(ao) => Some(ao),
+            _ => None,
+        }
+    }
+
+    /// text to display for role (ie. `Site Q of Gem`)
+    pub fn display_text(&self, registry: &AoRegistry) -> String {
+        match self {
+            Role::RegionAdmin => String::from("Region admin"),
+            Role::SiteQ(ao) => format!("Site Q of {}", ao.friendly_name(registry)),
+            Role::Comz => String::from("Comz"),
+            Role::Pax => String::from("Pax"),
+        }
+    }
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            Role::RegionAdmin => REGION_ADMIN,
+            Role::SiteQ(_) => SITE_Q,
+            Role::Comz => COMZ,
+            Role::Pax => PAX,
+        };
+        write!(f, "{}", str)
+    }
+}
+
+/// actions that need more than being a regular pax
+#[derive(Debug, Clone, PartialEq)]
+pub enum Permission {
+    /// edit or delete backblast at AO the user didn't Q
+    EditBackBlast(AO),
+    RestoreBackBlast,
+    /// edit or cancel preblast at AO the user isn't Q of
+    EditPreBlast(AO),
+    /// close days on Q line up of AO, and step in on Q swaps there
+    CloseQLineUp(AO),
+    /// add closure for AO, or region wide when `None`
+    AddClosure(Option<AO>),
+    Sync,
+    DirectMessage,
+    InviteAll,
+    MergePax,
+    EditExicon,
+    ManageRoles,
+}
+
+/// roles of single slack user
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UserRoles {
+    pub slack_id: String,
+    pub roles: Vec<Role>,
+}
+
+impl UserRoles {
+    pub fn new(slack_id: &str, roles: Vec<Role>) -> Self {
+        UserRoles {
+            slack_id: slack_id.to_string(),
+            roles,
+        }
+    }
+
+    pub fn is_region_admin(&self) -> bool {
+        self.roles.contains(&Role::RegionAdmin)
+    }
+
+    /// region admin or site Q of any AO
+    pub fn is_site_q(&self) -> bool {
+        self.is_region_admin() || self.roles.iter().any(|role| matches!(role, Role::SiteQ(_)))
+    }
+
+    fn is_site_q_of(&self, ao: &AO) -> bool {
+        self.roles.iter().any(|role| role.ao() == Some(ao))
+    }
+
+    pub fn can(&self, permission: &Permission) -> bool {
+        if self.is_region_admin() {
+            return true;
+        }
+        match permission {
+            Permission::EditBackBlast(ao)
+            | Permission::EditPreBlast(ao)
+            | Permission::CloseQLineUp(ao)
+            | Permission::AddClosure(Some(ao)) => self.is_site_q_of(ao),
+            Permission::DirectMessage | Permission::InviteAll => self.roles.contains(&Role::Comz),
+            _ => false,
+        }
+    }
+
+    /// roles to display, regular pax when none saved
+    pub fn display_text(&self, registry: &AoRegistry) -> String {
+        if self.roles.is_empty() {
+            return Role::Pax.display_text(registry);
+        }
+        self.roles
+            .iter()
+            .map(|role| role.display_text(registry))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_roles() {
+        assert_eq!(
+            Role::from_parts("region_admin", None),
+            Ok(Role::RegionAdmin)
+        );
+        assert_eq!(
+            Role::from_parts("site_q", Some(AO::Gem)),
+            Ok(Role::SiteQ(AO::Gem))
+        );
+        assert!(Role::from_parts("site_q", None).is_err());
+        assert!(Role::from_parts("nantan", None).is_err());
+        assert_eq!(Role::SiteQ(AO::Gem).to_string(), "site_q");
+    }
+
+    #[test]
+    fn permissions_scoped_to_ao() {
+        let site_q = UserRoles::new("U1", vec![Role::SiteQ(AO::Gem)]);
+        assert!(site_q.can(&Permission::CloseQLineUp(AO::Gem)));
+        assert!(site_q.can(&Permission::EditBackBlast(AO::Gem)));
+        assert!(!site_q.can(&Permission::CloseQLineUp(AO::Bleach)));
+        assert!(!site_q.can(&Permission::AddClosure(None)));
+        assert!(!site_q.can(&Permission::Sync));
+        assert!(site_q.is_site_q());
+
+        let comz = UserRoles::new("U2", vec![Role::Comz]);
+        assert!(comz.can(&Permission::InviteAll));
+        assert!(comz.can(&Permission::DirectMessage));
+        assert!(!comz.can(&Permission::EditPreBlast(AO::Gem)));
+
+        let admin = UserRoles::new("U3", vec![Role::RegionAdmin]);
+        assert!(admin.can(&Permission::ManageRoles));
+        assert!(admin.can(&Permission::CloseQLineUp(AO::Bleach)));
+
+        let pax = UserRoles::new("U4", vec![]);
+        assert!(!pax.can(&Permission::InviteAll));
+        assert_eq!(pax.display_text(&AoRegistry::default()), "Pax");
+    }
+}
@@ -0,0 +1,568 @@
+//! Routes recorded on watches (GPX or FIT files) uploaded with a backblast.
+//! Files are parsed locally for distance, elevation gain and duration, and the track
+//! is simplified to a small list of points for storing and drawing a map thumbnail.
+
+use crate::shared::common_errors::AppError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+const EARTH_RADIUS_METERS: f64 = 6_371_000.0;
+const METERS_PER_MILE: f64 = 1609.344;
+const FEET_PER_METER: f64 = 3.28084;
+/// elevation change needed before counting as gain (filters gps noise)
+const ELEVATION_THRESHOLD_METERS: f64 = 3.0;
+/// max distance a simplified track can stray from the recorded one
+const SIMPLIFY_TOLERANCE_METERS: f64 = 5.0;
+/// seconds between FIT epoch (1989-12-31) and unix epoch
+const FIT_EPOCH_OFFSET: i64 = 631_065_600;
+/// FIT global message number of a record (single track point)
+const FIT_RECORD_MESSAGE: u16 = 20;
+
+/// file types supported for route uploads
+pub const ROUTE_FILE_TYPES: [&str; 2] = ["gpx", "fit"];
+
+/// single recorded point of route
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackPoint {
+    pub lat: f64,
+    pub lon: f64,
+    /// elevation in meters
+    pub ele: Option<f64>,
+    pub time: Option<DateTime<Utc>>,
+}
+
+impl TrackPoint {
+    pub fn new(lat: f64, lon: f64) -> Self {
+        TrackPoint {
+            lat,
+            lon,
+            ele: None,
+            time: None,
+        }
+    }
+
+    /// great circle distance in meters
+    pub fn distance_to(&self, other: &TrackPoint) -> f64 {
+        let (lat_1, lat_2) = (self.lat.to_radians(), other.lat.to_radians());
+        let d_lat = lat_2 - lat_1;
+        let d_lon = (other.lon - self.lon).to_radians();
+        let a =
+            (d_lat / 2.0).sin().powi(2) + lat_1.cos() * lat_2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_METERS * a.sqrt().asin()
+    }
+}
+
+/// summary of route, as stored with backblast
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RouteSummary {
+    pub distance_miles: f64,
+    pub elevation_gain_ft: f64,
+    /// time from first to last point (when points have times)
+    pub duration_secs: Option<i64>,
+    /// simplified track as `[lat, lon]` pairs
+    pub track: Vec<[f64; 2]>,
+}
+
+impl RouteSummary {
+    pub fn from_points(points: &[TrackPoint]) -> Result<Self, AppError> {
+        if points.len() < 2 {
+            return Err(AppError::from("Route needs at least 2 track points"));
+        }
+        let distance = points
+            .windows(2)
+            .map(|pair| pair[0].distance_to(&pair[1]))
+            .sum::<f64>();
+        let times = points
+            .iter()
+            .filter_map(|point| point.time)
+            .collect::<Vec<DateTime<Utc>>>();
+        let duration_secs = match (times.first(), times.last()) {
+            (Some(start), Some(end)) if end > start => Some((*end - *start).num_seconds()),
+            _ => None,
+        };
+        let track = simplify_track(points, SIMPLIFY_TOLERANCE_METERS)
+            .into_iter()
+            .map(|point| [point.lat, point.lon])
+            .collect();
+        Ok(RouteSummary {
+            distance_miles: distance / METERS_PER_MILE,
+            elevation_gain_ft: elevation_gain(points) * FEET_PER_METER,
+            duration_secs,
+            track,
+        })
+    }
+
+    /// short text of route (ie. `3.12mi | 250ft gain | 45:10`)
+    pub fn summary_text(&self) -> String {
+        let mut parts = vec![
+            format!("{:.2}mi", self.distance_miles),
+            format!("{:.0}ft gain", self.elevation_gain_ft),
+        ];
+        if let Some(duration) = self.duration_secs {
+            let (hours, minutes, seconds) = (duration / 3600, duration % 3600 / 60, duration % 60);
+            parts.push(if hours > 0 {
+                format!("{}:{:02}:{:02}", hours, minutes, seconds)
+            } else {
+                format!("{}:{:02}", minutes, seconds)
+            });
+        }
+        parts.join(" | ")
+    }
+}
+
+/// parse route from file contents, based on slack filetype (or file extension)
+pub fn parse_route_file(file_type: &str, bytes: &[u8]) -> Result<RouteSummary, AppError> {
+    let points = match file_type.to_lowercase().as_str() {
+        "gpx" => parse_gpx(&String::from_utf8_lossy(bytes)),
+        "fit" => parse_fit(bytes)?,
+        other => {
+            return Err(AppError::General(format!(
+                "Unsupported route file type: {}",
+                other
+            )))
+        }
+    };
+    RouteSummary::from_points(&points)
+}
+
+/// track (or route) points of gpx file
+pub fn parse_gpx(xml: &str) -> Vec<TrackPoint> {
+    let mut points = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = find_point_tag(rest) {
+        rest = &rest[start..];
+        let Some(tag_end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[..tag_end];
+        let (content, next) = if tag.ends_with('/') {
+            ("", tag_end + 1)
+        } else {
+            let close = rest
+                .find("pt>")
+                .map(|close| close + 3)
+                .unwrap_or(rest.len());
+            (&rest[tag_end + 1..close], close)
+        };
+        let lat = xml_attribute(tag, "lat").and_then(|lat| lat.parse::<f64>().ok());
+        let lon = xml_attribute(tag, "lon").and_then(|lon| lon.parse::<f64>().ok());
+        if let (Some(lat), Some(lon)) = (lat, lon) {
+            points.push(TrackPoint {
+                lat,
+                lon,
+                ele: xml_element(content, "ele").and_then(|ele| ele.parse::<f64>().ok()),
+                time: xml_element(content, "time")
+                    .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+                    .map(|time| time.with_timezone(&Utc)),
+            });
+        }
+        rest = &rest[next.max(1)..];
+    }
+    points
+}
+
+fn find_point_tag(xml: &str) -> Option<usize> {
+    ["<trkpt", "<rtept"]
+        .iter()
+        .filter_map(|tag| xml.find(tag))
+        .min()
+}
+
+fn xml_attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = tag;
+    while let Some(index) = rest.find(name) {
+        let before = rest[..index].chars().last();
+        let after = rest[index + name.len()..].trim_start();
+        rest = &rest[index + name.len()..];
+        if !before.map(|c| c.is_whitespace()).unwrap_or(false) {
+            continue;
+        }
+        let Some(value) = after.strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let quote = value.chars().next()?;
+        if quote != '"' && quote != '\'' {
+            continue;
+        }
+        let value = &value[1..];
+        return value.find(quote).map(|end| value[..end].trim());
+    }
+    None
+}
+
+fn xml_element<'a>(content: &'a str, name: &str) -> Option<&'a str> {
+    let start = content.find(&format!("<{}>", name))? + name.len() + 2;
+    let end = content[start..].find(&format!("</{}>", name))? + start;
+    Some(content[start..end].trim())
+}
+
+/// field definition of FIT message
+struct FitField {
+    number: u8,
+    size: usize,
+}
+
+/// local message definition of FIT file
+struct FitDefinition {
+    global_message: u16,
+    big_endian: bool,
+    fields: Vec<FitField>,
+    /// total size of developer fields (skipped)
+    developer_size: usize,
+}
+
+/// record points of FIT activity file. Only position, altitude and timestamp fields are read.
+pub fn parse_fit(bytes: &[u8]) -> Result<Vec<TrackPoint>, AppError> {
+    let invalid = || AppError::from("Invalid FIT file");
+    let header_size = *bytes.first().ok_or_else(invalid)? as usize;
+    if header_size < 12 || bytes.len() < header_size || &bytes[8..12] != b".FIT" {
+        return Err(invalid());
+    }
+    let data_size = u32::from_le_bytes(bytes[4..8].try_into().map_err(|_| invalid())?) as usize;
+    let data_end = (header_size + data_size).min(bytes.len());
+
+    let mut definitions: [Option<FitDefinition>; 16] = Default::default();
+    let mut points = Vec::new();
+    let mut last_timestamp: Option<u32> = None;
+    let mut index = header_size;
+    while index < data_end {
+        let record_header = bytes[index];
+        index += 1;
+
+        // compressed timestamp header
+        if record_header & 0x80 != 0 {
+            let local = ((record_header >> 5) & 0x03) as usize;
+            let offset = (record_header & 0x1F) as u32;
+            let rolled_over = last_timestamp.map(|last| {
+                let timestamp = (last & !0x1F) | offset;
+                if offset < last & 0x1F {
+                    timestamp.checked_add(0x20)
+                } else {
+                    Some(timestamp)
+                }
+            });
+            // timestamp past what fits, so record is skipped
+            let overflowed = matches!(rolled_over, Some(None));
+            let timestamp = rolled_over.flatten();
+            last_timestamp = timestamp.or(last_timestamp);
+            let definition = definitions[local].as_ref().ok_or_else(invalid)?;
+            let (point, size) = read_fit_record(definition, &bytes[index..], timestamp)?;
+            index += size;
+            if !overflowed {
+                points.extend(point);
+            }
+            continue;
+        }
+
+        let local = (record_header & 0x0F) as usize;
+        if record_header & 0x40 != 0 {
+            let has_developer_data = record_header & 0x20 != 0;
+            let header = bytes.get(index..index + 5).ok_or_else(invalid)?;
+            let big_endian = header[1] == 1;
+            let global_message = if big_endian {
+                u16::from_be_bytes([header[2], header[3]])
+            } else {
+                u16::from_le_bytes([header[2], header[3]])
+            };
+            let field_count = header[4] as usize;
+            index += 5;
+            let field_bytes = bytes
+                .get(index..index + field_count * 3)
+                .ok_or_else(invalid)?;
+            let fields = field_bytes
+                .chunks(3)
+                .map(|field| FitField {
+                    number: field[0],
+                    size: field[1] as usize,
+                })
+                .collect();
+            index += field_count * 3;
+            let mut developer_size = 0;
+            if has_developer_data {
+                let count = *bytes.get(index).ok_or_else(invalid)? as usize;
+                index += 1;
+                let developer_fields = bytes.get(index..index + count * 3).ok_or_else(invalid)?;
+                developer_size = developer_fields
+                    .chunks(3)
+                    .map(|field| field[1] as usize)
+                    .sum();
+                index += count * 3;
+            }
+            definitions[local] = Some(FitDefinition {
+                global_message,
+                big_endian,
+                fields,
+                developer_size,
+            });
+        } else {
+            let definition = definitions[local].as_ref().ok_or_else(invalid)?;
+            let (point, size) = read_fit_record(definition, &bytes[index..], None)?;
+            if let Some(timestamp) = read_fit_timestamp(definition, &bytes[index..]) {
+                last_timestamp = Some(timestamp);
+            }
+            index += size;
+            points.extend(point);
+        }
+    }
+    Ok(points)
+}
+
+/// read data message. Returns point (if record with position) and size of message.
+fn read_fit_record(
+    definition: &FitDefinition,
+    bytes: &[u8],
+    compressed_timestamp: Option<u32>,
+) -> Result<(Option<TrackPoint>, usize), AppError> {
+    let size = definition
+        .fields
+        .iter()
+        .map(|field| field.size)
+        .sum::<usize>()
+        + definition.developer_size;
+    if bytes.len() < size {
+        return Err(AppError::from("Invalid FIT file"));
+    }
+    if definition.global_message != FIT_RECORD_MESSAGE {
+        return Ok((None, size));
+    }
+
+    let (mut lat, mut lon, mut altitude, mut enhanced_altitude) = (None, None, None, None);
+    let mut timestamp = compressed_timestamp;
+    let mut offset = 0;
+    for field in &definition.fields {
+        let value = &bytes[offset..offset + field.size];
+        offset += field.size;
+        match (field.number, field.size) {
+            (0, 4) => lat = fit_i32(value, definition.big_endian),
+            (1, 4) => lon = fit_i32(value, definition.big_endian),
+            (2, 2) => altitude = fit_u16(value, definition.big_endian).map(|alt| alt as u32),
+            (78, 4) => enhanced_altitude = fit_u32(value, definition.big_endian),
+            (253, 4) => timestamp = fit_u32(value, definition.big_endian).or(timestamp),
+            _ => {}
+        }
+    }
+
+    let semicircles = |value: i32| value as f64 * 180.0 / 2_147_483_648.0;
+    let point = match (lat, lon) {
+        (Some(lat), Some(lon)) => Some(TrackPoint {
+            lat: semicircles(lat),
+            lon: semicircles(lon),
+            ele: enhanced_altitude
+                .or(altitude)
+                .map(|alt| alt as f64 / 5.0 - 500.0),
+            time: timestamp.and_then(|timestamp| {
+                DateTime::from_timestamp(timestamp as i64 + FIT_EPOCH_OFFSET, 0)
+            }),
+        }),
+        _ => None,
+    };
+    Ok((point, size))
+}
+
+/// timestamp field of any data message (used as base for compressed timestamps)
+fn read_fit_timestamp(definition: &FitDefinition, bytes: &[u8]) -> Option<u32> {
+    let mut offset = 0;
+    for field in &definition.fields {
+        if field.number == 253 && field.size == 4 {
+            return fit_u32(bytes.get(offset..offset + 4)?, definition.big_endian);
+        }
+        offset += field.size;
+    }
+    None
+}
+
+fn fit_i32(value: &[u8], big_endian: bool) -> Option<i32> {
+    let bytes: [u8; 4] = value.try_into().ok()?;
+    let value = if big_endian {
+        i32::from_be_bytes(bytes)
+    } else {
+        i32::from_le_bytes(bytes)
+    };
+    (value != i32::MAX).then_some(value)
+}
+
+fn fit_u32(value: &[u8], big_endian: bool) -> Option<u32> {
+    let bytes: [u8; 4] = value.try_into().ok()?;
+    let value = if big_endian {
+        u32::from_be_bytes(bytes)
+    } else {
+        u32::from_le_bytes(bytes)
+    };
+    (value != u32::MAX).then_some(value)
+}
+
+fn fit_u16(value: &[u8], big_endian: bool) -> Option<u16> {
+    let bytes: [u8; 2] = value.try_into().ok()?;
+    let value = if big_endian {
+        u16::from_be_bytes(bytes)
+    } else {
+        u16::from_le_bytes(bytes)
+    };
+    (value != u16::MAX).then_some(value)
+}
+
+/// elevation gain in meters, only counting climbs past threshold
+fn elevation_gain(points: &[TrackPoint]) -> f64 {
+    let mut elevations = points.iter().filter_map(|point| point.ele);
+    let Some(mut reference) = elevations.next() else {
+        return 0.0;
+    };
+    let mut gain = 0.0;
+    for ele in elevations {
+        if ele - reference >= ELEVATION_THRESHOLD_METERS {
+            gain += ele - reference;
+            reference = ele;
+        } else if reference - ele >= ELEVATION_THRESHOLD_METERS {
+            reference = ele;
+        }
+    }
+    gain
+}
+
+/// Douglas-Peucker simplification of track, keeping first and last points
+pub fn simplify_track(points: &[TrackPoint], tolerance_meters: f64) -> Vec<TrackPoint> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+    let mut ranges = vec![(0, points.len() - 1)];
+    while let Some((start, end)) = ranges.pop() {
+        let farthest = (start + 1..end)
+            .map(|index| {
+                let distance = distance_to_segment(&points[index], &points[start], &points[end]);
+                (index, distance)
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((index, distance)) = farthest {
+            if distance > tolerance_meters {
+                keep[index] = true;
+                ranges.push((start, index));
+                ranges.push((index, end));
+            }
+        }
+    }
+    points
+        .iter()
+        .zip(keep)
+        .filter(|(_, keep)| *keep)
+        .map(|(point, _)| point.clone())
+        .collect()
+}
+
+/// distance in meters from point to segment, on local flat projection
+fn distance_to_segment(point: &TrackPoint, start: &TrackPoint, end: &TrackPoint) -> f64 {
+    let lat_scale = EARTH_RADIUS_METERS.to_radians();
+    let lon_scale = lat_scale * start.lat.to_radians().cos();
+    let project = |p: &TrackPoint| {
+        (
+            (p.lon - start.lon) * lon_scale,
+            (p.lat - start.lat) * lat_scale,
+        )
+    };
+    let (x, y) = project(point);
+    let (end_x, end_y) = project(end);
+    let length = end_x * end_x + end_y * end_y;
+    let t = if length == 0.0 {
+        0.0
+    } else {
+        ((x * end_x + y * end_y) / length).clamp(0.0, 1.0)
+    };
+    ((x - t * end_x).powi(2) + (y - t * end_y).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GPX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gpx version="1.1" creator="Watch">
+  <trk><name>Rebel</name><trkseg>
+    <trkpt lat="43.6150" lon="-116.2023"><ele>820.0</ele><time>2024-07-01T11:30:00Z</time></trkpt>
+    <trkpt lat="43.6160" lon="-116.2023"><ele>825.0</ele><time>2024-07-01T11:31:00Z</time></trkpt>
+    <trkpt lat='43.6170' lon='-116.2023'><ele>824.0</ele><time>2024-07-01T11:32:00Z</time></trkpt>
+    <trkpt lat="43.6170" lon="-116.2010"><ele>830.0</ele><time>2024-07-01T11:40:30Z</time></trkpt>
+  </trkseg></trk>
+</gpx>"#;
+
+    #[test]
+    fn parse_gpx_summary() {
+        let points = parse_gpx(GPX);
+        assert_eq!(points.len(), 4);
+        assert_eq!(points[2].lat, 43.617);
+        let summary = RouteSummary::from_points(&points).unwrap();
+        assert!((summary.distance_miles - 0.2032).abs() < 0.001);
+        assert!((summary.elevation_gain_ft - 10.0 * FEET_PER_METER).abs() < 0.01);
+        assert_eq!(summary.duration_secs, Some(630));
+        // middle point is on straight line
+        assert_eq!(summary.track.len(), 3);
+        assert_eq!(summary.summary_text(), "0.20mi | 33ft gain | 10:30");
+        assert!(parse_route_file("gpx", b"<gpx></gpx>").is_err());
+        assert!(parse_route_file("tcx", GPX.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn parse_fit_records() {
+        let mut data = vec![
+            // definition of local 0 as record: timestamp, lat, lon, altitude
+            0x40, 0, 0, 20, 0, 4, 253, 4, 0x86, 0, 4, 0x85, 1, 4, 0x85, 2, 2, 0x84,
+        ];
+        let semicircles = |degrees: f64| ((degrees * 2_147_483_648.0 / 180.0) as i32).to_le_bytes();
+        for (seconds, lat) in [(1_000_000_000u32, 43.615), (1_000_000_060, 43.616)] {
+            data.push(0x00);
+            data.extend(seconds.to_le_bytes());
+            data.extend(semicircles(lat));
+            data.extend(semicircles(-116.2023));
+            data.extend(((820u16 + 500) * 5).to_le_bytes());
+        }
+        let mut file = vec![12, 0x20, 0, 0];
+        file.extend((data.len() as u32).to_le_bytes());
+        file.extend(b".FIT");
+        file.extend(data);
+
+        let points = parse_fit(&file).unwrap();
+        assert_eq!(points.len(), 2);
+        assert!((points[1].lat - 43.616).abs() < 0.000_001);
+        assert_eq!(points[0].ele, Some(820.0));
+        let summary = parse_route_file("fit", &file).unwrap();
+        assert_eq!(summary.duration_secs, Some(60));
+        assert!(parse_fit(b"not a fit file").is_err());
+    }
+
+    #[test]
+    fn fit_timestamp_overflow_skips_record() {
+        let mut data = vec![
+            // local 0 record: timestamp, lat, lon. local 1 record (compressed timestamp): lat, lon
+            0x40, 0, 0, 20, 0, 3, 253, 4, 0x86, 0, 4, 0x85, 1, 4, 0x85, 0x41, 0, 0, 20, 0, 2, 0, 4,
+            0x85, 1, 4, 0x85,
+        ];
+        data.push(0x00);
+        data.extend((u32::MAX - 2).to_le_bytes());
+        data.extend(1_000_000i32.to_le_bytes());
+        data.extend(1_000_000i32.to_le_bytes());
+        // offset 1 is before last timestamp, so it rolls over past u32
+        data.push(0x80 | (1 << 5) | 1);
+        data.extend(1_000_000i32.to_le_bytes());
+        data.extend(1_000_000i32.to_le_bytes());
+        let mut file = vec![12, 0x20, 0, 0];
+        file.extend((data.len() as u32).to_le_bytes());
+        file.extend(b".FIT");
+        file.extend(data);
+
+        let points = parse_fit(&file).unwrap();
+        assert_eq!(points.len(), 1);
+    }
+
+    #[test]
+    fn simplify_keeps_corners() {
+        let points = (0..=10)
+            .map(|index| TrackPoint::new(43.6 + index as f64 * 0.0001, -116.2))
+            .chain((1..=10).map(|index| TrackPoint::new(43.601, -116.2 + index as f64 * 0.0001)))
+            .collect::<Vec<TrackPoint>>();
+        let simplified = simplify_track(&points, SIMPLIFY_TOLERANCE_METERS);
+        assert_eq!(simplified.len(), 3);
+        assert_eq!(simplified[1], TrackPoint::new(43.601, -116.2));
+    }
+}
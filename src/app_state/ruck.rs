@@ -0,0 +1,191 @@
+//! Ruck distance and weight carried per pax, for rucking and heavy AOs.
+//! Backblast modal takes miles and weight for everyone, with optional per pax lines
+//! (ie. `Stinger 4.2 45` or `Backslash 3mi 60lbs`) for pax that differ.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// ruck done by single pax
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuckEntry {
+    pub miles: Option<f32>,
+    /// pounds carried
+    pub weight: Option<f32>,
+}
+
+impl RuckEntry {
+    pub fn new(miles: Option<f32>, weight: Option<f32>) -> Self {
+        RuckEntry { miles, weight }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.miles.is_none() && self.weight.is_none()
+    }
+
+    /// miles times pounds carried
+    pub fn pound_miles(&self) -> Option<f32> {
+        Some(self.miles? * self.weight?)
+    }
+
+    /// fill in values not set from other entry
+    fn or(self, other: RuckEntry) -> Self {
+        RuckEntry {
+            miles: self.miles.or(other.miles),
+            weight: self.weight.or(other.weight),
+        }
+    }
+}
+
+impl Display for RuckEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts = [
+            self.miles.map(|miles| format!("{}mi", miles)),
+            self.weight.map(|weight| format!("{}lbs", weight)),
+        ];
+        let parts = parts.into_iter().flatten().collect::<Vec<String>>();
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+/// ruck per pax (lowercase name), from entry for everyone and per pax lines.
+/// Lines for names not in pax list are ignored.
+pub fn ruck_entries<'a>(
+    pax: impl Iterator<Item = &'a String>,
+    everyone: RuckEntry,
+    per_pax: &str,
+) -> HashMap<String, RuckEntry> {
+    let per_pax = parse_ruck_lines(per_pax);
+    pax.filter_map(|name| {
+        let name = name.trim().to_lowercase();
+        let entry = per_pax.get(&name).copied().unwrap_or_default().or(everyone);
+        if entry.is_empty() {
+            None
+        } else {
+            Some((name, entry))
+        }
+    })
+    .collect()
+}
+
+/// ruck per pax as lines, sorted by name (for modal and slack post)
+pub fn ruck_list_text(ruck: &HashMap<String, RuckEntry>) -> String {
+    let mut lines = ruck
+        .iter()
+        .filter(|(_, entry)| !entry.is_empty())
+        .map(|(name, entry)| format!("{} {}", name, entry))
+        .collect::<Vec<String>>();
+    lines.sort();
+    lines.join("\n")
+}
+
+/// parse per pax lines. Values after name are miles then weight, unless unit is given.
+pub fn parse_ruck_lines(text: &str) -> HashMap<String, RuckEntry> {
+    text.lines().filter_map(parse_ruck_line).collect()
+}
+
+fn parse_ruck_line(line: &str) -> Option<(String, RuckEntry)> {
+    let tokens = line.split_whitespace().collect::<Vec<&str>>();
+    let values_start = tokens
+        .iter()
+        .position(|token| token.starts_with(|c: char| c.is_ascii_digit() || c == '.'))?;
+    let name = tokens[..values_start]
+        .join(" ")
+        .trim_matches(|c: char| c == '@' || c == '-' || c == ':' || c.is_whitespace())
+        .to_lowercase();
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut entry = RuckEntry::default();
+    let mut unlabeled = Vec::<f32>::new();
+    let mut values = tokens[values_start..].iter().peekable();
+    while let Some(token) = values.next() {
+        let token = token.to_lowercase();
+        let number_end = token
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(token.len());
+        let Ok(number) = token[..number_end].parse::<f32>() else {
+            continue;
+        };
+        let mut unit = token[number_end..].to_string();
+        if unit.is_empty() {
+            if let Some(next) = values.peek().map(|next| next.to_lowercase()) {
+                if ruck_unit(&next).is_some() {
+                    unit = next;
+                    values.next();
+                }
+            }
+        }
+        match ruck_unit(&unit) {
+            Some(RuckUnit::Miles) => entry.miles = Some(number),
+            Some(RuckUnit::Pounds) => entry.weight = Some(number),
+            None => unlabeled.push(number),
+        }
+    }
+    let mut unlabeled = unlabeled.into_iter();
+    if entry.miles.is_none() {
+        entry.miles = unlabeled.next();
+    }
+    if entry.weight.is_none() {
+        entry.weight = unlabeled.next();
+    }
+    Some((name, entry))
+}
+
+enum RuckUnit {
+    Miles,
+    Pounds,
+}
+
+fn ruck_unit(unit: &str) -> Option<RuckUnit> {
+    match unit.trim_end_matches([',', '.']) {
+        "mi" | "mile" | "miles" => Some(RuckUnit::Miles),
+        "lb" | "lbs" | "#" | "pounds" => Some(RuckUnit::Pounds),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_lines() {
+        let lines =
+            parse_ruck_lines("Stinger 4.2 45\n@Backslash: 60lbs 3 mi\nBig Sky 5\nno values");
+        assert_eq!(
+            lines.get("stinger"),
+            Some(&RuckEntry::new(Some(4.2), Some(45.)))
+        );
+        assert_eq!(
+            lines.get("backslash"),
+            Some(&RuckEntry::new(Some(3.), Some(60.)))
+        );
+        assert_eq!(lines.get("big sky"), Some(&RuckEntry::new(Some(5.), None)));
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn everyone_with_per_pax() {
+        let pax = [
+            String::from("Stinger"),
+            String::from("Backslash"),
+            String::from("Puff"),
+        ];
+        let ruck = ruck_entries(
+            pax.iter(),
+            RuckEntry::new(Some(4.), Some(30.)),
+            "stinger 45lbs\nfreighter 2 20",
+        );
+        assert_eq!(ruck.len(), 3);
+        assert_eq!(ruck["stinger"], RuckEntry::new(Some(4.), Some(45.)));
+        assert_eq!(ruck["puff"].pound_miles(), Some(120.));
+        assert_eq!(
+            ruck_list_text(&ruck),
+            "backslash 4mi 30lbs\npuff 4mi 30lbs\nstinger 4mi 45lbs"
+        );
+        assert_eq!(parse_ruck_lines(&ruck_list_text(&ruck)), ruck);
+        assert!(ruck_entries(pax.iter(), RuckEntry::default(), "").is_empty());
+    }
+}
@@ -0,0 +1,428 @@
+//! Configurable workout scoring per AO. Each AO can define named input fields and a formula
+//! over them (ie. `weight * miles` for Ruckership). Formulas support numbers, field names,
+//! `+ - * /` and parenthesis.
+
+use crate::app_state::ao_data::AO;
+use crate::shared::common_errors::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// selectable option of select field, with value used in formula
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoringOption {
+    pub label: String,
+    pub value: f64,
+}
+
+/// type of input field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScoringFieldType {
+    /// whole number
+    Number,
+    Float,
+    Select {
+        options: Vec<ScoringOption>,
+    },
+}
+
+/// named input of scoring formula
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoringField {
+    /// name used in formula (lowercase letters, digits and `_`)
+    pub key: String,
+    pub label: String,
+    #[serde(flatten)]
+    pub field_type: ScoringFieldType,
+}
+
+impl ScoringField {
+    pub fn new(key: &str, label: &str, field_type: ScoringFieldType) -> Self {
+        ScoringField {
+            key: key.to_string(),
+            label: label.to_string(),
+            field_type,
+        }
+    }
+
+    /// parse value entered in modal
+    pub fn parse_input(&self, value: &str) -> Result<f64, AppError> {
+        let value = value.trim();
+        let parsed = match &self.field_type {
+            ScoringFieldType::Number => value.parse::<i64>().ok().map(|value| value as f64),
+            ScoringFieldType::Float => value.parse::<f64>().ok(),
+            ScoringFieldType::Select { options } => value
+                .parse::<f64>()
+                .ok()
+                .filter(|value| options.iter().any(|option| option.value == *value)),
+        };
+        parsed
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| AppError::General(format!("Invalid value for {}", self.label)))
+    }
+}
+
+/// scoring formula of an AO
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoringFormula {
+    /// name of score (ie. Ruck Score)
+    pub name: String,
+    pub fields: Vec<ScoringField>,
+    pub formula: String,
+    /// unit shown next to score (ie. lb-miles)
+    pub unit: Option<String>,
+    /// false when lower score is better (ie. pace)
+    pub higher_is_better: bool,
+}
+
+impl ScoringFormula {
+    /// make sure formula parses and only uses defined fields
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.name.trim().is_empty() {
+            return Err(AppError::from("Score name is required"));
+        }
+        let mut keys = BTreeSet::new();
+        for field in &self.fields {
+            if !is_identifier(&field.key) {
+                return Err(AppError::General(format!(
+                    "Invalid field key: {}",
+                    field.key
+                )));
+            }
+            if !keys.insert(field.key.as_str()) {
+                return Err(AppError::General(format!(
+                    "Duplicate field key: {}",
+                    field.key
+                )));
+            }
+            if let ScoringFieldType::Select { options } = &field.field_type {
+                if options.is_empty() {
+                    return Err(AppError::General(format!(
+                        "Select field {} needs options",
+                        field.key
+                    )));
+                }
+            }
+        }
+
+        let expr = Expr::parse(&self.formula)?;
+        let mut variables = BTreeSet::new();
+        expr.variables(&mut variables);
+        if let Some(unknown) = variables.iter().find(|name| !keys.contains(name.as_str())) {
+            return Err(AppError::General(format!(
+                "Formula uses unknown field: {}",
+                unknown
+            )));
+        }
+        Ok(())
+    }
+
+    /// compute score from field values
+    pub fn score(&self, inputs: &HashMap<String, f64>) -> Result<f64, AppError> {
+        let result = Expr::parse(&self.formula)?.eval(inputs)?;
+        if result.is_finite() {
+            Ok(result)
+        } else {
+            Err(AppError::from("Score could not be calculated"))
+        }
+    }
+
+    /// score with unit for display
+    pub fn score_fmt(&self, score: f64) -> String {
+        match &self.unit {
+            Some(unit) => format!("{:.2} {}", score, unit),
+            None => format!("{:.2}", score),
+        }
+    }
+
+    /// default formulas to seed, by ao
+    pub fn defaults() -> Vec<(AO, ScoringFormula)> {
+        let ruck = ScoringFormula {
+            name: String::from("Ruck Score"),
+            fields: vec![
+                ScoringField::new("weight", "Ruck weight (lbs)", ScoringFieldType::Float),
+                ScoringField::new("miles", "Miles", ScoringFieldType::Float),
+            ],
+            formula: String::from("weight * miles"),
+            unit: Some(String::from("lb-miles")),
+            higher_is_better: true,
+        };
+        let pace = ScoringFormula {
+            name: String::from("Pace"),
+            fields: vec![
+                ScoringField::new("miles", "Miles", ScoringFieldType::Float),
+                ScoringField::new("minutes", "Total minutes", ScoringFieldType::Float),
+            ],
+            formula: String::from("minutes / miles"),
+            unit: Some(String::from("min/mile")),
+            higher_is_better: false,
+        };
+        vec![
+            (AO::RuckershipWest, ruck.clone()),
+            (AO::RuckershipEast, ruck),
+            (AO::Rebel, pace),
+        ]
+    }
+}
+
+fn is_identifier(value: &str) -> bool {
+    let mut chars = value.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_lowercase() || c == '_')
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(char),
+    Open,
+    Close,
+}
+
+fn tokenize(formula: &str) -> Result<Vec<Token>, AppError> {
+    let chars = formula.chars().collect::<Vec<char>>();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        match c {
+            c if c.is_whitespace() => index += 1,
+            '+' | '-' | '*' | '/' => {
+                tokens.push(Token::Op(c));
+                index += 1;
+            }
+            '(' => {
+                tokens.push(Token::Open);
+                index += 1;
+            }
+            ')' => {
+                tokens.push(Token::Close);
+                index += 1;
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let start = index;
+                while index < chars.len() && (chars[index].is_ascii_digit() || chars[index] == '.')
+                {
+                    index += 1;
+                }
+                let number = chars[start..index].iter().collect::<String>();
+                let number = number
+                    .parse::<f64>()
+                    .map_err(|_| AppError::General(format!("Invalid number: {}", number)))?;
+                tokens.push(Token::Number(number));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let start = index;
+                while index < chars.len()
+                    && (chars[index].is_ascii_alphanumeric() || chars[index] == '_')
+                {
+                    index += 1;
+                }
+                let name = chars[start..index].iter().collect::<String>();
+                tokens.push(Token::Ident(name.to_lowercase()));
+            }
+            _ => {
+                return Err(AppError::General(format!(
+                    "Unexpected character in formula: {}",
+                    c
+                )))
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// parsed formula
+#[derive(Debug, PartialEq)]
+enum Expr {
+    Number(f64),
+    Field(String),
+    Negate(Box<Expr>),
+    Binary(char, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn parse(formula: &str) -> Result<Expr, AppError> {
+        let tokens = tokenize(formula)?;
+        let mut position = 0;
+        let expr = parse_sum(&tokens, &mut position)?;
+        if position != tokens.len() {
+            return Err(AppError::from("Unexpected end of formula"));
+        }
+        Ok(expr)
+    }
+
+    fn eval(&self, inputs: &HashMap<String, f64>) -> Result<f64, AppError> {
+        match self {
+            Expr::Number(value) => Ok(*value),
+            Expr::Field(name) => inputs
+                .get(name)
+                .copied()
+                .ok_or_else(|| AppError::General(format!("Missing value for {}", name))),
+            Expr::Negate(expr) => Ok(-expr.eval(inputs)?),
+            Expr::Binary(op, left, right) => {
+                let (left, right) = (left.eval(inputs)?, right.eval(inputs)?);
+                match op {
+                    '+' => Ok(left + right),
+                    '-' => Ok(left - right),
+                    '*' => Ok(left * right),
+                    _ if right == 0. => Err(AppError::from("Division by zero in formula")),
+                    _ => Ok(left / right),
+                }
+            }
+        }
+    }
+
+    fn variables(&self, names: &mut BTreeSet<String>) {
+        match self {
+            Expr::Number(_) => {}
+            Expr::Field(name) => {
+                names.insert(name.to_string());
+            }
+            Expr::Negate(expr) => expr.variables(names),
+            Expr::Binary(_, left, right) => {
+                left.variables(names);
+                right.variables(names);
+            }
+        }
+    }
+}
+
+fn parse_sum(tokens: &[Token], position: &mut usize) -> Result<Expr, AppError> {
+    let mut expr = parse_product(tokens, position)?;
+    while let Some(Token::Op(op @ ('+' | '-'))) = tokens.get(*position) {
+        *position += 1;
+        let right = parse_product(tokens, position)?;
+        expr = Expr::Binary(*op, Box::new(expr), Box::new(right));
+    }
+    Ok(expr)
+}
+
+fn parse_product(tokens: &[Token], position: &mut usize) -> Result<Expr, AppError> {
+    let mut expr = parse_factor(tokens, position)?;
+    while let Some(Token::Op(op @ ('*' | '/'))) = tokens.get(*position) {
+        *position += 1;
+        let right = parse_factor(tokens, position)?;
+        expr = Expr::Binary(*op, Box::new(expr), Box::new(right));
+    }
+    Ok(expr)
+}
+
+fn parse_factor(tokens: &[Token], position: &mut usize) -> Result<Expr, AppError> {
+    let token = tokens
+        .get(*position)
+        .ok_or_else(|| AppError::from("Unexpected end of formula"))?;
+    *position += 1;
+    match token {
+        Token::Number(value) => Ok(Expr::Number(*value)),
+        Token::Ident(name) => Ok(Expr::Field(name.to_string())),
+        Token::Op('-') => Ok(Expr::Negate(Box::new(parse_factor(tokens, position)?))),
+        Token::Open => {
+            let expr = parse_sum(tokens, position)?;
+            match tokens.get(*position) {
+                Some(Token::Close) => {
+                    *position += 1;
+                    Ok(expr)
+                }
+                _ => Err(AppError::from("Missing closing parenthesis in formula")),
+            }
+        }
+        _ => Err(AppError::from("Unexpected operator in formula")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(values: &[(&str, f64)]) -> HashMap<String, f64> {
+        values
+            .iter()
+            .map(|(key, value)| (key.to_string(), *value))
+            .collect()
+    }
+
+    #[test]
+    fn precedence_and_parenthesis() {
+        let values = inputs(&[("a", 2.), ("b", 3.)]);
+        assert_eq!(Expr::parse("a + b * 2").unwrap().eval(&values).unwrap(), 8.);
+        assert_eq!(
+            Expr::parse("(a + b) * 2").unwrap().eval(&values).unwrap(),
+            10.
+        );
+        assert_eq!(Expr::parse("-a - -b").unwrap().eval(&values).unwrap(), 1.);
+        assert!(Expr::parse("a +").is_err());
+        assert!(Expr::parse("(a + b").is_err());
+        assert!(Expr::parse("a $ b").is_err());
+        assert!(Expr::parse("a / 0").unwrap().eval(&values).is_err());
+    }
+
+    #[test]
+    fn black_diamond_as_formula() {
+        let formula = ScoringFormula {
+            name: String::from("Black Diamond Rating"),
+            fields: vec![
+                ScoringField::new("pax_count", "Number of Pax", ScoringFieldType::Number),
+                ScoringField::new("vests_removed", "Vests Removed", ScoringFieldType::Number),
+                ScoringField::new("miles", "Miles", ScoringFieldType::Float),
+                ScoringField::new("avg_hr", "Avg Heart Rate", ScoringFieldType::Float),
+            ],
+            formula: String::from("vests_removed / pax_count + miles / 2 + avg_hr / 140"),
+            unit: None,
+            higher_is_better: true,
+        };
+        assert!(formula.validate().is_ok());
+        let values = inputs(&[
+            ("pax_count", 8.),
+            ("vests_removed", 8.),
+            ("miles", 3.72),
+            ("avg_hr", 140.5),
+        ]);
+        let score = formula.score(&values).unwrap();
+        assert_eq!(formula.score_fmt(score), "3.86");
+    }
+
+    #[test]
+    fn validate_fields() {
+        let (_, mut formula) = ScoringFormula::defaults().remove(0);
+        assert!(formula.validate().is_ok());
+        formula.formula = String::from("weight * distance");
+        assert!(formula.validate().is_err());
+        formula.formula = String::from("weight * miles");
+        formula.fields.push(ScoringField::new(
+            "effort",
+            "Effort",
+            ScoringFieldType::Select { options: vec![] },
+        ));
+        assert!(formula.validate().is_err());
+    }
+
+    #[test]
+    fn parse_field_inputs() {
+        let select = ScoringField::new(
+            "terrain",
+            "Terrain",
+            ScoringFieldType::Select {
+                options: vec![
+                    ScoringOption {
+                        label: String::from("Flat"),
+                        value: 1.,
+                    },
+                    ScoringOption {
+                        label: String::from("Hills"),
+                        value: 1.5,
+                    },
+                ],
+            },
+        );
+        assert_eq!(select.parse_input("1.5").unwrap(), 1.5);
+        assert!(select.parse_input("2").is_err());
+        let number = ScoringField::new("pax", "Pax", ScoringFieldType::Number);
+        assert_eq!(number.parse_input(" 8 ").unwrap(), 8.);
+        assert!(number.parse_input("8.5").is_err());
+        assert!(ScoringField::new("miles", "Miles", ScoringFieldType::Float)
+            .parse_input("abc")
+            .is_err());
+    }
+}
@@ -0,0 +1,263 @@
+//! Posting streaks of pax, computed from backblasts. Weekly streaks count weeks in a row with
+//! enough posts, AO streaks count scheduled days in a row at AO (closed days don't break it).
+
+use crate::app_state::ao_closures::ClosureCalendar;
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// posts in a week needed to keep weekly streak going
+pub const WEEKLY_STREAK_MIN_POSTS: usize = 3;
+
+/// weekly streak lengths worth calling out, then every year after
+const WEEKLY_MILESTONES: [u32; 5] = [4, 8, 12, 26, 52];
+/// AO streak lengths worth calling out, then every 100 after
+const AO_MILESTONES: [u32; 4] = [10, 25, 50, 100];
+/// days left in week (including today) when short weekly streak counts as at risk
+const AT_RISK_DAYS_LEFT: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WeeklyStreak {
+    /// weeks in a row with enough posts
+    pub weeks: u32,
+    /// monday of first week in streak
+    pub start: Option<NaiveDate>,
+    pub posts_this_week: usize,
+    pub min_posts: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AoStreak {
+    pub ao: AO,
+    /// scheduled days in a row posted at AO
+    pub days: u32,
+    /// first day of streak
+    pub start: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PaxStreaks {
+    pub weekly: WeeklyStreak,
+    /// current AO streaks, longest first
+    pub aos: Vec<AoStreak>,
+}
+
+impl WeeklyStreak {
+    /// current streak as of today. Week in progress only counts once it has enough posts.
+    pub fn new(dates: &[NaiveDate], today: &NaiveDate, min_posts: usize) -> Self {
+        let mut per_week = HashMap::<NaiveDate, usize>::new();
+        for date in dates.iter().filter(|date| *date <= today) {
+            *per_week.entry(week_start(date)).or_insert(0) += 1;
+        }
+        let this_week = week_start(today);
+        let posts_this_week = per_week.get(&this_week).copied().unwrap_or_default();
+
+        let mut week = if posts_this_week >= min_posts {
+            this_week
+        } else {
+            this_week - Duration::weeks(1)
+        };
+        let mut weeks = 0;
+        let mut start = None;
+        while per_week.get(&week).copied().unwrap_or_default() >= min_posts {
+            weeks += 1;
+            start = Some(week);
+            week -= Duration::weeks(1);
+        }
+
+        WeeklyStreak {
+            weeks,
+            start,
+            posts_this_week,
+            min_posts,
+        }
+    }
+
+    /// week is almost over and pax is still short on posts (but can still make it)
+    pub fn at_risk(&self, today: &NaiveDate) -> bool {
+        let days_left = 7 - today.weekday().num_days_from_monday() as usize;
+        let missing = self.min_posts.saturating_sub(self.posts_this_week);
+        self.weeks > 0 && missing > 0 && missing <= days_left && days_left <= AT_RISK_DAYS_LEFT
+    }
+
+    /// biggest milestone reached by streak
+    pub fn milestone(&self) -> Option<u32> {
+        milestone(self.weeks, &WEEKLY_MILESTONES, 52)
+    }
+}
+
+impl AoStreak {
+    /// current streak at AO as of today. Today doesn't break it, since backblast may not be in yet.
+    pub fn new(
+        ao: &AO,
+        dates: &[NaiveDate],
+        closures: &ClosureCalendar,
+        registry: &AoRegistry,
+        today: &NaiveDate,
+    ) -> Self {
+        let posted = dates.iter().collect::<HashSet<&NaiveDate>>();
+        let earliest = dates.iter().min().copied().unwrap_or(*today);
+        let mut days = 0;
+        let mut start = None;
+        let mut date = *today;
+        while date >= earliest {
+            if closures.is_open(ao, &date, registry) {
+                if posted.contains(&date) {
+                    days += 1;
+                    start = Some(date);
+                } else if &date != today {
+                    break;
+                }
+            }
+            date -= Duration::days(1);
+        }
+
+        AoStreak {
+            ao: ao.clone(),
+            days,
+            start,
+        }
+    }
+
+    /// biggest milestone reached by streak
+    pub fn milestone(&self) -> Option<u32> {
+        milestone(self.days, &AO_MILESTONES, 100)
+    }
+}
+
+impl PaxStreaks {
+    /// streaks from AO and date of each post of pax
+    pub fn new(
+        posts: &[(AO, NaiveDate)],
+        closures: &ClosureCalendar,
+        registry: &AoRegistry,
+        today: &NaiveDate,
+    ) -> Self {
+        let dates = posts
+            .iter()
+            .map(|(_, date)| *date)
+            .collect::<Vec<NaiveDate>>();
+        let weekly = WeeklyStreak::new(&dates, today, WEEKLY_STREAK_MIN_POSTS);
+
+        let mut per_ao = HashMap::<String, (AO, Vec<NaiveDate>)>::new();
+        for (ao, date) in posts.iter() {
+            per_ao
+                .entry(ao.to_string())
+                .or_insert_with(|| (ao.clone(), Vec::new()))
+                .1
+                .push(*date);
+        }
+        let mut aos = per_ao
+            .values()
+            .map(|(ao, dates)| AoStreak::new(ao, dates, closures, registry, today))
+            .filter(|streak| streak.days > 0)
+            .collect::<Vec<AoStreak>>();
+        aos.sort_by_key(|streak| std::cmp::Reverse(streak.days));
+
+        PaxStreaks { weekly, aos }
+    }
+
+    /// lines to show pax their streaks
+    pub fn display_lines(&self, registry: &AoRegistry) -> Vec<String> {
+        let mut lines = vec![format!(
+            "*Weekly Streak*: {} weeks ({}/{} posts this week)",
+            self.weekly.weeks, self.weekly.posts_this_week, self.weekly.min_posts
+        )];
+        for streak in self.aos.iter() {
+            lines.push(format!(
+                "*{} Streak*: {} days in a row",
+                streak.ao.friendly_name(registry),
+                streak.days
+            ));
+        }
+        lines
+    }
+}
+
+/// monday of week date is in
+pub fn week_start(date: &NaiveDate) -> NaiveDate {
+    *date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+fn milestone(count: u32, milestones: &[u32], every: u32) -> Option<u32> {
+    let last = milestones.last().copied().unwrap_or(every);
+    if count >= last {
+        Some(count / every * every)
+    } else {
+        milestones.iter().rev().find(|m| count >= **m).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    #[test]
+    fn weekly_streak_counts_full_weeks() {
+        // mon, wed, fri each week of oct 5, 12, 19 and two posts week of oct 26
+        let dates = vec![
+            date(10, 5),
+            date(10, 7),
+            date(10, 9),
+            date(10, 12),
+            date(10, 14),
+            date(10, 16),
+            date(10, 19),
+            date(10, 21),
+            date(10, 23),
+            date(10, 26),
+            date(10, 28),
+        ];
+        // friday, week in progress short one post
+        let today = date(10, 30);
+        let streak = WeeklyStreak::new(&dates, &today, 3);
+        assert_eq!(streak.weeks, 3);
+        assert_eq!(streak.start, Some(date(10, 5)));
+        assert_eq!(streak.posts_this_week, 2);
+        assert!(streak.at_risk(&today));
+        // plenty of time left on monday
+        assert!(!streak.at_risk(&date(10, 26)));
+
+        let mut dates = dates;
+        dates.push(date(10, 30));
+        let streak = WeeklyStreak::new(&dates, &today, 3);
+        assert_eq!(streak.weeks, 4);
+        assert!(!streak.at_risk(&today));
+        assert_eq!(streak.milestone(), Some(4));
+    }
+
+    #[test]
+    fn ao_streak_skips_unscheduled_days() {
+        let ao = AO::Bleach;
+        let registry = AoRegistry::default();
+        let week_days = ao.week_days(&registry);
+        let today = date(10, 30);
+        let mut open_days = Vec::<NaiveDate>::new();
+        let mut day = today - Duration::days(1);
+        while open_days.len() < 5 {
+            if week_days.contains(&day.weekday()) {
+                open_days.push(day);
+            }
+            day -= Duration::days(1);
+        }
+        // posted last 3 scheduled days, missed the one before
+        let dates = vec![open_days[0], open_days[1], open_days[2], open_days[4]];
+        let streak = AoStreak::new(&ao, &dates, &ClosureCalendar::default(), &registry, &today);
+        assert_eq!(streak.days, 3);
+        assert_eq!(streak.start, Some(open_days[2]));
+    }
+
+    #[test]
+    fn milestones() {
+        assert_eq!(milestone(3, &WEEKLY_MILESTONES, 52), None);
+        assert_eq!(milestone(13, &WEEKLY_MILESTONES, 52), Some(12));
+        assert_eq!(milestone(110, &WEEKLY_MILESTONES, 52), Some(104));
+        assert_eq!(milestone(260, &AO_MILESTONES, 100), Some(200));
+    }
+}
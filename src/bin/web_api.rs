@@ -15,6 +15,12 @@ async fn main() -> Result<(), AppError> {
         let address = format!("{}:{}", config.application.host, config.application.port);
         let address = std::sync::Arc::new(address);
         let a_1 = std::sync::Arc::clone(&address);
+        let a_2 = std::sync::Arc::clone(&address);
+        let a_3 = std::sync::Arc::clone(&address);
+        let a_4 = std::sync::Arc::clone(&address);
+        let a_5 = std::sync::Arc::clone(&address);
+        let q_reminder_days = config.application.q_reminder_days;
+        let q_recurrence_horizon_days = config.application.q_recurrence_horizon_days;
 
         actix_rt::spawn(async move {
             scheduler::start_daily_scheduler(address.as_str()).await;
@@ -22,6 +28,18 @@ async fn main() -> Result<(), AppError> {
         actix_rt::spawn(async move {
             scheduler::start_leaderboard_scheduler(a_1.as_str()).await;
         });
+        actix_rt::spawn(async move {
+            scheduler::start_scheduled_pre_blasts_scheduler(a_2.as_str()).await;
+        });
+        actix_rt::spawn(async move {
+            scheduler::start_q_reminder_scheduler(a_3.as_str(), q_reminder_days).await;
+        });
+        actix_rt::spawn(async move {
+            scheduler::start_q_vacancies_scheduler(a_4.as_str()).await;
+        });
+        actix_rt::spawn(async move {
+            scheduler::start_q_recurrence_scheduler(a_5.as_str(), q_recurrence_horizon_days).await;
+        });
     } else {
         println!("skipping crons jobs");
     }
@@ -7,6 +7,8 @@ use std::collections::HashMap;
 pub struct BotUser {
     pub id: String,
     pub name: String,
+    /// bot id on messages posted by bot
+    pub bot_id: Option<String>,
 }
 
 impl BotUser {
@@ -14,6 +16,7 @@ impl BotUser {
         BotUser {
             id: user.id.to_string(),
             name: user.name.to_string(),
+            bot_id: user.profile.bot_id.clone(),
         }
     }
 }
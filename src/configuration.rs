@@ -17,6 +17,20 @@ pub struct ApplicationSettings {
     pub host: String,
     // pub base_url: String,
     pub hmac_secret: Secret<String>,
+    /// days before signed up Q date to DM Q a reminder
+    #[serde(default = "default_q_reminder_days")]
+    pub q_reminder_days: i64,
+    /// days ahead recurring Qs are added to q line up
+    #[serde(default = "default_q_recurrence_horizon_days")]
+    pub q_recurrence_horizon_days: i64,
+}
+
+fn default_q_reminder_days() -> i64 {
+    2
+}
+
+fn default_q_recurrence_horizon_days() -> i64 {
+    28
 }
 
 #[derive(Deserialize)]
@@ -1,5 +1,6 @@
 use crate::shared::time::local_boise_time;
-use chrono::{DateTime, FixedOffset, Timelike};
+use chrono::{DateTime, Timelike};
+use chrono_tz::Tz;
 use tokio::time;
 
 /// 8am local time
@@ -35,7 +36,7 @@ pub async fn start_cron() {
     }
 }
 
-fn get_tomorrow_at_scheduled_time() -> DateTime<FixedOffset> {
+fn get_tomorrow_at_scheduled_time() -> DateTime<Tz> {
     let tomorrow = local_boise_time()
         .checked_add_signed(chrono::Duration::days(1))
         .unwrap();
@@ -44,7 +45,7 @@ fn get_tomorrow_at_scheduled_time() -> DateTime<FixedOffset> {
     tomorrow.with_second(0).unwrap()
 }
 
-fn get_dev_time() -> DateTime<FixedOffset> {
+fn get_dev_time() -> DateTime<Tz> {
     local_boise_time()
         .checked_add_signed(chrono::Duration::seconds(5))
         .unwrap()
@@ -6,7 +6,7 @@
 //! 5. Upsert all users in app state to db
 
 use crate::app_state::ao_data::const_names::AO_LIST;
-use crate::app_state::ao_data::AoData;
+use crate::app_state::ao_registry::AoRegistryEntry;
 use crate::db::insert_ao::insert_ao_record;
 use crate::db::queries::users::get_db_users;
 use crate::db::save_user::{upsert_user, DbUser};
@@ -15,15 +15,14 @@ use crate::users::f3_user::F3User;
 use sqlx::PgPool;
 use std::collections::HashMap;
 
-/// Sync full ao list
+/// Seed ao list with hardcoded defaults (existing registry values are kept)
 pub async fn sync_ao_list(db_pool: &PgPool) -> Result<(), AppError> {
     match db_pool.try_begin().await {
         Ok(transaction) => {
             let mut transaction = transaction.expect("Failed to begin transaction");
             for item in AO_LIST {
-                let ao = AoData::from(&item);
-                let channel_id = item.channel_id();
-                insert_ao_record(&mut transaction, &ao, channel_id).await?;
+                let ao = AoRegistryEntry::from(&item);
+                insert_ao_record(&mut transaction, &ao).await?;
             }
 
             transaction
@@ -1,34 +1,161 @@
-use crate::app_state::ao_data::AoData;
+use crate::app_state::ao_registry::AoRegistryEntry;
+use crate::app_state::q_line_up_rules::QLineUpRules;
 use crate::shared::common_errors::AppError;
-use sqlx::{Postgres, Transaction};
+use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
-/// insert ao record if doesn't exist
+/// insert ao record if doesn't exist. Existing records only get missing fields filled in,
+/// so changes made through the registry are never overwritten by the hardcoded defaults.
 pub async fn insert_ao_record(
     transaction: &mut Transaction<'_, Postgres>,
-    ao: &AoData,
-    channel_id: &str,
+    ao: &AoRegistryEntry,
 ) -> Result<(), AppError> {
     let id = Uuid::new_v4();
-    let name = &ao.name;
-    let days = &ao.days;
-    let active = &ao.active;
+    let days = ao.days_db_value();
+    let schedule = ao.schedule_db_value()?;
     sqlx::query!(
         r#"
-    INSERT INTO ao_list (id, name, days, channel_id, active)
-    VALUES($1,$2,$3,$4,$5)
+    WITH existing AS (
+        UPDATE ao_list
+        SET active = COALESCE(active, $5),
+            friendly_name = COALESCE(friendly_name, $6),
+            ao_type = COALESCE(ao_type, $7),
+            address = COALESCE(address, $8),
+            map_url = COALESCE(map_url, $9),
+            schedule = COALESCE(schedule, $10)
+        WHERE channel_id = $4
+        RETURNING id
+    )
+    INSERT INTO ao_list (id, name, days, channel_id, active, friendly_name, ao_type, address, map_url, schedule)
+    SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, $10
+    WHERE NOT EXISTS (SELECT 1 FROM existing)
     ON CONFLICT (name)
-    DO UPDATE
-    SET days = EXCLUDED.days,
-        active = EXCLUDED.active;
+    DO NOTHING;
     "#,
         id,
-        name,
+        ao.name,
         days,
-        channel_id,
-        active
+        ao.channel_id,
+        ao.active,
+        ao.friendly_name,
+        ao.ao_type.db_name(),
+        ao.address,
+        ao.map_url,
+        schedule
     )
     .execute(&mut **transaction)
     .await?;
     Ok(())
 }
+
+/// create new ao in registry
+pub async fn create_ao_record(db_pool: &PgPool, ao: &AoRegistryEntry) -> Result<(), AppError> {
+    let id = Uuid::new_v4();
+    let days = ao.days_db_value();
+    let schedule = ao.schedule_db_value()?;
+    sqlx::query!(
+        r#"
+    INSERT INTO ao_list (id, name, days, channel_id, active, friendly_name, ao_type, address, map_url, schedule)
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10);
+    "#,
+        id,
+        ao.name,
+        days,
+        ao.channel_id,
+        ao.active,
+        ao.friendly_name,
+        ao.ao_type.db_name(),
+        ao.address,
+        ao.map_url,
+        schedule
+    )
+    .execute(db_pool)
+    .await?;
+    Ok(())
+}
+
+/// update existing ao in registry by name (name and channel id stay the same)
+pub async fn update_ao_record(db_pool: &PgPool, ao: &AoRegistryEntry) -> Result<(), AppError> {
+    let days = ao.days_db_value();
+    let schedule = ao.schedule_db_value()?;
+    sqlx::query!(
+        r#"
+    UPDATE ao_list
+    SET days = $2,
+        active = $3,
+        friendly_name = $4,
+        ao_type = $5,
+        address = $6,
+        map_url = $7,
+        schedule = $8
+    WHERE name = $1;
+    "#,
+        ao.name,
+        days,
+        ao.active,
+        ao.friendly_name,
+        ao.ao_type.db_name(),
+        ao.address,
+        ao.map_url,
+        schedule
+    )
+    .execute(db_pool)
+    .await?;
+    Ok(())
+}
+
+/// set emojis counted as HC on preblasts of ao
+pub async fn update_ao_hc_emojis(
+    db_pool: &PgPool,
+    name: &str,
+    emojis: &[String],
+) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+    UPDATE ao_list
+    SET hc_emojis = $2
+    WHERE name = $1;
+    "#,
+        name,
+        emojis
+    )
+    .execute(db_pool)
+    .await?;
+    Ok(())
+}
+
+/// set q line up rules of ao
+pub async fn update_ao_q_rules(
+    db_pool: &PgPool,
+    name: &str,
+    rules: &QLineUpRules,
+) -> Result<(), AppError> {
+    let rules = serde_json::to_value(rules)?;
+    sqlx::query!(
+        r#"
+    UPDATE ao_list
+    SET q_rules = $2
+    WHERE name = $1;
+    "#,
+        name,
+        rules
+    )
+    .execute(db_pool)
+    .await?;
+    Ok(())
+}
+
+/// retire ao (keeps history, but removes from active list)
+pub async fn retire_ao_record(db_pool: &PgPool, name: &str) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+    UPDATE ao_list
+    SET active = false
+    WHERE name = $1;
+    "#,
+        name
+    )
+    .execute(db_pool)
+    .await?;
+    Ok(())
+}
@@ -5,8 +5,19 @@ pub mod init;
 pub mod insert_ao;
 pub mod pax_parent_tree;
 pub mod queries;
+pub mod save_ao_closure;
+pub mod save_attendance;
 pub mod save_back_blast;
+pub mod save_black_diamond_rating;
+pub mod save_exercises;
+pub mod save_pax_merge;
 pub mod save_pre_blast;
 pub mod save_q_line_up;
+pub mod save_q_recurrence;
+pub mod save_q_swap;
 pub mod save_reaction_log;
+pub mod save_revision;
+pub mod save_route;
+pub mod save_scoring;
 pub mod save_user;
+pub mod save_user_role;
@@ -34,15 +34,16 @@ async fn back_blasts_by_channel_id_and_type(
         SELECT
             bb.id,
             al.name as ao,
-            string_to_array(lower(q), ',') as q,
-            string_to_array(lower(pax), ',') as pax,
+            coalesce(bal.q, '{}') as q,
+            coalesce(bal.pax, '{}') as pax,
             date,
             bb_type,
             bb.channel_id,
             title
         FROM back_blasts bb
         INNER JOIN ao_list al on bb.channel_id = al.channel_id
-        WHERE bb.bb_type = $1 AND bb.active = true AND bb.channel_id = $2
+        LEFT JOIN back_blast_attendance_lists bal on bal.back_blast_id = bb.id
+        WHERE bb.bb_type = $1 AND bb.active = true AND bb.deleted_at IS NULL AND bb.channel_id = $2
     )
     
     SELECT id, ao, channel_id, q as "q!", pax as "pax!", date, bb_type, title
@@ -104,16 +105,18 @@ async fn back_blasts_by_channel_id_and_date_range_and_type(
         SELECT
             bb.id,
             al.name as ao,
-            string_to_array(lower(q), ',') as q,
-            string_to_array(lower(pax), ',') as pax,
+            coalesce(bal.q, '{}') as q,
+            coalesce(bal.pax, '{}') as pax,
             date,
             bb_type,
             bb.channel_id,
             bb.title
         FROM back_blasts bb
         INNER JOIN ao_list al on bb.channel_id = al.channel_id
+        LEFT JOIN back_blast_attendance_lists bal on bal.back_blast_id = bb.id
         WHERE bb.bb_type = $1 
             AND bb.active = true 
+            AND bb.deleted_at IS NULL
             AND bb.channel_id = $2
             AND bb.date >= $3
             AND bb.date <= $4
@@ -0,0 +1,66 @@
+use crate::shared::common_errors::AppError;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// soft deleted backblast
+#[derive(Debug, Serialize)]
+pub struct DeletedBackBlastData {
+    pub id: Uuid,
+    pub ao: String,
+    pub channel_id: String,
+    pub date: NaiveDate,
+    pub bb_type: String,
+    pub title: Option<String>,
+    pub q: String,
+    pub pax: String,
+    pub deleted_at: NaiveDateTime,
+    pub deleted_by: Option<String>,
+}
+
+/// get all soft deleted backblasts, most recently deleted first
+pub async fn get_deleted_back_blasts(
+    db_pool: &PgPool,
+) -> Result<Vec<DeletedBackBlastData>, AppError> {
+    let rows: Vec<DeletedBackBlastData> = sqlx::query_as!(
+        DeletedBackBlastData,
+        r#"
+        SELECT
+            bb.id,
+            al.name as ao,
+            bb.channel_id,
+            bb.date,
+            bb.bb_type,
+            bb.title,
+            bb.q,
+            bb.pax,
+            bb.deleted_at as "deleted_at!",
+            bb.deleted_by
+        FROM back_blasts bb
+        INNER JOIN ao_list al on bb.channel_id = al.channel_id
+        WHERE bb.deleted_at IS NOT NULL
+        ORDER BY bb.deleted_at DESC;
+        "#
+    )
+    .fetch_all(db_pool)
+    .await?;
+    Ok(rows)
+}
+
+/// whether backblast was soft deleted
+pub async fn is_back_blast_deleted(db_pool: &PgPool, id: &str) -> Result<bool, AppError> {
+    let id = Uuid::from_str(id)?;
+    let row = sqlx::query!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM back_blasts WHERE id = $1 AND deleted_at IS NOT NULL
+        ) as "deleted!";
+        "#,
+        id
+    )
+    .fetch_one(db_pool)
+    .await?;
+    Ok(row.deleted)
+}
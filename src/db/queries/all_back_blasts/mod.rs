@@ -8,6 +8,7 @@ use uuid::Uuid;
 
 pub mod back_blasts_by_ao;
 pub mod calculate_bb_list_stats;
+pub mod deleted_back_blasts;
 pub mod pax_bd_stats;
 pub mod recent_bd_for_pax;
 
@@ -22,6 +23,7 @@ pub async fn get_all_dd(db_pool: &PgPool) -> Result<Vec<BackBlastJsonData>, AppE
 }
 
 /// get all backblast db data (double downs included) for purposes of csv download backup.
+/// Deleted backblasts are left out.
 pub async fn get_full_db_back_blasts(
     db_pool: &PgPool,
 ) -> Result<Vec<BackBlastFullJsonData>, AppError> {
@@ -32,17 +34,19 @@ pub async fn get_full_db_back_blasts(
         SELECT
             bb.id as id,
             al.name as ao,
-            string_to_array(lower(q), ',') as q,
-            string_to_array(lower(pax), ',') as pax,
+            coalesce(bal.q, '{}') as q,
+            coalesce(bal.pax, '{}') as pax,
             date,
             bb_type,
             bb.channel_id,
             bb.title,
             bb.moleskine,
-            string_to_array(lower(fngs), ',') as fngs,
+            bal.fngs,
             bb.ts
         FROM back_blasts bb
         INNER JOIN ao_list al on bb.channel_id = al.channel_id
+        LEFT JOIN back_blast_attendance_lists bal on bal.back_blast_id = bb.id
+        WHERE bb.deleted_at IS NULL
     )
     
     SELECT id, ao, channel_id, q as "q!", pax as "pax!", date, bb_type, title, moleskine, fngs, ts
@@ -68,17 +72,18 @@ pub async fn get_back_blast_by_id(
         SELECT
             bb.id as id,
             al.name as ao,
-            string_to_array(lower(q), ',') as q,
-            string_to_array(lower(pax), ',') as pax,
+            coalesce(bal.q, '{}') as q,
+            coalesce(bal.pax, '{}') as pax,
             date,
             bb_type,
             bb.channel_id,
             bb.title,
             bb.moleskine,
-            string_to_array(lower(fngs), ',') as fngs,
+            bal.fngs,
             bb.ts
         FROM back_blasts bb
         INNER JOIN ao_list al on bb.channel_id = al.channel_id
+        LEFT JOIN back_blast_attendance_lists bal on bal.back_blast_id = bb.id
     )
     
     SELECT id, ao, channel_id, q as "q!", pax as "pax!", date, bb_type, title, moleskine, fngs, ts
@@ -106,15 +111,16 @@ async fn get_all_by_type(
         SELECT
             bb.id,
             al.name as ao,
-            string_to_array(lower(q), ',') as q,
-            string_to_array(lower(pax), ',') as pax,
+            coalesce(bal.q, '{}') as q,
+            coalesce(bal.pax, '{}') as pax,
             date,
             bb_type,
             bb.channel_id,
             title
         FROM back_blasts bb
         INNER JOIN ao_list al on bb.channel_id = al.channel_id
-        WHERE bb.bb_type = $1 AND bb.active = true
+        LEFT JOIN back_blast_attendance_lists bal on bal.back_blast_id = bb.id
+        WHERE bb.bb_type = $1 AND bb.active = true AND bb.deleted_at IS NULL
     )
     
     SELECT id, ao, channel_id, q as "q!", pax as "pax!", date, bb_type, title
@@ -160,15 +166,16 @@ async fn get_all_with_date_range_by_type(
         SELECT
             bb.id,
             al.name as ao,
-            string_to_array(lower(q), ',') as q,
-            string_to_array(lower(pax), ',') as pax,
+            coalesce(bal.q, '{}') as q,
+            coalesce(bal.pax, '{}') as pax,
             date,
             bb_type,
             bb.channel_id,
             title
         FROM back_blasts bb
         INNER JOIN ao_list al on bb.channel_id = al.channel_id
-        WHERE bb.bb_type = $1 AND bb.active = true AND bb.date >= $2 AND bb.date <= $3
+        LEFT JOIN back_blast_attendance_lists bal on bal.back_blast_id = bb.id
+        WHERE bb.bb_type = $1 AND bb.active = true AND bb.deleted_at IS NULL AND bb.date >= $2 AND bb.date <= $3
     )
     
     SELECT id, ao, channel_id, q as "q!", pax as "pax!", date, bb_type, title
@@ -244,15 +251,16 @@ async fn get_list_with_pax_by_type(
         SELECT
             bb.id,
             al.name as ao,
-            string_to_array(lower(q), ',') as q,
-            string_to_array(lower(pax), ',') as pax,
+            coalesce(bal.q, '{}') as q,
+            coalesce(bal.pax, '{}') as pax,
             date,
             bb_type,
             bb.channel_id,
             title
         FROM back_blasts bb
         INNER JOIN ao_list al on bb.channel_id = al.channel_id
-        WHERE bb.bb_type = $1 AND bb.active = true
+        LEFT JOIN back_blast_attendance_lists bal on bal.back_blast_id = bb.id
+        WHERE bb.bb_type = $1 AND bb.active = true AND bb.deleted_at IS NULL
     )
     
     SELECT id, ao, channel_id, q as "q!", pax as "pax!", date, bb_type, title
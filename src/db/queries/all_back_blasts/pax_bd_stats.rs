@@ -3,6 +3,7 @@ use chrono::NaiveDate;
 use serde::Deserialize;
 use sqlx::PgPool;
 
+/// bd count and first bd per user. Matches attendance by slack id, falling back to name.
 pub async fn get_pax_bd_stats(db_pool: &PgPool) -> Result<Vec<PaxBdStats>, AppError> {
     let rows: Vec<PaxBdStats> = sqlx::query_as!(
         PaxBdStats,
@@ -10,22 +11,28 @@ pub async fn get_pax_bd_stats(db_pool: &PgPool) -> Result<Vec<PaxBdStats>, AppEr
     WITH backblast_participants AS (
     SELECT
         bb.date,
-        unnest(string_to_array(lower(pax), ',')) as user_name
+        a.name,
+        a.slack_id
     FROM
-        back_blasts bb
+        back_blast_attendance a
+            INNER JOIN back_blasts bb ON bb.id = a.back_blast_id
     WHERE
-        bb.bb_type = 'backblast' AND bb.active = true
+        bb.bb_type = 'backblast' AND bb.active = true AND bb.deleted_at IS NULL
 ),
 
      user_backblast_info AS (
          SELECT
-             user_name,
+             u.slack_id,
              COUNT(*) AS bd_count,
-             MIN(date) AS earliest_date
+             MIN(bp.date) AS earliest_date
          FROM
-             backblast_participants
+             users u
+                 INNER JOIN
+             backblast_participants bp
+                 ON bp.slack_id = u.slack_id
+                 OR (bp.slack_id IS NULL AND bp.name = lower(u.name))
          GROUP BY
-             user_name
+             u.slack_id
      )
 
 SELECT
@@ -36,7 +43,7 @@ SELECT
 FROM
     users u
         LEFT JOIN
-    user_backblast_info ubi ON lower(u.name) = lower(ubi.user_name);
+    user_backblast_info ubi ON u.slack_id = ubi.slack_id;
     "#
     )
     .fetch_all(db_pool)
@@ -45,6 +52,42 @@ FROM
     Ok(rows)
 }
 
+/// backblast dates (and AO channel) of every user, for streaks.
+/// Matches attendance the same way as bd stats.
+pub async fn get_pax_posts(db_pool: &PgPool) -> Result<Vec<PaxPost>, AppError> {
+    let rows: Vec<PaxPost> = sqlx::query_as!(
+        PaxPost,
+        r#"
+    SELECT DISTINCT
+        u.slack_id,
+        bb.channel_id as "channel_id!",
+        bb.date
+    FROM
+        back_blast_attendance a
+            INNER JOIN back_blasts bb ON bb.id = a.back_blast_id
+            INNER JOIN users u
+                ON a.slack_id = u.slack_id
+                OR (a.slack_id IS NULL AND a.name = lower(u.name))
+    WHERE
+        bb.bb_type = 'backblast'
+        AND bb.active = true
+        AND bb.deleted_at IS NULL
+        AND bb.channel_id IS NOT NULL;
+    "#
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(rows)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PaxPost {
+    pub slack_id: String,
+    pub channel_id: String,
+    pub date: NaiveDate,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct PaxBdStats {
     pub slack_id: String,
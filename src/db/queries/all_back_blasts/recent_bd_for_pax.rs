@@ -15,15 +15,16 @@ pub async fn get_recent_bd_for_pax(
         SELECT
             bb.id,
             al.name as ao,
-            string_to_array(lower(q), ',') as q,
-            string_to_array(lower(pax), ',') as pax,
+            coalesce(bal.q, '{}') as q,
+            coalesce(bal.pax, '{}') as pax,
             date,
             bb_type,
             bb.channel_id,
             bb.title
         FROM back_blasts bb
         INNER JOIN ao_list al on bb.channel_id = al.channel_id
-        WHERE bb.bb_type = 'backblast' AND bb.active = true
+        LEFT JOIN back_blast_attendance_lists bal on bal.back_blast_id = bb.id
+        WHERE bb.bb_type = 'backblast' AND bb.active = true AND bb.deleted_at IS NULL
     )
     
     SELECT id, ao, channel_id, q as "q!", pax as "pax!", date, bb_type, title
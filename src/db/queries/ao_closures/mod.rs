@@ -0,0 +1,63 @@
+use crate::app_state::ao_closures::{AoClosure, ClosureCalendar};
+use crate::app_state::ao_data::AO;
+use crate::shared::common_errors::AppError;
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// ao_closures row in db
+pub struct AoClosureDbData {
+    pub id: Uuid,
+    pub ao: Option<String>,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub recurring: bool,
+    pub reason: String,
+}
+
+impl From<AoClosureDbData> for AoClosure {
+    fn from(value: AoClosureDbData) -> Self {
+        AoClosure {
+            id: value.id.to_string(),
+            ao: value.ao.map(AO::from),
+            start_date: value.start_date,
+            end_date: value.end_date,
+            recurring: value.recurring,
+            reason: value.reason,
+        }
+    }
+}
+
+/// get closures that overlap date range (recurring closures always included)
+pub async fn get_closures_between_dates(
+    db_pool: &PgPool,
+    start_date: &NaiveDate,
+    end_date: &NaiveDate,
+) -> Result<Vec<AoClosure>, AppError> {
+    let rows: Vec<AoClosureDbData> = sqlx::query_as!(
+        AoClosureDbData,
+        r#"
+        SELECT id, ao, start_date, end_date, recurring, reason
+        FROM ao_closures
+        WHERE recurring = true
+            OR (start_date <= $2 AND end_date >= $1)
+        ORDER BY start_date;
+        "#,
+        start_date,
+        end_date
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(rows.into_iter().map(AoClosure::from).collect())
+}
+
+/// get closure calendar to check ao days against within date range
+pub async fn get_closure_calendar(
+    db_pool: &PgPool,
+    start_date: &NaiveDate,
+    end_date: &NaiveDate,
+) -> Result<ClosureCalendar, AppError> {
+    let closures = get_closures_between_dates(db_pool, start_date, end_date).await?;
+    Ok(ClosureCalendar::new(closures))
+}
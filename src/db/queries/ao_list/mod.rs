@@ -0,0 +1,140 @@
+use crate::app_state::ao_data::AoType;
+use crate::app_state::ao_registry::{AoRegistryEntry, AoScheduleDay};
+use crate::app_state::hard_commits::hc_emoji_list;
+use crate::app_state::q_line_up_rules::QLineUpRules;
+use crate::shared::common_errors::AppError;
+use sqlx::PgPool;
+use std::str::FromStr;
+
+/// ao_list row in db
+pub struct AoListDbItem {
+    pub name: String,
+    pub channel_id: Option<String>,
+    pub active: Option<bool>,
+    pub friendly_name: Option<String>,
+    pub ao_type: Option<String>,
+    pub address: Option<String>,
+    pub map_url: Option<String>,
+    pub schedule: Option<serde_json::Value>,
+}
+
+impl TryFrom<AoListDbItem> for AoRegistryEntry {
+    type Error = AppError;
+
+    fn try_from(value: AoListDbItem) -> Result<Self, Self::Error> {
+        let channel_id = value
+            .channel_id
+            .ok_or_else(|| AppError::General(format!("Missing channel id for {}", value.name)))?;
+
+        let schedule = value
+            .schedule
+            .map(serde_json::from_value::<Vec<AoScheduleDay>>)
+            .transpose()?
+            .unwrap_or_default();
+
+        let ao_type = value
+            .ao_type
+            .map(|ao_type| AoType::from_str(ao_type.as_str()))
+            .transpose()?
+            .unwrap_or(AoType::Bootcamp);
+
+        Ok(AoRegistryEntry {
+            friendly_name: value
+                .friendly_name
+                .unwrap_or_else(|| value.name.to_string()),
+            name: value.name,
+            channel_id,
+            ao_type,
+            address: value.address,
+            map_url: value.map_url,
+            schedule,
+            active: value.active.unwrap_or(false),
+        })
+    }
+}
+
+/// get full ao_list rows (active and retired)
+pub async fn get_ao_list_rows(db_pool: &PgPool) -> Result<Vec<AoListDbItem>, AppError> {
+    let rows: Vec<AoListDbItem> = sqlx::query_as!(
+        AoListDbItem,
+        r#"
+        SELECT name, channel_id, active, friendly_name, ao_type, address, map_url, schedule
+        FROM ao_list
+        ORDER BY name;
+        "#
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(rows)
+}
+
+/// get ao registry entries from ao_list. Rows that can't be parsed are skipped.
+pub async fn get_ao_registry_entries(db_pool: &PgPool) -> Result<Vec<AoRegistryEntry>, AppError> {
+    let rows = get_ao_list_rows(db_pool).await?;
+    let entries = rows
+        .into_iter()
+        .filter_map(|row| {
+            let name = row.name.to_string();
+            match AoRegistryEntry::try_from(row) {
+                Ok(entry) => Some(entry),
+                Err(err) => {
+                    println!("Skipping ao {} in registry: {:?}", name, err);
+                    None
+                }
+            }
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+/// get single ao registry entry by name
+pub async fn get_ao_registry_entry(
+    db_pool: &PgPool,
+    name: &str,
+) -> Result<Option<AoRegistryEntry>, AppError> {
+    let entries = get_ao_registry_entries(db_pool).await?;
+    let entry = entries.into_iter().find(|entry| entry.name == name);
+    Ok(entry)
+}
+
+/// get emojis counted as HC on preblasts of ao channel (default when not configured)
+pub async fn get_hc_emojis(db_pool: &PgPool, channel_id: &str) -> Result<Vec<String>, AppError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT hc_emojis
+        FROM ao_list
+        WHERE channel_id = $1;
+        "#,
+        channel_id
+    )
+    .fetch_optional(db_pool)
+    .await?;
+
+    let emojis = row.and_then(|row| row.hc_emojis).unwrap_or_default();
+    Ok(hc_emoji_list(&emojis))
+}
+
+/// q line up rules of AO, falling back to defaults when not set
+pub async fn get_q_line_up_rules(
+    db_pool: &PgPool,
+    channel_id: &str,
+) -> Result<QLineUpRules, AppError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT q_rules
+        FROM ao_list
+        WHERE channel_id = $1;
+        "#,
+        channel_id
+    )
+    .fetch_optional(db_pool)
+    .await?;
+
+    let rules = match row.and_then(|row| row.q_rules) {
+        Some(rules) => serde_json::from_value(rules)?,
+        None => QLineUpRules::default(),
+    };
+    Ok(rules)
+}
@@ -0,0 +1,158 @@
+use crate::shared::common_errors::AppError;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// black_diamond_ratings row in db, with Q of linked backblast
+#[derive(Debug, Serialize)]
+pub struct BlackDiamondRatingDbData {
+    pub id: Uuid,
+    pub back_blast_id: Option<Uuid>,
+    pub date: NaiveDate,
+    pub pax_count: i32,
+    pub vests_removed: i32,
+    pub miles: f32,
+    pub avg_hr: f32,
+    pub total: f32,
+    pub rated_by: Option<String>,
+    pub created: NaiveDateTime,
+    pub q: Option<String>,
+}
+
+/// get ratings within date range, oldest first
+pub async fn get_ratings_within_date_range(
+    db_pool: &PgPool,
+    start: &NaiveDate,
+    end: &NaiveDate,
+) -> Result<Vec<BlackDiamondRatingDbData>, AppError> {
+    let rows: Vec<BlackDiamondRatingDbData> = sqlx::query_as!(
+        BlackDiamondRatingDbData,
+        r#"
+        SELECT r.id, r.back_blast_id, r.date, r.pax_count, r.vests_removed, r.miles, r.avg_hr, r.total, r.rated_by, r.created, bb.q as "q?"
+        FROM black_diamond_ratings r
+        LEFT JOIN back_blasts bb on r.back_blast_id = bb.id
+        WHERE r.date BETWEEN $1 AND $2
+            AND (r.back_blast_id IS NULL OR bb.deleted_at IS NULL)
+        ORDER BY r.date, r.created;
+        "#,
+        start,
+        end
+    )
+    .fetch_all(db_pool)
+    .await?;
+    Ok(rows)
+}
+
+/// get most recent ratings, newest first
+pub async fn get_recent_ratings(
+    db_pool: &PgPool,
+    limit: i64,
+) -> Result<Vec<BlackDiamondRatingDbData>, AppError> {
+    let rows: Vec<BlackDiamondRatingDbData> = sqlx::query_as!(
+        BlackDiamondRatingDbData,
+        r#"
+        SELECT r.id, r.back_blast_id, r.date, r.pax_count, r.vests_removed, r.miles, r.avg_hr, r.total, r.rated_by, r.created, bb.q as "q?"
+        FROM black_diamond_ratings r
+        LEFT JOIN back_blasts bb on r.back_blast_id = bb.id
+        WHERE r.back_blast_id IS NULL OR bb.deleted_at IS NULL
+        ORDER BY r.date DESC, r.created DESC
+        LIMIT $1;
+        "#,
+        limit
+    )
+    .fetch_all(db_pool)
+    .await?;
+    Ok(rows)
+}
+
+/// get highest rated beatdowns of all time
+pub async fn get_top_ratings(
+    db_pool: &PgPool,
+    limit: i64,
+) -> Result<Vec<BlackDiamondRatingDbData>, AppError> {
+    let rows: Vec<BlackDiamondRatingDbData> = sqlx::query_as!(
+        BlackDiamondRatingDbData,
+        r#"
+        SELECT r.id, r.back_blast_id, r.date, r.pax_count, r.vests_removed, r.miles, r.avg_hr, r.total, r.rated_by, r.created, bb.q as "q?"
+        FROM black_diamond_ratings r
+        LEFT JOIN back_blasts bb on r.back_blast_id = bb.id
+        WHERE r.back_blast_id IS NULL OR bb.deleted_at IS NULL
+        ORDER BY r.total DESC, r.date DESC
+        LIMIT $1;
+        "#,
+        limit
+    )
+    .fetch_all(db_pool)
+    .await?;
+    Ok(rows)
+}
+
+/// how a rating compares to every rating done
+#[derive(Debug, Serialize)]
+pub struct RatingRank {
+    /// 1 is highest total
+    pub rank: i64,
+    pub total_ratings: i64,
+}
+
+/// rank of rating among all ratings (except ones of deleted backblasts)
+pub async fn get_rating_rank(db_pool: &PgPool, id: &Uuid) -> Result<RatingRank, AppError> {
+    let row = sqlx::query_as!(
+        RatingRank,
+        r#"
+        WITH ratings AS (
+            SELECT other.total
+            FROM black_diamond_ratings other
+            LEFT JOIN back_blasts bb on other.back_blast_id = bb.id
+            WHERE other.back_blast_id IS NULL OR bb.deleted_at IS NULL
+        )
+        SELECT
+            (SELECT COUNT(*) FROM ratings WHERE total > r.total) + 1 as "rank!",
+            (SELECT COUNT(*) FROM ratings) as "total_ratings!"
+        FROM black_diamond_ratings r
+        WHERE r.id = $1;
+        "#,
+        id
+    )
+    .fetch_one(db_pool)
+    .await?;
+    Ok(row)
+}
+
+/// ratings of beatdowns pax has Q'd
+#[derive(Debug, Serialize)]
+pub struct QRatingLeaderboardItem {
+    pub name: String,
+    pub beatdowns: i64,
+    pub avg_total: f64,
+    pub best_total: f32,
+}
+
+/// leaderboard of Qs by average rating of beatdowns they Q'd (or Co-Q'd)
+pub async fn get_q_rating_leaderboard(
+    db_pool: &PgPool,
+) -> Result<Vec<QRatingLeaderboardItem>, AppError> {
+    let rows: Vec<QRatingLeaderboardItem> = sqlx::query_as!(
+        QRatingLeaderboardItem,
+        r#"
+        SELECT
+            coalesce(u.name, bba.name) as "name!",
+            COUNT(DISTINCT r.back_blast_id) as "beatdowns!",
+            AVG(r.total)::float8 as "avg_total!",
+            MAX(r.total) as "best_total!"
+        FROM black_diamond_ratings r
+        INNER JOIN back_blasts bb on r.back_blast_id = bb.id
+        INNER JOIN back_blast_attendance bba on bba.back_blast_id = bb.id
+        LEFT JOIN users u on u.slack_id = bba.slack_id
+        WHERE bba.role IN ('q', 'coq')
+            AND bb.active = true
+            AND bb.deleted_at IS NULL
+        GROUP BY coalesce(u.name, bba.name)
+        ORDER BY 3 DESC, 2 DESC;
+        "#
+    )
+    .fetch_all(db_pool)
+    .await?;
+    Ok(rows)
+}
@@ -0,0 +1,238 @@
+use crate::app_state::exercises::{exercise_key, ExerciseEntry};
+use crate::app_state::exicon::ExiconEntry;
+use crate::shared::common_errors::AppError;
+use chrono::NaiveDate;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::str::FromStr;
+use uuid::Uuid;
+
+struct BackBlastExerciseRow {
+    name: String,
+    reps: Option<i32>,
+    seconds: Option<i32>,
+    meters: Option<i32>,
+    notes: Option<String>,
+}
+
+/// get exercises of backblast in order they were done
+pub async fn get_back_blast_exercises(
+    db_pool: &PgPool,
+    id: &str,
+) -> Result<Vec<ExerciseEntry>, AppError> {
+    let id = Uuid::from_str(id)?;
+    let rows: Vec<BackBlastExerciseRow> = sqlx::query_as!(
+        BackBlastExerciseRow,
+        r#"
+        SELECT coalesce(e.display_name, bbe.display_name) as "name!", bbe.reps, bbe.seconds, bbe.meters, bbe.notes
+        FROM back_blast_exercises bbe
+        LEFT JOIN exercises e on bbe.exercise_id = e.id
+        WHERE bbe.back_blast_id = $1
+        ORDER BY bbe.position;
+        "#,
+        id
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    let results = rows
+        .into_iter()
+        .map(|row| ExerciseEntry {
+            name: row.name,
+            amount: ExerciseEntry::amount_from_columns(row.reps, row.seconds, row.meters),
+            notes: row.notes,
+        })
+        .collect();
+    Ok(results)
+}
+
+/// how often exercise was done
+#[derive(Debug, Serialize)]
+pub struct ExerciseFrequency {
+    pub name: String,
+    /// number of backblasts exercise was part of
+    pub times: i64,
+    pub total_reps: Option<i64>,
+    pub total_seconds: Option<i64>,
+    pub total_meters: Option<i64>,
+    pub last_done: NaiveDate,
+}
+
+/// exercise frequency at ao (by channel id) within date range, most frequent first
+pub async fn exercise_frequency_by_ao(
+    db_pool: &PgPool,
+    channel_id: &str,
+    start: &NaiveDate,
+    end: &NaiveDate,
+) -> Result<Vec<ExerciseFrequency>, AppError> {
+    let rows: Vec<ExerciseFrequency> = sqlx::query_as!(
+        ExerciseFrequency,
+        r#"
+        SELECT
+            MIN(coalesce(e.display_name, bbe.display_name)) as "name!",
+            COUNT(DISTINCT bb.id) as "times!",
+            SUM(bbe.reps) as total_reps,
+            SUM(bbe.seconds) as total_seconds,
+            SUM(bbe.meters) as total_meters,
+            MAX(bb.date) as "last_done!"
+        FROM back_blast_exercises bbe
+        LEFT JOIN exercises e on bbe.exercise_id = e.id
+        INNER JOIN back_blasts bb on bbe.back_blast_id = bb.id
+        WHERE bb.channel_id = $1
+            AND bb.active = true
+            AND bb.deleted_at IS NULL
+            AND bb.date BETWEEN $2 AND $3
+        GROUP BY coalesce(e.name, bbe.name)
+        ORDER BY 2 DESC, 1;
+        "#,
+        channel_id,
+        start,
+        end
+    )
+    .fetch_all(db_pool)
+    .await?;
+    Ok(rows)
+}
+
+/// exercise frequency of backblasts pax has Q'd (or Co-Q'd) within date range, most frequent first
+pub async fn exercise_frequency_by_q(
+    db_pool: &PgPool,
+    name: &str,
+    start: &NaiveDate,
+    end: &NaiveDate,
+) -> Result<Vec<ExerciseFrequency>, AppError> {
+    let name = name.trim().to_lowercase();
+    let rows: Vec<ExerciseFrequency> = sqlx::query_as!(
+        ExerciseFrequency,
+        r#"
+        SELECT
+            MIN(coalesce(e.display_name, bbe.display_name)) as "name!",
+            COUNT(DISTINCT bb.id) as "times!",
+            SUM(bbe.reps) as total_reps,
+            SUM(bbe.seconds) as total_seconds,
+            SUM(bbe.meters) as total_meters,
+            MAX(bb.date) as "last_done!"
+        FROM back_blast_exercises bbe
+        LEFT JOIN exercises e on bbe.exercise_id = e.id
+        INNER JOIN back_blasts bb on bbe.back_blast_id = bb.id
+        INNER JOIN back_blast_attendance bba on bba.back_blast_id = bb.id
+        WHERE bba.name = $1
+            AND bba.role IN ('q', 'coq')
+            AND bb.active = true
+            AND bb.deleted_at IS NULL
+            AND bb.date BETWEEN $2 AND $3
+        GROUP BY coalesce(e.name, bbe.name)
+        ORDER BY 2 DESC, 1;
+        "#,
+        name,
+        start,
+        end
+    )
+    .fetch_all(db_pool)
+    .await?;
+    Ok(rows)
+}
+
+/// backblast exercise was done at
+#[derive(Debug, Serialize)]
+pub struct ExerciseHistoryItem {
+    pub back_blast_id: Uuid,
+    pub date: NaiveDate,
+    pub ao: String,
+    pub q: String,
+    pub reps: Option<i32>,
+    pub seconds: Option<i32>,
+    pub meters: Option<i32>,
+    pub notes: Option<String>,
+}
+
+/// backblasts exercise was done at, most recent first
+pub async fn exercise_history(
+    db_pool: &PgPool,
+    name: &str,
+) -> Result<Vec<ExerciseHistoryItem>, AppError> {
+    let key = exercise_key(name);
+    let rows: Vec<ExerciseHistoryItem> = sqlx::query_as!(
+        ExerciseHistoryItem,
+        r#"
+        SELECT
+            bb.id as back_blast_id,
+            bb.date,
+            bb.ao,
+            bb.q,
+            bbe.reps,
+            bbe.seconds,
+            bbe.meters,
+            bbe.notes
+        FROM back_blast_exercises bbe
+        LEFT JOIN exercises e on bbe.exercise_id = e.id
+        INNER JOIN back_blasts bb on bbe.back_blast_id = bb.id
+        WHERE coalesce(e.name, bbe.name) = $1
+            AND bb.active = true
+            AND bb.deleted_at IS NULL
+        ORDER BY bb.date DESC, bbe.position;
+        "#,
+        key
+    )
+    .fetch_all(db_pool)
+    .await?;
+    Ok(rows)
+}
+
+struct ExiconDbData {
+    name: String,
+    aliases: Vec<String>,
+    description: Option<String>,
+    category: Option<String>,
+    tags: Vec<String>,
+}
+
+impl From<ExiconDbData> for ExiconEntry {
+    fn from(value: ExiconDbData) -> Self {
+        ExiconEntry {
+            name: value.name,
+            aliases: value.aliases,
+            description: value.description.unwrap_or_default(),
+            category: value.category,
+            tags: value.tags,
+        }
+    }
+}
+
+/// get all exercises in exicon (ones with a description)
+pub async fn get_exicon_entries(db_pool: &PgPool) -> Result<Vec<ExiconEntry>, AppError> {
+    let rows: Vec<ExiconDbData> = sqlx::query_as!(
+        ExiconDbData,
+        r#"
+        SELECT display_name as name, aliases, description, category, tags
+        FROM exercises
+        WHERE description IS NOT NULL
+        ORDER BY name;
+        "#
+    )
+    .fetch_all(db_pool)
+    .await?;
+    Ok(rows.into_iter().map(ExiconEntry::from).collect())
+}
+
+/// get exicon exercise by name (or alias)
+pub async fn get_exicon_entry(
+    db_pool: &PgPool,
+    name: &str,
+) -> Result<Option<ExiconEntry>, AppError> {
+    let key = exercise_key(name);
+    let row: Option<ExiconDbData> = sqlx::query_as!(
+        ExiconDbData,
+        r#"
+        SELECT display_name as name, aliases, description, category, tags
+        FROM exercises
+        WHERE name = $1 OR $1 = ANY(aliases)
+        ORDER BY name = $1 DESC
+        LIMIT 1;
+        "#,
+        key
+    )
+    .fetch_optional(db_pool)
+    .await?;
+    Ok(row.map(ExiconEntry::from))
+}
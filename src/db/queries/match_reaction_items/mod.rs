@@ -6,13 +6,14 @@ use uuid::Uuid;
 
 /// data item in db that could be tied to a reaction/emoji
 pub enum ReactionRelatedItem {
-    /// returns id of preblast
-    PreBlast(String),
+    /// id of preblast and channel of its AO
+    PreBlast { id: String, channel_id: String },
 }
 
 struct ReactionRelatedDbItem {
     id: Uuid,
     item_type: String,
+    channel_id: String,
 }
 
 impl TryFrom<ReactionRelatedDbItem> for ReactionRelatedItem {
@@ -20,7 +21,10 @@ impl TryFrom<ReactionRelatedDbItem> for ReactionRelatedItem {
 
     fn try_from(value: ReactionRelatedDbItem) -> Result<Self, Self::Error> {
         match value.item_type.as_str() {
-            "pre-blast" => Ok(ReactionRelatedItem::PreBlast(value.id.to_string())),
+            "pre-blast" => Ok(ReactionRelatedItem::PreBlast {
+                id: value.id.to_string(),
+                channel_id: value.channel_id,
+            }),
             _ => Err(AppError::from("Couldn't match on reaction related item")),
         }
     }
@@ -36,9 +40,9 @@ pub async fn get_items_by_ts_and_channel(
     let result: Option<ReactionRelatedDbItem> = sqlx::query_as!(
         ReactionRelatedDbItem,
         r#"
-        SELECT 'pre-blast' as "item_type!", id
+        SELECT 'pre-blast' as "item_type!", id, channel_id
         FROM pre_blasts
-        WHERE ts = $1 AND channel_id = $2;
+        WHERE ts = $1 AND coalesce(post_channel_id, channel_id) = $2;
     "#,
         ts,
         channel_id
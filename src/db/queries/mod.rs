@@ -1,8 +1,20 @@
 pub mod all_back_blasts;
+pub mod ao_closures;
+pub mod ao_list;
+pub mod black_diamond_ratings;
+pub mod exercises;
 pub mod match_reaction_items;
 pub mod missing_back_blasts;
+pub mod pax_merge;
 pub mod pre_blasts;
 pub mod processed_items;
 pub mod q_line_up;
+pub mod q_recurrences;
+pub mod q_swaps;
 pub mod reactions_log;
+pub mod revisions;
+pub mod routes;
+pub mod ruck_stats;
+pub mod scoring;
+pub mod user_roles;
 pub mod users;
@@ -0,0 +1,266 @@
+use crate::app_state::pax_merge::{
+    replace_name_in_list, replace_name_in_parent_json, MergeColumn, PaxMergeChange, PaxMergePlan,
+    PaxMergeRequest,
+};
+use crate::db::queries::users::{get_user_by_name, get_user_by_slack_id};
+use crate::shared::common_errors::AppError;
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::str::FromStr;
+use uuid::Uuid;
+
+struct BackBlastNamesRow {
+    id: Uuid,
+    q: String,
+    pax: String,
+    fngs: Option<String>,
+}
+
+struct QsRow {
+    id: Uuid,
+    qs: String,
+}
+
+struct ParentPaxRow {
+    id: Uuid,
+    pax_name: String,
+    slack_id: Option<String>,
+    parent: serde_json::Value,
+}
+
+/// build plan of all changes needed to merge pax identity into slack user.
+pub async fn plan_pax_merge(
+    db_pool: &PgPool,
+    request: &PaxMergeRequest,
+) -> Result<PaxMergePlan, AppError> {
+    let from_name = request.from_name.trim().to_lowercase();
+    if from_name.is_empty() {
+        return Err(AppError::from("Missing name to merge"));
+    }
+
+    let to_user = get_user_by_slack_id(db_pool, request.to_slack_id.as_str())
+        .await?
+        .ok_or_else(|| AppError::from("User to merge into not found"))?;
+    let to_name = to_user.name.to_lowercase();
+    if to_name == from_name {
+        return Err(AppError::from("Cannot merge pax into themselves"));
+    }
+
+    // old slack account with the name being merged away
+    let from_slack_id = get_user_by_name(db_pool, from_name.as_str())
+        .await?
+        .and_then(|user| user.id)
+        .filter(|id| id != &request.to_slack_id);
+
+    let mut plan = PaxMergePlan::new(
+        from_name.as_str(),
+        from_slack_id.clone(),
+        to_name.as_str(),
+        request.to_slack_id.as_str(),
+    );
+
+    let back_blasts: Vec<BackBlastNamesRow> = sqlx::query_as!(
+        BackBlastNamesRow,
+        r#"
+        SELECT bb.id, bb.q, bb.pax, bb.fngs
+        FROM back_blasts bb
+        WHERE bb.id IN (
+            SELECT back_blast_id FROM back_blast_attendance WHERE name = $1
+        );
+        "#,
+        from_name
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    for row in back_blasts {
+        let lists = [
+            (MergeColumn::BackBlastQ, Some(row.q)),
+            (MergeColumn::BackBlastPax, Some(row.pax)),
+            (MergeColumn::BackBlastFngs, row.fngs),
+        ];
+        for (column, value) in lists {
+            if let Some(value) = value {
+                if let Some(after) = replace_name_in_list(&value, &from_name, &to_name) {
+                    plan.add_change(PaxMergeChange::new(
+                        column,
+                        row.id,
+                        Some(value),
+                        Some(after),
+                    ));
+                }
+            }
+        }
+    }
+
+    let q_line_up: Vec<QsRow> = sqlx::query_as!(
+        QsRow,
+        r#"
+        SELECT id, qs
+        FROM q_line_up
+        WHERE $1 = ANY(string_to_array(lower(qs), ','));
+        "#,
+        from_name
+    )
+    .fetch_all(db_pool)
+    .await?;
+    add_qs_changes(&mut plan, MergeColumn::QLineUpQs, q_line_up);
+
+    let pre_blasts: Vec<QsRow> = sqlx::query_as!(
+        QsRow,
+        r#"
+        SELECT id, qs
+        FROM pre_blasts
+        WHERE $1 = ANY(string_to_array(lower(qs), ','));
+        "#,
+        from_name
+    )
+    .fetch_all(db_pool)
+    .await?;
+    add_qs_changes(&mut plan, MergeColumn::PreBlastQs, pre_blasts);
+
+    let parents: Vec<ParentPaxRow> = sqlx::query_as!(
+        ParentPaxRow,
+        r#"
+        SELECT id, pax_name, slack_id, parent
+        FROM parent_pax_relationships;
+        "#
+    )
+    .fetch_all(db_pool)
+    .await?;
+    // pax name is unique, so only rename when target doesn't have own entry yet
+    let to_has_parent = parents
+        .iter()
+        .any(|row| row.pax_name.to_lowercase() == to_name);
+    for row in parents {
+        if !to_has_parent && row.pax_name.to_lowercase() == from_name {
+            plan.add_change(PaxMergeChange::new(
+                MergeColumn::ParentPaxName,
+                row.id,
+                Some(row.pax_name.to_string()),
+                Some(to_name.to_string()),
+            ));
+            plan.add_change(PaxMergeChange::new(
+                MergeColumn::ParentPaxSlackId,
+                row.id,
+                row.slack_id.clone(),
+                Some(request.to_slack_id.to_string()),
+            ));
+        }
+
+        if let Some(updated) = replace_name_in_parent_json(
+            &row.parent,
+            &from_name,
+            &to_name,
+            request.to_slack_id.as_str(),
+        ) {
+            plan.add_change(PaxMergeChange::new(
+                MergeColumn::ParentPaxParent,
+                row.id,
+                Some(row.parent.to_string()),
+                Some(updated.to_string()),
+            ));
+        }
+    }
+
+    if let Some(from_slack_id) = from_slack_id {
+        let reactions = sqlx::query!(
+            r#"
+            SELECT id
+            FROM reactions_log
+            WHERE slack_user = $1;
+            "#,
+            from_slack_id
+        )
+        .fetch_all(db_pool)
+        .await?;
+        for row in reactions {
+            plan.add_change(PaxMergeChange::new(
+                MergeColumn::ReactionSlackUser,
+                row.id,
+                Some(from_slack_id.to_string()),
+                Some(request.to_slack_id.to_string()),
+            ));
+        }
+    }
+
+    Ok(plan)
+}
+
+fn add_qs_changes(plan: &mut PaxMergePlan, column: MergeColumn, rows: Vec<QsRow>) {
+    for row in rows {
+        if let Some(after) = replace_name_in_list(&row.qs, &plan.from_name, &plan.to_name) {
+            plan.add_change(PaxMergeChange::new(
+                column,
+                row.id,
+                Some(row.qs),
+                Some(after),
+            ));
+        }
+    }
+}
+
+/// pax_merges row in db
+#[derive(Debug, Serialize)]
+pub struct PaxMergeDbData {
+    pub id: Uuid,
+    pub from_name: String,
+    pub from_slack_id: Option<String>,
+    pub to_name: String,
+    pub to_slack_id: String,
+    pub changes: serde_json::Value,
+    pub created_by: Option<String>,
+    pub created: NaiveDateTime,
+    pub undone: bool,
+    /// slack id alias pointed to before merge
+    pub previous_alias_slack_id: Option<String>,
+    pub previous_alias_merge_id: Option<Uuid>,
+}
+
+impl PaxMergeDbData {
+    pub fn parsed_changes(&self) -> Result<Vec<PaxMergeChange>, AppError> {
+        let changes = serde_json::from_value::<Vec<PaxMergeChange>>(self.changes.clone())?;
+        Ok(changes)
+    }
+}
+
+/// get merge by id
+pub async fn get_pax_merge(db_pool: &PgPool, id: &str) -> Result<Option<PaxMergeDbData>, AppError> {
+    let id = Uuid::from_str(id)?;
+    let result: Option<PaxMergeDbData> = sqlx::query_as!(
+        PaxMergeDbData,
+        r#"
+        SELECT id, from_name, from_slack_id, to_name, to_slack_id, changes, created_by, created, undone, previous_alias_slack_id, previous_alias_merge_id
+        FROM pax_merges
+        WHERE id = $1;
+        "#,
+        id
+    )
+    .fetch_optional(db_pool)
+    .await?;
+    Ok(result)
+}
+
+/// pax_aliases row in db
+#[derive(Debug, Serialize)]
+pub struct PaxAliasDbData {
+    pub alias: String,
+    pub slack_id: String,
+    pub merge_id: Option<Uuid>,
+}
+
+/// get all known aliases
+pub async fn get_pax_aliases(db_pool: &PgPool) -> Result<Vec<PaxAliasDbData>, AppError> {
+    let rows: Vec<PaxAliasDbData> = sqlx::query_as!(
+        PaxAliasDbData,
+        r#"
+        SELECT alias, slack_id, merge_id
+        FROM pax_aliases
+        ORDER BY alias;
+        "#
+    )
+    .fetch_all(db_pool)
+    .await?;
+    Ok(rows)
+}
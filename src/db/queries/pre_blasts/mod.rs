@@ -1,5 +1,5 @@
 use crate::shared::common_errors::AppError;
-use chrono::{NaiveDate, NaiveTime};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use sqlx::PgPool;
 use std::str::FromStr;
 use uuid::Uuid;
@@ -21,7 +21,7 @@ pub struct PreBlastJsonFullData {
     pub ts: Option<String>,
 }
 
-/// get all pre_blasts from db. mainly for syncing database
+/// get all pre_blasts from db (except cancelled or still pending). mainly for syncing database
 pub async fn get_all_pre_blasts(db: &PgPool) -> Result<Vec<PreBlastJsonFullData>, AppError> {
     let results: Vec<PreBlastJsonFullData> = sqlx::query_as!(
         PreBlastJsonFullData,
@@ -43,6 +43,8 @@ pub async fn get_all_pre_blasts(db: &PgPool) -> Result<Vec<PreBlastJsonFullData>
             pb.ts
         FROM pre_blasts pb
         INNER JOIN ao_list al on pb.channel_id = al.channel_id
+        WHERE pb.cancelled_at IS NULL
+          AND (pb.post_at IS NULL OR pb.ts IS NOT NULL)
     )
 
     SELECT
@@ -122,3 +124,141 @@ pub async fn get_pre_blast_by_id(
 
     Ok(result)
 }
+
+/// get id of latest preblast in channel for workout on date (or the day before),
+/// that has been posted and no backblast has been written from yet.
+pub async fn get_matching_pre_blast_id(
+    db: &PgPool,
+    channel_id: &str,
+    date: &NaiveDate,
+) -> Result<Option<String>, AppError> {
+    let result = sqlx::query!(
+        r#"
+    SELECT pb.id
+    FROM pre_blasts pb
+    WHERE pb.channel_id = $1
+      AND pb.cancelled_at IS NULL
+      AND (pb.post_at IS NULL OR pb.ts IS NOT NULL)
+      AND pb.date BETWEEN $2::date - 1 AND $2::date
+      AND NOT EXISTS (
+        SELECT 1 FROM back_blasts bb
+        WHERE bb.pre_blast_id = pb.id AND bb.deleted_at IS NULL
+      )
+    ORDER BY pb.date DESC
+    LIMIT 1;
+    "#,
+        channel_id,
+        date
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(result.map(|row| row.id.to_string()))
+}
+
+/// where preblast message was posted
+pub struct PostedPreBlast {
+    pub channel_id: String,
+    pub ts: String,
+}
+
+/// channel and ts of posted preblast message, if posted
+pub async fn get_posted_pre_blast(
+    db: &PgPool,
+    id: &str,
+) -> Result<Option<PostedPreBlast>, AppError> {
+    let id = Uuid::from_str(id)?;
+    let result: Option<PostedPreBlast> = sqlx::query_as!(
+        PostedPreBlast,
+        r#"
+    SELECT coalesce(post_channel_id, channel_id) as "channel_id!", ts as "ts!"
+    FROM pre_blasts
+    WHERE id = $1
+      AND ts IS NOT NULL;
+    "#,
+        id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(result)
+}
+
+/// preblast saved to be posted later by scheduler
+pub struct PendingPreBlast {
+    pub id: Uuid,
+    pub channel_id: String,
+    pub title: String,
+    pub date: NaiveDate,
+    pub start_time: NaiveTime,
+    pub post_at: DateTime<Utc>,
+    /// slack user that scheduled it
+    pub scheduled_by: Option<String>,
+}
+
+/// pending preblasts whose post time has come
+pub async fn get_due_pre_blasts(db: &PgPool) -> Result<Vec<PendingPreBlast>, AppError> {
+    let results: Vec<PendingPreBlast> = sqlx::query_as!(
+        PendingPreBlast,
+        r#"
+    SELECT id, channel_id, title, date, start_time, post_at as "post_at!", scheduled_by
+    FROM pre_blasts
+    WHERE post_at <= NOW()
+      AND ts IS NULL
+      AND cancelled_at IS NULL
+    ORDER BY post_at;
+    "#
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(results)
+}
+
+/// upcoming pending preblasts scheduled by user
+pub async fn get_pending_pre_blasts_by_user(
+    db: &PgPool,
+    slack_user: &str,
+) -> Result<Vec<PendingPreBlast>, AppError> {
+    let results: Vec<PendingPreBlast> = sqlx::query_as!(
+        PendingPreBlast,
+        r#"
+    SELECT id, channel_id, title, date, start_time, post_at as "post_at!", scheduled_by
+    FROM pre_blasts
+    WHERE scheduled_by = $1
+      AND post_at IS NOT NULL
+      AND ts IS NULL
+      AND cancelled_at IS NULL
+    ORDER BY post_at;
+    "#,
+        slack_user
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(results)
+}
+
+/// get preblast by id if still pending
+pub async fn get_pending_pre_blast(
+    db: &PgPool,
+    id: &str,
+) -> Result<Option<PendingPreBlast>, AppError> {
+    let id = Uuid::from_str(id)?;
+    let result: Option<PendingPreBlast> = sqlx::query_as!(
+        PendingPreBlast,
+        r#"
+    SELECT id, channel_id, title, date, start_time, post_at as "post_at!", scheduled_by
+    FROM pre_blasts
+    WHERE id = $1
+      AND post_at IS NOT NULL
+      AND ts IS NULL
+      AND cancelled_at IS NULL;
+    "#,
+        id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(result)
+}
@@ -136,3 +136,23 @@ pub struct RawQLineUpDbEntry {
     pub closed: bool,
     pub channel_id: String,
 }
+
+/// number of backblasts pax Q'd or co-Q'd
+pub async fn get_q_count_by_name(db_pool: &PgPool, name: &str) -> Result<i64, AppError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM back_blast_attendance a
+            INNER JOIN back_blasts bb on a.back_blast_id = bb.id
+            LEFT JOIN users u on u.slack_id = a.slack_id
+        WHERE lower(coalesce(u.name, a.name)) = lower($1)
+            AND a.role IN ('q', 'coq')
+            AND bb.active = true
+            AND bb.deleted_at IS NULL;
+        "#,
+        name
+    )
+    .fetch_one(db_pool)
+    .await?;
+    Ok(row.count)
+}
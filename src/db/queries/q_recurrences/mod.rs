@@ -0,0 +1,91 @@
+use crate::app_state::ao_data::AO;
+use crate::app_state::q_recurrence::{QRecurrence, RecurrenceRule};
+use crate::shared::common_errors::AppError;
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// q_recurrences row in db
+pub struct QRecurrenceDbData {
+    pub id: Uuid,
+    pub slack_id: String,
+    pub ao: String,
+    pub channel_id: String,
+    pub rule: String,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+}
+
+impl TryFrom<QRecurrenceDbData> for QRecurrence {
+    type Error = AppError;
+
+    fn try_from(value: QRecurrenceDbData) -> Result<Self, Self::Error> {
+        let rule = RecurrenceRule::try_from(value.rule.as_str()).map_err(AppError::General)?;
+        Ok(QRecurrence {
+            id: value.id.to_string(),
+            slack_id: value.slack_id,
+            ao: AO::from_saved(&value.ao, &value.channel_id),
+            rule,
+            start_date: value.start_date,
+            end_date: value.end_date,
+        })
+    }
+}
+
+fn map_recurrences(rows: Vec<QRecurrenceDbData>) -> Vec<QRecurrence> {
+    rows.into_iter()
+        .filter_map(|row| match QRecurrence::try_from(row) {
+            Ok(recurrence) => Some(recurrence),
+            Err(err) => {
+                println!("Skipping q recurrence: {:?}", err);
+                None
+            }
+        })
+        .collect()
+}
+
+/// recurrences not removed and not ended before date
+pub async fn get_active_recurrences(
+    db_pool: &PgPool,
+    from: &NaiveDate,
+) -> Result<Vec<QRecurrence>, AppError> {
+    let rows: Vec<QRecurrenceDbData> = sqlx::query_as!(
+        QRecurrenceDbData,
+        r#"
+        SELECT qr.id, qr.slack_id, al.name as ao, qr.channel_id, qr.rule, qr.start_date, qr.end_date
+        FROM q_recurrences qr
+        INNER JOIN ao_list al on qr.channel_id = al.channel_id
+        WHERE qr.deleted_at IS NULL
+            AND (qr.end_date IS NULL OR qr.end_date >= $1)
+        ORDER BY qr.created;
+        "#,
+        from
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(map_recurrences(rows))
+}
+
+/// recurrences of pax that haven't been removed
+pub async fn get_recurrences_by_user(
+    db_pool: &PgPool,
+    slack_id: &str,
+) -> Result<Vec<QRecurrence>, AppError> {
+    let rows: Vec<QRecurrenceDbData> = sqlx::query_as!(
+        QRecurrenceDbData,
+        r#"
+        SELECT qr.id, qr.slack_id, al.name as ao, qr.channel_id, qr.rule, qr.start_date, qr.end_date
+        FROM q_recurrences qr
+        INNER JOIN ao_list al on qr.channel_id = al.channel_id
+        WHERE qr.deleted_at IS NULL
+            AND qr.slack_id = $1
+        ORDER BY qr.created;
+        "#,
+        slack_id
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(map_recurrences(rows))
+}
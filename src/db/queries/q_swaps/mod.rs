@@ -0,0 +1,109 @@
+use crate::shared::common_errors::AppError;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::str::FromStr;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct QSwapRequestData {
+    pub id: Uuid,
+    pub ao: String,
+    pub channel_id: String,
+    pub date: NaiveDate,
+    pub requested_by: String,
+    pub original_qs: String,
+    pub offered_to: Vec<String>,
+    /// pending, accepted or cancelled
+    pub status: String,
+    pub accepted_by: Option<String>,
+    pub message_channel_id: Option<String>,
+    pub message_ts: Option<String>,
+    pub message_block_id: Option<String>,
+    pub created: NaiveDateTime,
+    pub resolved_at: Option<NaiveDateTime>,
+}
+
+pub async fn get_q_swap_request(
+    db_pool: &PgPool,
+    id: &str,
+) -> Result<Option<QSwapRequestData>, AppError> {
+    let id = Uuid::from_str(id)?;
+    let result: Option<QSwapRequestData> = sqlx::query_as!(
+        QSwapRequestData,
+        r#"
+        SELECT s.id, al.name as ao, s.channel_id, s.date, s.requested_by, s.original_qs,
+               s.offered_to, s.status, s.accepted_by, s.message_channel_id, s.message_ts,
+               s.message_block_id, s.created, s.resolved_at
+        FROM q_swap_requests s
+            INNER JOIN ao_list al on s.channel_id = al.channel_id
+        WHERE s.id = $1;
+        "#,
+        id
+    )
+    .fetch_optional(db_pool)
+    .await?;
+
+    Ok(result)
+}
+
+/// history of swap requests for q line up dates between 2 dates
+pub async fn get_q_swaps_between_dates(
+    db_pool: &PgPool,
+    start_date: &NaiveDate,
+    end_date: &NaiveDate,
+) -> Result<Vec<QSwapRequestData>, AppError> {
+    let results: Vec<QSwapRequestData> = sqlx::query_as!(
+        QSwapRequestData,
+        r#"
+        SELECT s.id, al.name as ao, s.channel_id, s.date, s.requested_by, s.original_qs,
+               s.offered_to, s.status, s.accepted_by, s.message_channel_id, s.message_ts,
+               s.message_block_id, s.created, s.resolved_at
+        FROM q_swap_requests s
+            INNER JOIN ao_list al on s.channel_id = al.channel_id
+        WHERE s.date BETWEEN $1 AND $2
+        ORDER BY s.date, s.created;
+        "#,
+        start_date,
+        end_date
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(results)
+}
+
+/// slack ids of pax that posted at AO at least `min_posts` times since date, most posts first
+pub async fn get_ao_regular_pax(
+    db_pool: &PgPool,
+    channel_id: &str,
+    since: &NaiveDate,
+    min_posts: i64,
+    limit: i64,
+) -> Result<Vec<String>, AppError> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT a.slack_id as "slack_id!"
+        FROM back_blast_attendance a
+            INNER JOIN back_blasts bb on a.back_blast_id = bb.id
+        WHERE bb.channel_id = $1
+            AND bb.date >= $2
+            AND bb.bb_type = 'backblast'
+            AND bb.active = true
+            AND bb.deleted_at IS NULL
+            AND a.slack_id IS NOT NULL
+        GROUP BY a.slack_id
+        HAVING COUNT(*) >= $3
+        ORDER BY COUNT(*) DESC
+        LIMIT $4;
+        "#,
+        channel_id,
+        since,
+        min_posts,
+        limit
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| row.slack_id).collect())
+}
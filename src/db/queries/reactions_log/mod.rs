@@ -1,7 +1,9 @@
 use crate::db::save_reaction_log::ReactionLogDbItem;
 use crate::shared::common_errors::AppError;
 use chrono::{NaiveDate, NaiveDateTime};
+use serde::Serialize;
 use sqlx::PgPool;
+use std::str::FromStr;
 use uuid::Uuid;
 
 /// download all reaction log items
@@ -84,3 +86,51 @@ ORDER BY rs.entity_id, rs.slack_user, rs.reaction;
 
     Ok(results)
 }
+
+/// pax with HC reaction currently on preblast
+#[derive(Debug, Serialize)]
+pub struct PreBlastHcItem {
+    pub slack_user: String,
+    pub name: String,
+    /// first time HC was added
+    pub hc_time: Option<NaiveDateTime>,
+}
+
+/// get pax that currently have one of HC emojis on preblast, in order of HC.
+/// Skin tone variants of emojis count as the same emoji.
+pub async fn get_pre_blast_hcs(
+    db: &PgPool,
+    pre_blast_id: &str,
+    emojis: &[String],
+) -> Result<Vec<PreBlastHcItem>, AppError> {
+    let pre_blast_id = Uuid::from_str(pre_blast_id)?;
+    let results: Vec<PreBlastHcItem> = sqlx::query_as!(
+        PreBlastHcItem,
+        r#"
+    WITH reaction_statuses AS (
+        SELECT slack_user,
+               lower(split_part(reaction, '::', 1)) AS reaction,
+               SUM(CASE WHEN reaction_added THEN 1 ELSE -1 END) AS net_added,
+               MIN(reaction_timestamp) FILTER (WHERE reaction_added = TRUE) AS first_added_time
+        FROM reactions_log
+        WHERE entity_type = 'pre_blast' AND entity_id = $1
+        GROUP BY slack_user, lower(split_part(reaction, '::', 1))
+    )
+
+    SELECT rs.slack_user as "slack_user!",
+           COALESCE(u.name, rs.slack_user) as "name!",
+           MIN(rs.first_added_time) as hc_time
+    FROM reaction_statuses rs
+             LEFT JOIN users u ON u.slack_id = rs.slack_user
+    WHERE rs.net_added > 0 AND rs.reaction = ANY($2)
+    GROUP BY rs.slack_user, u.name
+    ORDER BY MIN(rs.first_added_time);
+    "#,
+        pre_blast_id,
+        emojis
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(results)
+}
@@ -0,0 +1,67 @@
+use crate::app_state::revisions::RevisionEntity;
+use crate::shared::common_errors::AppError;
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// entity_revisions row in db
+#[derive(Debug, Serialize)]
+pub struct RevisionDbData {
+    pub id: Uuid,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub action: String,
+    pub source: String,
+    pub slack_user: Option<String>,
+    pub snapshot: serde_json::Value,
+    pub created: NaiveDateTime,
+}
+
+impl RevisionDbData {
+    /// whether revision belongs to entity
+    pub fn is_for(&self, entity: RevisionEntity, entity_id: &Uuid) -> bool {
+        self.entity_type == entity.to_string() && &self.entity_id == entity_id
+    }
+}
+
+/// get all revisions of entity, oldest first
+pub async fn get_revisions(
+    db_pool: &PgPool,
+    entity: RevisionEntity,
+    entity_id: &str,
+) -> Result<Vec<RevisionDbData>, AppError> {
+    let entity_id = Uuid::from_str(entity_id)?;
+    let rows: Vec<RevisionDbData> = sqlx::query_as!(
+        RevisionDbData,
+        r#"
+        SELECT id, entity_type, entity_id, action, source, slack_user, snapshot, created
+        FROM entity_revisions
+        WHERE entity_type = $1 AND entity_id = $2
+        ORDER BY created;
+        "#,
+        entity.to_string(),
+        entity_id
+    )
+    .fetch_all(db_pool)
+    .await?;
+    Ok(rows)
+}
+
+/// get revision by id
+pub async fn get_revision(db_pool: &PgPool, id: &str) -> Result<Option<RevisionDbData>, AppError> {
+    let id = Uuid::from_str(id)?;
+    let result: Option<RevisionDbData> = sqlx::query_as!(
+        RevisionDbData,
+        r#"
+        SELECT id, entity_type, entity_id, action, source, slack_user, snapshot, created
+        FROM entity_revisions
+        WHERE id = $1;
+        "#,
+        id
+    )
+    .fetch_optional(db_pool)
+    .await?;
+    Ok(result)
+}
@@ -0,0 +1,60 @@
+use crate::app_state::route_track::RouteSummary;
+use crate::shared::common_errors::AppError;
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// back_blast_routes row in db
+#[derive(Debug, Serialize)]
+pub struct BackBlastRouteDbData {
+    pub id: Uuid,
+    pub back_blast_id: Uuid,
+    pub file_id: String,
+    pub file_name: Option<String>,
+    pub file_type: String,
+    #[serde(flatten)]
+    pub summary: RouteSummary,
+    pub uploaded_by: Option<String>,
+    pub created: NaiveDateTime,
+}
+
+/// get routes uploaded with backblast
+pub async fn get_back_blast_routes(
+    db_pool: &PgPool,
+    back_blast_id: &str,
+) -> Result<Vec<BackBlastRouteDbData>, AppError> {
+    let back_blast_id = Uuid::from_str(back_blast_id)?;
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, back_blast_id, file_id, file_name, file_type, distance_miles, elevation_gain_ft, duration_secs, track, uploaded_by, created
+        FROM back_blast_routes
+        WHERE back_blast_id = $1
+        ORDER BY created;
+        "#,
+        back_blast_id
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    let mut results = Vec::with_capacity(rows.len());
+    for row in rows {
+        results.push(BackBlastRouteDbData {
+            id: row.id,
+            back_blast_id: row.back_blast_id,
+            file_id: row.file_id,
+            file_name: row.file_name,
+            file_type: row.file_type,
+            summary: RouteSummary {
+                distance_miles: row.distance_miles,
+                elevation_gain_ft: row.elevation_gain_ft,
+                duration_secs: row.duration_secs,
+                track: serde_json::from_value(row.track)?,
+            },
+            uploaded_by: row.uploaded_by,
+            created: row.created,
+        });
+    }
+    Ok(results)
+}
@@ -0,0 +1,111 @@
+use crate::app_state::ruck::RuckEntry;
+use crate::shared::common_errors::AppError;
+use chrono::NaiveDate;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// get ruck per pax of backblast
+pub async fn get_back_blast_ruck(
+    db_pool: &PgPool,
+    id: &str,
+) -> Result<HashMap<String, RuckEntry>, AppError> {
+    let id = Uuid::from_str(id)?;
+    let rows = sqlx::query!(
+        r#"
+        SELECT name, ruck_miles, ruck_weight
+        FROM back_blast_attendance
+        WHERE back_blast_id = $1 AND (ruck_miles IS NOT NULL OR ruck_weight IS NOT NULL);
+        "#,
+        id
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    let results = rows
+        .into_iter()
+        .map(|row| (row.name, RuckEntry::new(row.ruck_miles, row.ruck_weight)))
+        .collect();
+    Ok(results)
+}
+
+/// total rucked by pax
+#[derive(Debug, Serialize)]
+pub struct RuckStatsItem {
+    pub name: String,
+    /// number of beatdowns with ruck entered
+    pub rucks: i64,
+    pub miles: f64,
+    /// sum of miles times pounds carried
+    pub pound_miles: f64,
+}
+
+/// pax ranked by pound-miles within optional date range (all time when not set)
+pub async fn get_ruck_leaderboard(
+    db_pool: &PgPool,
+    start: Option<&NaiveDate>,
+    end: Option<&NaiveDate>,
+) -> Result<Vec<RuckStatsItem>, AppError> {
+    let rows: Vec<RuckStatsItem> = sqlx::query_as!(
+        RuckStatsItem,
+        r#"
+        SELECT
+            lower(coalesce(u.name, a.name)) as "name!",
+            COUNT(*) as "rucks!",
+            COALESCE(SUM(a.ruck_miles), 0)::float8 as "miles!",
+            COALESCE(SUM(a.ruck_miles * a.ruck_weight), 0)::float8 as "pound_miles!"
+        FROM back_blast_attendance a
+        INNER JOIN back_blasts bb on a.back_blast_id = bb.id
+        LEFT JOIN users u on u.slack_id = a.slack_id
+        WHERE (a.ruck_miles IS NOT NULL OR a.ruck_weight IS NOT NULL)
+            AND bb.active = true
+            AND bb.deleted_at IS NULL
+            AND ($1::date IS NULL OR bb.date >= $1)
+            AND ($2::date IS NULL OR bb.date <= $2)
+        GROUP BY 1
+        ORDER BY 4 DESC, 3 DESC;
+        "#,
+        start,
+        end
+    )
+    .fetch_all(db_pool)
+    .await?;
+    Ok(rows)
+}
+
+/// ruck totals of single pax within optional date range (all time when not set)
+pub async fn get_pax_ruck_stats(
+    db_pool: &PgPool,
+    slack_id: &str,
+    start: Option<&NaiveDate>,
+    end: Option<&NaiveDate>,
+) -> Result<Option<RuckStatsItem>, AppError> {
+    let row = sqlx::query_as!(
+        RuckStatsItem,
+        r#"
+        SELECT
+            lower(coalesce(u.name, MIN(a.name))) as "name!",
+            COUNT(*) as "rucks!",
+            COALESCE(SUM(a.ruck_miles), 0)::float8 as "miles!",
+            COALESCE(SUM(a.ruck_miles * a.ruck_weight), 0)::float8 as "pound_miles!"
+        FROM back_blast_attendance a
+        INNER JOIN back_blasts bb on a.back_blast_id = bb.id
+        LEFT JOIN users u on u.slack_id = a.slack_id
+        WHERE a.slack_id = $1
+            AND (a.ruck_miles IS NOT NULL OR a.ruck_weight IS NOT NULL)
+            AND bb.active = true
+            AND bb.deleted_at IS NULL
+            AND ($2::date IS NULL OR bb.date >= $2)
+            AND ($3::date IS NULL OR bb.date <= $3)
+        GROUP BY u.name;
+        "#,
+        slack_id,
+        start,
+        end
+    )
+    .fetch_optional(db_pool)
+    .await?;
+    Ok(row)
+}
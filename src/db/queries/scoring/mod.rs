@@ -0,0 +1,132 @@
+use crate::app_state::scoring::{ScoringField, ScoringFormula};
+use crate::shared::common_errors::AppError;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// scoring_formulas row in db
+#[derive(Debug)]
+struct ScoringFormulaDbRow {
+    id: Uuid,
+    channel_id: String,
+    name: String,
+    fields: serde_json::Value,
+    formula: String,
+    unit: Option<String>,
+    higher_is_better: bool,
+}
+
+/// scoring formula of ao channel
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoringFormulaDbData {
+    pub id: Uuid,
+    pub channel_id: String,
+    #[serde(flatten)]
+    pub formula: ScoringFormula,
+}
+
+impl TryFrom<ScoringFormulaDbRow> for ScoringFormulaDbData {
+    type Error = AppError;
+
+    fn try_from(row: ScoringFormulaDbRow) -> Result<Self, Self::Error> {
+        let fields: Vec<ScoringField> = serde_json::from_value(row.fields)?;
+        Ok(ScoringFormulaDbData {
+            id: row.id,
+            channel_id: row.channel_id,
+            formula: ScoringFormula {
+                name: row.name,
+                fields,
+                formula: row.formula,
+                unit: row.unit,
+                higher_is_better: row.higher_is_better,
+            },
+        })
+    }
+}
+
+/// get scoring formula of ao channel
+pub async fn get_scoring_formula(
+    db_pool: &PgPool,
+    channel_id: &str,
+) -> Result<Option<ScoringFormulaDbData>, AppError> {
+    let row = sqlx::query_as!(
+        ScoringFormulaDbRow,
+        r#"
+        SELECT id, channel_id, name, fields, formula, unit, higher_is_better
+        FROM scoring_formulas
+        WHERE channel_id = $1;
+        "#,
+        channel_id
+    )
+    .fetch_optional(db_pool)
+    .await?;
+    row.map(ScoringFormulaDbData::try_from).transpose()
+}
+
+/// workout_scores row in db, with Q of linked backblast
+#[derive(Debug, Serialize)]
+pub struct WorkoutScoreDbData {
+    pub id: Uuid,
+    pub back_blast_id: Option<Uuid>,
+    pub date: NaiveDate,
+    pub inputs: serde_json::Value,
+    pub formula: String,
+    pub score: f64,
+    pub scored_by: Option<String>,
+    pub created: NaiveDateTime,
+    pub q: Option<String>,
+}
+
+/// get scores of ao channel within date range, oldest first
+pub async fn get_scores_within_date_range(
+    db_pool: &PgPool,
+    channel_id: &str,
+    start: &NaiveDate,
+    end: &NaiveDate,
+) -> Result<Vec<WorkoutScoreDbData>, AppError> {
+    let rows: Vec<WorkoutScoreDbData> = sqlx::query_as!(
+        WorkoutScoreDbData,
+        r#"
+        SELECT s.id, s.back_blast_id, s.date, s.inputs, s.formula, s.score, s.scored_by, s.created, bb.q as "q?"
+        FROM workout_scores s
+        LEFT JOIN back_blasts bb on s.back_blast_id = bb.id
+        WHERE s.channel_id = $1 AND s.date BETWEEN $2 AND $3
+            AND (s.back_blast_id IS NULL OR bb.deleted_at IS NULL)
+        ORDER BY s.date, s.created;
+        "#,
+        channel_id,
+        start,
+        end
+    )
+    .fetch_all(db_pool)
+    .await?;
+    Ok(rows)
+}
+
+/// best scores of ao channel, respecting if higher or lower score is better
+pub async fn get_top_scores(
+    db_pool: &PgPool,
+    channel_id: &str,
+    higher_is_better: bool,
+    limit: i64,
+) -> Result<Vec<WorkoutScoreDbData>, AppError> {
+    let rows: Vec<WorkoutScoreDbData> = sqlx::query_as!(
+        WorkoutScoreDbData,
+        r#"
+        SELECT s.id, s.back_blast_id, s.date, s.inputs, s.formula, s.score, s.scored_by, s.created, bb.q as "q?"
+        FROM workout_scores s
+        LEFT JOIN back_blasts bb on s.back_blast_id = bb.id
+        WHERE s.channel_id = $1
+            AND (s.back_blast_id IS NULL OR bb.deleted_at IS NULL)
+        ORDER BY CASE WHEN $2 THEN -s.score ELSE s.score END, s.date DESC
+        LIMIT $3;
+        "#,
+        channel_id,
+        higher_is_better,
+        limit
+    )
+    .fetch_all(db_pool)
+    .await?;
+    Ok(rows)
+}
@@ -0,0 +1,78 @@
+use crate::app_state::ao_data::AO;
+use crate::app_state::roles::{Permission, Role, UserRoles};
+use crate::shared::common_errors::AppError;
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// user_roles row in db
+#[derive(Serialize)]
+pub struct UserRoleDbData {
+    pub id: Uuid,
+    pub slack_id: String,
+    pub role: String,
+    /// name of AO for site Q roles
+    pub ao: Option<String>,
+    pub channel_id: Option<String>,
+}
+
+impl TryFrom<&UserRoleDbData> for Role {
+    type Error = AppError;
+
+    fn try_from(value: &UserRoleDbData) -> Result<Self, Self::Error> {
+        let ao = value
+            .channel_id
+            .as_deref()
+            .map(|channel_id| AO::from_saved(value.ao.as_deref().unwrap_or_default(), channel_id));
+        Role::from_parts(value.role.as_str(), ao).map_err(AppError::General)
+    }
+}
+
+/// roles of slack user. Regular pax when none saved.
+pub async fn get_user_roles(db_pool: &PgPool, slack_id: &str) -> Result<UserRoles, AppError> {
+    let rows: Vec<UserRoleDbData> = sqlx::query_as!(
+        UserRoleDbData,
+        r#"
+        SELECT ur.id, ur.slack_id, ur.role, al.name as "ao?", ur.channel_id
+        FROM user_roles ur
+        LEFT JOIN ao_list al on ur.channel_id = al.channel_id
+        WHERE ur.slack_id = $1
+        ORDER BY ur.created;
+        "#,
+        slack_id
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    let roles = rows
+        .iter()
+        .filter_map(|row| Role::try_from(row).ok())
+        .collect::<Vec<Role>>();
+    Ok(UserRoles::new(slack_id, roles))
+}
+
+/// whether slack user is allowed to do action
+pub async fn user_can(
+    db_pool: &PgPool,
+    slack_id: &str,
+    permission: &Permission,
+) -> Result<bool, AppError> {
+    let roles = get_user_roles(db_pool, slack_id).await?;
+    Ok(roles.can(permission))
+}
+
+/// all saved roles
+pub async fn get_all_user_roles(db_pool: &PgPool) -> Result<Vec<UserRoleDbData>, AppError> {
+    let rows: Vec<UserRoleDbData> = sqlx::query_as!(
+        UserRoleDbData,
+        r#"
+        SELECT ur.id, ur.slack_id, ur.role, al.name as "ao?", ur.channel_id
+        FROM user_roles ur
+        LEFT JOIN ao_list al on ur.channel_id = al.channel_id
+        ORDER BY ur.role, ur.channel_id, ur.created;
+        "#
+    )
+    .fetch_all(db_pool)
+    .await?;
+    Ok(rows)
+}
@@ -0,0 +1,90 @@
+use crate::shared::common_errors::AppError;
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// new closure to save to db
+#[derive(Debug)]
+pub struct NewAoClosure {
+    /// ao name. `None` for region-wide closure
+    pub ao: Option<String>,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub recurring: bool,
+    pub reason: String,
+    /// slack id of user that added closure
+    pub created_by: Option<String>,
+}
+
+impl NewAoClosure {
+    /// validate closure before saving
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.end_date < self.start_date {
+            return Err(AppError::from("End date can't be before start date"));
+        }
+
+        if self.reason.trim().is_empty() {
+            return Err(AppError::from("Closure needs a reason"));
+        }
+
+        Ok(())
+    }
+}
+
+/// save closure to db, returning id.
+pub async fn save_closure(db_pool: &PgPool, closure: &NewAoClosure) -> Result<String, AppError> {
+    closure.validate()?;
+    let id = Uuid::new_v4();
+    sqlx::query!(
+        r#"
+        INSERT INTO ao_closures (id, ao, start_date, end_date, recurring, reason, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7);
+        "#,
+        id,
+        closure.ao,
+        closure.start_date,
+        closure.end_date,
+        closure.recurring,
+        closure.reason,
+        closure.created_by
+    )
+    .execute(db_pool)
+    .await?;
+
+    Ok(id.to_string())
+}
+
+/// remove closure from calendar
+pub async fn delete_closure(db_pool: &PgPool, id: &str) -> Result<(), AppError> {
+    let id = Uuid::from_str(id)?;
+    sqlx::query!(
+        r#"
+        DELETE FROM ao_closures
+        WHERE id = $1;
+        "#,
+        id
+    )
+    .execute(db_pool)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_closure_dates() {
+        let closure = NewAoClosure {
+            ao: None,
+            start_date: NaiveDate::from_ymd_opt(2024, 7, 5).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2024, 7, 4).unwrap(),
+            recurring: false,
+            reason: String::from("Holiday"),
+            created_by: None,
+        };
+        assert!(closure.validate().is_err());
+    }
+}
@@ -0,0 +1,299 @@
+use crate::app_state::backblast_data::BackBlastData;
+use crate::app_state::ruck::RuckEntry;
+use crate::shared::common_errors::AppError;
+use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, Transaction};
+use std::collections::HashSet;
+use std::fmt::Display;
+use uuid::Uuid;
+
+/// role pax had at a backblast
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttendanceRole {
+    Q,
+    CoQ,
+    Pax,
+    Fng,
+}
+
+impl Display for AttendanceRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            AttendanceRole::Q => "q",
+            AttendanceRole::CoQ => "coq",
+            AttendanceRole::Pax => "pax",
+            AttendanceRole::Fng => "fng",
+        };
+        write!(f, "{}", str)
+    }
+}
+
+impl From<&str> for AttendanceRole {
+    fn from(role: &str) -> Self {
+        match role {
+            "q" => AttendanceRole::Q,
+            "coq" => AttendanceRole::CoQ,
+            "fng" => AttendanceRole::Fng,
+            _ => AttendanceRole::Pax,
+        }
+    }
+}
+
+/// single back_blast_attendance row (without backblast id)
+#[derive(Debug, PartialEq)]
+pub struct AttendanceEntry {
+    /// lowercase f3 name
+    pub name: String,
+    pub role: AttendanceRole,
+    /// ruck distance and weight (rucking AOs)
+    pub ruck: RuckEntry,
+}
+
+/// attendance rows for backblast. Qs are sorted and first one is considered the Q.
+pub fn attendance_from_back_blast(data: &BackBlastData) -> Vec<AttendanceEntry> {
+    let pax = data.get_pax();
+    let mut entries = attendance_entries(
+        data.qs.iter().map(|name| name.as_str()),
+        pax.iter().map(|name| name.as_str()),
+        data.fngs.iter().map(|name| name.as_str()),
+    );
+    for entry in entries.iter_mut() {
+        if let Some(ruck) = data.ruck.get(&entry.name) {
+            entry.ruck = *ruck;
+        }
+    }
+    entries
+}
+
+/// attendance rows from comma separated back_blasts columns (pax column includes qs).
+pub fn attendance_from_columns(q: &str, pax: &str, fngs: Option<&str>) -> Vec<AttendanceEntry> {
+    attendance_entries(
+        q.split(','),
+        pax.split(','),
+        fngs.unwrap_or_default().split(','),
+    )
+}
+
+fn attendance_entries<'a>(
+    qs: impl Iterator<Item = &'a str>,
+    pax: impl Iterator<Item = &'a str>,
+    fngs: impl Iterator<Item = &'a str>,
+) -> Vec<AttendanceEntry> {
+    let mut qs = qs
+        .map(|name| name.trim().to_lowercase())
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<String>>();
+    qs.sort();
+    qs.dedup();
+
+    let fngs = fngs
+        .map(|name| name.trim().to_lowercase())
+        .collect::<Vec<String>>();
+
+    let mut pax = pax
+        .map(|name| name.trim().to_lowercase())
+        .filter(|name| !name.is_empty() && !qs.contains(name))
+        .collect::<Vec<String>>();
+    pax.sort();
+    pax.dedup();
+
+    let q_entries = qs.into_iter().enumerate().map(|(index, name)| {
+        let role = if index == 0 {
+            AttendanceRole::Q
+        } else {
+            AttendanceRole::CoQ
+        };
+        AttendanceEntry {
+            name,
+            role,
+            ruck: RuckEntry::default(),
+        }
+    });
+
+    let pax_entries = pax.into_iter().map(|name| {
+        let role = if fngs.contains(&name) {
+            AttendanceRole::Fng
+        } else {
+            AttendanceRole::Pax
+        };
+        AttendanceEntry {
+            name,
+            role,
+            ruck: RuckEntry::default(),
+        }
+    });
+
+    q_entries.chain(pax_entries).collect()
+}
+
+/// replace attendance rows for backblast. Slack id is filled in from users (or aliases) matched by name.
+pub async fn save_attendance(
+    transaction: &mut Transaction<'_, Postgres>,
+    back_blast_id: &Uuid,
+    entries: &[AttendanceEntry],
+) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+    DELETE FROM back_blast_attendance
+    WHERE back_blast_id = $1;
+    "#,
+        back_blast_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+
+    for entry in entries {
+        sqlx::query!(
+            r#"
+        INSERT INTO back_blast_attendance (back_blast_id, name, role, slack_id, ruck_miles, ruck_weight)
+        VALUES ($1, $2, $3, coalesce(
+            (SELECT slack_id FROM users WHERE lower(name) = $2 LIMIT 1),
+            (SELECT slack_id FROM pax_aliases WHERE alias = $2)
+        ), $4, $5);
+        "#,
+            back_blast_id,
+            entry.name,
+            entry.role.to_string(),
+            entry.ruck.miles,
+            entry.ruck.weight
+        )
+        .execute(&mut **transaction)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// rebuild attendance rows for backblasts from their q, pax and fngs columns.
+/// Ruck values are kept for pax matched by name or slack id.
+pub async fn rebuild_attendance(
+    transaction: &mut Transaction<'_, Postgres>,
+    back_blast_ids: &HashSet<Uuid>,
+) -> Result<(), AppError> {
+    for id in back_blast_ids {
+        let existing_ruck = sqlx::query!(
+            r#"
+        SELECT name, slack_id, ruck_miles, ruck_weight
+        FROM back_blast_attendance
+        WHERE back_blast_id = $1 AND (ruck_miles IS NOT NULL OR ruck_weight IS NOT NULL);
+        "#,
+            id
+        )
+        .fetch_all(&mut **transaction)
+        .await?;
+
+        let row = sqlx::query!(
+            r#"
+        SELECT q, pax, fngs
+        FROM back_blasts
+        WHERE id = $1;
+        "#,
+            id
+        )
+        .fetch_optional(&mut **transaction)
+        .await?;
+
+        if let Some(row) = row {
+            let entries = attendance_from_columns(&row.q, &row.pax, row.fngs.as_deref());
+            save_attendance(transaction, id, &entries).await?;
+        }
+
+        for ruck in existing_ruck {
+            sqlx::query!(
+                r#"
+            UPDATE back_blast_attendance
+            SET ruck_miles = $4, ruck_weight = $5
+            WHERE back_blast_id = $1 AND (name = $2 OR slack_id = $3);
+            "#,
+                id,
+                ruck.name,
+                ruck.slack_id,
+                ruck.ruck_miles,
+                ruck.ruck_weight
+            )
+            .execute(&mut **transaction)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::ao_data::AO;
+    use chrono::NaiveDate;
+    use std::collections::HashMap;
+
+    #[test]
+    fn roles_from_back_blast() {
+        let mut data = BackBlastData::new(
+            AO::Bleach,
+            HashSet::from([String::from("Stinger"), String::from("Backslash")]),
+            HashSet::from([String::from("Deepdish"), String::from("Bob")]),
+            NaiveDate::from_ymd_opt(2024, 7, 1).unwrap(),
+        );
+        data.fngs = HashSet::from([String::from("Bob")]);
+        let entries = attendance_from_back_blast(&data);
+        assert_eq!(
+            entries,
+            vec![
+                AttendanceEntry {
+                    name: String::from("backslash"),
+                    role: AttendanceRole::Q,
+                    ruck: RuckEntry::default(),
+                },
+                AttendanceEntry {
+                    name: String::from("stinger"),
+                    role: AttendanceRole::CoQ,
+                    ruck: RuckEntry::default(),
+                },
+                AttendanceEntry {
+                    name: String::from("bob"),
+                    role: AttendanceRole::Fng,
+                    ruck: RuckEntry::default(),
+                },
+                AttendanceEntry {
+                    name: String::from("deepdish"),
+                    role: AttendanceRole::Pax,
+                    ruck: RuckEntry::default(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ruck_from_back_blast() {
+        let mut data = BackBlastData::new(
+            AO::RuckershipWest,
+            HashSet::from([String::from("Stinger")]),
+            HashSet::from([String::from("Bob")]),
+            NaiveDate::from_ymd_opt(2024, 7, 5).unwrap(),
+        );
+        data.ruck = HashMap::from([(String::from("bob"), RuckEntry::new(Some(4.), Some(30.)))]);
+        let entries = attendance_from_back_blast(&data);
+        assert_eq!(entries[0].ruck, RuckEntry::default());
+        assert_eq!(entries[1].name, "bob");
+        assert_eq!(entries[1].ruck, RuckEntry::new(Some(4.), Some(30.)));
+    }
+
+    #[test]
+    fn roles_from_columns() {
+        let entries = attendance_from_columns("Stinger", "Bob,Stinger", Some("Bob"));
+        assert_eq!(
+            entries,
+            vec![
+                AttendanceEntry {
+                    name: String::from("stinger"),
+                    role: AttendanceRole::Q,
+                    ruck: RuckEntry::default(),
+                },
+                AttendanceEntry {
+                    name: String::from("bob"),
+                    role: AttendanceRole::Fng,
+                    ruck: RuckEntry::default(),
+                },
+            ]
+        );
+    }
+}
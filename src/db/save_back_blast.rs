@@ -1,4 +1,13 @@
-use crate::app_state::backblast_data::BackBlastData;
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::backblast_data::{BackBlastData, BackBlastType};
+use crate::app_state::exercises::{extract_exercises, ExerciseEntry};
+use crate::app_state::revisions::{RevisionAction, RevisionActor};
+use crate::db::save_attendance::{attendance_from_back_blast, save_attendance, AttendanceEntry};
+use crate::db::save_black_diamond_rating::link_ratings_to_back_blast;
+use crate::db::save_exercises::save_back_blast_exercises;
+use crate::db::save_revision::record_back_blast_revision;
+use crate::db::save_scoring::link_scores_to_back_blast;
 use crate::shared::common_errors::AppError;
 use chrono::NaiveDate;
 use sqlx::{PgPool, Postgres, Transaction};
@@ -18,16 +27,30 @@ pub struct BackBlastDbEntry {
     pub title: Option<String>,
     pub moleskine: Option<String>,
     pub fngs: Option<String>,
+    /// rows for back_blast_attendance
+    pub attendance: Vec<AttendanceEntry>,
+    /// rows for back_blast_exercises
+    pub exercises: Vec<ExerciseEntry>,
+    /// whether exercises were extracted from moleskine
+    pub exercises_extracted: bool,
 }
 
-impl From<&BackBlastData> for BackBlastDbEntry {
-    fn from(data: &BackBlastData) -> Self {
+impl BackBlastDbEntry {
+    /// db entry of backblast, with channel id of AO from registry
+    pub fn new(data: &BackBlastData, registry: &AoRegistry) -> Self {
         let mut q: Vec<String> = data.qs.clone().into_iter().collect();
         let mut pax: Vec<String> = data.get_pax().into_iter().collect();
         let mut fngs: Vec<String> = data.fngs.clone().into_iter().collect();
         q.sort();
         pax.sort();
         fngs.sort();
+        // fall back to best effort extraction when thang not entered
+        let (exercises, exercises_extracted) = if data.exercises.is_empty() {
+            let extracted = extract_exercises(data.moleskine.as_deref().unwrap_or_default());
+            (extracted, true)
+        } else {
+            (data.exercises.clone(), false)
+        };
         BackBlastDbEntry {
             id: Uuid::new_v4(),
             ao: data.ao.to_string(),
@@ -35,21 +58,29 @@ impl From<&BackBlastData> for BackBlastDbEntry {
             q: q.join(","),
             pax: pax.join(","),
             bb_type: data.bb_type.to_string(),
-            channel_id: data.ao.channel_id().to_string(),
+            channel_id: data.ao.channel_id(registry).to_string(),
             active: !data.ao.is_otb(),
             title: data.title.clone(),
             moleskine: data.moleskine.clone(),
             fngs: Some(fngs.join(",")),
+            attendance: attendance_from_back_blast(data),
+            exercises,
+            exercises_extracted,
         }
     }
 }
 
 /// save backblasts to db.
-pub async fn save_multiple(db_pool: &PgPool, list: &[BackBlastData]) -> Result<(), AppError> {
+pub async fn save_multiple(
+    db_pool: &PgPool,
+    list: &[BackBlastData],
+    registry: &AoRegistry,
+    actor: &RevisionActor,
+) -> Result<(), AppError> {
     let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
     for back_blast in list {
-        let db_bb = BackBlastDbEntry::from(back_blast);
-        save_back_blast(&mut transaction, &db_bb).await?;
+        let db_bb = BackBlastDbEntry::new(back_blast, registry);
+        save_back_blast(&mut transaction, &db_bb, actor).await?;
     }
 
     transaction
@@ -60,11 +91,16 @@ pub async fn save_multiple(db_pool: &PgPool, list: &[BackBlastData]) -> Result<(
 }
 
 /// sync backblasts to db (for syncing from other DB purposes).
-pub async fn sync_multiple(db_pool: &PgPool, list: &[BackBlastData]) -> Result<(), AppError> {
+pub async fn sync_multiple(
+    db_pool: &PgPool,
+    list: &[BackBlastData],
+    registry: &AoRegistry,
+) -> Result<(), AppError> {
+    let actor = RevisionActor::sync();
     let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
     for back_blast in list {
-        let db_bb = BackBlastDbEntry::from(back_blast);
-        sync_back_blast(&mut transaction, &db_bb).await?;
+        let db_bb = BackBlastDbEntry::new(back_blast, registry);
+        sync_back_blast(&mut transaction, &db_bb, &actor).await?;
     }
 
     transaction
@@ -74,16 +110,21 @@ pub async fn sync_multiple(db_pool: &PgPool, list: &[BackBlastData]) -> Result<(
     Ok(())
 }
 
-pub async fn save_single(db_pool: &PgPool, data: &BackBlastData) -> Result<String, AppError> {
-    let db_bb = BackBlastDbEntry::from(data);
-    let id = db_bb.id.to_string();
+/// save single backblast. `None` when one already exists for channel and date, so nothing was saved.
+pub async fn save_single(
+    db_pool: &PgPool,
+    data: &BackBlastData,
+    registry: &AoRegistry,
+    actor: &RevisionActor,
+) -> Result<Option<String>, AppError> {
+    let db_bb = BackBlastDbEntry::new(data, registry);
     let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
-    save_back_blast(&mut transaction, &db_bb).await?;
+    let saved_id = save_back_blast(&mut transaction, &db_bb, actor).await?;
     transaction
         .commit()
         .await
         .expect("Could not commit transaction");
-    Ok(id)
+    Ok(saved_id.map(|id| id.to_string()))
 }
 
 /// update timestamp for backblast, to be able to edit most recent message post.
@@ -108,15 +149,40 @@ pub async fn update_back_blast_ts(db_pool: &PgPool, id: &str, ts: String) -> Res
     Ok(())
 }
 
-/// update single back blast by id
+/// link backblast to preblast it was written from
+pub async fn link_back_blast_to_pre_blast(
+    db_pool: &PgPool,
+    id: &str,
+    pre_blast_id: &str,
+) -> Result<(), AppError> {
+    let id = Uuid::from_str(id)?;
+    let pre_blast_id = Uuid::from_str(pre_blast_id)?;
+    sqlx::query!(
+        r#"
+    UPDATE back_blasts
+    SET pre_blast_id = $2
+    WHERE id = $1
+    "#,
+        id,
+        pre_blast_id
+    )
+    .execute(db_pool)
+    .await?;
+    Ok(())
+}
+
+/// update single back blast by id. Errors when backblast was deleted.
 pub async fn update_back_blast(
     db_pool: &PgPool,
     id: &str,
     data: &BackBlastData,
+    registry: &AoRegistry,
+    actor: &RevisionActor,
 ) -> Result<(), AppError> {
     let uuid = Uuid::from_str(id)?;
-    let db_entry = BackBlastDbEntry::from(data);
-    sqlx::query!(
+    let db_entry = BackBlastDbEntry::new(data, registry);
+    let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
+    let updated = sqlx::query!(
         r#"
     UPDATE back_blasts
     SET ao = $2,
@@ -128,7 +194,8 @@ pub async fn update_back_blast(
         title = $8,
         moleskine = $9,
         fngs = $10
-    WHERE id = $1
+    WHERE id = $1 AND deleted_at IS NULL
+    RETURNING id;
     "#,
         uuid,
         db_entry.ao,
@@ -141,22 +208,128 @@ pub async fn update_back_blast(
         db_entry.moleskine,
         db_entry.fngs
     )
-    .execute(db_pool)
+    .fetch_optional(&mut *transaction)
     .await?;
+    if updated.is_none() {
+        return Err(AppError::from("Backblast not found or deleted"));
+    }
+    save_attendance(&mut transaction, &uuid, &db_entry.attendance).await?;
+    save_back_blast_exercises(
+        &mut transaction,
+        &uuid,
+        &db_entry.exercises,
+        db_entry.exercises_extracted,
+    )
+    .await?;
+    record_back_blast_revision(&mut transaction, &uuid, RevisionAction::Edit, actor).await?;
+    transaction
+        .commit()
+        .await
+        .expect("Could not commit transaction");
     Ok(())
 }
 
-/// insert backblast if not constraint on ao, date, and bb_type
+/// soft delete backblast. Row is kept so it can be restored, but no longer counts towards stats.
+pub async fn delete_back_blast(
+    db_pool: &PgPool,
+    id: &str,
+    actor: &RevisionActor,
+) -> Result<(), AppError> {
+    let uuid = Uuid::from_str(id)?;
+    let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
+    let deleted = sqlx::query!(
+        r#"
+    UPDATE back_blasts
+    SET active = false,
+        deleted_at = NOW(),
+        deleted_by = $2
+    WHERE id = $1 AND deleted_at IS NULL
+    RETURNING id;
+    "#,
+        uuid,
+        actor.slack_user
+    )
+    .fetch_optional(&mut *transaction)
+    .await?;
+    if deleted.is_none() {
+        return Err(AppError::from("Backblast not found or already deleted"));
+    }
+    record_back_blast_revision(&mut transaction, &uuid, RevisionAction::Deactivate, actor).await?;
+    transaction
+        .commit()
+        .await
+        .expect("Could not commit transaction");
+    Ok(())
+}
+
+/// restore soft deleted backblast.
+pub async fn restore_back_blast(
+    db_pool: &PgPool,
+    id: &str,
+    actor: &RevisionActor,
+) -> Result<(), AppError> {
+    let uuid = Uuid::from_str(id)?;
+    let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
+    let row = sqlx::query!(
+        r#"
+    SELECT ao, channel_id
+    FROM back_blasts
+    WHERE id = $1 AND deleted_at IS NOT NULL;
+    "#,
+        uuid
+    )
+    .fetch_optional(&mut *transaction)
+    .await?
+    .ok_or_else(|| AppError::from("Deleted backblast not found"))?;
+
+    // otb backblasts stay inactive
+    let active = !AO::from_saved(&row.ao, &row.channel_id).is_otb();
+    sqlx::query!(
+        r#"
+    UPDATE back_blasts
+    SET active = $2,
+        deleted_at = NULL,
+        deleted_by = NULL
+    WHERE id = $1;
+    "#,
+        uuid,
+        active
+    )
+    .execute(&mut *transaction)
+    .await?;
+    record_back_blast_revision(&mut transaction, &uuid, RevisionAction::Restore, actor).await?;
+    transaction
+        .commit()
+        .await
+        .expect("Could not commit transaction");
+    Ok(())
+}
+
+/// insert backblast if not constraint on ao, date, and bb_type.
+/// A deleted backblast with same constraint is replaced. Returns id of saved row (if saved).
 async fn save_back_blast(
     transaction: &mut Transaction<'_, Postgres>,
     db_bb: &BackBlastDbEntry,
-) -> Result<(), AppError> {
-    sqlx::query!(
+    actor: &RevisionActor,
+) -> Result<Option<Uuid>, AppError> {
+    let inserted = sqlx::query!(
         r#"
     INSERT INTO back_blasts (id, ao, q, pax, date, bb_type, channel_id, active, title, moleskine, fngs)
     VALUES($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11)
     ON CONFLICT ON CONSTRAINT back_blasts_channel_id_date_bb_type_key
-        DO NOTHING;
+        DO UPDATE
+    SET ao = EXCLUDED.ao,
+        q = EXCLUDED.q,
+        pax = EXCLUDED.pax,
+        active = EXCLUDED.active,
+        title = EXCLUDED.title,
+        moleskine = EXCLUDED.moleskine,
+        fngs = EXCLUDED.fngs,
+        ts = NULL,
+        deleted_at = NULL,
+        deleted_by = NULL
+    WHERE back_blasts.deleted_at IS NOT NULL
+    RETURNING id;
     "#,
         db_bb.id,
         db_bb.ao,
@@ -170,18 +343,37 @@ async fn save_back_blast(
         db_bb.moleskine,
         db_bb.fngs
     )
-    .execute(&mut **transaction)
+    .fetch_optional(&mut **transaction)
     .await?;
 
-    Ok(())
+    // only set attendance when backblast was actually inserted
+    if let Some(row) = &inserted {
+        save_attendance(transaction, &row.id, &db_bb.attendance).await?;
+        save_back_blast_exercises(
+            transaction,
+            &row.id,
+            &db_bb.exercises,
+            db_bb.exercises_extracted,
+        )
+        .await?;
+        if db_bb.bb_type == BackBlastType::BackBlast.to_string() {
+            link_ratings_to_back_blast(transaction, &row.id, &db_bb.channel_id, &db_bb.date)
+                .await?;
+            link_scores_to_back_blast(transaction, &row.id, &db_bb.channel_id, &db_bb.date).await?;
+        }
+        record_back_blast_revision(transaction, &row.id, RevisionAction::Create, actor).await?;
+    }
+
+    Ok(inserted.map(|row| row.id))
 }
 
 /// update backblast with expectation on sync from other db
 async fn sync_back_blast(
     transaction: &mut Transaction<'_, Postgres>,
     db_bb: &BackBlastDbEntry,
+    actor: &RevisionActor,
 ) -> Result<(), AppError> {
-    sqlx::query!(
+    let synced = sqlx::query!(
         r#"
     INSERT INTO back_blasts (id, ao, q, pax, date, bb_type, channel_id, active, title, moleskine, fngs)
     VALUES($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11)
@@ -196,7 +388,9 @@ async fn sync_back_blast(
         active = EXCLUDED.active,
         title = EXCLUDED.title,
         moleskine = EXCLUDED.moleskine,
-        fngs = EXCLUDED.fngs;
+        fngs = EXCLUDED.fngs
+    WHERE back_blasts.deleted_at IS NULL
+    RETURNING id;
     "#,
         db_bb.id,
         db_bb.ao,
@@ -210,8 +404,14 @@ async fn sync_back_blast(
         db_bb.moleskine,
         db_bb.fngs
     )
-        .execute(&mut **transaction)
-        .await?;
+    .fetch_optional(&mut **transaction)
+    .await?;
+
+    // existing row keeps its id on conflict, deleted rows are left alone
+    if let Some(synced) = synced {
+        save_attendance(transaction, &synced.id, &db_bb.attendance).await?;
+        record_back_blast_revision(transaction, &synced.id, RevisionAction::Sync, actor).await?;
+    }
 
     Ok(())
 }
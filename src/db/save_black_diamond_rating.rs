@@ -0,0 +1,71 @@
+use crate::shared::common_errors::AppError;
+use chrono::NaiveDate;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// new black diamond rating to save to db
+#[derive(Debug, PartialEq)]
+pub struct NewBlackDiamondRating {
+    /// channel of rated beatdown
+    pub channel_id: String,
+    pub date: NaiveDate,
+    pub pax_count: i32,
+    pub vests_removed: i32,
+    pub miles: f32,
+    pub avg_hr: f32,
+    pub total: f32,
+    /// slack id of user that rated
+    pub rated_by: Option<String>,
+}
+
+/// save rating, linked to backblast of the day (if already posted). Returns id.
+pub async fn save_rating(
+    db_pool: &PgPool,
+    rating: &NewBlackDiamondRating,
+) -> Result<Uuid, AppError> {
+    let id = Uuid::new_v4();
+    sqlx::query!(
+        r#"
+        INSERT INTO black_diamond_ratings (id, back_blast_id, channel_id, date, pax_count, vests_removed, miles, avg_hr, total, rated_by)
+        VALUES ($1, (
+            SELECT id FROM back_blasts
+            WHERE channel_id = $2 AND date = $3 AND bb_type = 'backblast' AND deleted_at IS NULL
+            LIMIT 1
+        ), $2, $3, $4, $5, $6, $7, $8, $9);
+        "#,
+        id,
+        rating.channel_id,
+        rating.date,
+        rating.pax_count,
+        rating.vests_removed,
+        rating.miles,
+        rating.avg_hr,
+        rating.total,
+        rating.rated_by
+    )
+    .execute(db_pool)
+    .await?;
+    Ok(id)
+}
+
+/// link ratings done before backblast was posted to the backblast
+pub async fn link_ratings_to_back_blast(
+    transaction: &mut Transaction<'_, Postgres>,
+    back_blast_id: &Uuid,
+    channel_id: &str,
+    date: &NaiveDate,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+        UPDATE black_diamond_ratings
+        SET back_blast_id = $1
+        WHERE back_blast_id IS NULL AND channel_id = $2 AND date = $3;
+        "#,
+        back_blast_id,
+        channel_id,
+        date
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,183 @@
+use crate::app_state::exercises::{exercise_key, extract_exercises, ExerciseEntry};
+use crate::app_state::exicon::ExiconEntry;
+use crate::shared::common_errors::AppError;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// replace exercises of backblast. Exercises are linked to dictionary by name or alias,
+/// ones not in dictionary are kept on backblast only (and linked once added to exicon).
+pub async fn save_back_blast_exercises(
+    transaction: &mut Transaction<'_, Postgres>,
+    back_blast_id: &Uuid,
+    exercises: &[ExerciseEntry],
+    extracted: bool,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+    DELETE FROM back_blast_exercises
+    WHERE back_blast_id = $1;
+    "#,
+        back_blast_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+
+    for (position, exercise) in exercises.iter().enumerate() {
+        let key = exercise_key(&exercise.name);
+        sqlx::query!(
+            r#"
+        INSERT INTO back_blast_exercises (back_blast_id, position, exercise_id, name, display_name, reps, seconds, meters, notes, extracted)
+        VALUES (
+            $1,
+            $2,
+            (SELECT e.id FROM exercises e WHERE e.name = $3 OR $3 = ANY(e.aliases) ORDER BY e.name = $3 DESC LIMIT 1),
+            $3,
+            $4,
+            $5,
+            $6,
+            $7,
+            $8,
+            $9
+        );
+        "#,
+            back_blast_id,
+            position as i32,
+            key,
+            exercise.name.trim(),
+            exercise.reps(),
+            exercise.seconds(),
+            exercise.meters(),
+            exercise.notes,
+            extracted
+        )
+        .execute(&mut **transaction)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// extract exercises from moleskine of every backblast that has none yet.
+/// Returns number of backblasts that had exercises extracted.
+pub async fn backfill_extracted_exercises(db_pool: &PgPool) -> Result<usize, AppError> {
+    let rows = sqlx::query!(
+        r#"
+    SELECT bb.id, bb.moleskine
+    FROM back_blasts bb
+    WHERE bb.moleskine IS NOT NULL
+        AND NOT EXISTS (
+            SELECT 1 FROM back_blast_exercises bbe WHERE bbe.back_blast_id = bb.id
+        );
+    "#
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
+    let mut count = 0;
+    for row in rows {
+        let exercises = extract_exercises(row.moleskine.as_deref().unwrap_or_default());
+        if exercises.is_empty() {
+            continue;
+        }
+        save_back_blast_exercises(&mut transaction, &row.id, &exercises, true).await?;
+        count += 1;
+    }
+    transaction
+        .commit()
+        .await
+        .expect("Could not commit transaction");
+    Ok(count)
+}
+
+/// add or update exicon entry. When `original` name is passed in, that entry is renamed.
+pub async fn save_exicon_entry(
+    db_pool: &PgPool,
+    original: Option<&str>,
+    entry: &ExiconEntry,
+) -> Result<(), AppError> {
+    entry.validate()?;
+    let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
+    if let Some(original) = original.map(exercise_key) {
+        sqlx::query!(
+            r#"
+        UPDATE exercises
+        SET name = $2
+        WHERE name = $1 AND name <> $2;
+        "#,
+            original,
+            entry.key()
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+    upsert_exicon_entry(&mut transaction, entry).await?;
+    transaction
+        .commit()
+        .await
+        .expect("Could not commit transaction");
+    Ok(())
+}
+
+/// import exicon entries (ie. from csv), updating existing ones. Returns number imported.
+pub async fn import_exicon(db_pool: &PgPool, entries: &[ExiconEntry]) -> Result<usize, AppError> {
+    let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
+    let mut count = 0;
+    for entry in entries {
+        if entry.validate().is_err() {
+            continue;
+        }
+        upsert_exicon_entry(&mut transaction, entry).await?;
+        count += 1;
+    }
+    transaction
+        .commit()
+        .await
+        .expect("Could not commit transaction");
+    Ok(count)
+}
+
+async fn upsert_exicon_entry(
+    transaction: &mut Transaction<'_, Postgres>,
+    entry: &ExiconEntry,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+    INSERT INTO exercises (id, name, display_name, description, category, aliases, tags, updated)
+    VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())
+    ON CONFLICT (name)
+        DO UPDATE
+    SET display_name = EXCLUDED.display_name,
+        description = EXCLUDED.description,
+        category = EXCLUDED.category,
+        aliases = EXCLUDED.aliases,
+        tags = EXCLUDED.tags,
+        updated = NOW();
+    "#,
+        Uuid::new_v4(),
+        entry.key(),
+        entry.name,
+        entry.description,
+        entry.category,
+        &entry.aliases,
+        &entry.tags
+    )
+    .execute(&mut **transaction)
+    .await?;
+
+    // link backblast exercises written before entry was in exicon
+    sqlx::query!(
+        r#"
+    UPDATE back_blast_exercises
+    SET exercise_id = e.id
+    FROM exercises e
+    WHERE back_blast_exercises.exercise_id IS NULL
+        AND e.name = $1
+        AND (back_blast_exercises.name = e.name OR back_blast_exercises.name = ANY(e.aliases));
+    "#,
+        entry.key()
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
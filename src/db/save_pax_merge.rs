@@ -0,0 +1,252 @@
+use crate::app_state::pax_merge::{back_blast_ids, MergeColumn, PaxMergeChange, PaxMergePlan};
+use crate::db::queries::pax_merge::get_pax_merge;
+use crate::db::save_attendance::rebuild_attendance;
+use crate::shared::common_errors::AppError;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// apply merge plan, record alias and changes. Returns id of merge.
+pub async fn apply_pax_merge(
+    db_pool: &PgPool,
+    plan: &PaxMergePlan,
+    created_by: Option<String>,
+) -> Result<String, AppError> {
+    let id = Uuid::new_v4();
+    let changes = serde_json::to_value(&plan.changes)?;
+    let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
+
+    // alias may already point somewhere from earlier merge, keep it to restore on undo
+    let previous_alias = sqlx::query!(
+        r#"
+    SELECT slack_id, merge_id
+    FROM pax_aliases
+    WHERE alias = $1;
+    "#,
+        plan.from_name
+    )
+    .fetch_optional(&mut *transaction)
+    .await?;
+    let (previous_slack_id, previous_merge_id) = match previous_alias {
+        Some(row) => (Some(row.slack_id), row.merge_id),
+        None => (None, None),
+    };
+
+    sqlx::query!(
+        r#"
+    INSERT INTO pax_merges (id, from_name, from_slack_id, to_name, to_slack_id, changes, created_by, previous_alias_slack_id, previous_alias_merge_id)
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9);
+    "#,
+        id,
+        plan.from_name,
+        plan.from_slack_id,
+        plan.to_name,
+        plan.to_slack_id,
+        changes,
+        created_by,
+        previous_slack_id,
+        previous_merge_id
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    sqlx::query!(
+        r#"
+    INSERT INTO pax_aliases (alias, slack_id, merge_id)
+    VALUES ($1, $2, $3)
+    ON CONFLICT (alias)
+        DO UPDATE
+        SET slack_id = EXCLUDED.slack_id,
+            merge_id = EXCLUDED.merge_id;
+    "#,
+        plan.from_name,
+        plan.to_slack_id,
+        id
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    // alias is in place before rebuilding, so old name resolves to user as well
+    for change in plan.changes.iter() {
+        apply_change(&mut transaction, change).await?;
+    }
+    rebuild_attendance(&mut transaction, &plan.back_blast_ids()).await?;
+
+    transaction
+        .commit()
+        .await
+        .expect("Could not commit transaction");
+    Ok(id.to_string())
+}
+
+/// revert merge by applying its changes in reverse. Rows changed since merge are left alone.
+/// Alias goes back to what it pointed to before merge (or is removed if it was new).
+pub async fn undo_pax_merge(db_pool: &PgPool, id: &str) -> Result<(), AppError> {
+    let merge = get_pax_merge(db_pool, id)
+        .await?
+        .ok_or_else(|| AppError::from("Merge not found"))?;
+    if merge.undone {
+        return Err(AppError::from("Merge was already undone"));
+    }
+
+    let changes = merge.parsed_changes()?;
+    let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
+    match &merge.previous_alias_slack_id {
+        Some(previous_slack_id) => {
+            sqlx::query!(
+                r#"
+    UPDATE pax_aliases
+    SET slack_id = $2,
+        merge_id = $3
+    WHERE merge_id = $1;
+    "#,
+                merge.id,
+                previous_slack_id,
+                merge.previous_alias_merge_id
+            )
+            .execute(&mut *transaction)
+            .await?;
+        }
+        None => {
+            sqlx::query!(
+                r#"
+    DELETE FROM pax_aliases
+    WHERE merge_id = $1;
+    "#,
+                merge.id
+            )
+            .execute(&mut *transaction)
+            .await?;
+        }
+    }
+
+    for change in changes.iter().rev() {
+        apply_change(&mut transaction, &change.reverted()).await?;
+    }
+
+    rebuild_attendance(&mut transaction, &back_blast_ids(&changes)).await?;
+
+    sqlx::query!(
+        r#"
+    UPDATE pax_merges
+    SET undone = true
+    WHERE id = $1;
+    "#,
+        merge.id
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    transaction
+        .commit()
+        .await
+        .expect("Could not commit transaction");
+    Ok(())
+}
+
+/// set column to `after` value, only if it still has the `before` value.
+async fn apply_change(
+    transaction: &mut Transaction<'_, Postgres>,
+    change: &PaxMergeChange,
+) -> Result<(), AppError> {
+    let (id, before, after) = (&change.row_id, &change.before, &change.after);
+    match change.column {
+        MergeColumn::BackBlastQ => {
+            sqlx::query!(
+                "UPDATE back_blasts SET q = $3 WHERE id = $1 AND q = $2;",
+                id,
+                before.clone().unwrap_or_default(),
+                after.clone().unwrap_or_default()
+            )
+            .execute(&mut **transaction)
+            .await?;
+        }
+        MergeColumn::BackBlastPax => {
+            sqlx::query!(
+                "UPDATE back_blasts SET pax = $3 WHERE id = $1 AND pax = $2;",
+                id,
+                before.clone().unwrap_or_default(),
+                after.clone().unwrap_or_default()
+            )
+            .execute(&mut **transaction)
+            .await?;
+        }
+        MergeColumn::BackBlastFngs => {
+            sqlx::query!(
+                "UPDATE back_blasts SET fngs = $3 WHERE id = $1 AND fngs IS NOT DISTINCT FROM $2;",
+                id,
+                before.as_deref(),
+                after.as_deref()
+            )
+            .execute(&mut **transaction)
+            .await?;
+        }
+        MergeColumn::QLineUpQs => {
+            sqlx::query!(
+                "UPDATE q_line_up SET qs = $3 WHERE id = $1 AND qs = $2;",
+                id,
+                before.clone().unwrap_or_default(),
+                after.clone().unwrap_or_default()
+            )
+            .execute(&mut **transaction)
+            .await?;
+        }
+        MergeColumn::PreBlastQs => {
+            sqlx::query!(
+                "UPDATE pre_blasts SET qs = $3 WHERE id = $1 AND qs = $2;",
+                id,
+                before.clone().unwrap_or_default(),
+                after.clone().unwrap_or_default()
+            )
+            .execute(&mut **transaction)
+            .await?;
+        }
+        MergeColumn::ParentPaxName => {
+            sqlx::query!(
+                "UPDATE parent_pax_relationships SET pax_name = $3 WHERE id = $1 AND pax_name = $2;",
+                id,
+                before.clone().unwrap_or_default(),
+                after.clone().unwrap_or_default()
+            )
+            .execute(&mut **transaction)
+            .await?;
+        }
+        MergeColumn::ParentPaxSlackId => {
+            sqlx::query!(
+                "UPDATE parent_pax_relationships SET slack_id = $3 WHERE id = $1 AND slack_id IS NOT DISTINCT FROM $2;",
+                id,
+                before.as_deref(),
+                after.as_deref()
+            )
+            .execute(&mut **transaction)
+            .await?;
+        }
+        MergeColumn::ParentPaxParent => {
+            let before = parse_json(before)?;
+            let after = parse_json(after)?;
+            sqlx::query!(
+                "UPDATE parent_pax_relationships SET parent = $3::json WHERE id = $1 AND parent::jsonb = $2::jsonb;",
+                id,
+                before,
+                after
+            )
+            .execute(&mut **transaction)
+            .await?;
+        }
+        MergeColumn::ReactionSlackUser => {
+            sqlx::query!(
+                "UPDATE reactions_log SET slack_user = $3 WHERE id = $1 AND slack_user = $2;",
+                id,
+                before.clone().unwrap_or_default(),
+                after.clone().unwrap_or_default()
+            )
+            .execute(&mut **transaction)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+fn parse_json(value: &Option<String>) -> Result<serde_json::Value, AppError> {
+    let value = serde_json::from_str(value.as_deref().unwrap_or("null"))?;
+    Ok(value)
+}
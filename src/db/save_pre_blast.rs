@@ -1,8 +1,11 @@
 use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
 use crate::app_state::pre_blast_data::PreBlastData;
+use crate::app_state::revisions::{RevisionAction, RevisionActor};
+use crate::db::save_revision::record_pre_blast_revision;
 use crate::shared::common_errors::AppError;
 use crate::web_api_routes::pre_blast_data::PreBlastRow;
-use chrono::{NaiveDate, NaiveTime};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use sqlx::{PgPool, Postgres, Transaction};
 use std::str::FromStr;
 use uuid::Uuid;
@@ -22,10 +25,15 @@ pub struct PreBlastDbEntry {
     pub mole_skin: Option<String>,
     pub img_ids: Option<String>,
     pub ts: Option<String>,
+    /// when scheduler should post preblast, if not posted right away
+    pub post_at: Option<DateTime<Utc>>,
+    /// slack user that scheduled preblast
+    pub scheduled_by: Option<String>,
 }
 
-impl From<&PreBlastData> for PreBlastDbEntry {
-    fn from(value: &PreBlastData) -> Self {
+impl PreBlastDbEntry {
+    /// db entry of preblast, with channel id of AO from registry
+    pub fn new(value: &PreBlastData, registry: &AoRegistry) -> Self {
         let mut qs: Vec<String> = value.qs.clone().into_iter().collect();
         qs.sort();
 
@@ -58,7 +66,7 @@ impl From<&PreBlastData> for PreBlastDbEntry {
         PreBlastDbEntry {
             id: Uuid::new_v4(),
             ao: value.ao.to_string(),
-            channel_id: value.ao.channel_id().to_string(),
+            channel_id: value.ao.channel_id(registry).to_string(),
             title: value.title.to_string(),
             qs: qs.join(","),
             date: value.date,
@@ -69,17 +77,18 @@ impl From<&PreBlastData> for PreBlastDbEntry {
             mole_skin: value.mole_skin.clone(),
             img_ids,
             ts: None,
+            post_at: None,
+            scheduled_by: None,
         }
     }
-}
 
-impl From<&PreBlastRow> for PreBlastDbEntry {
-    fn from(value: &PreBlastRow) -> Self {
+    /// db entry of csv preblast row, with channel id of AO from registry
+    pub fn from_row(value: &PreBlastRow, registry: &AoRegistry) -> Self {
         let ao = AO::from(value.ao.to_string());
         PreBlastDbEntry {
             id: Uuid::from_str(value.id.as_str()).unwrap(),
             ao: ao.to_string(),
-            channel_id: ao.channel_id().to_string(),
+            channel_id: ao.channel_id(registry).to_string(),
             title: value.title.to_string(),
             qs: value.qs.to_string(),
             date: value.date,
@@ -110,17 +119,48 @@ impl From<&PreBlastRow> for PreBlastDbEntry {
             } else {
                 Some(value.ts.to_string())
             },
+            post_at: None,
+            scheduled_by: None,
         }
     }
 }
 
-pub async fn save_single(db_pool: &PgPool, data: &PreBlastData) -> Result<String, AppError> {
-    let db_pb = PreBlastDbEntry::from(data);
+pub async fn save_single(
+    db_pool: &PgPool,
+    data: &PreBlastData,
+    registry: &AoRegistry,
+    actor: &RevisionActor,
+) -> Result<String, AppError> {
+    let db_pb = PreBlastDbEntry::new(data, registry);
+    let id = db_pb.id.to_string();
+
+    let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
+
+    save_pre_blast(&mut transaction, &db_pb, RevisionAction::Create, actor).await?;
+
+    transaction
+        .commit()
+        .await
+        .expect("Could not commit transaction");
+    Ok(id)
+}
+
+/// save preblast as pending, to be posted by scheduler at `post_at`
+pub async fn save_scheduled(
+    db_pool: &PgPool,
+    data: &PreBlastData,
+    registry: &AoRegistry,
+    post_at: DateTime<Utc>,
+    actor: &RevisionActor,
+) -> Result<String, AppError> {
+    let mut db_pb = PreBlastDbEntry::new(data, registry);
+    db_pb.post_at = Some(post_at);
+    db_pb.scheduled_by = actor.slack_user.clone();
     let id = db_pb.id.to_string();
 
     let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
 
-    save_pre_blast(&mut transaction, &db_pb).await?;
+    save_pre_blast(&mut transaction, &db_pb, RevisionAction::Create, actor).await?;
 
     transaction
         .commit()
@@ -129,12 +169,17 @@ pub async fn save_single(db_pool: &PgPool, data: &PreBlastData) -> Result<String
     Ok(id)
 }
 
-pub async fn save_from_csv_rows(db: &PgPool, list: &[PreBlastRow]) -> Result<(), AppError> {
+pub async fn save_from_csv_rows(
+    db: &PgPool,
+    list: &[PreBlastRow],
+    registry: &AoRegistry,
+) -> Result<(), AppError> {
+    let actor = RevisionActor::sync();
     let mut transaction = db.begin().await.expect("Failed to begin transaction");
     for item in list.iter() {
-        let entry = PreBlastDbEntry::from(item);
+        let entry = PreBlastDbEntry::from_row(item, registry);
 
-        save_pre_blast(&mut transaction, &entry).await?;
+        save_pre_blast(&mut transaction, &entry, RevisionAction::Sync, &actor).await?;
     }
 
     transaction
@@ -145,19 +190,26 @@ pub async fn save_from_csv_rows(db: &PgPool, list: &[PreBlastRow]) -> Result<(),
     Ok(())
 }
 
-/// update timestamp for pre-blast, to be able to edit most recent message post.
-pub async fn update_pre_blast_ts(db_pool: &PgPool, id: &str, ts: String) -> Result<(), AppError> {
+/// update timestamp and channel of posted pre-blast, to be able to edit most recent message post.
+pub async fn update_pre_blast_ts(
+    db_pool: &PgPool,
+    id: &str,
+    channel_id: &str,
+    ts: String,
+) -> Result<(), AppError> {
     let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
     let uuid = Uuid::from_str(id)?;
 
     sqlx::query!(
         r#"
     UPDATE pre_blasts
-    SET ts = $2
+    SET ts = $2,
+        post_channel_id = $3
     WHERE id = $1
     "#,
         uuid,
-        ts
+        ts,
+        channel_id
     )
     .execute(&mut *transaction)
     .await?;
@@ -170,14 +222,68 @@ pub async fn update_pre_blast_ts(db_pool: &PgPool, id: &str, ts: String) -> Resu
     Ok(())
 }
 
+/// change when pending preblast gets posted
+pub async fn update_pre_blast_post_at(
+    db_pool: &PgPool,
+    id: &str,
+    post_at: DateTime<Utc>,
+) -> Result<(), AppError> {
+    let uuid = Uuid::from_str(id)?;
+    sqlx::query!(
+        r#"
+    UPDATE pre_blasts
+    SET post_at = $2
+    WHERE id = $1 AND ts IS NULL AND cancelled_at IS NULL
+    "#,
+        uuid,
+        post_at
+    )
+    .execute(db_pool)
+    .await?;
+    Ok(())
+}
+
+/// cancel pending preblast so scheduler never posts it
+pub async fn cancel_pending_pre_blast(
+    db_pool: &PgPool,
+    id: &str,
+    actor: &RevisionActor,
+) -> Result<(), AppError> {
+    let uuid = Uuid::from_str(id)?;
+    let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
+    let cancelled = sqlx::query!(
+        r#"
+    UPDATE pre_blasts
+    SET cancelled_at = NOW()
+    WHERE id = $1 AND ts IS NULL AND cancelled_at IS NULL
+    RETURNING id;
+    "#,
+        uuid
+    )
+    .fetch_optional(&mut *transaction)
+    .await?;
+    if cancelled.is_none() {
+        return Err(AppError::from("Preblast not found or no longer pending"));
+    }
+    record_pre_blast_revision(&mut transaction, &uuid, RevisionAction::Deactivate, actor).await?;
+    transaction
+        .commit()
+        .await
+        .expect("Could not commit transaction");
+    Ok(())
+}
+
 /// update full pre blast data
 pub async fn update_pre_blast(
     db_pool: &PgPool,
     id: &str,
     pre_blast: &PreBlastData,
+    registry: &AoRegistry,
+    actor: &RevisionActor,
 ) -> Result<(), AppError> {
     let uuid = Uuid::from_str(id)?;
-    let db_entry = PreBlastDbEntry::from(pre_blast);
+    let db_entry = PreBlastDbEntry::new(pre_blast, registry);
+    let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
     sqlx::query!(
         r#"
      UPDATE pre_blasts
@@ -207,8 +313,14 @@ pub async fn update_pre_blast(
         db_entry.mole_skin,
         db_entry.img_ids,
     )
-    .execute(db_pool)
+    .execute(&mut *transaction)
     .await?;
+    record_pre_blast_revision(&mut transaction, &uuid, RevisionAction::Edit, actor).await?;
+
+    transaction
+        .commit()
+        .await
+        .expect("Could not commit transaction");
 
     Ok(())
 }
@@ -216,13 +328,16 @@ pub async fn update_pre_blast(
 async fn save_pre_blast(
     transaction: &mut Transaction<'_, Postgres>,
     entry: &PreBlastDbEntry,
+    action: RevisionAction,
+    actor: &RevisionActor,
 ) -> Result<(), AppError> {
-    sqlx::query!(
+    let inserted = sqlx::query!(
         r#"
-        INSERT INTO pre_blasts (id, ao, channel_id, title, qs, date, start_time, why, equipment, fng_message, mole_skin, img_ids)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        INSERT INTO pre_blasts (id, ao, channel_id, title, qs, date, start_time, why, equipment, fng_message, mole_skin, img_ids, post_at, scheduled_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
         ON CONFLICT (id)
-            DO NOTHING;
+            DO NOTHING
+        RETURNING id;
         "#,
         entry.id,
         entry.ao,
@@ -235,9 +350,15 @@ async fn save_pre_blast(
         entry.equipment,
         entry.fng_message,
         entry.mole_skin,
-        entry.img_ids
+        entry.img_ids,
+        entry.post_at,
+        entry.scheduled_by
     )
-        .execute(&mut **transaction)
+        .fetch_optional(&mut **transaction)
         .await?;
+
+    if let Some(row) = inserted {
+        record_pre_blast_revision(transaction, &row.id, action, actor).await?;
+    }
     Ok(())
 }
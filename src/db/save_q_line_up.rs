@@ -57,10 +57,11 @@ pub fn map_from_action(
 }
 
 /// save  list of q line up entries
-pub async fn save_list(db_pool: &PgPool, list: &[NewQLineUpDbEntry]) -> Result<(), AppError> {
+pub async fn save_list(db_pool: &PgPool, list: &[NewQLineUpDbEntry]) -> Result<u64, AppError> {
     let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
+    let mut saved = 0;
     for item in list {
-        save_entry(&mut transaction, item).await?;
+        saved += save_entry(&mut transaction, item).await?;
     }
 
     transaction
@@ -68,14 +69,14 @@ pub async fn save_list(db_pool: &PgPool, list: &[NewQLineUpDbEntry]) -> Result<(
         .await
         .expect("Could not commit transaction");
 
-    Ok(())
+    Ok(saved)
 }
 
 async fn save_entry(
     transaction: &mut Transaction<'_, Postgres>,
     db_entry: &NewQLineUpDbEntry,
-) -> Result<(), AppError> {
-    sqlx::query!(
+) -> Result<u64, AppError> {
+    let result = sqlx::query!(
         r#"
         INSERT INTO q_line_up (id, qs, ao, date, closed, channel_id)
         VALUES ($1, $2, $3, $4, $5, $6)
@@ -92,7 +93,7 @@ async fn save_entry(
     .execute(&mut **transaction)
     .await?;
 
-    Ok(())
+    Ok(result.rows_affected())
 }
 
 pub async fn close_q_line_up_entry(
@@ -0,0 +1,70 @@
+use crate::app_state::q_recurrence::RecurrenceRule;
+use crate::shared::common_errors::AppError;
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// db entry of new recurring Q reservation
+pub struct NewQRecurrence {
+    pub id: Uuid,
+    pub slack_id: String,
+    pub channel_id: String,
+    pub rule: String,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+}
+
+impl NewQRecurrence {
+    pub fn new(
+        slack_id: &str,
+        channel_id: &str,
+        rule: &RecurrenceRule,
+        start_date: NaiveDate,
+        end_date: Option<NaiveDate>,
+    ) -> Self {
+        NewQRecurrence {
+            id: Uuid::new_v4(),
+            slack_id: slack_id.to_string(),
+            channel_id: channel_id.to_string(),
+            rule: rule.to_string(),
+            start_date,
+            end_date,
+        }
+    }
+}
+
+pub async fn save_recurrence(db_pool: &PgPool, entry: &NewQRecurrence) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO q_recurrences (id, slack_id, channel_id, rule, start_date, end_date)
+        VALUES ($1, $2, $3, $4, $5, $6);
+        "#,
+        entry.id,
+        entry.slack_id,
+        entry.channel_id,
+        entry.rule,
+        entry.start_date,
+        entry.end_date
+    )
+    .execute(db_pool)
+    .await?;
+    Ok(())
+}
+
+/// remove recurrence of pax. Q line up entries already added stay.
+pub async fn remove_recurrence(db_pool: &PgPool, id: &str, slack_id: &str) -> Result<(), AppError> {
+    let id = Uuid::from_str(id)?;
+    sqlx::query!(
+        r#"
+        UPDATE q_recurrences
+        SET deleted_at = NOW()
+        WHERE id = $1 AND slack_id = $2 AND deleted_at IS NULL;
+        "#,
+        id,
+        slack_id
+    )
+    .execute(db_pool)
+    .await?;
+    Ok(())
+}
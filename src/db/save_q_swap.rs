@@ -0,0 +1,142 @@
+use crate::app_state::pax_merge::replace_name_in_list;
+use crate::shared::common_errors::AppError;
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// db entry of Q asking for a sub
+pub struct NewQSwapRequest {
+    pub id: Uuid,
+    pub channel_id: String,
+    pub date: NaiveDate,
+    pub requested_by: String,
+    /// qs on line up when asking, so accepting only swaps if line up hasn't changed since
+    pub original_qs: String,
+    pub offered_to: Vec<String>,
+    /// q line up message row that asked for sub, to update when accepted
+    pub message_channel_id: Option<String>,
+    pub message_ts: Option<String>,
+    pub message_block_id: Option<String>,
+}
+
+/// outcome of pax accepting swap offer
+#[derive(Debug, PartialEq)]
+pub enum QSwapAcceptance {
+    /// swap went through, with the updated comma separated qs of the line up
+    Accepted(String),
+    /// someone else already took it, or it was cancelled
+    NotPending,
+    /// line up was cleared or requester is no longer on it, so swap got cancelled
+    LineUpChanged,
+}
+
+pub async fn save_swap_request(
+    db_pool: &PgPool,
+    request: &NewQSwapRequest,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO q_swap_requests (id, channel_id, date, requested_by, original_qs, offered_to,
+                                     message_channel_id, message_ts, message_block_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9);
+        "#,
+        request.id,
+        request.channel_id,
+        request.date,
+        request.requested_by,
+        request.original_qs,
+        &request.offered_to,
+        request.message_channel_id,
+        request.message_ts,
+        request.message_block_id
+    )
+    .execute(db_pool)
+    .await?;
+    Ok(())
+}
+
+/// accept pending swap and swap the requesting Q for the new Q on the line up in one
+/// transaction, so only the first pax to accept gets the spot. Any co-Qs stay on the line up.
+pub async fn accept_swap_request(
+    db_pool: &PgPool,
+    id: &str,
+    accepted_by: &str,
+    requester_name: &str,
+    q_name: &str,
+) -> Result<QSwapAcceptance, AppError> {
+    let id = Uuid::from_str(id)?;
+    let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
+
+    let accepted = sqlx::query!(
+        r#"
+        UPDATE q_swap_requests
+        SET status = 'accepted', accepted_by = $2, resolved_at = NOW()
+        WHERE id = $1 AND status = 'pending'
+        RETURNING id;
+        "#,
+        id,
+        accepted_by
+    )
+    .fetch_optional(&mut *transaction)
+    .await?;
+
+    if accepted.is_none() {
+        return Ok(QSwapAcceptance::NotPending);
+    }
+
+    let line_up = sqlx::query!(
+        r#"
+        SELECT qlu.id, qlu.qs
+        FROM q_line_up qlu
+        INNER JOIN q_swap_requests s
+            ON qlu.channel_id = s.channel_id AND qlu.date = s.date
+        WHERE s.id = $1
+          AND qlu.closed = false
+        FOR UPDATE OF qlu;
+        "#,
+        id
+    )
+    .fetch_optional(&mut *transaction)
+    .await?;
+
+    let updated_qs = line_up.and_then(|line_up| {
+        replace_name_in_list(&line_up.qs, requester_name, q_name).map(|qs| (line_up.id, qs))
+    });
+
+    let acceptance = match updated_qs {
+        Some((line_up_id, qs)) => {
+            sqlx::query!(
+                r#"
+                UPDATE q_line_up
+                SET qs = $2
+                WHERE id = $1;
+                "#,
+                line_up_id,
+                qs
+            )
+            .execute(&mut *transaction)
+            .await?;
+            QSwapAcceptance::Accepted(qs)
+        }
+        None => {
+            sqlx::query!(
+                r#"
+                UPDATE q_swap_requests
+                SET status = 'cancelled', accepted_by = NULL
+                WHERE id = $1;
+                "#,
+                id
+            )
+            .execute(&mut *transaction)
+            .await?;
+            QSwapAcceptance::LineUpChanged
+        }
+    };
+
+    transaction
+        .commit()
+        .await
+        .expect("Could not commit transaction");
+    Ok(acceptance)
+}
@@ -0,0 +1,170 @@
+use crate::app_state::revisions::{RevisionAction, RevisionActor, RevisionEntity};
+use crate::db::queries::revisions::get_revision;
+use crate::db::save_attendance::rebuild_attendance;
+use crate::shared::common_errors::AppError;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashSet;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// record revision with snapshot of current backblast row.
+/// Skipped when row is unchanged since the last revision (ignoring ts).
+pub async fn record_back_blast_revision(
+    transaction: &mut Transaction<'_, Postgres>,
+    back_blast_id: &Uuid,
+    action: RevisionAction,
+    actor: &RevisionActor,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+    INSERT INTO entity_revisions (id, entity_type, entity_id, action, source, slack_user, snapshot)
+    SELECT $1, 'backblast', bb.id, $3, $4, $5, to_jsonb(bb)
+    FROM back_blasts bb
+    WHERE bb.id = $2
+      AND (to_jsonb(bb) - 'ts') IS DISTINCT FROM (
+        SELECT er.snapshot - 'ts'
+        FROM entity_revisions er
+        WHERE er.entity_type = 'backblast' AND er.entity_id = $2
+        ORDER BY er.created DESC
+        LIMIT 1
+      );
+    "#,
+        Uuid::new_v4(),
+        back_blast_id,
+        action.to_string(),
+        actor.source.to_string(),
+        actor.slack_user
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
+
+/// record revision with snapshot of current preblast row.
+/// Skipped when row is unchanged since the last revision (ignoring ts).
+pub async fn record_pre_blast_revision(
+    transaction: &mut Transaction<'_, Postgres>,
+    pre_blast_id: &Uuid,
+    action: RevisionAction,
+    actor: &RevisionActor,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+    INSERT INTO entity_revisions (id, entity_type, entity_id, action, source, slack_user, snapshot)
+    SELECT $1, 'preblast', pb.id, $3, $4, $5, to_jsonb(pb)
+    FROM pre_blasts pb
+    WHERE pb.id = $2
+      AND (to_jsonb(pb) - 'ts') IS DISTINCT FROM (
+        SELECT er.snapshot - 'ts'
+        FROM entity_revisions er
+        WHERE er.entity_type = 'preblast' AND er.entity_id = $2
+        ORDER BY er.created DESC
+        LIMIT 1
+      );
+    "#,
+        Uuid::new_v4(),
+        pre_blast_id,
+        action.to_string(),
+        actor.source.to_string(),
+        actor.slack_user
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
+
+/// restore backblast to snapshot of revision (keeping current ts) and record it as a new revision.
+pub async fn rollback_back_blast(
+    db_pool: &PgPool,
+    back_blast_id: &str,
+    revision_id: &str,
+    actor: &RevisionActor,
+) -> Result<(), AppError> {
+    let id = Uuid::from_str(back_blast_id)?;
+    let revision = get_revision(db_pool, revision_id)
+        .await?
+        .ok_or_else(|| AppError::from("Revision not found"))?;
+    if !revision.is_for(RevisionEntity::BackBlast, &id) {
+        return Err(AppError::from("Revision is not for this backblast"));
+    }
+
+    let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
+    sqlx::query!(
+        r#"
+    UPDATE back_blasts bb
+    SET ao = s.ao,
+        q = s.q,
+        pax = s.pax,
+        date = s.date,
+        bb_type = s.bb_type,
+        channel_id = s.channel_id,
+        active = s.active,
+        title = s.title,
+        moleskine = s.moleskine,
+        fngs = s.fngs,
+        deleted_at = s.deleted_at,
+        deleted_by = s.deleted_by
+    FROM jsonb_populate_record(NULL::back_blasts, $2) s
+    WHERE bb.id = $1;
+    "#,
+        id,
+        revision.snapshot
+    )
+    .execute(&mut *transaction)
+    .await?;
+    rebuild_attendance(&mut transaction, &HashSet::from([id])).await?;
+    record_back_blast_revision(&mut transaction, &id, RevisionAction::Rollback, actor).await?;
+
+    transaction
+        .commit()
+        .await
+        .expect("Could not commit transaction");
+    Ok(())
+}
+
+/// restore preblast to snapshot of revision (keeping current ts) and record it as a new revision.
+pub async fn rollback_pre_blast(
+    db_pool: &PgPool,
+    pre_blast_id: &str,
+    revision_id: &str,
+    actor: &RevisionActor,
+) -> Result<(), AppError> {
+    let id = Uuid::from_str(pre_blast_id)?;
+    let revision = get_revision(db_pool, revision_id)
+        .await?
+        .ok_or_else(|| AppError::from("Revision not found"))?;
+    if !revision.is_for(RevisionEntity::PreBlast, &id) {
+        return Err(AppError::from("Revision is not for this preblast"));
+    }
+
+    let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
+    sqlx::query!(
+        r#"
+    UPDATE pre_blasts pb
+    SET ao = s.ao,
+        channel_id = s.channel_id,
+        title = s.title,
+        qs = s.qs,
+        date = s.date,
+        start_time = s.start_time,
+        why = s.why,
+        equipment = s.equipment,
+        fng_message = s.fng_message,
+        mole_skin = s.mole_skin,
+        img_ids = s.img_ids
+    FROM jsonb_populate_record(NULL::pre_blasts, $2) s
+    WHERE pb.id = $1;
+    "#,
+        id,
+        revision.snapshot
+    )
+    .execute(&mut *transaction)
+    .await?;
+    record_pre_blast_revision(&mut transaction, &id, RevisionAction::Rollback, actor).await?;
+
+    transaction
+        .commit()
+        .await
+        .expect("Could not commit transaction");
+    Ok(())
+}
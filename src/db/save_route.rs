@@ -0,0 +1,54 @@
+use crate::app_state::route_track::RouteSummary;
+use crate::shared::common_errors::AppError;
+use sqlx::PgPool;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// new route file of backblast to save to db
+#[derive(Debug, PartialEq)]
+pub struct NewBackBlastRoute {
+    /// slack id of uploaded file
+    pub file_id: String,
+    pub file_name: Option<String>,
+    pub file_type: String,
+    pub summary: RouteSummary,
+    /// slack id of user that uploaded
+    pub uploaded_by: Option<String>,
+}
+
+/// save route of backblast (replacing route of same file). Returns id.
+pub async fn save_back_blast_route(
+    db_pool: &PgPool,
+    back_blast_id: &str,
+    route: &NewBackBlastRoute,
+) -> Result<Uuid, AppError> {
+    let back_blast_id = Uuid::from_str(back_blast_id)?;
+    let track = serde_json::to_value(&route.summary.track)?;
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO back_blast_routes (id, back_blast_id, file_id, file_name, file_type, distance_miles, elevation_gain_ft, duration_secs, track, uploaded_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        ON CONFLICT (back_blast_id, file_id) DO UPDATE
+        SET file_name = EXCLUDED.file_name,
+            file_type = EXCLUDED.file_type,
+            distance_miles = EXCLUDED.distance_miles,
+            elevation_gain_ft = EXCLUDED.elevation_gain_ft,
+            duration_secs = EXCLUDED.duration_secs,
+            track = EXCLUDED.track
+        RETURNING id;
+        "#,
+        Uuid::new_v4(),
+        back_blast_id,
+        route.file_id,
+        route.file_name,
+        route.file_type,
+        route.summary.distance_miles,
+        route.summary.elevation_gain_ft,
+        route.summary.duration_secs,
+        track,
+        route.uploaded_by
+    )
+    .fetch_one(db_pool)
+    .await?;
+    Ok(row.id)
+}
@@ -0,0 +1,140 @@
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::scoring::ScoringFormula;
+use crate::shared::common_errors::AppError;
+use chrono::NaiveDate;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// new workout score to save to db
+#[derive(Debug, PartialEq)]
+pub struct NewWorkoutScore {
+    pub formula_id: Uuid,
+    /// channel of scored beatdown
+    pub channel_id: String,
+    pub date: NaiveDate,
+    pub inputs: HashMap<String, f64>,
+    pub formula: String,
+    pub score: f64,
+    /// slack id of user that scored
+    pub scored_by: Option<String>,
+}
+
+/// save scoring formula of ao channel (replacing existing one)
+pub async fn save_scoring_formula(
+    db_pool: &PgPool,
+    channel_id: &str,
+    formula: &ScoringFormula,
+) -> Result<(), AppError> {
+    formula.validate()?;
+    let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
+    upsert_scoring_formula(&mut transaction, channel_id, formula, true).await?;
+    transaction
+        .commit()
+        .await
+        .expect("Could not commit transaction");
+    Ok(())
+}
+
+/// insert formula for channel. When `replace`, existing formula is updated, otherwise kept.
+async fn upsert_scoring_formula(
+    transaction: &mut Transaction<'_, Postgres>,
+    channel_id: &str,
+    formula: &ScoringFormula,
+    replace: bool,
+) -> Result<(), AppError> {
+    let fields = serde_json::to_value(&formula.fields)?;
+    sqlx::query!(
+        r#"
+        INSERT INTO scoring_formulas (id, channel_id, name, fields, formula, unit, higher_is_better)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (channel_id) DO UPDATE
+        SET name = EXCLUDED.name,
+            fields = EXCLUDED.fields,
+            formula = EXCLUDED.formula,
+            unit = EXCLUDED.unit,
+            higher_is_better = EXCLUDED.higher_is_better,
+            updated = NOW()
+        WHERE $8;
+        "#,
+        Uuid::new_v4(),
+        channel_id,
+        formula.name,
+        fields,
+        formula.formula,
+        formula.unit,
+        formula.higher_is_better,
+        replace
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
+
+/// Seed default scoring formulas (existing formulas are kept)
+pub async fn seed_scoring_formulas(
+    db_pool: &PgPool,
+    registry: &AoRegistry,
+) -> Result<(), AppError> {
+    let mut transaction = db_pool.begin().await.expect("Failed to begin transaction");
+    for (ao, formula) in ScoringFormula::defaults() {
+        upsert_scoring_formula(&mut transaction, ao.channel_id(registry), &formula, false).await?;
+    }
+    transaction
+        .commit()
+        .await
+        .expect("Could not commit transaction");
+    Ok(())
+}
+
+/// save score, linked to backblast of the day (if already posted). Returns id.
+pub async fn save_workout_score(
+    db_pool: &PgPool,
+    score: &NewWorkoutScore,
+) -> Result<Uuid, AppError> {
+    let id = Uuid::new_v4();
+    let inputs = serde_json::to_value(&score.inputs)?;
+    sqlx::query!(
+        r#"
+        INSERT INTO workout_scores (id, formula_id, back_blast_id, channel_id, date, inputs, formula, score, scored_by)
+        VALUES ($1, $2, (
+            SELECT id FROM back_blasts
+            WHERE channel_id = $3 AND date = $4 AND bb_type = 'backblast' AND deleted_at IS NULL
+            LIMIT 1
+        ), $3, $4, $5, $6, $7, $8);
+        "#,
+        id,
+        score.formula_id,
+        score.channel_id,
+        score.date,
+        inputs,
+        score.formula,
+        score.score,
+        score.scored_by
+    )
+    .execute(db_pool)
+    .await?;
+    Ok(id)
+}
+
+/// link scores done before backblast was posted to the backblast
+pub async fn link_scores_to_back_blast(
+    transaction: &mut Transaction<'_, Postgres>,
+    back_blast_id: &Uuid,
+    channel_id: &str,
+    date: &NaiveDate,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+        UPDATE workout_scores
+        SET back_blast_id = $1
+        WHERE back_blast_id IS NULL AND channel_id = $2 AND date = $3;
+        "#,
+        back_blast_id,
+        channel_id,
+        date
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
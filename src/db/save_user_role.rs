@@ -0,0 +1,69 @@
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::roles::Role;
+use crate::shared::common_errors::AppError;
+use sqlx::PgPool;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// give slack user role. Returns false if user already had it.
+pub async fn add_user_role(
+    db_pool: &PgPool,
+    slack_id: &str,
+    role: &Role,
+    registry: &AoRegistry,
+) -> Result<bool, AppError> {
+    let channel_id = role.ao().map(|ao| ao.channel_id(registry).to_string());
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO user_roles (id, slack_id, role, channel_id)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT DO NOTHING;
+        "#,
+        Uuid::new_v4(),
+        slack_id,
+        role.to_string(),
+        channel_id
+    )
+    .execute(db_pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// take role away from slack user. Returns false if user didn't have it.
+pub async fn remove_user_role(
+    db_pool: &PgPool,
+    slack_id: &str,
+    role: &Role,
+    registry: &AoRegistry,
+) -> Result<bool, AppError> {
+    let channel_id = role.ao().map(|ao| ao.channel_id(registry).to_string());
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM user_roles
+        WHERE slack_id = $1
+            AND role = $2
+            AND coalesce(channel_id, '') = coalesce($3, '');
+        "#,
+        slack_id,
+        role.to_string(),
+        channel_id
+    )
+    .execute(db_pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn delete_user_role(db_pool: &PgPool, id: &str) -> Result<(), AppError> {
+    let id = Uuid::from_str(id)?;
+    sqlx::query!(
+        r#"
+        DELETE FROM user_roles
+        WHERE id = $1;
+        "#,
+        id
+    )
+    .execute(db_pool)
+    .await?;
+
+    Ok(())
+}
@@ -1,10 +1,13 @@
 use crate::app_state::ao_data::const_names::AO_LIST;
 use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
 use crate::app_state::backblast_data::BackBlastData;
+use crate::app_state::revisions::RevisionActor;
 use crate::db::queries::all_back_blasts::recent_bd_for_pax::get_recent_bd_for_pax;
 use crate::db::queries::all_back_blasts::{
     get_all_dd_within_date_range, get_all_within_date_range,
 };
+use crate::db::queries::ao_list::get_ao_registry_entries;
 use crate::db::queries::users::get_slack_id_map;
 use crate::db::save_q_line_up::NewQLineUpDbEntry;
 use crate::db::{save_back_blast, save_q_line_up};
@@ -62,10 +65,12 @@ pub const AOLIST: [(AO, &str); 7] = [
 ];
 
 pub async fn save_old_back_blasts(db_pool: &PgPool) -> Result<(), AppError> {
+    let registry = AoRegistry::new(get_ao_registry_entries(db_pool).await?);
     for (ao, file_path) in AOLIST.iter() {
         let ao_name = ao.to_string();
         let bb = read_back_blasts(ao, &back_blast_path(file_path))?;
-        save_back_blast::save_multiple(db_pool, &bb).await?;
+        save_back_blast::save_multiple(db_pool, &bb, &registry, &RevisionActor::migration())
+            .await?;
         println!("Saved: {}", ao_name);
     }
 
@@ -74,13 +79,15 @@ pub async fn save_old_back_blasts(db_pool: &PgPool) -> Result<(), AppError> {
 
 /// v2 sync method to sync prod db to local
 pub async fn sync_prod_db(db_pool: &PgPool) -> Result<(), AppError> {
+    let registry = AoRegistry::new(get_ao_registry_entries(db_pool).await?);
     let bb = read_back_blast_csv()?;
-    save_back_blast::save_multiple(db_pool, &bb).await?;
+    save_back_blast::save_multiple(db_pool, &bb, &registry, &RevisionActor::migration()).await?;
     println!("Saved all");
     Ok(())
 }
 
 pub async fn cleanup_pax_in_channels(db_pool: &PgPool) -> Result<(), AppError> {
+    let registry = AoRegistry::new(get_ao_registry_entries(db_pool).await?);
     let pax = get_slack_id_map(db_pool).await?;
     let now = local_boise_time().date_naive();
     let ninety_days_ago = now.sub(Months::new(3));
@@ -102,7 +109,7 @@ pub async fn cleanup_pax_in_channels(db_pool: &PgPool) -> Result<(), AppError> {
         println!("Checking {}", ao);
 
         let users_in_channel = api
-            .get_channel_members(ao.channel_id())
+            .get_channel_members(ao.channel_id(&registry))
             .await
             .unwrap_or_default();
 
@@ -112,7 +119,7 @@ pub async fn cleanup_pax_in_channels(db_pool: &PgPool) -> Result<(), AppError> {
             println!("======");
         }
 
-        let request = ChannelHistoryRequest::new(ao.channel_id())
+        let request = ChannelHistoryRequest::new(ao.channel_id(&registry))
             .with_limit(1000)
             .with_oldest(ninety_days_ts);
 
@@ -172,8 +179,10 @@ pub async fn cleanup_pax_in_channels(db_pool: &PgPool) -> Result<(), AppError> {
 
                             if attended_bds == 0 && attended_dds == 0 {
                                 // kick them out.
-                                let request =
-                                    KickFromChannelRequest::new(pax_id.as_str(), ao.channel_id());
+                                let request = KickFromChannelRequest::new(
+                                    pax_id.as_str(),
+                                    ao.channel_id(&registry),
+                                );
                                 println!("{} - {:?}", pax_name, request);
                                 match api.kick_user_from_channel(request).await {
                                     Ok(_) => {
@@ -218,45 +227,46 @@ fn read_back_blast_csv() -> Result<Vec<BackBlastData>, AppError> {
 }
 
 pub async fn save_old_q_line_up(db_pool: &PgPool) -> Result<(), AppError> {
+    let registry = AoRegistry::new(get_ao_registry_entries(db_pool).await?);
     let mut rdr = csv::ReaderBuilder::new().from_path("migration_files/q_line_up/q_sheet.csv")?;
     let mut results = Vec::<NewQLineUpDbEntry>::new();
     for record in rdr.deserialize() {
         let record: OldQSheetRow = record?;
         let date = NaiveDate::parse_from_str(record.date.as_str(), "%m/%d/%Y").unwrap();
         if let Some(q) = record.gem {
-            add_q_entry(&mut results, AO::Gem, q, &date);
+            add_q_entry(&mut results, AO::Gem, q, (&date, &registry));
         }
 
         if let Some(q) = record.oldglory {
-            add_q_entry(&mut results, AO::OldGlory, q, &date);
+            add_q_entry(&mut results, AO::OldGlory, q, (&date, &registry));
         }
 
         if let Some(q) = record.backyard {
-            add_q_entry(&mut results, AO::Backyard, q, &date);
+            add_q_entry(&mut results, AO::Backyard, q, (&date, &registry));
         }
 
         if let Some(q) = record.rebel {
-            add_q_entry(&mut results, AO::Rebel, q, &date);
+            add_q_entry(&mut results, AO::Rebel, q, (&date, &registry));
         }
 
         if let Some(q) = record.bleach {
-            add_q_entry(&mut results, AO::Bleach, q, &date);
+            add_q_entry(&mut results, AO::Bleach, q, (&date, &registry));
         }
 
         if let Some(q) = record.ruckership {
-            add_q_entry(&mut results, AO::RuckershipWest, q, &date);
+            add_q_entry(&mut results, AO::RuckershipWest, q, (&date, &registry));
         }
 
         if let Some(q) = record.ironmountain {
-            add_q_entry(&mut results, AO::IronMountain, q, &date);
+            add_q_entry(&mut results, AO::IronMountain, q, (&date, &registry));
         }
 
         if let Some(q) = record.rise {
-            add_q_entry(&mut results, AO::Rise, q, &date);
+            add_q_entry(&mut results, AO::Rise, q, (&date, &registry));
         }
 
         if let Some(q) = record.lakeview_park {
-            add_q_entry(&mut results, AO::WarHorse, q, &date);
+            add_q_entry(&mut results, AO::WarHorse, q, (&date, &registry));
         }
     }
 
@@ -265,8 +275,13 @@ pub async fn save_old_q_line_up(db_pool: &PgPool) -> Result<(), AppError> {
     Ok(())
 }
 
-fn add_q_entry(list: &mut Vec<NewQLineUpDbEntry>, ao: AO, q: String, date: &NaiveDate) {
-    let new_entry = NewQLineUpDbEntry::new(vec![q], &ao, date, ao.channel_id());
+fn add_q_entry(
+    list: &mut Vec<NewQLineUpDbEntry>,
+    ao: AO,
+    q: String,
+    (date, registry): (&NaiveDate, &AoRegistry),
+) {
+    let new_entry = NewQLineUpDbEntry::new(vec![q], &ao, date, ao.channel_id(registry));
     list.push(new_entry);
 }
 
@@ -24,6 +24,57 @@ pub async fn trigger_leaderboard_stats(base_url: &str) -> Result<(), AppError> {
     Ok(())
 }
 
+pub async fn trigger_post_scheduled_pre_blasts(base_url: &str) -> Result<(), AppError> {
+    let url_call = build_url(base_url, "/pre_blasts/post-scheduled");
+    let client = build_client()?;
+    let response = client.get(url_call).send().await?;
+    if !response.status().is_success() {
+        println!("Unsuccessful scheduled preblasts check");
+    }
+
+    Ok(())
+}
+
+pub async fn trigger_q_reminders(base_url: &str, days_before: i64) -> Result<(), AppError> {
+    let url_call = build_url(
+        base_url,
+        format!("/q_line_up/remind-qs?days_before={}", days_before).as_str(),
+    );
+    let client = build_client()?;
+    let response = client.get(url_call).send().await?;
+    if response.status().is_success() {
+        println!("Successful trigger for q reminders");
+    }
+    Ok(())
+}
+
+pub async fn trigger_q_vacancies(base_url: &str) -> Result<(), AppError> {
+    let url_call = build_url(base_url, "/q_line_up/post-vacancies");
+    let client = build_client()?;
+    let response = client.get(url_call).send().await?;
+    if response.status().is_success() {
+        println!("Successful trigger for q vacancies");
+    }
+    Ok(())
+}
+
+pub async fn trigger_q_recurrences(base_url: &str, horizon_days: i64) -> Result<(), AppError> {
+    let url_call = build_url(
+        base_url,
+        format!(
+            "/q_line_up/materialize-recurrences?horizon_days={}",
+            horizon_days
+        )
+        .as_str(),
+    );
+    let client = build_client()?;
+    let response = client.get(url_call).send().await?;
+    if response.status().is_success() {
+        println!("Successful trigger for q recurrences");
+    }
+    Ok(())
+}
+
 fn build_client() -> Result<reqwest::Client, AppError> {
     let mut headers = reqwest::header::HeaderMap::new();
     headers.insert(
@@ -1,15 +1,14 @@
 mod internal_requests;
 
-use crate::shared::time::local_boise_time;
+use crate::shared::time::BOISE_TZ;
+use chrono::Weekday;
 use tokio_schedule::{every, Job};
 
 pub async fn start_daily_scheduler(base_url: &str) {
-    let local = local_boise_time().timezone();
-
     let daily = every(1)
         .day()
         .at(9, 0, 0)
-        .in_timezone(&local)
+        .in_timezone(&BOISE_TZ)
         .perform(|| async {
             println!("starting daily task");
             match internal_requests::trigger_remind_missing_bb(base_url).await {
@@ -23,11 +22,10 @@ pub async fn start_daily_scheduler(base_url: &str) {
 }
 
 pub async fn start_leaderboard_scheduler(base_url: &str) {
-    let local = local_boise_time().timezone();
     let daily = every(3)
         .hours()
         .at(0, 0)
-        .in_timezone(&local)
+        .in_timezone(&BOISE_TZ)
         .perform(|| async {
             match internal_requests::trigger_leaderboard_stats(base_url).await {
                 Ok(_) => println!("after leaderboard trigger"),
@@ -36,3 +34,63 @@ pub async fn start_leaderboard_scheduler(base_url: &str) {
         });
     daily.await;
 }
+
+/// DM Qs about upcoming Q sign ups, and about missing backblasts the morning after
+pub async fn start_q_reminder_scheduler(base_url: &str, days_before: i64) {
+    let daily = every(1)
+        .day()
+        .at(8, 0, 0)
+        .in_timezone(&BOISE_TZ)
+        .perform(|| async {
+            match internal_requests::trigger_q_reminders(base_url, days_before).await {
+                Ok(_) => println!("after q reminders trigger"),
+                Err(err) => println!("err with q reminders: {:?}", err),
+            }
+        });
+    daily.await;
+}
+
+/// post open Q dates of next 2 weeks to AO channels, every sunday afternoon
+pub async fn start_q_vacancies_scheduler(base_url: &str) {
+    let weekly = every(1)
+        .week()
+        .on(Weekday::Sun)
+        .at(15, 0, 0)
+        .in_timezone(&BOISE_TZ)
+        .perform(|| async {
+            match internal_requests::trigger_q_vacancies(base_url).await {
+                Ok(_) => println!("after q vacancies trigger"),
+                Err(err) => println!("err with q vacancies: {:?}", err),
+            }
+        });
+    weekly.await;
+}
+
+/// add recurring Qs to q line up, ahead of reminders going out
+pub async fn start_q_recurrence_scheduler(base_url: &str, horizon_days: i64) {
+    let daily = every(1)
+        .day()
+        .at(3, 0, 0)
+        .in_timezone(&BOISE_TZ)
+        .perform(|| async {
+            match internal_requests::trigger_q_recurrences(base_url, horizon_days).await {
+                Ok(_) => println!("after q recurrences trigger"),
+                Err(err) => println!("err with q recurrences: {:?}", err),
+            }
+        });
+    daily.await;
+}
+
+/// post preblasts scheduled for later, checking every few minutes
+pub async fn start_scheduled_pre_blasts_scheduler(base_url: &str) {
+    let every_few_minutes = every(5)
+        .minutes()
+        .at(0)
+        .in_timezone(&BOISE_TZ)
+        .perform(|| async {
+            if let Err(err) = internal_requests::trigger_post_scheduled_pre_blasts(base_url).await {
+                println!("err with scheduled preblasts: {:?}", err);
+            }
+        });
+    every_few_minutes.await;
+}
@@ -3,3 +3,6 @@ pub const DB_BACKBLAST_DIR_PATH: &str = "db_files/backblasts/";
 pub const Q_LINE_UP_BTN_TEXT: &str = "Take it";
 pub const Q_LINE_UP_CANCEL_TEXT: &str = "Clear";
 pub const Q_LINE_UP_CLOSED_TEXT: &str = "Closed";
+pub const Q_LINE_UP_SWAP_TEXT: &str = "Request swap / find sub";
+pub const BACK_BLAST_DELETE_TEXT: &str = "Delete backblast";
+pub const BACK_BLAST_RESTORE_TEXT: &str = "Restore backblast";
@@ -98,6 +98,15 @@ pub fn format_q_empty_row(friendly_date: &str, ao_string: Option<&str>) -> Strin
     }
 }
 
+/// row for date that is on closure calendar
+pub fn format_q_closure_row(friendly_date: &str, ao_string: Option<&str>, reason: &str) -> String {
+    if let Some(ao) = ao_string {
+        format!("`{}` - *{}* - _CLOSED_ :x: {}", friendly_date, ao, reason)
+    } else {
+        format!("`{}` - _CLOSED_ :x: {}", friendly_date, reason)
+    }
+}
+
 pub fn map_q_line_up_existing(
     friendly_date: &str,
     ao_string: Option<&str>,
@@ -121,6 +130,24 @@ pub fn map_q_line_up_existing(
     }
 }
 
+/// levenshtein distance between two strings
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<char>>();
+    let mut previous = (0..=b.len()).collect::<Vec<usize>>();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let cost = usize::from(a_char != *b_char);
+            let value = (previous[j] + cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+            current.push(value);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -158,4 +185,13 @@ mod tests {
         assert_eq!(end, now);
         assert_eq!(start, thirty_days_ago);
     }
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("merkin", "merkin"), 0);
+        assert_eq!(edit_distance("merkin", "merkins"), 1);
+        assert_eq!(edit_distance("burpee", "burpie"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
 }
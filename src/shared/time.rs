@@ -1,6 +1,10 @@
-use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone, Utc};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
 use serde::Deserialize;
 
+/// IANA timezone for Boise (Mountain time, observes DST)
+pub const BOISE_TZ: Tz = chrono_tz::America::Boise;
+
 /// Basic date range struct
 #[derive(Deserialize)]
 pub struct DateRange {
@@ -9,12 +13,26 @@ pub struct DateRange {
 }
 
 /// Get local Boise time
-pub fn local_boise_time() -> DateTime<FixedOffset> {
-    let now = Utc::now().naive_utc();
-    let hour = 3600;
-    FixedOffset::west_opt(6 * hour)
-        .unwrap()
-        .from_utc_datetime(&now)
+pub fn local_boise_time() -> DateTime<Tz> {
+    to_boise_time(Utc::now())
+}
+
+/// convert utc datetime to Boise local time (MST or MDT depending on date)
+pub fn to_boise_time(date_time: DateTime<Utc>) -> DateTime<Tz> {
+    date_time.with_timezone(&BOISE_TZ)
+}
+
+/// convert Boise wall clock time to zoned datetime.
+/// Ambiguous times (fall back) resolve to the first occurrence,
+/// and times skipped by spring forward get pushed forward an hour.
+pub fn boise_datetime(local: NaiveDateTime) -> DateTime<Tz> {
+    match BOISE_TZ.from_local_datetime(&local).earliest() {
+        Some(date_time) => date_time,
+        None => BOISE_TZ
+            .from_local_datetime(&(local + Duration::hours(1)))
+            .earliest()
+            .unwrap_or_else(|| BOISE_TZ.from_utc_datetime(&local)),
+    }
 }
 
 /// converts event_ts string to valid utc datetime
@@ -27,6 +45,11 @@ pub fn convert_event_ts(event_ts: &str) -> DateTime<Utc> {
     DateTime::from_timestamp(secs, nanos).unwrap()
 }
 
+/// converts event_ts string to Boise local time
+pub fn convert_event_ts_to_boise(event_ts: &str) -> DateTime<Tz> {
+    to_boise_time(convert_event_ts(event_ts))
+}
+
 /// converts date time utc to event_ts string
 pub fn convert_date_time_to_event_ts(date_time: DateTime<Utc>) -> String {
     let secs = date_time.timestamp();
@@ -39,6 +62,15 @@ pub fn convert_date_time_to_event_ts(date_time: DateTime<Utc>) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::{NaiveTime, Offset, Timelike};
+
+    fn utc(date: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(date).unwrap().to_utc()
+    }
+
+    fn offset_hours(date_time: &DateTime<Tz>) -> i32 {
+        date_time.offset().fix().local_minus_utc() / 3600
+    }
 
     #[test]
     fn event_ts_to_datetime() {
@@ -56,4 +88,80 @@ mod tests {
         let event_ts = convert_date_time_to_event_ts(time);
         assert_eq!(event_ts, "1725039792.016800".to_string());
     }
+
+    #[test]
+    fn winter_and_summer_offsets() {
+        let winter = to_boise_time(utc("2024-01-15T06:30:00Z"));
+        assert_eq!(offset_hours(&winter), -7);
+        assert_eq!(
+            winter.date_naive(),
+            NaiveDate::from_ymd_opt(2024, 1, 14).unwrap()
+        );
+
+        let summer = to_boise_time(utc("2024-07-01T05:30:00Z"));
+        assert_eq!(offset_hours(&summer), -6);
+        assert_eq!(
+            summer.date_naive(),
+            NaiveDate::from_ymd_opt(2024, 6, 30).unwrap()
+        );
+    }
+
+    #[test]
+    fn spring_forward_day() {
+        // 2am MST jumps to 3am MDT on 2024-03-10
+        let before = to_boise_time(utc("2024-03-10T08:59:00Z"));
+        assert_eq!(before.hour(), 1);
+        assert_eq!(offset_hours(&before), -7);
+
+        let after = to_boise_time(utc("2024-03-10T09:00:00Z"));
+        assert_eq!(after.hour(), 3);
+        assert_eq!(offset_hours(&after), -6);
+
+        // 2:30am doesn't exist that day, so gets pushed forward
+        let skipped = boise_datetime(
+            NaiveDate::from_ymd_opt(2024, 3, 10)
+                .unwrap()
+                .and_time(NaiveTime::from_hms_opt(2, 30, 0).unwrap()),
+        );
+        assert_eq!(skipped.to_utc(), utc("2024-03-10T09:30:00Z"));
+    }
+
+    #[test]
+    fn fall_back_day() {
+        // 2am MDT falls back to 1am MST on 2024-11-03
+        let first = to_boise_time(utc("2024-11-03T07:30:00Z"));
+        assert_eq!(first.hour(), 1);
+        assert_eq!(offset_hours(&first), -6);
+
+        let second = to_boise_time(utc("2024-11-03T08:30:00Z"));
+        assert_eq!(second.hour(), 1);
+        assert_eq!(offset_hours(&second), -7);
+
+        // ambiguous 1:30am resolves to first occurrence
+        let ambiguous = boise_datetime(
+            NaiveDate::from_ymd_opt(2024, 11, 3)
+                .unwrap()
+                .and_time(NaiveTime::from_hms_opt(1, 30, 0).unwrap()),
+        );
+        assert_eq!(ambiguous, first);
+
+        // 5:15am workout after fall back is in MST
+        let workout = boise_datetime(
+            NaiveDate::from_ymd_opt(2024, 11, 3)
+                .unwrap()
+                .and_time(NaiveTime::from_hms_opt(5, 15, 0).unwrap()),
+        );
+        assert_eq!(workout.to_utc(), utc("2024-11-03T12:15:00Z"));
+    }
+
+    #[test]
+    fn event_ts_in_boise() {
+        // 2024-01-15T06:30:00Z is still the 14th in Boise
+        let time = convert_event_ts_to_boise("1705300200.000000");
+        assert_eq!(
+            time.date_naive(),
+            NaiveDate::from_ymd_opt(2024, 1, 14).unwrap()
+        );
+        assert_eq!(time.hour(), 23);
+    }
 }
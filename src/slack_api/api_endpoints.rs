@@ -5,9 +5,11 @@ pub const USERS_LIST: &str = "users.list";
 pub const REACTIONS_ADD: &str = "reactions.add";
 pub const CHAT_POST_MESSAGE: &str = "chat.postMessage";
 pub const CHAT_UPDATE_MESSAGE: &str = "chat.update";
+pub const CHAT_POST_EPHEMERAL: &str = "chat.postEphemeral";
 pub const CONVERSATION_INVITE: &str = "conversations.invite";
 pub const CONVERSATION_KICK: &str = "conversations.kick";
 pub const CONVERSATION_OPEN: &str = "conversations.open";
+pub const FILES_INFO: &str = "files.info";
 pub const FILES_LIST: &str = "files.list";
 pub const FILES_GET_UPLOAD_URL_EXTERNAL: &str = "files.getUploadURLExternal";
 pub const FILES_COMPLETE_UPLOAD_URL_EXTERNAL: &str = "files.completeUploadExternal";
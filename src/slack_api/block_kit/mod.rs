@@ -128,6 +128,21 @@ impl BlockBuilder {
         )));
     }
 
+    /// button with overflow menu next to it
+    pub fn add_btn_with_overflow(
+        &mut self,
+        btn: (&str, &str, &str),
+        overflow_action_id: &str,
+        options: Vec<OptionElement>,
+    ) {
+        let (label, action_id, value) = btn;
+        let mut block = ActionBlock::new_btn(label, action_id, value);
+        block
+            .elements
+            .push(BlockElementType::new_overflow(overflow_action_id, options));
+        self.blocks.push(BlockType::Actions(block));
+    }
+
     pub fn plain_input(
         mut self,
         label: &str,
@@ -34,6 +34,15 @@ pub mod request {
             self.limit = Some(limit);
             self
         }
+
+        /// only the message posted at ts
+        pub fn with_message(mut self, ts: &str) -> Self {
+            self.latest = Some(ts.to_string());
+            self.oldest = Some(ts.to_string());
+            self.inclusive = Some(true);
+            self.limit = Some(1);
+            self
+        }
     }
 
     impl SlackUrlRequest for ChannelHistoryRequest {
@@ -59,6 +68,7 @@ pub mod request {
 
 pub mod response {
     use crate::slack_api::cursor_data::CursorData;
+    use crate::web_api_routes::interactive_events::interaction_payload::InteractionMessage;
     use serde::{Deserialize, Serialize};
 
     #[derive(Serialize, Deserialize, Debug)]
@@ -71,6 +81,14 @@ pub mod response {
         pub response_metadata: Option<CursorData>,
     }
 
+    /// history response keeping blocks of bot messages, for updating a single message
+    #[derive(Serialize, Deserialize, Debug)]
+    pub struct ChannelMessageResponse {
+        pub ok: bool,
+        pub messages: Option<Vec<InteractionMessage>>,
+        pub error: Option<String>,
+    }
+
     #[derive(Serialize, Deserialize, Debug)]
     pub struct MessageData {
         // full raw string,
@@ -41,7 +41,7 @@ impl PublicChannels {
             PublicChannels::HelpDesk => "C03T2ND0YF7",
             PublicChannels::Welcome => "C03T2Q7U337",
 
-            _ => ao.channel_id(),
+            _ => ao.default_channel_id(),
         }
         .to_string()
     }
@@ -74,6 +74,7 @@ impl From<&AO> for PublicChannels {
             AO::BlackCanyon => PublicChannels::BlackCanyon,
             AO::Liberty => PublicChannels::Liberty,
             AO::DR => PublicChannels::DR,
+            AO::Custom { .. } => PublicChannels::Unknown(ao.to_string()),
             AO::Unknown(name) => PublicChannels::Unknown(name.to_string()),
         }
     }
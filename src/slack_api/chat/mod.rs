@@ -1,2 +1,3 @@
+pub mod post_ephemeral;
 pub mod post_message;
 pub mod update_message;
@@ -0,0 +1,42 @@
+pub mod request {
+    use crate::slack_api::api_endpoints::CHAT_POST_EPHEMERAL;
+    use crate::slack_api::block_kit::BlockType;
+    use crate::slack_api::url_requests::SlackUrlRequest;
+    use serde::Serialize;
+
+    /// message only visible to single user in channel
+    #[derive(Serialize)]
+    pub struct PostEphemeralRequest {
+        pub channel: String,
+        /// slack user that will see the message
+        pub user: String,
+        pub blocks: Vec<BlockType>,
+    }
+
+    impl PostEphemeralRequest {
+        pub fn new(channel: &str, user: &str, blocks: Vec<BlockType>) -> Self {
+            PostEphemeralRequest {
+                channel: channel.to_string(),
+                user: user.to_string(),
+                blocks,
+            }
+        }
+    }
+
+    impl SlackUrlRequest for PostEphemeralRequest {
+        fn get_api_url(&self) -> &str {
+            CHAT_POST_EPHEMERAL
+        }
+    }
+}
+
+pub mod response {
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug)]
+    pub struct PostEphemeralResponse {
+        pub ok: bool,
+        pub message_ts: Option<String>,
+        pub error: Option<String>,
+    }
+}
@@ -5,6 +5,9 @@ pub mod request {
     use crate::users::f3_user::F3User;
     use serde::Serialize;
 
+    /// appended to username of messages posted on behalf of user
+    pub const POSTED_AS_USER_SUFFIX: &str = "(via BoiseBot)";
+
     #[derive(Serialize)]
     pub struct PostMessageRequest {
         /// Channel, private group, or IM channel to send message to. Can be an encoded ID, or a name. See below for more details
@@ -22,6 +25,9 @@ pub mod request {
         /// Set your bot's user name. Must be used in conjunction with as_user set to false, otherwise ignored. See authorship below
         #[serde(skip_serializing_if = "Option::is_none")]
         pub username: Option<String>,
+        /// Provide another message's ts value to make this message a reply.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub thread_ts: Option<String>,
     }
 
     impl PostMessageRequest {
@@ -33,6 +39,7 @@ pub mod request {
                 icon_url: None,
                 icon_emoji: None,
                 username: None,
+                thread_ts: None,
             }
         }
 
@@ -43,9 +50,16 @@ pub mod request {
                 as_user: None,
                 icon_url: user.img_url.clone(),
                 icon_emoji: None,
-                username: Some(format!("{} (via BoiseBot)", user.name)),
+                username: Some(format!("{} {}", user.name, POSTED_AS_USER_SUFFIX)),
+                thread_ts: None,
             }
         }
+
+        /// reply in thread of message
+        pub fn in_thread(mut self, ts: &str) -> Self {
+            self.thread_ts = Some(ts.to_string());
+            self
+        }
     }
 
     impl SlackUrlRequest for PostMessageRequest {
@@ -0,0 +1,50 @@
+pub mod request {
+    use crate::slack_api::api_endpoints::FILES_INFO;
+    use crate::slack_api::url_requests::SlackUrlRequest;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    pub struct FilesInfoRequest {
+        /// Specify a file by providing its ID. e.g: "F2147483862"
+        pub file: String,
+    }
+
+    impl FilesInfoRequest {
+        pub fn new(file_id: &str) -> Self {
+            FilesInfoRequest {
+                file: file_id.to_string(),
+            }
+        }
+    }
+
+    impl SlackUrlRequest for FilesInfoRequest {
+        fn get_api_url(&self) -> &str {
+            FILES_INFO
+        }
+    }
+}
+
+pub mod response {
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct FilesInfoResponse {
+        pub ok: bool,
+        pub error: Option<String>,
+        pub file: Option<FileInfo>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct FileInfo {
+        /// The ID of the file object
+        pub id: String,
+        /// Name of the file; may be null for unnamed files
+        pub name: Option<String>,
+        /// The file's type. e.g: "gpx"
+        pub filetype: String,
+        /// The filesize in bytes
+        pub size: usize,
+        /// Url to download file contents, requires auth header with token
+        pub url_private_download: Option<String>,
+    }
+}
@@ -1,7 +1,7 @@
 pub mod request {
     use crate::slack_api::api_endpoints::FILES_LIST;
     use crate::slack_api::url_requests::SlackUrlRequest;
-    use chrono::FixedOffset;
+    use chrono::TimeZone;
     use serde::Serialize;
 
     #[derive(Serialize, Default)]
@@ -44,12 +44,12 @@ pub mod request {
             self
         }
 
-        pub fn time_from(mut self, date: chrono::DateTime<FixedOffset>) -> Self {
+        pub fn time_from<Tz: TimeZone>(mut self, date: chrono::DateTime<Tz>) -> Self {
             self.ts_from = Some(date.timestamp().to_string());
             self
         }
 
-        pub fn time_to(mut self, date: chrono::DateTime<FixedOffset>) -> Self {
+        pub fn time_to<Tz: TimeZone>(mut self, date: chrono::DateTime<Tz>) -> Self {
             self.ts_to = Some(date.timestamp().to_string());
             self
         }
@@ -1,4 +1,5 @@
 pub mod complete_upload_url_external;
+pub mod files_info;
 pub mod files_list;
 pub mod get_upload_url_external;
 
@@ -76,5 +76,7 @@ pub mod response {
         pub last_name: String,
         pub image_24: Option<String>,
         pub image_72: Option<String>,
+        /// set for bot users (ie. `B123`), matches `bot_id` on messages they post
+        pub bot_id: Option<String>,
     }
 }
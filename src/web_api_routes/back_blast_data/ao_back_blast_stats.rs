@@ -1,4 +1,5 @@
 use crate::app_state::ao_data::AO;
+use crate::app_state::MutableAppState;
 use crate::db::queries::all_back_blasts::back_blasts_by_ao::back_blasts_by_channel_id;
 use crate::db::queries::all_back_blasts::calculate_bb_list_stats::get_avg_pax_per_bd;
 use crate::db::queries::all_back_blasts::BackBlastJsonData;
@@ -49,9 +50,11 @@ impl AOBackBlastsStats {
 pub async fn get_back_blast_stats_by_ao(
     path: web::Path<RouteData>,
     db_pool: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
 ) -> impl Responder {
-    let ao = AO::from(path.into_inner().ao_name);
-    let channel_id = ao.channel_id();
+    let registry = app_state.get_ao_registry();
+    let ao = AO::from_name(&path.into_inner().ao_name, &registry);
+    let channel_id = ao.channel_id(&registry);
 
     match back_blasts_by_channel_id(&db_pool, channel_id).await {
         Ok(results) => HttpResponse::Ok().json(AOBackBlastsStats::new(results)),
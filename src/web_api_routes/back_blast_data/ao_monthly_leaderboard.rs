@@ -1,3 +1,4 @@
+use crate::app_state::MutableAppState;
 use crate::web_api_routes::graphs::ao_monthly_leaderboard::get_ao_monthly_stats_graph;
 use crate::web_api_state::MutableWebState;
 use actix_web::{web, HttpResponse, Responder};
@@ -15,11 +16,13 @@ pub async fn ao_monthly_leaderboard_route(
     db_pool: web::Data<PgPool>,
     query: web::Query<MonthLeaderboardQuery>,
     web_state: web::Data<MutableWebState>,
+    app_state: web::Data<MutableAppState>,
 ) -> impl Responder {
     match get_ao_monthly_stats_graph(
         &db_pool,
         &query.date,
         &web_state,
+        &app_state.get_ao_registry(),
         String::from("C03TZV5RRF1"),
     )
     .await
@@ -1,5 +1,7 @@
 use crate::app_state::backblast_data::BackBlastData;
 use crate::db::queries::all_back_blasts::get_back_blast_by_id;
+use crate::db::queries::exercises::get_back_blast_exercises;
+use crate::db::queries::routes::get_back_blast_routes;
 use actix_web::{web, HttpResponse, Responder};
 use serde::Deserialize;
 use sqlx::PgPool;
@@ -14,11 +16,32 @@ pub async fn get_single_back_blast_data(
     path: web::Path<SingleRouteData>,
     db_pool: web::Data<PgPool>,
 ) -> impl Responder {
-    match get_back_blast_by_id(&db_pool, path.id.as_str()).await {
-        Ok(response) => {
-            let data = response.map(BackBlastData::from);
-            HttpResponse::Ok().json(data)
+    let result = match get_back_blast_by_id(&db_pool, path.id.as_str()).await {
+        Ok(Some(response)) => {
+            let mut data = BackBlastData::from(response);
+            get_back_blast_exercises(&db_pool, path.id.as_str())
+                .await
+                .map(|exercises| {
+                    data.exercises = exercises;
+                    Some(data)
+                })
         }
+        Ok(None) => Ok(None),
+        Err(err) => Err(err),
+    };
+    match result {
+        Ok(data) => HttpResponse::Ok().json(data),
+        Err(err) => HttpResponse::BadRequest().body(err.to_string()),
+    }
+}
+
+/// return routes (summary and simplified track) uploaded with backblast
+pub async fn get_single_back_blast_routes(
+    path: web::Path<SingleRouteData>,
+    db_pool: web::Data<PgPool>,
+) -> impl Responder {
+    match get_back_blast_routes(&db_pool, path.id.as_str()).await {
+        Ok(routes) => HttpResponse::Ok().json(routes),
         Err(err) => HttpResponse::BadRequest().body(err.to_string()),
     }
 }
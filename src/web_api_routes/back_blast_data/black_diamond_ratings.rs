@@ -0,0 +1,67 @@
+use crate::db::queries::black_diamond_ratings::{
+    get_q_rating_leaderboard, get_ratings_within_date_range, get_top_ratings,
+};
+use crate::shared::responses::failure;
+use crate::shared::time::DateRange;
+use crate::web_api_routes::graphs::black_diamond_ratings::post_black_diamond_trend_graph;
+use crate::web_api_state::MutableWebState;
+use actix_web::{web, HttpResponse, Responder};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+/// number of beatdowns on all time leaderboard
+const TOP_RATINGS_LIMIT: i64 = 10;
+
+#[derive(Deserialize)]
+pub struct TrendGraphQuery {
+    /// channel to post graph to
+    pub channel_id: String,
+    /// optional range. Format: 2023/04/05-2023/05/30
+    pub range: Option<String>,
+}
+
+/// route to get black diamond ratings within date range
+pub async fn black_diamond_ratings_route(
+    db: web::Data<PgPool>,
+    query: web::Query<DateRange>,
+) -> impl Responder {
+    match get_ratings_within_date_range(&db, &query.start, &query.end).await {
+        Ok(results) => HttpResponse::Ok().json(results),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to get highest rated black diamond beatdowns of all time
+pub async fn black_diamond_leaderboard_route(db: web::Data<PgPool>) -> impl Responder {
+    match get_top_ratings(&db, TOP_RATINGS_LIMIT).await {
+        Ok(results) => HttpResponse::Ok().json(results),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to get Qs by average rating of black diamond beatdowns they Q'd
+pub async fn black_diamond_q_leaderboard_route(db: web::Data<PgPool>) -> impl Responder {
+    match get_q_rating_leaderboard(&db).await {
+        Ok(results) => HttpResponse::Ok().json(results),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to post black diamond ratings trend graph to channel
+pub async fn black_diamond_trend_graph_route(
+    db: web::Data<PgPool>,
+    web_state: web::Data<MutableWebState>,
+    query: web::Query<TrendGraphQuery>,
+) -> impl Responder {
+    match post_black_diamond_trend_graph(
+        &db,
+        &web_state,
+        query.channel_id.to_string(),
+        query.range.as_deref().unwrap_or_default(),
+    )
+    .await
+    {
+        Ok(_) => HttpResponse::Ok().body("Done"),
+        Err(err) => HttpResponse::BadRequest().body(err.to_string()),
+    }
+}
@@ -0,0 +1,79 @@
+use crate::app_state::revisions::{RevisionActor, RevisionSource};
+use crate::app_state::MutableAppState;
+use crate::db::queries::all_back_blasts::deleted_back_blasts::get_deleted_back_blasts;
+use crate::shared::responses::{failure, success};
+use crate::web_api_routes::auth::internal_auth;
+use crate::web_api_routes::back_blast_data::back_blast_single::SingleRouteData;
+use crate::web_api_routes::interactive_events::delete_backblast::{
+    delete_and_update_message, restore_and_update_message,
+};
+use crate::web_api_state::MutableWebState;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+#[derive(Deserialize)]
+pub struct ActingUserQuery {
+    /// slack user doing the action
+    pub user: Option<String>,
+}
+
+impl ActingUserQuery {
+    fn actor(&self) -> RevisionActor {
+        RevisionActor::new(RevisionSource::Admin, self.user.as_deref())
+    }
+}
+
+/// route to list deleted backblasts
+pub async fn deleted_back_blasts_route(db: web::Data<PgPool>) -> impl Responder {
+    match get_deleted_back_blasts(&db).await {
+        Ok(list) => HttpResponse::Ok().json(list),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to soft delete backblast (admins only)
+pub async fn delete_back_blast_route(
+    db: web::Data<PgPool>,
+    web_state: web::Data<MutableWebState>,
+    req: HttpRequest,
+    path: web::Path<SingleRouteData>,
+    query: web::Query<ActingUserQuery>,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    match delete_and_update_message(&db, &web_state, path.id.as_str(), &query.actor(), None).await {
+        Ok(_) => success(),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to restore deleted backblast (admins only)
+pub async fn restore_back_blast_route(
+    db: web::Data<PgPool>,
+    web_state: web::Data<MutableWebState>,
+    app_state: web::Data<MutableAppState>,
+    req: HttpRequest,
+    path: web::Path<SingleRouteData>,
+    query: web::Query<ActingUserQuery>,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    match restore_and_update_message(
+        &db,
+        &web_state,
+        path.id.as_str(),
+        &query.actor(),
+        None,
+        &app_state.get_ao_registry(),
+    )
+    .await
+    {
+        Ok(_) => success(),
+        Err(err) => failure(err),
+    }
+}
@@ -0,0 +1,88 @@
+use crate::app_state::ao_data::AO;
+use crate::app_state::MutableAppState;
+use crate::db::queries::exercises::{
+    exercise_frequency_by_ao, exercise_frequency_by_q, exercise_history,
+};
+use crate::db::save_exercises::backfill_extracted_exercises;
+use crate::shared::responses::failure;
+use crate::shared::time::DateRange;
+use crate::web_api_routes::auth::internal_auth;
+use crate::web_api_state::MutableWebState;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+#[derive(Deserialize)]
+pub struct AoRouteData {
+    pub ao_name: String,
+}
+
+#[derive(Deserialize)]
+pub struct PaxRouteData {
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct ExerciseRouteData {
+    pub exercise: String,
+}
+
+#[derive(Serialize)]
+pub struct BackfillResponse {
+    pub back_blasts: usize,
+}
+
+/// route to get how often exercises were done at ao within date range
+pub async fn ao_exercise_frequency_route(
+    db: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
+    path: web::Path<AoRouteData>,
+    query: web::Query<DateRange>,
+) -> impl Responder {
+    let registry = app_state.get_ao_registry();
+    let ao = AO::from_name(&path.into_inner().ao_name, &registry);
+    let channel_id = ao.channel_id(&registry);
+    match exercise_frequency_by_ao(&db, channel_id, &query.start, &query.end).await {
+        Ok(results) => HttpResponse::Ok().json(results),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to get how often exercises were done at backblasts pax has Q'd within date range
+pub async fn q_exercise_frequency_route(
+    db: web::Data<PgPool>,
+    path: web::Path<PaxRouteData>,
+    query: web::Query<DateRange>,
+) -> impl Responder {
+    match exercise_frequency_by_q(&db, path.name.as_str(), &query.start, &query.end).await {
+        Ok(results) => HttpResponse::Ok().json(results),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to get every backblast an exercise was done at (ie. last time someone Q'd a Dora)
+pub async fn exercise_history_route(
+    db: web::Data<PgPool>,
+    path: web::Path<ExerciseRouteData>,
+) -> impl Responder {
+    match exercise_history(&db, path.exercise.as_str()).await {
+        Ok(results) => HttpResponse::Ok().json(results),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to extract exercises from moleskines of backblasts without any (admins only)
+pub async fn backfill_exercises_route(
+    db: web::Data<PgPool>,
+    web_state: web::Data<MutableWebState>,
+    req: HttpRequest,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    match backfill_extracted_exercises(&db).await {
+        Ok(back_blasts) => HttpResponse::Ok().json(BackfillResponse { back_blasts }),
+        Err(err) => failure(err),
+    }
+}
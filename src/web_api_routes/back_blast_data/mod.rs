@@ -1,10 +1,11 @@
-use crate::app_state::ao_data::const_names::AO_LIST;
 use crate::app_state::backblast_data::BackBlastData;
+use crate::app_state::MutableAppState;
 use crate::db::queries::all_back_blasts::{get_all, get_all_dd};
 use crate::db::queries::missing_back_blasts::get_back_blasts_since;
+use crate::shared::time::local_boise_time;
 use crate::web_api_routes::back_blast_data::top_pax_per_ao::get_top_pax_per_ao;
 use actix_web::{web, HttpResponse, Responder};
-use chrono::{Datelike, Months, NaiveDate, Utc};
+use chrono::{Datelike, Months, NaiveDate};
 use serde::Serialize;
 use sqlx::PgPool;
 use std::ops::Sub;
@@ -12,12 +13,16 @@ use std::ops::Sub;
 pub mod ao_back_blast_stats;
 pub mod ao_monthly_leaderboard;
 pub mod back_blast_single;
+pub mod black_diamond_ratings;
 pub mod csv_download_all;
+pub mod deleted_back_blasts;
 pub mod double_downs;
+pub mod exercise_stats;
 pub mod pax_leaderboard_graph;
 pub mod remind_missing_back_blasts;
 pub mod test_png;
 pub mod top_pax_per_ao;
+pub mod workout_scores;
 pub mod yearly_stats;
 
 /// route to get all back blast data
@@ -81,17 +86,21 @@ pub struct MissingBackBlastData {
 }
 
 /// get missing back blast data.
-pub async fn get_missing_back_blasts(db_pool: web::Data<PgPool>) -> impl Responder {
-    let now = Utc::now().date_naive();
+pub async fn get_missing_back_blasts(
+    db_pool: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
+) -> impl Responder {
+    let registry = app_state.get_ao_registry();
+    let now = local_boise_time().date_naive();
     let six_months_ago = now.sub(Months::new(6));
     match get_back_blasts_since(&db_pool, &six_months_ago).await {
         Ok(list) => {
             let mut results: Vec<MissingBackBlastData> = vec![];
-            for ao in AO_LIST {
+            for ao in registry.active_aos() {
                 let mut date_to_check = six_months_ago;
 
                 while date_to_check < now {
-                    if ao.week_days().contains(&date_to_check.weekday()) {
+                    if ao.week_days(&registry).contains(&date_to_check.weekday()) {
                         // if checked date is part of ao week day
                         let exists = list
                             .iter()
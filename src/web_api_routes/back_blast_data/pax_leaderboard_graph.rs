@@ -1,4 +1,5 @@
 use crate::app_state::ao_data::AO;
+use crate::app_state::MutableAppState;
 use crate::web_api_routes::graphs::ao_pax_leaderboard::post_ao_pax_leaderboard_graph;
 use crate::web_api_state::MutableWebState;
 use actix_web::{web, HttpResponse, Responder};
@@ -14,10 +15,19 @@ pub struct PaxLeaderboardQuery {
 pub async fn pax_leaderboard_route(
     db_pool: web::Data<PgPool>,
     web_state: web::Data<MutableWebState>,
+    app_state: web::Data<MutableAppState>,
     query: web::Query<PaxLeaderboardQuery>,
 ) -> impl Responder {
-    let ao = AO::from(query.ao.to_string());
-    match post_ao_pax_leaderboard_graph(&db_pool, &web_state, ao.channel_id().to_string()).await {
+    let registry = app_state.get_ao_registry();
+    let ao = AO::from_name(&query.ao, &registry);
+    match post_ao_pax_leaderboard_graph(
+        &db_pool,
+        &web_state,
+        &registry,
+        ao.channel_id(&registry).to_string(),
+    )
+    .await
+    {
         Ok(_) => HttpResponse::Ok().body("Done"),
         Err(err) => HttpResponse::BadRequest().body(err.to_string()),
     }
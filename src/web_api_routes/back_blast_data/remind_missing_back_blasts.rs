@@ -1,6 +1,8 @@
-use crate::app_state::ao_data::const_names::AO_LIST;
 use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::MutableAppState;
 use crate::db::queries::all_back_blasts::BackBlastJsonData;
+use crate::db::queries::ao_closures::get_closure_calendar;
 use crate::db::queries::q_line_up::QLineUpDbData;
 use crate::db::queries::{all_back_blasts, q_line_up};
 use crate::shared::common_errors::AppError;
@@ -16,28 +18,33 @@ use sqlx::PgPool;
 pub async fn remind_missing_back_blasts(
     db_pool: web::Data<PgPool>,
     web_state: web::Data<MutableWebState>,
+    app_state: web::Data<MutableAppState>,
     req: HttpRequest,
 ) -> impl Responder {
     if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_ok() {
+        let registry = app_state.get_ao_registry();
         let today = local_boise_time().date_naive();
         let yesterday = today.pred_opt().unwrap();
         match (
             yesterdays_bb(&db_pool, &yesterday, &today).await,
             yesterdays_signups(&db_pool, &yesterday, &today).await,
+            get_closure_calendar(&db_pool, &yesterday, &yesterday).await,
         ) {
-            (Ok(bb), Ok(sign_ups)) => {
-                let filtered: Vec<AO> = AO_LIST
+            (Ok(bb), Ok(sign_ups), Ok(closures)) => {
+                let filtered: Vec<AO> = registry
+                    .active_aos()
                     .into_iter()
                     .filter(|ao| {
                         // if ao that meets on day yesterday
-                        if ao.week_days().contains(&yesterday.weekday()) {
+                        if ao.week_days(&registry).contains(&yesterday.weekday()) {
                             let posted = bb
                                 .iter()
                                 .find(|item| item.ao == ao.to_string() && item.date == yesterday);
                             let sign_up = sign_ups
                                 .iter()
                                 .find(|item| item.ao == ao.to_string() && item.date == yesterday);
-                            let was_closed = sign_up.map(|data| data.closed).unwrap_or(false);
+                            let was_closed = sign_up.map(|data| data.closed).unwrap_or(false)
+                                || closures.is_closed(ao, &yesterday);
                             // if not posted and wasn't closed yesterday
                             posted.is_none() && !was_closed
                         } else {
@@ -48,7 +55,7 @@ pub async fn remind_missing_back_blasts(
 
                 for ao in filtered {
                     println!("missing BB for {:?}", ao.to_string());
-                    let request = get_message_request(&ao);
+                    let request = get_message_request(&ao, &registry);
                     match web_state.post_message(request).await {
                         Ok(_) => {}
                         Err(err) => println!("error calling slack: {:?}", err),
@@ -63,11 +70,11 @@ pub async fn remind_missing_back_blasts(
     }
 }
 
-fn get_message_request(ao: &AO) -> PostMessageRequest {
+fn get_message_request(ao: &AO, registry: &AoRegistry) -> PostMessageRequest {
     let block_builder = BlockBuilder::new()
         .section_markdown("*Reminder*")
         .section_markdown("Please don't forget to post back-blast from yesterday.");
-    PostMessageRequest::new(ao.channel_id(), block_builder.blocks)
+    PostMessageRequest::new(ao.channel_id(registry), block_builder.blocks)
 }
 
 async fn yesterdays_bb(
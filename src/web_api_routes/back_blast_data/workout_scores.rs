@@ -0,0 +1,63 @@
+use crate::app_state::ao_data::AO;
+use crate::app_state::MutableAppState;
+use crate::db::queries::scoring::{
+    get_scores_within_date_range, get_scoring_formula, get_top_scores,
+};
+use crate::shared::common_errors::AppError;
+use crate::shared::responses::failure;
+use crate::shared::time::DateRange;
+use actix_web::{web, HttpResponse, Responder};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+/// number of scores on all time leaderboard
+const TOP_SCORES_LIMIT: i64 = 10;
+
+#[derive(Deserialize)]
+pub struct AoRouteData {
+    pub ao_name: String,
+}
+
+/// route to get workout scores of ao within date range
+pub async fn ao_workout_scores_route(
+    db: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
+    path: web::Path<AoRouteData>,
+    query: web::Query<DateRange>,
+) -> impl Responder {
+    let registry = app_state.get_ao_registry();
+    let ao = AO::from_name(&path.into_inner().ao_name, &registry);
+    let channel_id = ao.channel_id(&registry);
+    match get_scores_within_date_range(&db, channel_id, &query.start, &query.end).await {
+        Ok(results) => HttpResponse::Ok().json(results),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to get best workout scores of ao of all time
+pub async fn ao_workout_score_leaderboard_route(
+    db: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
+    path: web::Path<AoRouteData>,
+) -> impl Responder {
+    let registry = app_state.get_ao_registry();
+    let ao = AO::from_name(&path.into_inner().ao_name, &registry);
+    let channel_id = ao.channel_id(&registry);
+    let result = match get_scoring_formula(&db, channel_id).await {
+        Ok(Some(formula)) => {
+            get_top_scores(
+                &db,
+                channel_id,
+                formula.formula.higher_is_better,
+                TOP_SCORES_LIMIT,
+            )
+            .await
+        }
+        Ok(None) => Err(AppError::from("No scoring formula for AO")),
+        Err(err) => Err(err),
+    };
+    match result {
+        Ok(results) => HttpResponse::Ok().json(results),
+        Err(err) => failure(err),
+    }
+}
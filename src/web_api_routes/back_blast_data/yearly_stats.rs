@@ -1,6 +1,10 @@
+use crate::app_state::ao_closures::ClosureCalendar;
 use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
 use crate::app_state::backblast_data::BackBlastData;
+use crate::app_state::MutableAppState;
 use crate::db::queries::all_back_blasts::{get_all_within_date_range, BackBlastJsonData};
+use crate::db::queries::ao_closures::get_closure_calendar;
 use crate::shared::common_errors::AppError;
 use actix_web::{web, HttpResponse, Responder};
 use chrono::{Datelike, NaiveDate};
@@ -27,6 +31,12 @@ pub struct YearlyStats {
 #[derive(Debug, Default)]
 struct YearlyStatIterCollection {
     year: i32,
+    start: NaiveDate,
+    end: NaiveDate,
+    /// closures within date range
+    closures: ClosureCalendar,
+    /// ao schedules to count open days with
+    registry: AoRegistry,
     /// total number of pax posts
     pax_posts: usize,
     /// set of all pax (for uniqueness)
@@ -38,7 +48,13 @@ struct YearlyStatIterCollection {
 }
 
 impl YearlyStatIterCollection {
-    fn new(start: NaiveDate, bb: Vec<BackBlastJsonData>) -> Self {
+    fn new(
+        start: NaiveDate,
+        end: NaiveDate,
+        bb: Vec<BackBlastJsonData>,
+        closures: ClosureCalendar,
+        registry: AoRegistry,
+    ) -> Self {
         let mut result = bb
             .iter()
             .fold(YearlyStatIterCollection::default(), |mut acc, item| {
@@ -53,9 +69,28 @@ impl YearlyStatIterCollection {
             });
 
         result.year = start.year();
+        result.start = start;
+        result.end = end;
+        result.closures = closures;
+        result.registry = registry;
         result
     }
 
+    /// average number of open workouts per week across aos (closures excluded).
+    fn get_weekly_post_options(&self) -> usize {
+        let days = self.end.signed_duration_since(self.start).num_days() + 1;
+        if days <= 0 {
+            return 0;
+        }
+        let open_days = self.aos.iter().fold(0, |acc, ao| {
+            acc + self
+                .closures
+                .open_days_between(ao, &self.start, &self.end, &self.registry)
+        });
+        let weeks = days as f32 / 7.;
+        (open_days as f32 / weeks).round() as usize
+    }
+
     /// get average daily attendance.
     fn get_avg_daily_attendance(&self) -> usize {
         let value_counts = self.bb_dates.values().count();
@@ -70,10 +105,7 @@ impl YearlyStatIterCollection {
 
 impl From<YearlyStatIterCollection> for YearlyStats {
     fn from(stats: YearlyStatIterCollection) -> Self {
-        let weekly_post_options = stats.aos.iter().fold(0, |mut acc, ao| {
-            acc += ao.week_days().len();
-            acc
-        });
+        let weekly_post_options = stats.get_weekly_post_options();
 
         YearlyStats {
             year: stats.year,
@@ -94,9 +126,11 @@ pub struct YearlyStatsQuery {
 /// route to get yearly stats for whole region
 pub async fn get_yearly_stats_route(
     db_pool: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
     request: web::Query<YearlyStatsQuery>,
 ) -> impl Responder {
-    match get_yearly_stats(&db_pool, request.start, request.end).await {
+    let registry = app_state.get_ao_registry();
+    match get_yearly_stats(&db_pool, registry, request.start, request.end).await {
         Ok(response) => HttpResponse::Ok().json(response),
         Err(err) => HttpResponse::BadRequest().body(err.to_string()),
     }
@@ -105,6 +139,7 @@ pub async fn get_yearly_stats_route(
 /// get yearly stats for whole region.
 async fn get_yearly_stats(
     db_pool: &PgPool,
+    registry: AoRegistry,
     start: NaiveDate,
     end: NaiveDate,
 ) -> Result<YearlyStats, AppError> {
@@ -115,7 +150,43 @@ async fn get_yearly_stats(
     }
 
     let bb = get_all_within_date_range(db_pool, &start, &end).await?;
-    let stats_collection = YearlyStatIterCollection::new(start, bb);
+    let closures = get_closure_calendar(db_pool, &start, &end).await?;
+    let stats_collection = YearlyStatIterCollection::new(start, end, bb, closures, registry);
     let stats = YearlyStats::from(stats_collection);
     Ok(stats)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::ao_closures::AoClosure;
+
+    #[test]
+    fn weekly_post_options_exclude_closures() {
+        // bleach meets mon, wed, sat
+        let start = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 7, 14).unwrap();
+        let mut stats = YearlyStatIterCollection::new(
+            start,
+            end,
+            vec![],
+            ClosureCalendar::default(),
+            AoRegistry::default(),
+        );
+        stats.aos.insert(AO::Bleach);
+        assert_eq!(stats.get_weekly_post_options(), 3);
+
+        let closures = ClosureCalendar::new(vec![AoClosure {
+            id: String::from("1"),
+            ao: None,
+            start_date: NaiveDate::from_ymd_opt(2024, 7, 1).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2024, 7, 6).unwrap(),
+            recurring: false,
+            reason: String::from("Convergence week"),
+        }]);
+        let mut stats =
+            YearlyStatIterCollection::new(start, end, vec![], closures, AoRegistry::default());
+        stats.aos.insert(AO::Bleach);
+        assert_eq!(stats.get_weekly_post_options(), 2);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::app_state::ao_data::const_names::AO_LIST;
+use crate::app_state::ao_registry::AoRegistry;
 use crate::db::queries::all_back_blasts::{get_all_within_date_range, BackBlastJsonData};
 use crate::shared::common_errors::AppError;
 use crate::shared::time::local_boise_time;
@@ -15,13 +15,14 @@ pub async fn get_ao_monthly_stats_graph(
     db_pool: &PgPool,
     date: &Option<NaiveDate>,
     web_state: &MutableWebState,
+    registry: &AoRegistry,
     channel_id: String,
 ) -> Result<(), AppError> {
     let default_end_date = local_boise_time().date_naive();
     let end_date = date.unwrap_or(default_end_date);
     let start_date = NaiveDate::from_ymd_opt(end_date.year(), end_date.month(), 1).unwrap();
     let bb_list = get_all_within_date_range(db_pool, &start_date, &end_date).await?;
-    let ao_monthly_stats = AoMonthlyStatsGraph::new(bb_list, start_date);
+    let ao_monthly_stats = AoMonthlyStatsGraph::new(bb_list, start_date, get_ao_list(registry));
     let file = graph_generator(ao_monthly_stats)?;
 
     let start = Instant::now();
@@ -44,16 +45,18 @@ pub async fn get_ao_monthly_stats_graph(
 struct AoMonthlyStatsGraph {
     bb_list: Vec<BackBlastJsonData>,
     date: NaiveDate,
+    aos: Vec<String>,
 }
 
 impl AoMonthlyStatsGraph {
-    fn new(bb_list: Vec<BackBlastJsonData>, date: NaiveDate) -> Self {
-        AoMonthlyStatsGraph { bb_list, date }
+    fn new(bb_list: Vec<BackBlastJsonData>, date: NaiveDate, aos: Vec<String>) -> Self {
+        AoMonthlyStatsGraph { bb_list, date, aos }
     }
 
     /// get chart data from back blasts to consume into charts. This is to get BD post avg.
     fn get_chart_data(&self) -> Vec<(String, f32, String)> {
-        let aos: Vec<(String, f32, String)> = get_ao_list()
+        let aos: Vec<(String, f32, String)> = self
+            .aos
             .iter()
             .map(|ao| {
                 let filtered = self.bb_list.iter().filter_map(|bb| {
@@ -94,7 +97,7 @@ impl GraphWrapper for AoMonthlyStatsGraph {
         let (top, right, bottom, left) = (90, 40, 50, 120);
 
         let y = charts::ScaleBand::new()
-            .set_domain(get_ao_list())
+            .set_domain(self.aos.clone())
             .set_range(vec![height - top - bottom, 0])
             .set_inner_padding(0.1)
             .set_outer_padding(0.1);
@@ -135,6 +138,10 @@ impl GraphWrapper for AoMonthlyStatsGraph {
     }
 }
 
-fn get_ao_list() -> Vec<String> {
-    AO_LIST.map(|ao| ao.to_string()).to_vec()
+fn get_ao_list(registry: &AoRegistry) -> Vec<String> {
+    registry
+        .active_aos()
+        .iter()
+        .map(|ao| ao.to_string())
+        .collect()
 }
@@ -1,4 +1,5 @@
 use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
 use crate::app_state::backblast_data::BackBlastData;
 use crate::db::queries::all_back_blasts::back_blasts_by_ao::back_blasts_by_channel_id_and_date_range;
 use crate::db::queries::all_back_blasts::BackBlastJsonData;
@@ -17,9 +18,10 @@ use std::ops::Sub;
 pub async fn post_ao_pax_leaderboard_graph(
     db_pool: &PgPool,
     web_state: &MutableWebState,
+    registry: &AoRegistry,
     channel_id: String,
 ) -> Result<(), AppError> {
-    let ao = AO::from_channel_id(channel_id.as_str());
+    let ao = AO::from_channel_id(channel_id.as_str(), registry);
     let now = local_boise_time().date_naive();
     // TODO temp
     // let now = now.sub(Months::new(3));
@@ -0,0 +1,182 @@
+//! Line Graph for Black Diamond ratings over time
+
+use crate::db::queries::black_diamond_ratings::{
+    get_ratings_within_date_range, BlackDiamondRatingDbData,
+};
+use crate::shared::common_errors::AppError;
+use crate::shared::string_utils::resolve_date_range;
+use crate::slack_api::files::request::FileUpload;
+use crate::web_api_routes::graphs::{graph_generator, GraphWrapper};
+use crate::web_api_state::MutableWebState;
+use charts::{MarkerType, PointLabelPosition};
+use chrono::NaiveDate;
+use sqlx::PgPool;
+
+/// post line graph of black diamond ratings within date range (defaults to last year)
+pub async fn post_black_diamond_trend_graph(
+    db_pool: &PgPool,
+    web_state: &MutableWebState,
+    channel_id: String,
+    date_range_text: &str,
+) -> Result<(), AppError> {
+    let (start, end) = resolve_date_range(date_range_text, 12);
+    let ratings = get_ratings_within_date_range(db_pool, &start, &end).await?;
+    if ratings.is_empty() {
+        return Err(AppError::from("No Black Diamond ratings in date range"));
+    }
+    let graph = BlackDiamondRatingsGraph::new(&ratings, (start, end));
+    let png = graph_generator(graph)?;
+    let text = format!(
+        "Black Diamond ratings from {} to {}",
+        start.format("%b %d, %Y"),
+        end.format("%b %d, %Y")
+    );
+
+    let file_request = FileUpload::new(&channel_id, png, "black-diamond-ratings.png", "image/png")
+        .with_title(&text);
+
+    web_state.upload_file(file_request).await?;
+    Ok(())
+}
+
+struct BlackDiamondRatingsGraph {
+    /// (date, total) averaged per date, oldest first
+    data: Vec<(NaiveDate, f32)>,
+    date_range: (NaiveDate, NaiveDate),
+}
+
+impl BlackDiamondRatingsGraph {
+    fn new(ratings: &[BlackDiamondRatingDbData], date_range: (NaiveDate, NaiveDate)) -> Self {
+        let mut data = Vec::<(NaiveDate, f32, usize)>::new();
+        for rating in ratings {
+            match data.iter_mut().find(|(date, ..)| date == &rating.date) {
+                Some((_, total, count)) => {
+                    *total += rating.total;
+                    *count += 1;
+                }
+                None => data.push((rating.date, rating.total, 1)),
+            }
+        }
+        data.sort_by_key(|(date, ..)| *date);
+        BlackDiamondRatingsGraph {
+            data: data
+                .into_iter()
+                .map(|(date, total, count)| (date, total / count as f32))
+                .collect(),
+            date_range,
+        }
+    }
+
+    fn get_max_total(&self) -> f32 {
+        self.data
+            .iter()
+            .map(|(_, total)| *total)
+            .fold(0., f32::max)
+            .ceil()
+    }
+
+    fn get_chart_data(&self) -> Vec<(String, f32)> {
+        self.data
+            .iter()
+            .map(|(date, total)| (format_date(date), *total))
+            .collect()
+    }
+
+    fn get_dates(&self) -> Vec<String> {
+        self.data
+            .iter()
+            .map(|(date, _)| format_date(date))
+            .collect()
+    }
+}
+
+fn format_date(date: &NaiveDate) -> String {
+    date.format("%m/%d/%y").to_string()
+}
+
+impl GraphWrapper for BlackDiamondRatingsGraph {
+    const WIDTH: u32 = 800;
+    const HEIGHT: u32 = 600;
+
+    fn generate_chart(&self) -> Result<(), String> {
+        let width = self.width() as isize;
+        let height = self.height() as isize;
+        let (top, right, bottom, left) = (90, 40, 50, 60);
+
+        let y = charts::ScaleLinear::new()
+            .set_domain(vec![0.0, self.get_max_total()])
+            .set_range(vec![height - top - bottom, 0]);
+
+        let x = charts::ScaleBand::new()
+            .set_domain(self.get_dates())
+            .set_range(vec![0, width - left - right])
+            .set_inner_padding(0.1)
+            .set_outer_padding(0.1);
+
+        let view = charts::LineSeriesView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_colors(charts::Color::color_scheme_dark())
+            .set_marker_type(MarkerType::Circle)
+            .set_label_position(PointLabelPosition::N)
+            .set_label_visibility(false)
+            .load_data(&self.get_chart_data())
+            .unwrap();
+
+        charts::Chart::new()
+            .set_width(width)
+            .set_height(height)
+            .set_margins(top, right, bottom, left)
+            .add_title("Black Diamond Ratings".to_string())
+            .add_view(&view)
+            .add_axis_bottom(&x)
+            .add_axis_left(&y)
+            .save(self.file_path())
+    }
+
+    fn file_name(&self) -> String {
+        let (start, end) = self.date_range;
+        format!("black-diamond-ratings-{}-{}", start, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn rating(day: u32, total: f32) -> BlackDiamondRatingDbData {
+        let date = NaiveDate::from_ymd_opt(2024, 7, day).unwrap();
+        BlackDiamondRatingDbData {
+            id: Uuid::new_v4(),
+            back_blast_id: None,
+            date,
+            pax_count: 8,
+            vests_removed: 4,
+            miles: 3.,
+            avg_hr: 140.,
+            total,
+            rated_by: None,
+            created: date.and_hms_opt(6, 0, 0).unwrap(),
+            q: None,
+        }
+    }
+
+    #[test]
+    fn averages_same_day() {
+        let ratings = vec![rating(10, 3.), rating(3, 2.5), rating(10, 4.)];
+        let range = (
+            NaiveDate::from_ymd_opt(2024, 7, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 7, 31).unwrap(),
+        );
+        let graph = BlackDiamondRatingsGraph::new(&ratings, range);
+        assert_eq!(
+            graph.get_chart_data(),
+            vec![
+                (String::from("07/03/24"), 2.5),
+                (String::from("07/10/24"), 3.5)
+            ]
+        );
+        assert_eq!(graph.get_max_total(), 4.);
+    }
+}
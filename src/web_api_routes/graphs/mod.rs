@@ -2,8 +2,11 @@ use crate::shared::common_errors::AppError;
 
 pub mod ao_monthly_leaderboard;
 pub mod ao_pax_leaderboard;
+pub mod black_diamond_ratings;
 pub mod overall_pax_leaderboard;
 pub mod pax_bd_overtime;
+pub mod route_map;
+pub mod ruck_leaderboard;
 
 /// Trait for building graph with common methods like generating chart and declaring file name.
 pub trait GraphWrapper {
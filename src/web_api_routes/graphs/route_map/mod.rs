@@ -0,0 +1,142 @@
+//! Small map thumbnail of route uploaded with backblast (track line with start and end markers)
+
+use crate::app_state::route_track::RouteSummary;
+use crate::shared::common_errors::AppError;
+use crate::slack_api::files::request::FileUpload;
+use crate::web_api_routes::graphs::{graph_generator, GraphWrapper};
+use crate::web_api_state::MutableWebState;
+
+/// space around track in pixels
+const PADDING: f64 = 20.0;
+
+/// post route map thumbnail with summary as title
+pub async fn post_route_map(
+    web_state: &MutableWebState,
+    channel_id: &str,
+    file_id: &str,
+    summary: &RouteSummary,
+) -> Result<(), AppError> {
+    let graph = RouteMapGraph::new(&summary.track, file_id);
+    let file_name = format!("{}.png", graph.file_name());
+    let text = format!("Route: {}", summary.summary_text());
+    let png = graph_generator(graph)?;
+    let file_request = FileUpload::new(channel_id, png, &file_name, "image/png").with_title(&text);
+    web_state.upload_file(file_request).await
+}
+
+struct RouteMapGraph {
+    /// `[lat, lon]` pairs
+    track: Vec<[f64; 2]>,
+    file_id: String,
+}
+
+impl RouteMapGraph {
+    fn new(track: &[[f64; 2]], file_id: &str) -> Self {
+        RouteMapGraph {
+            track: track.to_vec(),
+            file_id: file_id.to_string(),
+        }
+    }
+
+    /// track scaled to pixel coordinates, keeping aspect ratio and centered
+    fn get_pixel_points(&self) -> Vec<(f64, f64)> {
+        if self.track.is_empty() {
+            return Vec::new();
+        }
+        let mean_lat = self.track.iter().map(|[lat, _]| lat).sum::<f64>() / self.track.len() as f64;
+        let lon_scale = mean_lat.to_radians().cos();
+        let projected = self
+            .track
+            .iter()
+            .map(|[lat, lon]| (lon * lon_scale, -lat))
+            .collect::<Vec<(f64, f64)>>();
+
+        let (min_x, max_x) = min_max(projected.iter().map(|(x, _)| *x));
+        let (min_y, max_y) = min_max(projected.iter().map(|(_, y)| *y));
+        let width = self.width() as f64 - PADDING * 2.0;
+        let height = self.height() as f64 - PADDING * 2.0;
+        let scale = (width / (max_x - min_x)).min(height / (max_y - min_y));
+        let scale = if scale.is_finite() { scale } else { 0.0 };
+        let offset_x = PADDING + (width - (max_x - min_x) * scale) / 2.0;
+        let offset_y = PADDING + (height - (max_y - min_y) * scale) / 2.0;
+        projected
+            .into_iter()
+            .map(|(x, y)| {
+                (
+                    offset_x + (x - min_x) * scale,
+                    offset_y + (y - min_y) * scale,
+                )
+            })
+            .collect()
+    }
+
+    fn get_svg(&self) -> String {
+        let points = self.get_pixel_points();
+        let line = points
+            .iter()
+            .map(|(x, y)| format!("{:.1},{:.1}", x, y))
+            .collect::<Vec<String>>()
+            .join(" ");
+        let marker = |point: Option<&(f64, f64)>, color: &str| {
+            point
+                .map(|(x, y)| {
+                    format!(
+                        r#"<circle cx="{:.1}" cy="{:.1}" r="6" fill="{}" stroke="white" stroke-width="2"/>"#,
+                        x, y, color
+                    )
+                })
+                .unwrap_or_default()
+        };
+        format!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}"><rect width="100%" height="100%" fill="#f4f1ea"/><polyline points="{line}" fill="none" stroke="#d1495b" stroke-width="4" stroke-linejoin="round" stroke-linecap="round"/>{start}{end}</svg>"##,
+            width = self.width(),
+            height = self.height(),
+            line = line,
+            start = marker(points.first(), "#2e7d32"),
+            end = marker(points.last(), "#263238"),
+        )
+    }
+}
+
+fn min_max(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::MAX, f64::MIN), |(min, max), value| {
+        (min.min(value), max.max(value))
+    })
+}
+
+impl GraphWrapper for RouteMapGraph {
+    const WIDTH: u32 = 400;
+    const HEIGHT: u32 = 400;
+
+    fn generate_chart(&self) -> Result<(), String> {
+        std::fs::write(self.file_path(), self.get_svg()).map_err(|err| err.to_string())
+    }
+
+    fn file_name(&self) -> String {
+        format!("route-{}", self.file_id.to_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn track_fits_thumbnail() {
+        let graph = RouteMapGraph::new(
+            &[[43.615, -116.2023], [43.617, -116.2023], [43.617, -116.201]],
+            "F123",
+        );
+        let points = graph.get_pixel_points();
+        for (x, y) in &points {
+            assert!(*x >= PADDING - 0.01 && *x <= 400.0 - PADDING + 0.01);
+            assert!(*y >= PADDING - 0.01 && *y <= 400.0 - PADDING + 0.01);
+        }
+        // north is up
+        assert!(points[1].1 < points[0].1);
+        // taller than wide, so fills height
+        assert!((points[0].1 - points[1].1 - 360.0).abs() < 0.01);
+        assert!(graph.get_svg().contains("<polyline"));
+        assert_eq!(graph.file_name(), "route-f123");
+    }
+}
@@ -0,0 +1,234 @@
+//! Bar graphs of top pax by ruck miles and pound-miles
+
+use crate::db::queries::ruck_stats::{get_ruck_leaderboard, RuckStatsItem};
+use crate::shared::common_errors::AppError;
+use crate::shared::time::local_boise_time;
+use crate::slack_api::files::request::FileUpload;
+use crate::web_api_routes::graphs::{graph_generator, GraphWrapper};
+use crate::web_api_state::MutableWebState;
+use charts::BarLabelPosition;
+use chrono::{Datelike, NaiveDate};
+use sqlx::PgPool;
+
+/// number of pax shown on graph
+const TOP_PAX: usize = 10;
+
+/// time period of ruck stats
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RuckPeriod {
+    /// current month to date
+    Month,
+    AllTime,
+}
+
+impl From<&str> for RuckPeriod {
+    fn from(text: &str) -> Self {
+        match text.trim().to_lowercase().as_str() {
+            "all" | "all-time" | "alltime" => RuckPeriod::AllTime,
+            _ => RuckPeriod::Month,
+        }
+    }
+}
+
+impl RuckPeriod {
+    /// (start, end) of period, `None` when unbounded
+    pub fn date_range(&self, today: NaiveDate) -> (Option<NaiveDate>, Option<NaiveDate>) {
+        match self {
+            RuckPeriod::Month => (today.with_day(1), Some(today)),
+            RuckPeriod::AllTime => (None, None),
+        }
+    }
+
+    pub fn label(&self, today: NaiveDate) -> String {
+        match self {
+            RuckPeriod::Month => today.format("%B %Y").to_string(),
+            RuckPeriod::AllTime => String::from("All Time"),
+        }
+    }
+}
+
+/// value graphed per pax
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RuckMetric {
+    Miles,
+    PoundMiles,
+}
+
+impl RuckMetric {
+    fn value(&self, item: &RuckStatsItem) -> f64 {
+        match self {
+            RuckMetric::Miles => item.miles,
+            RuckMetric::PoundMiles => item.pound_miles,
+        }
+    }
+
+    fn label(&self) -> &str {
+        match self {
+            RuckMetric::Miles => "Miles",
+            RuckMetric::PoundMiles => "Pound-Miles",
+        }
+    }
+}
+
+/// post graphs of top pax by ruck miles and pound-miles for period
+pub async fn post_ruck_leaderboard_graphs(
+    db_pool: &PgPool,
+    web_state: &MutableWebState,
+    channel_id: String,
+    period: RuckPeriod,
+) -> Result<(), AppError> {
+    let today = local_boise_time().date_naive();
+    let (start, end) = period.date_range(today);
+    let stats = get_ruck_leaderboard(db_pool, start.as_ref(), end.as_ref()).await?;
+    if stats.is_empty() {
+        return Err(AppError::from("No ruck stats for this period"));
+    }
+
+    for metric in [RuckMetric::PoundMiles, RuckMetric::Miles] {
+        let graph = RuckLeaderboardGraph::new(&stats, metric, period.label(today));
+        let file_name = format!("{}.png", graph.file_name());
+        let text = format!(
+            "Top {} rucking PAX by {} ({})",
+            TOP_PAX,
+            metric.label().to_lowercase(),
+            graph.period
+        );
+        let png = graph_generator(graph)?;
+        let file_request =
+            FileUpload::new(&channel_id, png, &file_name, "image/png").with_title(&text);
+        web_state.upload_file(file_request).await?;
+    }
+    Ok(())
+}
+
+struct RuckLeaderboardGraph {
+    /// (name, value) highest first
+    data: Vec<(String, f32)>,
+    metric: RuckMetric,
+    period: String,
+}
+
+impl RuckLeaderboardGraph {
+    fn new(stats: &[RuckStatsItem], metric: RuckMetric, period: String) -> Self {
+        let mut data = stats
+            .iter()
+            .map(|item| (item.name.to_string(), metric.value(item) as f32))
+            .filter(|(_, value)| *value > 0.)
+            .collect::<Vec<(String, f32)>>();
+        data.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        data.truncate(TOP_PAX);
+        RuckLeaderboardGraph {
+            data,
+            metric,
+            period,
+        }
+    }
+
+    fn get_max_value(&self) -> f32 {
+        self.data
+            .iter()
+            .map(|(_, value)| *value)
+            .fold(0., f32::max)
+            .ceil()
+    }
+
+    fn get_chart_data(&self) -> Vec<(String, f32, String)> {
+        self.data
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.round(), name.to_string()))
+            .collect()
+    }
+}
+
+impl GraphWrapper for RuckLeaderboardGraph {
+    const WIDTH: u32 = 800;
+    const HEIGHT: u32 = 600;
+
+    fn generate_chart(&self) -> Result<(), String> {
+        let width = self.width() as isize;
+        let height = self.height() as isize;
+        let (top, right, bottom, left) = (90, 40, 50, 80);
+
+        let data = self.get_chart_data();
+        let domain: Vec<String> = data.iter().map(|(name, ..)| name.to_string()).collect();
+
+        let y = charts::ScaleBand::new()
+            .set_domain(domain)
+            .set_range(vec![height - top - bottom, 0])
+            .set_inner_padding(0.1)
+            .set_outer_padding(0.1);
+
+        let x = charts::ScaleLinear::new()
+            .set_domain(vec![0.0, self.get_max_value()])
+            .set_range(vec![0, width - left - right]);
+
+        let view = charts::HorizontalBarView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .set_colors(charts::Color::color_scheme_dark())
+            .set_label_position(BarLabelPosition::Center)
+            .load_data(&data)
+            .unwrap();
+
+        charts::Chart::new()
+            .set_width(width)
+            .set_height(height)
+            .set_margins(top, right, bottom, left)
+            .add_title(format!("Ruck {} - {}", self.metric.label(), self.period))
+            .add_view(&view)
+            .add_axis_bottom(&x)
+            .add_axis_left(&y)
+            .add_bottom_axis_label(self.metric.label())
+            .save(self.file_path())
+    }
+
+    fn file_name(&self) -> String {
+        format!(
+            "ruck-{}-{}",
+            self.metric.label().to_lowercase(),
+            self.period.to_lowercase().replace(' ', "-")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str, miles: f64, pound_miles: f64) -> RuckStatsItem {
+        RuckStatsItem {
+            name: name.to_string(),
+            rucks: 1,
+            miles,
+            pound_miles,
+        }
+    }
+
+    #[test]
+    fn sorted_by_metric() {
+        let stats = vec![
+            item("stinger", 4., 180.),
+            item("backslash", 6., 120.),
+            item("puff", 0., 0.),
+        ];
+        let graph = RuckLeaderboardGraph::new(&stats, RuckMetric::Miles, String::from("All Time"));
+        assert_eq!(
+            graph.get_chart_data(),
+            vec![
+                (String::from("backslash"), 6., String::from("backslash")),
+                (String::from("stinger"), 4., String::from("stinger")),
+            ]
+        );
+        assert_eq!(graph.file_name(), "ruck-miles-all-time");
+    }
+
+    #[test]
+    fn period_range() {
+        let today = NaiveDate::from_ymd_opt(2024, 7, 18).unwrap();
+        assert_eq!(
+            RuckPeriod::from("").date_range(today),
+            (NaiveDate::from_ymd_opt(2024, 7, 1), Some(today))
+        );
+        assert_eq!(RuckPeriod::from("All").date_range(today), (None, None));
+    }
+}
@@ -1,13 +1,24 @@
+use crate::app_state::ao_data::AO;
 use crate::app_state::backblast_data::BackBlastData;
 use crate::app_state::pre_blast_data::PreBlastData;
+use crate::app_state::revisions::RevisionActor;
+use crate::app_state::roles::{Permission, UserRoles};
 use crate::app_state::MutableAppState;
+use crate::db::queries::all_back_blasts::deleted_back_blasts::is_back_blast_deleted;
+use crate::db::queries::pre_blasts;
+use crate::db::queries::user_roles::get_user_roles;
 use crate::db::queries::users::get_slack_id_map;
-use crate::shared::admin::admin_users;
+use crate::db::save_pre_blast;
 use crate::shared::common_errors::AppError;
 use crate::shared::constants;
+use crate::shared::string_utils::map_slack_id_to_link;
+use crate::shared::time::to_boise_time;
+use crate::slack_api::block_kit::BlockBuilder;
 use crate::slack_api::channels::public_channels::PublicChannels;
+use crate::slack_api::chat::post_ephemeral::request::PostEphemeralRequest;
 use crate::slack_api::views::payload::ViewPayload;
 use crate::slack_api::views::request::ViewsOpenRequest;
+use crate::web_api_routes::interactive_events::delete_backblast;
 use crate::web_api_routes::interactive_events::edit_backblast;
 use crate::web_api_routes::interactive_events::edit_pre_blast;
 use crate::web_api_routes::interactive_events::interaction_payload::{
@@ -17,10 +28,12 @@ use crate::web_api_routes::interactive_events::interaction_payload::{
 use crate::web_api_routes::interactive_events::interaction_types::{
     InteractionTypes, QSheetActionComboData,
 };
+use crate::web_api_routes::interactive_events::q_line_up::q_swap;
 use crate::web_api_routes::interactive_events::q_line_up::{
     clear_and_update_message, close_and_update_message, process_q_line_up_event,
-    update_existing_q_line_up_message,
+    update_existing_q_line_up_message, QSignUpResult,
 };
+use crate::web_api_routes::slash_commands::{back_blast, pre_blast};
 use crate::web_api_state::MutableWebState;
 use sqlx::PgPool;
 
@@ -54,14 +67,15 @@ pub async fn handle_block_actions(
                         web_state,
                         first_action,
                         &user,
-                        &message,
-                        &action_channel,
+                        (&message, &action_channel),
+                        trigger_id.as_str(),
                     )
                     .await?
                 }
                 InteractionTypes::EditBackBlast(id) => {
                     handle_edit_back_blast(
                         db_pool,
+                        app_state,
                         web_state,
                         id,
                         &action_channel,
@@ -70,9 +84,22 @@ pub async fn handle_block_actions(
                     )
                     .await?
                 }
+                InteractionTypes::BackBlastOptions(id) => {
+                    handle_back_blast_options(
+                        db_pool,
+                        app_state,
+                        web_state,
+                        id,
+                        first_action,
+                        &action_channel,
+                        &user,
+                    )
+                    .await?
+                }
                 InteractionTypes::EditPreBlast(id) => {
                     handle_edit_pre_blast(
                         db_pool,
+                        app_state,
                         web_state,
                         id,
                         &action_channel,
@@ -81,6 +108,43 @@ pub async fn handle_block_actions(
                     )
                     .await?
                 }
+                InteractionTypes::WriteBackBlast(id) => {
+                    handle_write_back_blast(
+                        db_pool,
+                        app_state,
+                        web_state,
+                        id,
+                        &action_channel,
+                        trigger_id.as_str(),
+                        &user,
+                    )
+                    .await?
+                }
+                InteractionTypes::CancelPreBlast(id) => {
+                    handle_cancel_pre_blast(db_pool, app_state, web_state, id, &user).await?
+                }
+                InteractionTypes::WritePreBlast(QSheetActionComboData { date, ao }) => {
+                    let registry = app_state.get_ao_registry();
+                    pre_blast::generate_modal_for_date(
+                        trigger_id.as_str(),
+                        web_state,
+                        ao.channel_id(&registry),
+                        user.id.as_str(),
+                        *date,
+                        &registry,
+                    )
+                    .await?
+                }
+                InteractionTypes::AcceptQSwap(id) => {
+                    q_swap::accept_swap(
+                        db_pool,
+                        web_state,
+                        &app_state.get_ao_registry(),
+                        id,
+                        user.id.as_str(),
+                    )
+                    .await?
+                }
                 InteractionTypes::Unknown => {
                     println!("Unknown interaction");
                 }
@@ -93,12 +157,16 @@ pub async fn handle_block_actions(
 
 async fn handle_edit_back_blast(
     db_pool: &PgPool,
+    app_state: &MutableAppState,
     web_state: &MutableWebState,
     id: &str,
     action_channel: &Option<ActionChannel>,
     trigger_id: &str,
     user: &ActionUser,
 ) -> Result<(), AppError> {
+    if is_back_blast_deleted(db_pool, id).await? {
+        return Err(AppError::from("Deleted backblasts can't be edited"));
+    }
     let bb = edit_backblast::get_back_blast(db_pool, id).await?;
     let channel = action_channel.as_ref().map(|c| c.id.to_string());
     if channel.is_none() {
@@ -107,18 +175,101 @@ async fn handle_edit_back_blast(
 
     let channel = channel.unwrap();
     let users = edit_backblast::get_user_data(db_pool, &bb).await?;
+    let roles = get_user_roles(db_pool, &user.id).await?;
     // only Q's can edit backblast
-    if !user_allowed_to_edit_back_blast(user, &bb, &users) {
+    if !user_allowed_to_edit_back_blast(user, &roles, &bb, &users) {
         return Ok(());
     }
-    let modal = edit_backblast::create_edit_modal(channel.as_str(), &bb, users, id);
+    let modal = edit_backblast::create_edit_modal(
+        channel.as_str(),
+        &bb,
+        users,
+        id,
+        &app_state.get_ao_registry(),
+    );
     let view = ViewsOpenRequest::new(trigger_id, ViewPayload::Modal(modal));
     web_state.open_view(view).await?;
     Ok(())
 }
 
+/// open backblast modal prefilled from preblast it was posted with
+async fn handle_write_back_blast(
+    db_pool: &PgPool,
+    app_state: &MutableAppState,
+    web_state: &MutableWebState,
+    id: &str,
+    action_channel: &Option<ActionChannel>,
+    trigger_id: &str,
+    user: &ActionUser,
+) -> Result<(), AppError> {
+    let channel = action_channel
+        .as_ref()
+        .map(|c| c.id.to_string())
+        .ok_or(AppError::from("Missing channel id"))?;
+    back_blast::generate_modal_from_pre_blast(
+        db_pool,
+        trigger_id,
+        web_state,
+        id,
+        channel.as_str(),
+        user.id.as_str(),
+        &app_state.get_ao_registry(),
+    )
+    .await
+}
+
+/// delete (qs and admins) or restore (admins only) backblast from overflow on posted message
+async fn handle_back_blast_options(
+    db_pool: &PgPool,
+    app_state: &MutableAppState,
+    web_state: &MutableWebState,
+    id: &str,
+    action_type: &ActionType,
+    action_channel: &Option<ActionChannel>,
+    user: &ActionUser,
+) -> Result<(), AppError> {
+    let selected = match action_type {
+        ActionType::Overflow(OverflowAction {
+            selected_option, ..
+        }) => selected_option.value.as_str(),
+        ActionType::Button(_) => return Ok(()),
+    };
+    let channel = action_channel.as_ref().map(|c| c.id.as_str());
+    let actor = RevisionActor::slack_action(user.id.as_str());
+    let roles = get_user_roles(db_pool, &user.id).await?;
+
+    match selected {
+        constants::BACK_BLAST_DELETE_TEXT => {
+            let bb = edit_backblast::get_back_blast(db_pool, id).await?;
+            let users = edit_backblast::get_user_data(db_pool, &bb).await?;
+            if !user_allowed_to_edit_back_blast(user, &roles, &bb, &users) {
+                return Ok(());
+            }
+            delete_backblast::delete_and_update_message(db_pool, web_state, id, &actor, channel)
+                .await?;
+        }
+        constants::BACK_BLAST_RESTORE_TEXT => {
+            if !roles.can(&Permission::RestoreBackBlast) {
+                return Ok(());
+            }
+            delete_backblast::restore_and_update_message(
+                db_pool,
+                web_state,
+                id,
+                &actor,
+                channel,
+                &app_state.get_ao_registry(),
+            )
+            .await?;
+        }
+        _ => println!("Unknown backblast option"),
+    }
+    Ok(())
+}
+
 async fn handle_edit_pre_blast(
     db_pool: &PgPool,
+    app_state: &MutableAppState,
     web_state: &MutableWebState,
     id: &str,
     action_channel: &Option<ActionChannel>,
@@ -133,39 +284,77 @@ async fn handle_edit_pre_blast(
 
     let channel = channel.unwrap();
     let users = edit_pre_blast::get_pre_blast_user_data(db_pool, &pb).await?;
-    if !user_allowed_to_edit_pre_blast(user, &pb, &users) {
+    let roles = get_user_roles(db_pool, &user.id).await?;
+    if !user_allowed_to_edit_pre_blast(user, &roles, &pb, &users) {
         return Ok(());
     }
+    let post_at = pre_blasts::get_pending_pre_blast(db_pool, id)
+        .await?
+        .map(|pending| to_boise_time(pending.post_at).naive_local());
     println!("creating preblast modal");
-    let modal = edit_pre_blast::create_edit_modal(channel.as_str(), &pb, users, id);
+    let modal = edit_pre_blast::create_edit_modal(
+        channel.as_str(),
+        &pb,
+        users,
+        id,
+        (post_at, &app_state.get_ao_registry()),
+    );
     println!("creating view");
     let view = ViewsOpenRequest::new(trigger_id, ViewPayload::Modal(modal));
     web_state.open_view(view).await?;
     Ok(())
 }
 
-/// only qs can edit back blast
+/// cancel pending preblast (Q that scheduled it or site Qs of AO)
+async fn handle_cancel_pre_blast(
+    db_pool: &PgPool,
+    app_state: &MutableAppState,
+    web_state: &MutableWebState,
+    id: &str,
+    user: &ActionUser,
+) -> Result<(), AppError> {
+    let Some(pending) = pre_blasts::get_pending_pre_blast(db_pool, id).await? else {
+        return Err(AppError::from("Preblast is no longer pending"));
+    };
+    let roles = get_user_roles(db_pool, &user.id).await?;
+    let ao = AO::from_channel_id(pending.channel_id.as_str(), &app_state.get_ao_registry());
+    let allowed =
+        pending.scheduled_by.as_ref() == Some(&user.id) || roles.can(&Permission::EditPreBlast(ao));
+    if !allowed {
+        return Ok(());
+    }
+    save_pre_blast::cancel_pending_pre_blast(
+        db_pool,
+        id,
+        &RevisionActor::slack_action(user.id.as_str()),
+    )
+    .await?;
+    let message = BlockBuilder::new()
+        .section_markdown(&format!("Cancelled scheduled preblast *{}*", pending.title));
+    web_state.send_direct_message(&user.id, message).await
+}
+
+/// only qs (or site Qs of AO) can edit back blast
 fn user_allowed_to_edit_back_blast(
     user: &ActionUser,
+    roles: &UserRoles,
     bb: &BackBlastData,
     user_edit: &edit_backblast::BackBlastUsersEdit,
 ) -> bool {
-    let mut slack_ids = user_edit.convert_to_slack_ids(&bb.qs);
-    let admins = admin_users();
-    slack_ids.extend(admins);
+    let slack_ids = user_edit.convert_to_slack_ids(&bb.qs);
     slack_ids.iter().any(|id| &user.id == id)
+        || roles.can(&Permission::EditBackBlast(bb.ao.clone()))
 }
 
-/// on qs and admin can edit preblast
+/// only qs (or site Qs of AO) can edit preblast
 fn user_allowed_to_edit_pre_blast(
     user: &ActionUser,
+    roles: &UserRoles,
     pb: &PreBlastData,
     user_edit: &edit_pre_blast::PreBlastUsersEdit,
 ) -> bool {
-    let mut slack_ids = user_edit.convert_to_slack_ids(&pb.qs);
-    let admins = admin_users();
-    slack_ids.extend(admins);
-    slack_ids.iter().any(|id| &user.id == id)
+    let slack_ids = user_edit.convert_to_slack_ids(&pb.qs);
+    slack_ids.iter().any(|id| &user.id == id) || roles.can(&Permission::EditPreBlast(pb.ao.clone()))
 }
 
 async fn handle_q_lineup_interaction(
@@ -174,8 +363,8 @@ async fn handle_q_lineup_interaction(
     web_state: &MutableWebState,
     action_type: &ActionType,
     user: &ActionUser,
-    message: &Option<InteractionMessageTypes>,
-    action_channel: &Option<ActionChannel>,
+    (message, action_channel): (&Option<InteractionMessageTypes>, &Option<ActionChannel>),
+    trigger_id: &str,
 ) -> Result<(), AppError> {
     println!("q line up action");
     let interaction_type = InteractionTypes::from(action_type.get_action_id().as_str());
@@ -196,8 +385,22 @@ async fn handle_q_lineup_interaction(
                 }
 
                 let channel_id = get_channel_id_from_action(action_combo, app_state)?;
-                process_q_line_up_event(db_pool, action_combo, vec![user.to_string()], channel_id)
-                    .await?;
+                let result = process_q_line_up_event(
+                    db_pool,
+                    action_combo,
+                    vec![user.to_string()],
+                    channel_id,
+                    &app_state.get_ao_registry(),
+                )
+                .await?;
+                if let QSignUpResult::Rejected(reason) = result {
+                    if let Some(ActionChannel { id, .. }) = action_channel {
+                        let blocks = BlockBuilder::new().section_markdown(&reason).blocks;
+                        let request = PostEphemeralRequest::new(id, slack_id, blocks);
+                        web_state.post_ephemeral(request).await?;
+                    }
+                    return Ok(());
+                }
                 println!("succeeded saving q line up!");
                 update_existing_q_line_up_message(
                     web_state,
@@ -206,7 +409,7 @@ async fn handle_q_lineup_interaction(
                     action_combo,
                     &action_type.get_action_id(),
                     &action_type.get_block_id(),
-                    slack_id,
+                    vec![map_slack_id_to_link(slack_id)],
                 )
                 .await?;
             }
@@ -231,6 +434,10 @@ async fn handle_q_lineup_interaction(
                     }
                     // when closing existing q line up
                     constants::Q_LINE_UP_CLOSED_TEXT => {
+                        let roles = get_user_roles(db_pool, &user.id).await?;
+                        if !roles.can(&Permission::CloseQLineUp(action_combo.ao.clone())) {
+                            return Ok(());
+                        }
                         let channel_id = get_channel_id_from_action(action_combo, app_state)?;
                         close_and_update_message(
                             db_pool,
@@ -243,6 +450,34 @@ async fn handle_q_lineup_interaction(
                         )
                         .await?;
                     }
+                    // when q is looking for a sub
+                    constants::Q_LINE_UP_SWAP_TEXT => {
+                        let registry = app_state.get_ao_registry();
+                        if !q_swap::user_is_q_of_spot(db_pool, action_combo, &user.id, &registry)
+                            .await?
+                        {
+                            return Ok(());
+                        }
+                        let message_ts = match message {
+                            Some(InteractionMessageTypes::Message(message)) => {
+                                message.ts.to_string()
+                            }
+                            None => String::new(),
+                        };
+                        let meta = q_swap::QSwapModalMeta {
+                            action_combo: QSheetActionComboData::new_q_line_up(
+                                &action_combo.date,
+                                &action_combo.ao,
+                            ),
+                            message_channel_id: action_channel
+                                .as_ref()
+                                .map(|channel| channel.id.to_string())
+                                .unwrap_or_default(),
+                            message_ts,
+                            block_id: action_type.get_block_id(),
+                        };
+                        q_swap::open_swap_modal(web_state, trigger_id, &meta, &registry).await?;
+                    }
                     _ => println!("Unknown overflow option"),
                 }
             }
@@ -276,7 +511,7 @@ fn get_channel_id_from_action(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::app_state::ao_data::AO;
+    use crate::app_state::roles::Role;
     use crate::users::f3_user::F3User;
     use chrono::NaiveDate;
     use std::collections::HashSet;
@@ -308,14 +543,18 @@ mod tests {
             vec![mock_user("123", "stinger"), mock_user("22", "backslash")],
             vec![],
         );
-        let allowed = user_allowed_to_edit_back_blast(&action_user, &bb, &edit_data);
+        let roles = UserRoles::new("123", vec![]);
+        let allowed = user_allowed_to_edit_back_blast(&action_user, &roles, &bb, &edit_data);
         assert_eq!(allowed, false);
+        let site_q = UserRoles::new("123", vec![Role::SiteQ(AO::Tower)]);
+        let allowed = user_allowed_to_edit_back_blast(&action_user, &site_q, &bb, &edit_data);
+        assert_eq!(allowed, true);
         let action_user = ActionUser {
             id: "22".to_string(),
             name: "backslash".to_string(),
             username: "backslash".to_string(),
         };
-        let allowed = user_allowed_to_edit_back_blast(&action_user, &bb, &edit_data);
+        let allowed = user_allowed_to_edit_back_blast(&action_user, &roles, &bb, &edit_data);
         assert_eq!(allowed, true);
     }
 }
@@ -0,0 +1,94 @@
+//! soft delete and restore of backblasts, updating the posted slack message.
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::backblast_data::BackBlastData;
+use crate::app_state::revisions::RevisionActor;
+use crate::db::queries::all_back_blasts::get_back_blast_by_id;
+use crate::db::save_back_blast;
+use crate::shared::common_errors::AppError;
+use crate::shared::constants;
+use crate::slack_api::block_kit::block_elements::OptionElement;
+use crate::slack_api::block_kit::{BlockBuilder, SectionBlock};
+use crate::slack_api::chat::update_message::request::UpdateMessageRequest;
+use crate::web_api_routes::interactive_events::edit_backblast::update_posted_message;
+use crate::web_api_routes::interactive_events::interaction_types::InteractionTypes;
+use crate::web_api_state::MutableWebState;
+use sqlx::PgPool;
+
+/// delete backblast and replace posted message with deleted notice.
+/// Message is expected in AO channel, unless channel id is passed in.
+pub async fn delete_and_update_message(
+    db_pool: &PgPool,
+    web_state: &MutableWebState,
+    id: &str,
+    actor: &RevisionActor,
+    channel_id: Option<&str>,
+) -> Result<(), AppError> {
+    save_back_blast::delete_back_blast(db_pool, id, actor).await?;
+
+    let back_blast = get_back_blast_by_id(db_pool, id)
+        .await?
+        .ok_or_else(|| AppError::from("Could not find backblast"))?;
+    let Some(ts) = back_blast.ts.clone() else {
+        return Ok(());
+    };
+
+    let ao_channel_id = back_blast.channel_id.to_string();
+    let data = BackBlastData::from(back_blast);
+    let channel_id = channel_id
+        .map(|channel_id| channel_id.to_string())
+        .unwrap_or_else(|| ao_channel_id.to_string());
+    let blocks = deleted_message(&data, id, &ao_channel_id, actor.slack_user.as_deref());
+    let message = UpdateMessageRequest::new(&channel_id, &ts, blocks.blocks);
+    if let Some(ts) = web_state.update_message(message).await? {
+        save_back_blast::update_back_blast_ts(db_pool, id, ts).await?;
+    }
+    Ok(())
+}
+
+/// restore deleted backblast and repost its original message content.
+pub async fn restore_and_update_message(
+    db_pool: &PgPool,
+    web_state: &MutableWebState,
+    id: &str,
+    actor: &RevisionActor,
+    channel_id: Option<&str>,
+    registry: &AoRegistry,
+) -> Result<(), AppError> {
+    save_back_blast::restore_back_blast(db_pool, id, actor).await?;
+    update_posted_message(db_pool, web_state, id, channel_id, registry).await
+}
+
+/// struck through summary of deleted backblast, with option for admins to restore.
+fn deleted_message(
+    data: &BackBlastData,
+    id: &str,
+    ao_channel_id: &str,
+    deleted_by: Option<&str>,
+) -> BlockBuilder {
+    let text = format!(
+        "~*Slackblast*: {} | {} | <#{}>~",
+        data.title.as_deref().unwrap_or_default(),
+        data.date,
+        ao_channel_id
+    );
+    let options = InteractionTypes::new_back_blast_options(id);
+    let deleted_by = deleted_by
+        .map(|user| format!(" by <@{}>", user))
+        .unwrap_or_default();
+    BlockBuilder::new()
+        .section(SectionBlock::new_markdown_with_overflow(
+            text.as_str(),
+            options.to_string().as_str(),
+            vec![OptionElement::new(
+                constants::BACK_BLAST_RESTORE_TEXT,
+                constants::BACK_BLAST_RESTORE_TEXT,
+            )],
+        ))
+        .context(
+            format!(
+                ":wastebasket: Backblast deleted{} and no longer counts towards stats.",
+                deleted_by
+            )
+            .as_str(),
+        )
+}
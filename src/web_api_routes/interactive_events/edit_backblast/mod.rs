@@ -1,15 +1,24 @@
+use crate::app_state::ao_registry::AoRegistry;
 use crate::app_state::backblast_data::BackBlastData;
+use crate::app_state::exercises::exercise_list_text;
+use crate::app_state::ruck::{ruck_list_text, RuckEntry};
 use crate::db::queries::all_back_blasts::get_back_blast_by_id;
+use crate::db::queries::exercises::get_back_blast_exercises;
+use crate::db::queries::ruck_stats::get_back_blast_ruck;
 use crate::db::queries::users::get_db_users;
+use crate::db::save_back_blast;
 use crate::shared::common_errors::AppError;
 use crate::slack_api::block_kit::BlockBuilder;
 use crate::slack_api::views::payload::ViewModal;
 use crate::users::f3_user::F3User;
-use crate::web_api_routes::slash_commands::back_blast::back_blast_post;
+use crate::web_api_routes::slash_commands::back_blast::back_blast_post::{self, BackBlastPost};
+use crate::web_api_routes::slash_commands::back_blast::{add_route_file_input, add_ruck_inputs};
 use crate::web_api_routes::slash_commands::modal_utils::view_ids::ViewIds;
 use crate::web_api_routes::slash_commands::modal_utils::{
     back_blast_types_list, default_back_blast_type, default_post_option, where_to_post_list,
+    BlastWhere,
 };
+use crate::web_api_state::MutableWebState;
 use sqlx::PgPool;
 use std::collections::{HashMap, HashSet};
 
@@ -19,7 +28,9 @@ pub async fn get_back_blast(db_pool: &PgPool, id: &str) -> Result<BackBlastData,
         return Err(AppError::from("Could not find backblast"));
     }
 
-    let bb = BackBlastData::from(bb.unwrap());
+    let mut bb = BackBlastData::from(bb.unwrap());
+    bb.exercises = get_back_blast_exercises(db_pool, id).await?;
+    bb.ruck = get_back_blast_ruck(db_pool, id).await?;
 
     Ok(bb)
 }
@@ -110,17 +121,69 @@ fn get_slack_user_split(
         })
 }
 
+/// update posted backblast message (if any) with current data in db.
+/// Message is expected in AO channel, unless channel id is passed in.
+pub async fn update_posted_message(
+    db_pool: &PgPool,
+    web_state: &MutableWebState,
+    id: &str,
+    channel_id: Option<&str>,
+    registry: &AoRegistry,
+) -> Result<(), AppError> {
+    let ts = get_back_blast_by_id(db_pool, id)
+        .await?
+        .and_then(|bb| bb.ts);
+    let Some(ts) = ts else {
+        return Ok(());
+    };
+
+    let back_blast = get_back_blast(db_pool, id).await?;
+    let users = get_user_data(db_pool, &back_blast).await?;
+    let post = BackBlastPost {
+        title: back_blast.title.clone().unwrap_or_default(),
+        date: back_blast.date,
+        qs: users
+            .convert_to_slack_ids(&back_blast.qs)
+            .into_iter()
+            .collect(),
+        pax: users
+            .get_non_q_slack_ids(&back_blast.qs)
+            .into_iter()
+            .collect(),
+        non_slack_pax: users.get_non_slack_users().into_iter().collect(),
+        fngs: back_blast.fngs.clone(),
+        mole_skine: back_blast.moleskine.clone().unwrap_or_default(),
+        thang: exercise_list_text(&back_blast.exercises),
+        ruck: RuckEntry::default(),
+        ruck_pax: ruck_list_text(&back_blast.ruck),
+        route_file_ids: Vec::new(),
+        blast_where: channel_id
+            .map(|channel_id| BlastWhere::CurrentChannel(channel_id.to_string()))
+            .unwrap_or(BlastWhere::AoChannel),
+        bb_type: back_blast.bb_type.clone(),
+        ao: back_blast.ao,
+    };
+    let message =
+        back_blast_post::convert_to_update_message(post, true, Some(id.to_string()), &ts, registry);
+    if let Some(ts) = web_state.update_message(message).await? {
+        save_back_blast::update_back_blast_ts(db_pool, id, ts).await?;
+    }
+    Ok(())
+}
+
 pub fn create_edit_modal(
     channel_id: &str,
     back_blast: &BackBlastData,
     pax: BackBlastUsersEdit,
     id: &str,
+    registry: &AoRegistry,
 ) -> ViewModal {
     let qs = pax.convert_to_slack_ids(&back_blast.qs);
     let fngs = back_blast.fngs.clone().into_iter().collect::<Vec<String>>();
     let slack_pax = pax.get_non_q_slack_ids(&back_blast.qs);
     let non_slack = pax.get_non_slack_users();
-    let block_builder = BlockBuilder::new()
+    let thang = Some(exercise_list_text(&back_blast.exercises)).filter(|text| !text.is_empty());
+    let mut block_builder = BlockBuilder::new()
         .plain_input(
             "Title",
             back_blast_post::back_blast_post_action_ids::TITLE,
@@ -131,7 +194,7 @@ pub fn create_edit_modal(
         .channel_select(
             "The AO",
             back_blast_post::back_blast_post_action_ids::AO,
-            Some(back_blast.ao.channel_id().to_string()),
+            Some(back_blast.ao.channel_id(registry).to_string()),
             false,
         )
         .date_picker(
@@ -173,6 +236,19 @@ pub fn create_edit_modal(
             back_blast.moleskine.clone(),
             false,
         ).context("If trying to tag PAX in here, substitute _ for spaces and do not include titles in parenthesis (ie, @Moneyball not @Moneyball_(F3_STC)). Spelling is important, capitalization is not!")
+        .text_box(
+            "The Thang",
+            back_blast_post::back_blast_post_action_ids::THANG,
+            Some("One exercise per line (ie. 20 Merkins (IC))".to_string()),
+            thang,
+            true,
+        );
+    if back_blast.ao.ao_type(registry).tracks_ruck() || !back_blast.ruck.is_empty() {
+        let per_pax = Some(ruck_list_text(&back_blast.ruck)).filter(|text| !text.is_empty());
+        add_ruck_inputs(&mut block_builder, per_pax);
+    }
+    add_route_file_input(&mut block_builder);
+    let block_builder = block_builder
         .select(
             "Backblast type",
             back_blast_post::back_blast_post_action_ids::BB_TYPE,
@@ -268,7 +344,13 @@ mod tests {
             hash_set_user("33", "puff"),
         ]);
         let users_edit = get_slack_user_split(&bb_data, users);
-        let modal = create_edit_modal("channel-1", &bb_data, users_edit, "bb-id-123");
+        let modal = create_edit_modal(
+            "channel-1",
+            &bb_data,
+            users_edit,
+            "bb-id-123",
+            &AoRegistry::default(),
+        );
         // strictly for debugging
         println!("{:?}", modal);
         assert_eq!(1, 1);
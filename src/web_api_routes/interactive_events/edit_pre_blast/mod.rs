@@ -1,13 +1,24 @@
+use crate::app_state::ao_registry::AoRegistry;
 use crate::app_state::pre_blast_data::PreBlastData;
+use crate::db::queries::ao_list::get_hc_emojis;
 use crate::db::queries::pre_blasts;
+use crate::db::queries::reactions_log::get_pre_blast_hcs;
 use crate::db::queries::users::get_db_users;
+use crate::db::save_pre_blast;
 use crate::shared::common_errors::AppError;
 use crate::slack_api::block_kit::BlockBuilder;
 use crate::slack_api::views::payload::ViewModal;
 use crate::users::f3_user::F3User;
 use crate::web_api_routes::slash_commands::modal_utils::view_ids::ViewIds;
-use crate::web_api_routes::slash_commands::modal_utils::{default_post_option, where_to_post_list};
-use crate::web_api_routes::slash_commands::pre_blast::{equipment_list, pre_blast_post};
+use crate::web_api_routes::slash_commands::modal_utils::{
+    default_post_option, where_to_post_list, BlastWhere,
+};
+use crate::web_api_routes::slash_commands::pre_blast::pre_blast_post::PreBlastPost;
+use crate::web_api_routes::slash_commands::pre_blast::{
+    add_post_at_inputs, equipment_list, pre_blast_post,
+};
+use crate::web_api_state::MutableWebState;
+use chrono::NaiveDateTime;
 use sqlx::PgPool;
 use std::collections::{HashMap, HashSet};
 
@@ -32,6 +43,67 @@ pub async fn get_pre_blast_user_data(
     Ok(results)
 }
 
+/// names of pax that HC'd preblast, using HC emojis of its AO
+pub async fn get_pre_blast_hc_names(
+    db: &PgPool,
+    id: &str,
+    channel_id: &str,
+) -> Result<Vec<String>, AppError> {
+    let emojis = get_hc_emojis(db, channel_id).await?;
+    let hcs = get_pre_blast_hcs(db, id, &emojis).await?;
+    Ok(hcs.into_iter().map(|hc| hc.name).collect())
+}
+
+/// update posted preblast message (if any) with current data and HCs in db.
+pub async fn update_posted_pre_blast_message(
+    db_pool: &PgPool,
+    web_state: &MutableWebState,
+    id: &str,
+    registry: &AoRegistry,
+) -> Result<(), AppError> {
+    let Some(posted) = pre_blasts::get_posted_pre_blast(db_pool, id).await? else {
+        return Ok(());
+    };
+
+    let mut post = get_pre_blast_post(db_pool, id, registry).await?;
+    post.post_where = BlastWhere::CurrentChannel(posted.channel_id.to_string());
+    let message =
+        pre_blast_post::convert_to_update_message(post, Some(id.to_string()), &posted.ts, registry);
+    if let Some(ts) = web_state.update_message(message).await? {
+        save_pre_blast::update_pre_blast_ts(db_pool, id, &posted.channel_id, ts).await?;
+    }
+    Ok(())
+}
+
+/// build preblast message data from db, with slack ids of Qs and current HCs
+pub async fn get_pre_blast_post(
+    db_pool: &PgPool,
+    id: &str,
+    registry: &AoRegistry,
+) -> Result<PreBlastPost, AppError> {
+    let pre_blast = get_pre_blast_data(db_pool, id).await?;
+    let users = get_pre_blast_user_data(db_pool, &pre_blast).await?;
+    let hcs = get_pre_blast_hc_names(db_pool, id, pre_blast.ao.channel_id(registry)).await?;
+    Ok(PreBlastPost {
+        qs: users
+            .convert_to_slack_ids(&pre_blast.qs)
+            .into_iter()
+            .collect(),
+        title: pre_blast.title,
+        date: pre_blast.date,
+        start_time: pre_blast.start_time,
+        ao: pre_blast.ao,
+        why: pre_blast.why,
+        equipment: pre_blast.equipment,
+        fng_message: pre_blast.fng_message,
+        mole_skin: pre_blast.mole_skin,
+        post_where: BlastWhere::AoChannel,
+        img_ids: pre_blast.img_ids,
+        hcs,
+        post_at: None,
+    })
+}
+
 #[derive(Debug, Default)]
 pub struct PreBlastUsersEdit {
     slack_users: Vec<F3User>,
@@ -82,9 +154,10 @@ pub fn create_edit_modal(
     pre_blast: &PreBlastData,
     edit_data: PreBlastUsersEdit,
     id: &str,
+    (post_at, registry): (Option<NaiveDateTime>, &AoRegistry),
 ) -> ViewModal {
     let qs = edit_data.convert_to_slack_ids(&pre_blast.qs);
-    let block_builder = BlockBuilder::new()
+    let mut block_builder = BlockBuilder::new()
         .plain_input(
             "Title",
             pre_blast_post::pre_blast_action_ids::TITLE,
@@ -95,7 +168,7 @@ pub fn create_edit_modal(
         .channel_select(
             "AO",
             pre_blast_post::pre_blast_action_ids::AO_SELECT,
-            Some(pre_blast.ao.channel_id().to_string()),
+            Some(pre_blast.ao.channel_id(registry).to_string()),
             false,
         )
         .date_picker(
@@ -158,7 +231,12 @@ pub fn create_edit_modal(
             pre_blast_post::pre_blast_action_ids::FILE,
             vec!["jpg", "jpeg", "png", "gif"],
             true,
-        )
+        );
+    // pending preblast can be rescheduled
+    if post_at.is_some() {
+        add_post_at_inputs(&mut block_builder, post_at);
+    }
+    let block_builder = block_builder
         .select(
             "Choose where to post this",
             pre_blast_post::pre_blast_action_ids::WHERE_POST,
@@ -782,6 +782,7 @@ pub struct ViewContainer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app_state::ao_registry::AoRegistry;
     use crate::web_api_routes::interactive_events::interaction_types::InteractionTypes;
     use crate::web_api_routes::slash_commands::black_diamond_rating::black_diamond_rating_post;
     use crate::web_api_routes::slash_commands::pre_blast::pre_blast_post::PreBlastPost;
@@ -806,7 +807,10 @@ mod tests {
         match parsed.view {
             ViewSubmissionPayloadView::Modal(modal) => {
                 let values = modal.state.get_values();
-                let post = black_diamond_rating_post::BlackDiamondRatingPost::from(values);
+                let post = black_diamond_rating_post::BlackDiamondRatingPost::from_values(
+                    values,
+                    &AoRegistry::default(),
+                );
                 println!("{:?}", post);
                 assert_eq!(post.total_fmt(), 3.86.to_string());
             }
@@ -838,7 +842,7 @@ mod tests {
                     ])
                 );
 
-                let post = PreBlastPost::from(values);
+                let post = PreBlastPost::from_values(values, &AoRegistry::default());
                 assert_eq!(post.start_time, NaiveTime::from_hms_opt(5, 15, 0).unwrap());
             }
         }
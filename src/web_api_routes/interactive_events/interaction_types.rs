@@ -7,7 +7,17 @@ use std::str::FromStr;
 pub enum InteractionTypes {
     QLineUp(QSheetActionComboData),
     EditBackBlast(String),
+    /// overflow options (delete / restore) of saved backblast
+    BackBlastOptions(String),
     EditPreBlast(String),
+    /// write backblast prefilled from saved preblast
+    WriteBackBlast(String),
+    /// cancel pending (scheduled) preblast
+    CancelPreBlast(String),
+    /// open preblast modal for AO and date
+    WritePreBlast(QSheetActionComboData),
+    /// take over Q spot offered in swap request
+    AcceptQSwap(String),
     Unknown,
 }
 
@@ -21,10 +31,35 @@ impl InteractionTypes {
         InteractionTypes::EditBackBlast(id.to_string())
     }
 
+    /// pass in the id of saved backblast for delete / restore options
+    pub fn new_back_blast_options(id: &str) -> Self {
+        InteractionTypes::BackBlastOptions(id.to_string())
+    }
+
     /// pas in id of saved preblast for editing
     pub fn new_edit_pre_blast(id: &str) -> Self {
         InteractionTypes::EditPreBlast(id.to_string())
     }
+
+    /// pass in id of saved preblast to write backblast from
+    pub fn new_write_back_blast(id: &str) -> Self {
+        InteractionTypes::WriteBackBlast(id.to_string())
+    }
+
+    /// pass in id of pending preblast to cancel
+    pub fn new_cancel_pre_blast(id: &str) -> Self {
+        InteractionTypes::CancelPreBlast(id.to_string())
+    }
+
+    /// preblast modal prefilled for AO and workout date
+    pub fn new_write_pre_blast(date: &NaiveDate, ao: &AO) -> Self {
+        InteractionTypes::WritePreBlast(QSheetActionComboData::new_q_line_up(date, ao))
+    }
+
+    /// pass in id of swap request to accept
+    pub fn new_accept_q_swap(id: &str) -> Self {
+        InteractionTypes::AcceptQSwap(id.to_string())
+    }
 }
 
 impl From<&str> for InteractionTypes {
@@ -33,7 +68,12 @@ impl From<&str> for InteractionTypes {
         match first_type {
             Q_LINE_UP => InteractionTypes::QLineUp(QSheetActionComboData::from(rest)),
             EDIT_BACK_BLAST => InteractionTypes::EditBackBlast(rest.to_string()),
+            BACK_BLAST_OPTIONS => InteractionTypes::BackBlastOptions(rest.to_string()),
             EDIT_PRE_BLAST => InteractionTypes::EditPreBlast(rest.to_string()),
+            WRITE_BACK_BLAST => InteractionTypes::WriteBackBlast(rest.to_string()),
+            CANCEL_PRE_BLAST => InteractionTypes::CancelPreBlast(rest.to_string()),
+            WRITE_PRE_BLAST => InteractionTypes::WritePreBlast(QSheetActionComboData::from(rest)),
+            ACCEPT_Q_SWAP => InteractionTypes::AcceptQSwap(rest.to_string()),
             _ => InteractionTypes::Unknown,
         }
     }
@@ -44,7 +84,12 @@ impl Display for InteractionTypes {
         let str = match self {
             InteractionTypes::QLineUp(data) => format!("{Q_LINE_UP}::{data}"),
             InteractionTypes::EditBackBlast(id) => format!("{EDIT_BACK_BLAST}::{id}"),
+            InteractionTypes::BackBlastOptions(id) => format!("{BACK_BLAST_OPTIONS}::{id}"),
             InteractionTypes::EditPreBlast(id) => format!("{EDIT_PRE_BLAST}::{id}"),
+            InteractionTypes::WriteBackBlast(id) => format!("{WRITE_BACK_BLAST}::{id}"),
+            InteractionTypes::CancelPreBlast(id) => format!("{CANCEL_PRE_BLAST}::{id}"),
+            InteractionTypes::WritePreBlast(data) => format!("{WRITE_PRE_BLAST}::{data}"),
+            InteractionTypes::AcceptQSwap(id) => format!("{ACCEPT_Q_SWAP}::{id}"),
             InteractionTypes::Unknown => "unknown".to_string(),
         };
         write!(f, "{}", str)
@@ -53,7 +98,12 @@ impl Display for InteractionTypes {
 
 const Q_LINE_UP: &str = "q_line_up";
 const EDIT_BACK_BLAST: &str = "edit_back_blast";
+const BACK_BLAST_OPTIONS: &str = "back_blast_options";
 const EDIT_PRE_BLAST: &str = "edit_pre_blast";
+const WRITE_BACK_BLAST: &str = "write_back_blast";
+const CANCEL_PRE_BLAST: &str = "cancel_pre_blast";
+const WRITE_PRE_BLAST: &str = "write_pre_blast";
+const ACCEPT_Q_SWAP: &str = "accept_q_swap";
 
 #[derive(Debug, PartialEq)]
 pub struct QSheetActionComboData {
@@ -62,7 +112,7 @@ pub struct QSheetActionComboData {
 }
 
 impl QSheetActionComboData {
-    fn new_q_line_up(date: &NaiveDate, ao: &AO) -> Self {
+    pub fn new_q_line_up(date: &NaiveDate, ao: &AO) -> Self {
         QSheetActionComboData {
             date: *date,
             ao: ao.clone(),
@@ -114,4 +164,42 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn back_blast_options_convert() {
+        let options = InteractionTypes::new_back_blast_options("abc-123");
+        assert_eq!(options.to_string(), "back_blast_options::abc-123");
+        assert_eq!(
+            InteractionTypes::from("back_blast_options::abc-123"),
+            InteractionTypes::BackBlastOptions("abc-123".to_string())
+        );
+    }
+
+    #[test]
+    fn write_back_blast_convert() {
+        let write = InteractionTypes::new_write_back_blast("abc-123");
+        assert_eq!(write.to_string(), "write_back_blast::abc-123");
+        assert_eq!(
+            InteractionTypes::from("write_back_blast::abc-123"),
+            InteractionTypes::WriteBackBlast("abc-123".to_string())
+        );
+    }
+
+    #[test]
+    fn write_pre_blast_convert() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 22).unwrap();
+        let write = InteractionTypes::new_write_pre_blast(&date, &AO::Bleach);
+        assert_eq!(write.to_string(), "write_pre_blast::2026-10-22::bleach");
+        assert_eq!(InteractionTypes::from(write.to_string().as_str()), write);
+    }
+
+    #[test]
+    fn accept_q_swap_convert() {
+        let accept = InteractionTypes::new_accept_q_swap("abc-123");
+        assert_eq!(accept.to_string(), "accept_q_swap::abc-123");
+        assert_eq!(
+            InteractionTypes::from("accept_q_swap::abc-123"),
+            InteractionTypes::AcceptQSwap("abc-123".to_string())
+        );
+    }
 }
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 
 pub mod block_actions;
+pub mod delete_backblast;
 pub mod edit_backblast;
 pub mod edit_pre_blast;
 pub mod interaction_payload;
@@ -55,8 +56,13 @@ pub async fn interactive_events(
             }
             InteractionPayload::ViewSubmission => {
                 println!("parsing view submission");
-                match view_submission::handle_view_submission(&body.payload, &web_state, &db_pool)
-                    .await
+                match view_submission::handle_view_submission(
+                    &body.payload,
+                    &web_state,
+                    &db_pool,
+                    &app_state.get_ao_registry(),
+                )
+                .await
                 {
                     Ok(()) => println!("Successfully handled view submission"),
                     Err(err) => {
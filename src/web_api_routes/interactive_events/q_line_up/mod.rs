@@ -1,12 +1,19 @@
-use crate::db::queries::q_line_up::get_single_q_line_up;
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::q_line_up_rules::{QLineUpRules, QSignUpCheck};
+use crate::db::queries::ao_list::get_q_line_up_rules;
+use crate::db::queries::q_line_up::{
+    get_q_count_by_name, get_q_line_up_between_dates, get_single_q_line_up, QLineUpDbData,
+};
 use crate::db::save_q_line_up::{
-    close_q_line_up_entry, delete_q_line_up_entry, map_from_action, save_list,
+    close_q_line_up_entry, delete_q_line_up_entry, map_from_action, save_list, NewQLineUpDbEntry,
 };
 use crate::shared::common_errors::AppError;
 use crate::shared::constants;
 use crate::shared::string_utils::{
-    format_q_empty_row, format_q_line_up_date, map_q_line_up_existing, map_slack_id_to_link,
+    format_q_empty_row, format_q_line_up_date, map_q_line_up_existing,
 };
+use crate::shared::time::local_boise_time;
 use crate::slack_api::block_kit::block_elements::BlockElementType;
 use crate::slack_api::block_kit::BlockType;
 use crate::web_api_routes::interactive_events::interaction_payload::{
@@ -18,8 +25,10 @@ use crate::web_api_routes::interactive_events::q_line_up::utils::{
     get_ao_string_from_blocks, get_existing_q_overflow_options,
 };
 use crate::web_api_state::MutableWebState;
+use chrono::{Datelike, Months};
 use sqlx::PgPool;
 
+pub mod q_swap;
 pub mod utils;
 
 pub async fn clear_and_update_message(
@@ -62,20 +71,72 @@ pub async fn close_and_update_message(
     Ok(())
 }
 
+/// outcome of pax trying to sign up on q line up
+#[derive(Debug, PartialEq)]
+pub enum QSignUpResult {
+    Saved,
+    /// not saved, with reason to show pax
+    Rejected(String),
+}
+
 pub async fn process_q_line_up_event(
     db_pool: &PgPool,
     action: &QSheetActionComboData,
     users: Vec<String>,
     channel_id: String,
-) -> Result<(), AppError> {
+    registry: &AoRegistry,
+) -> Result<QSignUpResult, AppError> {
     let action = map_from_action(action, users, channel_id)?;
     let already_exists = get_single_q_line_up(db_pool, &action.date, &action.channel_id).await?;
     if already_exists.is_some() {
-        return Err(AppError::General("Spot already taken".to_string()));
+        return Ok(QSignUpResult::Rejected("Spot already taken".to_string()));
+    }
+    let rules = get_q_line_up_rules(db_pool, &action.channel_id).await?;
+    let sign_up = get_sign_up_check(db_pool, &rules, &action, registry).await?;
+    if let Err(violation) = rules.check(&sign_up) {
+        return Ok(QSignUpResult::Rejected(violation.message()));
     }
     let list = vec![action];
     save_list(db_pool, &list).await?;
-    Ok(())
+    Ok(QSignUpResult::Saved)
+}
+
+/// what pax signing up already has lined up, to check against q line up rules
+async fn get_sign_up_check(
+    db_pool: &PgPool,
+    rules: &QLineUpRules,
+    action: &NewQLineUpDbEntry,
+    registry: &AoRegistry,
+) -> Result<QSignUpCheck, AppError> {
+    let name = action.qs.to_lowercase();
+    let today = local_boise_time().date_naive();
+    let start = action.date.with_day(1).unwrap_or(action.date);
+    let end = (start + Months::new(1)).pred_opt().unwrap_or(action.date);
+    let line_up = get_q_line_up_between_dates(db_pool, &start, &end).await?;
+    let signed_up = line_up
+        .iter()
+        .filter(|item| !item.closed && item.qs.contains(&name))
+        .collect::<Vec<&QLineUpDbData>>();
+
+    let same_day_aos = signed_up
+        .iter()
+        .filter(|item| item.date == action.date && item.ao != action.ao)
+        .map(|item| AO::from_name(&item.ao, registry).friendly_name(registry))
+        .collect::<Vec<String>>();
+    let month_sign_ups = signed_up.iter().filter(|item| item.ao == action.ao).count();
+    let total_qs = if rules.held_for_new_qs(&today, &action.date) {
+        Some(get_q_count_by_name(db_pool, &name).await?)
+    } else {
+        None
+    };
+
+    Ok(QSignUpCheck {
+        date: action.date,
+        today,
+        same_day_aos,
+        month_sign_ups,
+        total_qs,
+    })
 }
 
 /// update existing q line up message that was interacted with.
@@ -86,7 +147,7 @@ pub async fn update_existing_q_line_up_message(
     action_combo: &QSheetActionComboData,
     action_id: &str,
     action_block_id: &str,
-    slack_id: &str,
+    q_links: Vec<String>,
 ) -> Result<(), AppError> {
     if let Some(ActionChannel { id: channel_id, .. }) = channel {
         match message {
@@ -103,11 +164,8 @@ pub async fn update_existing_q_line_up_message(
                                         &message.blocks,
                                         ao_combo_str.as_str(),
                                     );
-                                    let updated_text = map_q_line_up_existing(
-                                        &friendly_date,
-                                        ao_string,
-                                        vec![map_slack_id_to_link(slack_id)],
-                                    );
+                                    let updated_text =
+                                        map_q_line_up_existing(&friendly_date, ao_string, q_links);
                                     section_block.text.text = updated_text;
                                     let options = get_existing_q_overflow_options();
                                     section_block.accessory =
@@ -0,0 +1,406 @@
+//! Q asking for a sub from the Q line up overflow. Offer is DM'd to the AO's regular pax
+//! (or pax the Q picked), and first one to accept takes over the spot.
+
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::roles::Permission;
+use crate::db::queries::q_line_up::get_single_q_line_up;
+use crate::db::queries::q_swaps::{get_ao_regular_pax, get_q_swap_request, QSwapRequestData};
+use crate::db::queries::user_roles::get_user_roles;
+use crate::db::queries::users::get_slack_id_map;
+use crate::db::save_q_swap::{
+    accept_swap_request, save_swap_request, NewQSwapRequest, QSwapAcceptance,
+};
+use crate::shared::common_errors::AppError;
+use crate::shared::string_utils::map_slack_id_to_link;
+use crate::shared::time::local_boise_time;
+use crate::slack_api::block_kit::BlockBuilder;
+use crate::slack_api::views::payload::{ViewModal, ViewPayload};
+use crate::slack_api::views::request::ViewsOpenRequest;
+use crate::web_api_routes::interactive_events::interaction_payload::{
+    ActionChannel, BasicValue, InteractionMessageTypes,
+};
+use crate::web_api_routes::interactive_events::interaction_types::{
+    InteractionTypes, QSheetActionComboData,
+};
+use crate::web_api_routes::interactive_events::q_line_up::update_existing_q_line_up_message;
+use crate::web_api_routes::slash_commands::modal_utils::value_utils;
+use crate::web_api_routes::slash_commands::modal_utils::view_ids::ViewIds;
+use crate::web_api_state::MutableWebState;
+use chrono::{Datelike, Duration, NaiveDate};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub mod post_ids {
+    pub const SWAP_PAX: &str = "q_swap_pax.select";
+}
+
+/// how far back to look for AO regulars
+const REGULAR_PAX_DAYS: i64 = 60;
+/// posts at AO within those days to count as regular
+const REGULAR_PAX_MIN_POSTS: i64 = 4;
+/// most regulars to DM offer to
+const REGULAR_PAX_LIMIT: i64 = 15;
+
+/// q line up spot and message row the swap was asked from. Passed through modal private metadata.
+#[derive(Debug, PartialEq)]
+pub struct QSwapModalMeta {
+    pub action_combo: QSheetActionComboData,
+    pub message_channel_id: String,
+    pub message_ts: String,
+    pub block_id: String,
+}
+
+impl std::fmt::Display for QSwapModalMeta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}::{}::{}::{}",
+            self.action_combo, self.message_channel_id, self.message_ts, self.block_id
+        )
+    }
+}
+
+impl From<&str> for QSwapModalMeta {
+    fn from(value: &str) -> Self {
+        let mut parts = value.splitn(5, "::");
+        let date = parts.next().unwrap_or_default();
+        let ao = parts.next().unwrap_or_default();
+        QSwapModalMeta {
+            action_combo: QSheetActionComboData::from(format!("{}::{}", date, ao).as_str()),
+            message_channel_id: parts.next().unwrap_or_default().to_string(),
+            message_ts: parts.next().unwrap_or_default().to_string(),
+            block_id: parts.next().unwrap_or_default().to_string(),
+        }
+    }
+}
+
+/// only qs on the spot (or site Qs of AO) can ask for a sub
+pub async fn user_is_q_of_spot(
+    db_pool: &PgPool,
+    action_combo: &QSheetActionComboData,
+    slack_id: &str,
+    registry: &AoRegistry,
+) -> Result<bool, AppError> {
+    let roles = get_user_roles(db_pool, slack_id).await?;
+    if roles.can(&Permission::CloseQLineUp(action_combo.ao.clone())) {
+        return Ok(true);
+    }
+    let users = get_slack_id_map(db_pool).await?;
+    let channel_id = action_combo.ao.channel_id(registry);
+    let line_up = get_single_q_line_up(db_pool, &action_combo.date, channel_id).await?;
+    let allowed = match (users.get(slack_id), line_up) {
+        (Some(name), Some(line_up)) => line_up.qs.contains(&name.to_lowercase()),
+        _ => false,
+    };
+    Ok(allowed)
+}
+
+/// open modal for Q to pick who to ask (defaults to AO regulars)
+pub async fn open_swap_modal(
+    web_state: &MutableWebState,
+    trigger_id: &str,
+    meta: &QSwapModalMeta,
+    registry: &AoRegistry,
+) -> Result<(), AppError> {
+    let block_builder = BlockBuilder::new()
+        .section_markdown(&format!(
+            "Find a sub to Q <#{}> on {}",
+            meta.action_combo.ao.channel_id(registry),
+            meta.action_combo.date.format("%A %b %-d")
+        ))
+        .multi_users_select("Ask specific pax", post_ids::SWAP_PAX, None, true)
+        .context("Leave empty to ask the AO's regular pax. First one to accept takes the Q.");
+    let modal = ViewModal::new("Find a Sub", block_builder, "Send", ViewIds::QSwap)
+        .with_private_meta(&meta.to_string());
+    let view = ViewsOpenRequest::new(trigger_id, ViewPayload::Modal(modal));
+    web_state.open_view(view).await?;
+    Ok(())
+}
+
+/// pax picked in modal, if any
+pub fn chosen_pax(values: &HashMap<String, BasicValue>) -> Vec<String> {
+    let mut pax = value_utils::get_value(
+        values,
+        post_ids::SWAP_PAX,
+        value_utils::get_hash_set_strings_from_multi,
+    )
+    .unwrap_or_default()
+    .into_iter()
+    .collect::<Vec<String>>();
+    pax.sort();
+    pax
+}
+
+/// pax to DM offer to, without Q asking
+fn offer_recipients(chosen: Vec<String>, regulars: Vec<String>, requested_by: &str) -> Vec<String> {
+    let pax = if chosen.is_empty() { regulars } else { chosen };
+    pax.into_iter()
+        .filter(|slack_id| slack_id != requested_by)
+        .collect()
+}
+
+/// DM offering the Q spot with button to take it
+fn swap_offer_message(
+    requested_by: &str,
+    ao: &AO,
+    date: &NaiveDate,
+    id: &str,
+    registry: &AoRegistry,
+) -> BlockBuilder {
+    let start_time = ao
+        .default_time(&date.weekday(), registry)
+        .map(|time| format!(" at {}", time.format("%-I:%M %p")))
+        .unwrap_or_default();
+    let mut block_builder = BlockBuilder::new()
+        .section_markdown("*Sub needed*")
+        .section_markdown(&format!(
+            "<@{}> is looking for someone to take their Q at <#{}> on {}{}",
+            requested_by,
+            ao.channel_id(registry),
+            date.format("%A %b %-d"),
+            start_time
+        ));
+    let accept = InteractionTypes::new_accept_q_swap(id);
+    block_builder.add_btn("Take the Q", accept.to_string().as_str(), "accept-q-swap");
+    block_builder
+}
+
+/// save swap request from modal and DM offer to chosen pax or AO regulars
+pub async fn request_swap(
+    db_pool: &PgPool,
+    web_state: &MutableWebState,
+    registry: &AoRegistry,
+    requested_by: &str,
+    meta: &QSwapModalMeta,
+    chosen: Vec<String>,
+) -> Result<(), AppError> {
+    let QSwapModalMeta { action_combo, .. } = meta;
+    let channel_id = action_combo.ao.channel_id(registry);
+    if !user_is_q_of_spot(db_pool, action_combo, requested_by, registry).await? {
+        return Err(AppError::from("Only the Q can ask for a sub"));
+    }
+    let line_up = get_single_q_line_up(db_pool, &action_combo.date, channel_id)
+        .await?
+        .filter(|line_up| !line_up.closed)
+        .ok_or_else(|| AppError::from("No Q signed up for that spot"))?;
+
+    let since = local_boise_time().date_naive() - Duration::days(REGULAR_PAX_DAYS);
+    let regulars = if chosen.is_empty() {
+        get_ao_regular_pax(
+            db_pool,
+            channel_id,
+            &since,
+            REGULAR_PAX_MIN_POSTS,
+            REGULAR_PAX_LIMIT,
+        )
+        .await?
+    } else {
+        Vec::new()
+    };
+    let offered_to = offer_recipients(chosen, regulars, requested_by);
+    if offered_to.is_empty() {
+        return Err(AppError::from(
+            "No regular pax found for AO, pick pax to ask instead",
+        ));
+    }
+
+    let request = NewQSwapRequest {
+        id: Uuid::new_v4(),
+        channel_id: channel_id.to_string(),
+        date: action_combo.date,
+        requested_by: requested_by.to_string(),
+        original_qs: line_up.qs.join(","),
+        offered_to: offered_to.clone(),
+        message_channel_id: Some(meta.message_channel_id.to_string()).filter(|id| !id.is_empty()),
+        message_ts: Some(meta.message_ts.to_string()).filter(|ts| !ts.is_empty()),
+        message_block_id: Some(meta.block_id.to_string()).filter(|id| !id.is_empty()),
+    };
+    save_swap_request(db_pool, &request).await?;
+
+    let id = request.id.to_string();
+    for slack_id in offered_to.iter() {
+        let message = swap_offer_message(
+            requested_by,
+            &action_combo.ao,
+            &action_combo.date,
+            &id,
+            registry,
+        );
+        if let Err(err) = web_state.send_direct_message(slack_id, message).await {
+            println!("Error sending Q swap offer: {:?}", err);
+        }
+    }
+    let message = BlockBuilder::new().section_markdown(&format!(
+        "Asked {} pax to take your Q at <#{}> on {}. You'll get a DM when someone does.",
+        offered_to.len(),
+        channel_id,
+        action_combo.date.format("%A %b %-d")
+    ));
+    web_state.send_direct_message(requested_by, message).await
+}
+
+/// pax accepting swap offer. First to accept gets the Q and line up message is updated.
+pub async fn accept_swap(
+    db_pool: &PgPool,
+    web_state: &MutableWebState,
+    registry: &AoRegistry,
+    id: &str,
+    slack_id: &str,
+) -> Result<(), AppError> {
+    let swap = get_q_swap_request(db_pool, id)
+        .await?
+        .ok_or_else(|| AppError::from("Could not find swap request"))?;
+    if !swap.offered_to.contains(&slack_id.to_string()) {
+        let roles = get_user_roles(db_pool, slack_id).await?;
+        if !roles.can(&Permission::CloseQLineUp(AO::from_channel_id(
+            &swap.channel_id,
+            registry,
+        ))) {
+            return Ok(());
+        }
+    }
+    let users = get_slack_id_map(db_pool).await?;
+    let name = users
+        .get(slack_id)
+        .map(|name| name.to_string())
+        .ok_or_else(|| AppError::from("Could not find user"))?;
+    let requester_name = users
+        .get(&swap.requested_by)
+        .map(|name| name.to_string())
+        .ok_or_else(|| AppError::from("Could not find requesting Q"))?;
+
+    let date_text = swap.date.format("%A %b %-d");
+    match accept_swap_request(db_pool, id, slack_id, &requester_name, &name).await? {
+        QSwapAcceptance::Accepted(qs) => {
+            let q_links = line_up_q_links(&qs, &users);
+            update_line_up_message(web_state, registry, &swap, q_links).await?;
+            let message = BlockBuilder::new().section_markdown(&format!(
+                "You're now the Q at <#{}> on {}. Thanks for stepping up!",
+                swap.channel_id, date_text
+            ));
+            web_state.send_direct_message(slack_id, message).await?;
+            let message = BlockBuilder::new().section_markdown(&format!(
+                "<@{}> took your Q at <#{}> on {}",
+                slack_id, swap.channel_id, date_text
+            ));
+            web_state
+                .send_direct_message(&swap.requested_by, message)
+                .await
+        }
+        QSwapAcceptance::NotPending => {
+            let message = BlockBuilder::new().section_markdown(&format!(
+                "Q at <#{}> on {} was already taken. Thanks anyway!",
+                swap.channel_id, date_text
+            ));
+            web_state.send_direct_message(slack_id, message).await
+        }
+        QSwapAcceptance::LineUpChanged => {
+            let message = BlockBuilder::new().section_markdown(&format!(
+                "<@{}> is no longer on the Q line up at <#{}> on {}, so the offer was cancelled.",
+                swap.requested_by, swap.channel_id, date_text
+            ));
+            web_state.send_direct_message(slack_id, message).await
+        }
+    }
+}
+
+/// map comma separated qs of line up to slack links, keeping names without a slack user as is
+fn line_up_q_links(qs: &str, users: &HashMap<String, String>) -> Vec<String> {
+    qs.split(',')
+        .map(|q| q.trim())
+        .filter(|q| !q.is_empty())
+        .map(|q| {
+            users
+                .iter()
+                .find(|(_, name)| name.eq_ignore_ascii_case(q))
+                .map(|(slack_id, _)| map_slack_id_to_link(slack_id))
+                .unwrap_or_else(|| q.to_string())
+        })
+        .collect()
+}
+
+/// update q line up message row the swap was asked from, with latest blocks of message
+async fn update_line_up_message(
+    web_state: &MutableWebState,
+    registry: &AoRegistry,
+    swap: &QSwapRequestData,
+    q_links: Vec<String>,
+) -> Result<(), AppError> {
+    let (Some(channel_id), Some(ts), Some(block_id)) = (
+        &swap.message_channel_id,
+        &swap.message_ts,
+        &swap.message_block_id,
+    ) else {
+        return Ok(());
+    };
+    let Some(message) = web_state.get_message(channel_id, ts).await? else {
+        return Ok(());
+    };
+    let action_combo = QSheetActionComboData::new_q_line_up(
+        &swap.date,
+        &AO::from_channel_id(swap.channel_id.as_str(), registry),
+    );
+    let action_id = InteractionTypes::new_q_line_up(&swap.date, &action_combo.ao).to_string();
+    let channel = Some(ActionChannel {
+        id: channel_id.to_string(),
+        name: String::new(),
+    });
+    update_existing_q_line_up_message(
+        web_state,
+        &channel,
+        &Some(InteractionMessageTypes::Message(message)),
+        &action_combo,
+        &action_id,
+        block_id,
+        q_links,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swap_modal_meta_convert() {
+        let meta = QSwapModalMeta {
+            action_combo: QSheetActionComboData::new_q_line_up(
+                &NaiveDate::from_ymd_opt(2026, 10, 22).unwrap(),
+                &AO::Bleach,
+            ),
+            message_channel_id: "C123".to_string(),
+            message_ts: "1700000000.1234".to_string(),
+            block_id: "ab=c".to_string(),
+        };
+        let text = meta.to_string();
+        assert_eq!(text, "2026-10-22::bleach::C123::1700000000.1234::ab=c");
+        assert_eq!(QSwapModalMeta::from(text.as_str()), meta);
+    }
+
+    #[test]
+    fn recipients_skip_requester() {
+        let regulars = vec!["U1".to_string(), "U2".to_string()];
+        assert_eq!(
+            offer_recipients(Vec::new(), regulars.clone(), "U1"),
+            vec!["U2".to_string()]
+        );
+        assert_eq!(
+            offer_recipients(vec!["U3".to_string()], regulars, "U1"),
+            vec!["U3".to_string()]
+        );
+    }
+
+    #[test]
+    fn line_up_links_keep_co_qs() {
+        let users = HashMap::from([
+            ("U1".to_string(), "Stinger".to_string()),
+            ("U2".to_string(), "Backdraft".to_string()),
+        ]);
+        assert_eq!(
+            line_up_q_links("backdraft, Guest Q", &users),
+            vec!["<@U2>".to_string(), "Guest Q".to_string()]
+        );
+    }
+}
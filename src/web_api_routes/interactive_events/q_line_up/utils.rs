@@ -1,4 +1,4 @@
-use crate::shared::constants::{Q_LINE_UP_CANCEL_TEXT, Q_LINE_UP_CLOSED_TEXT};
+use crate::shared::constants::{Q_LINE_UP_CANCEL_TEXT, Q_LINE_UP_CLOSED_TEXT, Q_LINE_UP_SWAP_TEXT};
 use crate::slack_api::block_kit::block_elements::OptionElement;
 use crate::slack_api::block_kit::{BlockType, SectionBlock};
 use crate::web_api_routes::slash_commands::q_line_up::unwrap_message_data;
@@ -6,7 +6,8 @@ use crate::web_api_routes::slash_commands::q_line_up::unwrap_message_data;
 pub fn get_existing_q_overflow_options() -> Vec<OptionElement> {
     let clear = OptionElement::new(Q_LINE_UP_CANCEL_TEXT, Q_LINE_UP_CANCEL_TEXT);
     let closed = OptionElement::new(Q_LINE_UP_CLOSED_TEXT, Q_LINE_UP_CLOSED_TEXT);
-    vec![clear, closed]
+    let swap = OptionElement::new(Q_LINE_UP_SWAP_TEXT, Q_LINE_UP_SWAP_TEXT);
+    vec![clear, closed, swap]
 }
 
 pub fn get_ao_string_from_blocks<'a>(
@@ -1,21 +1,36 @@
+use crate::app_state::ao_registry::AoRegistry;
 use crate::app_state::pre_blast_data::PreBlastData;
+use crate::app_state::revisions::RevisionActor;
+use crate::app_state::roles::Permission;
+use crate::db::queries::user_roles::get_user_roles;
 use crate::db::queries::users::get_slack_id_map;
 use crate::shared::common_errors::AppError;
+use crate::shared::time::{local_boise_time, to_boise_time};
+use crate::slack_api::block_kit::BlockBuilder;
+use crate::slack_api::chat::post_message::request::PostMessageRequest;
+use crate::web_api_routes::interactive_events::edit_pre_blast::get_pre_blast_hc_names;
 use crate::web_api_routes::interactive_events::interaction_payload::{
     ActionUser, ViewSubmissionPayload, ViewSubmissionPayloadView, ViewSubmissionPayloadViewModal,
 };
+use crate::web_api_routes::pre_blast_data::scheduled_pre_blasts::post_pending_pre_blast;
+use crate::web_api_routes::slash_commands::ao_closure::ao_closure_post;
 use crate::web_api_routes::slash_commands::back_blast::back_blast_post;
+use crate::web_api_routes::slash_commands::back_blast::back_blast_route::attach_route_files;
 use crate::web_api_routes::slash_commands::black_diamond_rating::black_diamond_rating_post;
 use crate::web_api_routes::slash_commands::modal_utils::view_ids::ViewIds;
+use crate::web_api_routes::slash_commands::modal_utils::BlastWhere;
 use crate::web_api_routes::slash_commands::pre_blast::pre_blast_post;
+use crate::web_api_routes::slash_commands::workout_score::workout_score_post;
 use crate::web_api_state::MutableWebState;
+use actix_web::web;
 use sqlx::PgPool;
 
 /// handle a view submission from interactive event
 pub async fn handle_view_submission(
     payload: &str,
-    web_state: &MutableWebState,
+    web_state: &web::Data<MutableWebState>,
     db_pool: &PgPool,
+    registry: &AoRegistry,
 ) -> Result<(), AppError> {
     let view_payload = serde_json::from_str::<ViewSubmissionPayload>(payload)?;
 
@@ -25,20 +40,40 @@ pub async fn handle_view_submission(
             if let Some(view_id) = modal.modal_view_id() {
                 match view_id {
                     ViewIds::PreBlast => {
-                        handle_pre_blast_submission(modal, db_pool, web_state, user).await
+                        handle_pre_blast_submission(modal, db_pool, web_state, user, registry).await
                     }
                     ViewIds::BackBlast => {
-                        handle_back_blast_submission(modal, web_state, db_pool, user).await
+                        handle_back_blast_submission(modal, web_state, db_pool, user, registry)
+                            .await
                     }
                     ViewIds::BlackDiamondRating => {
-                        handle_black_diamond_rating_submission(modal, web_state, db_pool, user)
-                            .await
+                        handle_black_diamond_rating_submission(
+                            modal, web_state, db_pool, user, registry,
+                        )
+                        .await
                     }
                     ViewIds::BackBlastEdit => {
-                        handle_edit_back_blast_submission(modal, web_state, db_pool).await
+                        handle_edit_back_blast_submission(modal, web_state, db_pool, user, registry)
+                            .await
                     }
                     ViewIds::PreBlastEdit => {
-                        handle_edit_pre_blast_submission(modal, web_state, db_pool).await
+                        handle_edit_pre_blast_submission(modal, web_state, db_pool, user, registry)
+                            .await
+                    }
+                    ViewIds::AoClosure => {
+                        handle_ao_closure_submission(modal, web_state, db_pool, user, registry)
+                            .await
+                    }
+                    ViewIds::ExiconEdit => handle_exicon_submission(modal, db_pool, user).await,
+                    ViewIds::WorkoutScore => {
+                        handle_workout_score_submission(modal, web_state, db_pool, user).await
+                    }
+                    ViewIds::QSwap => {
+                        handle_q_swap_submission(modal, web_state, db_pool, user, registry).await
+                    }
+                    ViewIds::QRecurrence => {
+                        handle_q_recurrence_submission(modal, web_state, db_pool, user, registry)
+                            .await
                     }
                     ViewIds::Unknown => Ok(()),
                 }
@@ -54,43 +89,226 @@ async fn handle_black_diamond_rating_submission(
     web_state: &MutableWebState,
     db_pool: &PgPool,
     user: &ActionUser,
+    registry: &AoRegistry,
 ) -> Result<(), AppError> {
+    use crate::db::queries::black_diamond_ratings::{get_rating_rank, get_recent_ratings};
+    use crate::db::save_black_diamond_rating;
+    use crate::web_api_routes::slash_commands::black_diamond_rating::PREVIOUS_RATINGS_COUNT;
+
     let form_values = modal.state.get_values();
-    let post = black_diamond_rating_post::BlackDiamondRatingPost::from(form_values);
+    let post =
+        black_diamond_rating_post::BlackDiamondRatingPost::from_values(form_values, registry);
+    let rating = post.to_new_rating(user.id.as_str(), registry)?;
+    let id = save_black_diamond_rating::save_rating(db_pool, &rating).await?;
+    let previous = get_recent_ratings(db_pool, PREVIOUS_RATINGS_COUNT + 1)
+        .await?
+        .into_iter()
+        .filter(|rating| rating.id != id)
+        .take(PREVIOUS_RATINGS_COUNT as usize)
+        .collect::<Vec<_>>();
+    let rank = get_rating_rank(db_pool, &id).await?;
+    let message = black_diamond_rating_post::convert_to_message(
+        post,
+        db_pool,
+        user.id.as_str(),
+        &previous,
+        Some(&rank),
+        registry,
+    )
+    .await;
+    web_state.post_message(message).await?;
+    Ok(())
+}
+
+async fn handle_workout_score_submission(
+    modal: &ViewSubmissionPayloadViewModal,
+    web_state: &MutableWebState,
+    db_pool: &PgPool,
+    user: &ActionUser,
+) -> Result<(), AppError> {
+    use crate::db::queries::scoring::get_scoring_formula;
+    use crate::db::save_scoring;
+
+    let channel_id = modal
+        .private_metadata
+        .as_deref()
+        .ok_or_else(|| AppError::from("Missing channel of scoring formula"))?;
+    let formula = get_scoring_formula(db_pool, channel_id)
+        .await?
+        .ok_or_else(|| AppError::from("No scoring formula set up for this channel"))?;
+    let form_values = modal.state.get_values();
+    let post = workout_score_post::WorkoutScorePost::from(form_values);
+    let score = post.to_new_score(&formula, user.id.as_str())?;
+    save_scoring::save_workout_score(db_pool, &score).await?;
     let message =
-        black_diamond_rating_post::convert_to_message(post, db_pool, user.id.as_str()).await;
+        workout_score_post::convert_to_message(&formula.formula, &score, user.id.as_str());
+    web_state.post_message(message).await?;
+    Ok(())
+}
+
+async fn handle_q_swap_submission(
+    modal: &ViewSubmissionPayloadViewModal,
+    web_state: &MutableWebState,
+    db_pool: &PgPool,
+    user: &ActionUser,
+    registry: &AoRegistry,
+) -> Result<(), AppError> {
+    use crate::web_api_routes::interactive_events::q_line_up::q_swap;
+
+    let meta = modal
+        .private_metadata
+        .as_deref()
+        .map(q_swap::QSwapModalMeta::from)
+        .ok_or_else(|| AppError::from("Missing q line up of swap"))?;
+    let chosen = q_swap::chosen_pax(&modal.state.get_values());
+    q_swap::request_swap(db_pool, web_state, registry, &user.id, &meta, chosen).await
+}
+
+async fn handle_q_recurrence_submission(
+    modal: &ViewSubmissionPayloadViewModal,
+    web_state: &MutableWebState,
+    db_pool: &PgPool,
+    user: &ActionUser,
+    registry: &AoRegistry,
+) -> Result<(), AppError> {
+    use crate::db::save_q_recurrence;
+    use crate::web_api_routes::slash_commands::q_recurrences::q_recurrence_post::QRecurrencePost;
+
+    let form_values = modal.state.get_values();
+    let post = QRecurrencePost::from(form_values);
+    for id in post.remove.iter() {
+        save_q_recurrence::remove_recurrence(db_pool, id, &user.id).await?;
+    }
+    let today = local_boise_time().date_naive();
+    let mut message = BlockBuilder::new();
+    if let Some(recurrence) = post.to_new_recurrence(&user.id, today, registry)? {
+        save_q_recurrence::save_recurrence(db_pool, &recurrence).await?;
+        message.add_section_markdown(
+            "Saved your recurring Q. It will show up on the Q line up within a day.",
+        );
+    }
+    if !post.remove.is_empty() {
+        message.add_section_markdown(&format!(
+            "Removed {} recurring Q(s). Spots already on the Q line up stay, use Clear to drop them.",
+            post.remove.len()
+        ));
+    }
+    if message.blocks.is_empty() {
+        return Ok(());
+    }
+    web_state.send_direct_message(&user.id, message).await
+}
+
+async fn handle_ao_closure_submission(
+    modal: &ViewSubmissionPayloadViewModal,
+    web_state: &MutableWebState,
+    db_pool: &PgPool,
+    user: &ActionUser,
+    registry: &AoRegistry,
+) -> Result<(), AppError> {
+    use crate::app_state::ao_closures::AoClosure;
+    use crate::db::save_ao_closure;
+
+    let form_values = modal.state.get_values();
+    let post = ao_closure_post::AoClosurePost::from(form_values);
+    let roles = get_user_roles(db_pool, &user.id).await?;
+    if !roles.can(&Permission::AddClosure(post.ao(registry)?)) {
+        return Err(AppError::from("Only site Qs of AO can add closures"));
+    }
+    let new_closure = post.to_new_closure(user.id.as_str(), registry)?;
+    let id = save_ao_closure::save_closure(db_pool, &new_closure).await?;
+    let closure = AoClosure {
+        id,
+        ao: post.ao(registry)?,
+        start_date: new_closure.start_date,
+        end_date: new_closure.end_date,
+        recurring: new_closure.recurring,
+        reason: new_closure.reason,
+    };
+    // let channel know about closure
+    let channel_id = closure
+        .ao
+        .as_ref()
+        .map(|ao| ao.channel_id(registry).to_string())
+        .or_else(|| modal.private_metadata.clone())
+        .unwrap_or_else(|| user.id.to_string());
+    let message = PostMessageRequest::new(
+        channel_id.as_str(),
+        BlockBuilder::new()
+            .section_markdown(format!(":no_entry: {}", closure.display_text(registry)).as_str())
+            .blocks,
+    );
     web_state.post_message(message).await?;
     Ok(())
 }
 
+async fn handle_exicon_submission(
+    modal: &ViewSubmissionPayloadViewModal,
+    db_pool: &PgPool,
+    user: &ActionUser,
+) -> Result<(), AppError> {
+    use crate::app_state::exicon::ExiconEntry;
+    use crate::db::save_exercises;
+
+    let roles = get_user_roles(db_pool, &user.id).await?;
+    if !roles.can(&Permission::EditExicon) {
+        return Err(AppError::from("Only site Qs can edit the exicon"));
+    }
+
+    let form_values = modal.state.get_values();
+    let entry = ExiconEntry::from(form_values);
+    save_exercises::save_exicon_entry(db_pool, modal.private_metadata.as_deref(), &entry).await
+}
+
 async fn handle_edit_pre_blast_submission(
     modal: &ViewSubmissionPayloadViewModal,
     web_state: &MutableWebState,
     db_pool: &PgPool,
+    user: &ActionUser,
+    registry: &AoRegistry,
 ) -> Result<(), AppError> {
     use crate::db::queries::pre_blasts;
     use crate::db::save_pre_blast;
 
     let form_values = modal.state.get_values();
-    let post = pre_blast_post::PreBlastPost::from(form_values);
+    let mut post = pre_blast_post::PreBlastPost::from_values(form_values, registry);
     let users = get_slack_id_map(db_pool).await?;
     let db_data = PreBlastData::from(&post).with_qs(&post.qs, users);
     if let Some(id) = &modal.private_metadata {
         // save to backend
-        save_pre_blast::update_pre_blast(db_pool, id, &db_data).await?;
-        // fetch latest
-        let updated_pb = pre_blasts::get_pre_blast_by_id(db_pool, id).await?;
-        if let Some(ts) = updated_pb.map(|pb| pb.ts).unwrap_or_default() {
+        save_pre_blast::update_pre_blast(
+            db_pool,
+            id,
+            &db_data,
+            registry,
+            &RevisionActor::modal(&user.id),
+        )
+        .await?;
+        // pending preblast gets rescheduled, or posted now when schedule cleared
+        if let Some(pending) = pre_blasts::get_pending_pre_blast(db_pool, id).await? {
+            return match post.future_post_at() {
+                Some(post_at) => {
+                    save_pre_blast::update_pre_blast_post_at(db_pool, id, post_at).await
+                }
+                None => post_pending_pre_blast(db_pool, web_state, &pending, registry).await,
+            };
+        }
+        // keep HCs on updated message
+        post.hcs = get_pre_blast_hc_names(db_pool, id, post.ao.channel_id(registry)).await?;
+        // update message where it was posted, even if AO or post location changed
+        if let Some(posted) = pre_blasts::get_posted_pre_blast(db_pool, id).await? {
+            post.post_where = BlastWhere::CurrentChannel(posted.channel_id.to_string());
             let message = pre_blast_post::convert_to_update_message(
                 post,
                 modal.private_metadata.clone(),
-                ts.as_str(),
+                posted.ts.as_str(),
+                registry,
             );
             // send message update to slack
             let ts = web_state.update_message(message).await?;
             if let Some(ts) = ts {
                 // update ts on preblast
-                save_pre_blast::update_pre_blast_ts(db_pool, id, ts).await?;
+                save_pre_blast::update_pre_blast_ts(db_pool, id, &posted.channel_id, ts).await?;
             }
         }
     }
@@ -100,21 +318,32 @@ async fn handle_edit_pre_blast_submission(
 
 async fn handle_edit_back_blast_submission(
     modal: &ViewSubmissionPayloadViewModal,
-    web_state: &MutableWebState,
+    web_state: &web::Data<MutableWebState>,
     db_pool: &PgPool,
+    user: &ActionUser,
+    registry: &AoRegistry,
 ) -> Result<(), AppError> {
     use crate::db::queries::all_back_blasts;
     use crate::db::save_back_blast;
 
     let form_values = modal.state.get_values();
-    let post = back_blast_post::BackBlastPost::from(form_values);
+    let post = back_blast_post::BackBlastPost::from_values(form_values, registry);
     let users = get_slack_id_map(db_pool).await?;
     let db_data = back_blast_post::convert_to_bb_data(&post, users);
     let is_valid = db_data.is_valid_back_blast();
     if is_valid {
         if let Some(id) = &modal.private_metadata {
             // save to backend
-            save_back_blast::update_back_blast(db_pool, id, &db_data).await?;
+            save_back_blast::update_back_blast(
+                db_pool,
+                id,
+                &db_data,
+                registry,
+                &RevisionActor::modal(&user.id),
+            )
+            .await?;
+            let channel_id = post.post_channel_id(registry);
+            let route_file_ids = post.route_file_ids.clone();
             // fetch latest update
             let updated_bb = all_back_blasts::get_back_blast_by_id(db_pool, id).await?;
             if let Some(ts) = updated_bb.map(|bb| bb.ts).unwrap_or_default() {
@@ -123,6 +352,7 @@ async fn handle_edit_back_blast_submission(
                     true,
                     modal.private_metadata.clone(),
                     ts.as_str(),
+                    registry,
                 );
                 // send message update to slack
                 let ts = web_state.update_message(message).await?;
@@ -131,6 +361,14 @@ async fn handle_edit_back_blast_submission(
                     save_back_blast::update_back_blast_ts(db_pool, id, ts).await?;
                 }
             }
+            attach_route_files(
+                db_pool,
+                web_state,
+                id,
+                &route_file_ids,
+                &channel_id,
+                &user.id,
+            );
         }
     }
     Ok(())
@@ -138,31 +376,66 @@ async fn handle_edit_back_blast_submission(
 
 async fn handle_back_blast_submission(
     modal: &ViewSubmissionPayloadViewModal,
-    web_state: &MutableWebState,
+    web_state: &web::Data<MutableWebState>,
     db_pool: &PgPool,
     user: &ActionUser,
+    registry: &AoRegistry,
 ) -> Result<(), AppError> {
     use crate::db::save_back_blast;
 
     let form_values = modal.state.get_values();
-    let post = back_blast_post::BackBlastPost::from(form_values);
+    let post = back_blast_post::BackBlastPost::from_values(form_values, registry);
     let users = get_slack_id_map(db_pool).await?;
     let db_data = back_blast_post::convert_to_bb_data(&post, users);
     let is_valid = db_data.is_valid_back_blast();
     let mut id: Option<String> = None;
     if is_valid {
-        // save single back blast
-        let saved_id = save_back_blast::save_single(db_pool, &db_data).await?;
-        id = Some(saved_id);
+        // save single back blast (none saved when one already exists for AO and date)
+        id = save_back_blast::save_single(
+            db_pool,
+            &db_data,
+            registry,
+            &RevisionActor::modal(&user.id),
+        )
+        .await?;
+        // modal opened from preblast carries its id
+        if let (Some(saved_id), Some(pre_blast_id)) = (
+            id.as_deref(),
+            modal
+                .private_metadata
+                .as_deref()
+                .filter(|pre_blast_id| !pre_blast_id.is_empty()),
+        ) {
+            save_back_blast::link_back_blast_to_pre_blast(db_pool, saved_id, pre_blast_id).await?;
+        }
     }
-    let message =
-        back_blast_post::convert_to_message(post, db_pool, is_valid, id.clone(), &user.id).await;
+    let channel_id = post.post_channel_id(registry);
+    let route_file_ids = post.route_file_ids.clone();
+    let message = back_blast_post::convert_to_message(
+        post,
+        db_pool,
+        id.is_some(),
+        id.clone(),
+        &user.id,
+        registry,
+    )
+    .await;
 
     // post message to slack
     let ts = web_state.post_message(message).await?;
-    if let (Some(id), Some(ts)) = (id, ts) {
-        // update backblast ts in db
-        save_back_blast::update_back_blast_ts(db_pool, id.as_str(), ts).await?;
+    if let Some(id) = id {
+        if let Some(ts) = ts {
+            // update backblast ts in db
+            save_back_blast::update_back_blast_ts(db_pool, id.as_str(), ts).await?;
+        }
+        attach_route_files(
+            db_pool,
+            web_state,
+            id.as_str(),
+            &route_file_ids,
+            &channel_id,
+            &user.id,
+        );
     }
     Ok(())
 }
@@ -172,19 +445,42 @@ async fn handle_pre_blast_submission(
     db_pool: &PgPool,
     web_state: &MutableWebState,
     user: &ActionUser,
+    registry: &AoRegistry,
 ) -> Result<(), AppError> {
     use crate::db::save_pre_blast;
 
     let form_values = modal.state.get_values();
-    let post = pre_blast_post::PreBlastPost::from(form_values);
+    let post = pre_blast_post::PreBlastPost::from_values(form_values, registry);
     let users = get_slack_id_map(db_pool).await?;
     let db_data = PreBlastData::from(&post).with_qs(&post.qs, users);
-    let saved_id = save_pre_blast::save_single(db_pool, &db_data).await?;
-    let message = pre_blast_post::convert_to_message(db_pool, post, &saved_id, &user.id).await;
+    // scheduled for later, so stays pending until scheduler posts it
+    if let Some(post_at) = post.future_post_at() {
+        save_pre_blast::save_scheduled(
+            db_pool,
+            &db_data,
+            registry,
+            post_at,
+            &RevisionActor::modal(&user.id),
+        )
+        .await?;
+        let message = BlockBuilder::new().section_markdown(&format!(
+            "Preblast *{}* is scheduled to post {}. Use `/my-preblasts` to edit or cancel it.",
+            post.title,
+            to_boise_time(post_at).format("%a %b %-d at %-I:%M %p")
+        ));
+        web_state.send_direct_message(&user.id, message).await?;
+        return Ok(());
+    }
+    let saved_id =
+        save_pre_blast::save_single(db_pool, &db_data, registry, &RevisionActor::modal(&user.id))
+            .await?;
+    let message =
+        pre_blast_post::convert_to_message(db_pool, post, &saved_id, &user.id, registry).await;
+    let channel_id = message.channel.to_string();
     // post message to slack
     let ts = web_state.post_message(message).await?;
     if let Some(ts) = ts {
-        save_pre_blast::update_pre_blast_ts(db_pool, &saved_id, ts).await?;
+        save_pre_blast::update_pre_blast_ts(db_pool, &saved_id, &channel_id, ts).await?;
     }
     Ok(())
 }
@@ -8,6 +8,7 @@ pub mod pre_blast_data;
 pub mod q_line_up;
 pub mod reactions_log_data;
 pub mod region_data;
+pub mod revision_history;
 pub mod slack_events;
 pub mod slash_commands;
 pub mod sync;
@@ -1,25 +1,45 @@
+use crate::app_state::roles::Permission;
+use crate::db::queries::user_roles::user_can;
 use crate::shared::admin::{backslash_id, stinger_id};
 use crate::shared::responses::{failure, success};
 use crate::slack_api::block_kit::BlockBuilder;
 use crate::slack_api::channels::public_channels::PublicChannels;
+use crate::web_api_routes::auth::internal_auth;
 use crate::web_api_state::MutableWebState;
-use actix_web::{web, Responder};
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use serde::Deserialize;
+use sqlx::PgPool;
 
 #[derive(Deserialize)]
 pub struct DMRequest {
     slack_id: String,
     msg: String,
+    /// slack user sending message, needs comz or admin role
+    sent_by: String,
+}
+
+async fn allowed_to_dm(db_pool: &PgPool, body: &DMRequest) -> bool {
+    user_can(db_pool, &body.sent_by, &Permission::DirectMessage)
+        .await
+        .unwrap_or(false)
 }
 
 /// send direct message to someone on slack
 pub async fn send_direct_message_route(
+    db_pool: web::Data<PgPool>,
     web_state: web::Data<MutableWebState>,
-    req: web::Json<DMRequest>,
+    req: HttpRequest,
+    body: web::Json<DMRequest>,
 ) -> impl Responder {
-    let block_builder = BlockBuilder::new().section_markdown(&req.msg);
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+    if !allowed_to_dm(&db_pool, &body).await {
+        return HttpResponse::Forbidden().body("Only comz and admins can send direct messages");
+    }
+    let block_builder = BlockBuilder::new().section_markdown(&body.msg);
     match web_state
-        .send_direct_message(&req.slack_id, block_builder)
+        .send_direct_message(&body.slack_id, block_builder)
         .await
     {
         Ok(_) => success(),
@@ -29,9 +49,17 @@ pub async fn send_direct_message_route(
 
 /// testing welcome direct message route
 pub async fn test_welcome_direct_message(
+    db_pool: web::Data<PgPool>,
     web_state: web::Data<MutableWebState>,
-    req: web::Json<DMRequest>,
+    req: HttpRequest,
+    body: web::Json<DMRequest>,
 ) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+    if !allowed_to_dm(&db_pool, &body).await {
+        return HttpResponse::Forbidden().body("Only comz and admins can send direct messages");
+    }
     let backslash = backslash_id();
     let stinger = stinger_id();
     let help_desk_channel = PublicChannels::HelpDesk.channel_id();
@@ -77,7 +105,7 @@ pub async fn test_welcome_direct_message(
         .section_markdown("Thanks!");
 
     match web_state
-        .send_direct_message(&req.slack_id, block_builder)
+        .send_direct_message(&body.slack_id, block_builder)
         .await
     {
         Ok(_) => success(),
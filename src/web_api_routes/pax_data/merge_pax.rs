@@ -0,0 +1,180 @@
+//! routes to merge two pax identities (preview, merge and undo).
+use crate::app_state::pax_merge::PaxMergeRequest;
+use crate::db::queries::pax_merge::{get_pax_aliases, plan_pax_merge};
+use crate::db::save_pax_merge::{apply_pax_merge, undo_pax_merge};
+use crate::shared::common_errors::AppError;
+use crate::shared::responses::{failure, success};
+use crate::slack_api::block_kit::BlockBuilder;
+use crate::web_api_routes::auth::internal_auth;
+use crate::web_api_routes::slash_commands::SlashCommandForm;
+use crate::web_api_state::MutableWebState;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+#[derive(Deserialize)]
+pub struct PaxMergeRouteData {
+    id: String,
+}
+
+#[derive(Serialize)]
+pub struct PaxMergeResponse {
+    pub id: String,
+}
+
+/// route to preview what a merge would change
+pub async fn preview_pax_merge_route(
+    db: web::Data<PgPool>,
+    body: web::Json<PaxMergeRequest>,
+) -> impl Responder {
+    match plan_pax_merge(&db, &body).await {
+        Ok(plan) => HttpResponse::Ok().json(plan),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to merge pax identity into slack user
+pub async fn pax_merge_route(
+    db: web::Data<PgPool>,
+    web_state: web::Data<MutableWebState>,
+    req: HttpRequest,
+    body: web::Json<PaxMergeRequest>,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    let result = match plan_pax_merge(&db, &body).await {
+        Ok(plan) => apply_pax_merge(&db, &plan, None).await,
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok(id) => HttpResponse::Ok().json(PaxMergeResponse { id }),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to undo merge
+pub async fn undo_pax_merge_route(
+    db: web::Data<PgPool>,
+    web_state: web::Data<MutableWebState>,
+    req: HttpRequest,
+    path: web::Path<PaxMergeRouteData>,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    match undo_pax_merge(&db, path.id.as_str()).await {
+        Ok(_) => success(),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to list known pax aliases
+pub async fn pax_aliases_route(db: web::Data<PgPool>) -> impl Responder {
+    match get_pax_aliases(&db).await {
+        Ok(aliases) => HttpResponse::Ok().json(aliases),
+        Err(err) => failure(err),
+    }
+}
+
+/// parsed `/merge-pax` command text
+#[derive(Debug, PartialEq)]
+enum MergePaxCommand {
+    /// `old name @user` previews, `old name @user confirm` merges
+    Merge {
+        request: PaxMergeRequest,
+        confirm: bool,
+    },
+    /// `undo <merge id>`
+    Undo(String),
+}
+
+impl TryFrom<&str> for MergePaxCommand {
+    type Error = AppError;
+
+    fn try_from(text: &str) -> Result<Self, Self::Error> {
+        let text = text.trim();
+        if let Some(id) = text.strip_prefix("undo ") {
+            return Ok(MergePaxCommand::Undo(id.trim().to_string()));
+        }
+
+        let usage = "Usage: `/merge-pax old name @user [confirm]` or `/merge-pax undo <merge id>`";
+        let (from_name, rest) = text.split_once("<@").ok_or_else(|| AppError::from(usage))?;
+        let (mention, rest) = rest.split_once('>').ok_or_else(|| AppError::from(usage))?;
+        let (to_slack_id, _) = mention.split_once('|').unwrap_or((mention, ""));
+        let from_name = from_name.trim();
+        if from_name.is_empty() {
+            return Err(AppError::from(usage));
+        }
+
+        Ok(MergePaxCommand::Merge {
+            request: PaxMergeRequest {
+                from_name: from_name.to_string(),
+                to_slack_id: to_slack_id.to_string(),
+            },
+            confirm: rest.trim().eq_ignore_ascii_case("confirm"),
+        })
+    }
+}
+
+/// handle `/merge-pax` slash command (admins only)
+pub async fn handle_merge_pax_command(
+    db_pool: &PgPool,
+    form: &SlashCommandForm,
+) -> Result<BlockBuilder, AppError> {
+    let message = match MergePaxCommand::try_from(form.text.as_str())? {
+        MergePaxCommand::Merge {
+            request,
+            confirm: false,
+        } => {
+            let plan = plan_pax_merge(db_pool, &request).await?;
+            format!(
+                "{}\n\nRun again with `confirm` at the end to merge.",
+                plan.preview_text()
+            )
+        }
+        MergePaxCommand::Merge {
+            request,
+            confirm: true,
+        } => {
+            let plan = plan_pax_merge(db_pool, &request).await?;
+            let id = apply_pax_merge(db_pool, &plan, Some(form.user_id.to_string())).await?;
+            format!(
+                "{}\n\nMerged! To revert run `/merge-pax undo {}`",
+                plan.preview_text(),
+                id
+            )
+        }
+        MergePaxCommand::Undo(id) => {
+            undo_pax_merge(db_pool, id.as_str()).await?;
+            String::from("Merge reverted")
+        }
+    };
+    Ok(BlockBuilder::new().section_markdown(message.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_command() {
+        let command = MergePaxCommand::try_from("Old Name <@U123|newname> confirm").unwrap();
+        assert_eq!(
+            command,
+            MergePaxCommand::Merge {
+                request: PaxMergeRequest {
+                    from_name: String::from("Old Name"),
+                    to_slack_id: String::from("U123"),
+                },
+                confirm: true,
+            }
+        );
+        let command = MergePaxCommand::try_from("undo abc").unwrap();
+        assert_eq!(command, MergePaxCommand::Undo(String::from("abc")));
+        assert!(MergePaxCommand::try_from("<@U123>").is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use crate::app_state::ao_data::AO;
 use crate::app_state::backblast_data::BackBlastData;
 use crate::app_state::double_downs::DoubleDownProgram;
+use crate::app_state::pax_merge::{merge_candidates, MergeCandidate};
 use crate::db::queries::all_back_blasts::{
     get_all, get_dd_list_with_pax, get_list_with_pax, BackBlastJsonData,
 };
@@ -15,6 +16,7 @@ use std::collections::{HashMap, HashSet};
 
 pub mod direct_message;
 pub mod get_pax_tree;
+pub mod merge_pax;
 pub mod pax_leaderboards;
 pub mod set_pax_parent;
 pub mod stats;
@@ -210,6 +212,8 @@ pub struct BadUserDataResponse {
     pub slack_users: HashSet<String>,
     pub missing_pax: HashSet<String>,
     pub data: Vec<BadUser>,
+    /// users that missing pax might be (to merge with)
+    pub merge_candidates: Vec<MergeCandidate>,
 }
 
 impl BadUserDataResponse {
@@ -218,6 +222,7 @@ impl BadUserDataResponse {
             slack_users: slack_users.clone(),
             missing_pax: HashSet::new(),
             data: Vec::new(),
+            merge_candidates: Vec::new(),
         }
     }
 }
@@ -236,6 +241,14 @@ impl BadUser {
 pub async fn get_bad_data(db_pool: web::Data<PgPool>) -> impl Responder {
     match get_db_users(&db_pool).await {
         Ok(users) => {
+            let user_names = users
+                .values()
+                .filter_map(|user| {
+                    user.id
+                        .as_ref()
+                        .map(|id| (id.to_string(), user.name.to_string()))
+                })
+                .collect::<Vec<(String, String)>>();
             let users = users
                 .iter()
                 .fold(HashSet::<String>::new(), |mut acc, (_, user)| {
@@ -255,6 +268,7 @@ pub async fn get_bad_data(db_pool: web::Data<PgPool>) -> impl Responder {
                     }
                 }
             }
+            response.merge_candidates = merge_candidates(&response.missing_pax, &user_names);
             return HttpResponse::Ok().json(response);
         }
         Err(err) => {
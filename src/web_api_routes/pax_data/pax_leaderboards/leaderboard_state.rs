@@ -1,3 +1,6 @@
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::streaks::{week_start, PaxStreaks};
 use crate::shared::processed_type::ProcessedType;
 use chrono::{Datelike, NaiveDate};
 
@@ -9,6 +12,12 @@ pub enum LeaderboardState {
     ApproachingBDBD(u16),
     /// number of years at F3
     HitBDBD(u16),
+    /// weeks in a row with enough posts, and monday streak started
+    HitWeeklyStreak(u32, NaiveDate),
+    /// weekly streak could end this week (monday of week)
+    WeeklyStreakAtRisk(NaiveDate),
+    /// scheduled days in a row at AO, and day streak started
+    HitAoStreak(AO, u32, NaiveDate),
     None,
 }
 
@@ -57,16 +66,39 @@ impl LeaderboardState {
         }
     }
 
+    /// streak milestones hit and weekly streak at risk
+    pub fn streaks(streaks: &PaxStreaks, now: &NaiveDate) -> Vec<Self> {
+        let mut states = Vec::<LeaderboardState>::new();
+        if let (Some(weeks), Some(start)) = (streaks.weekly.milestone(), streaks.weekly.start) {
+            states.push(LeaderboardState::HitWeeklyStreak(weeks, start));
+        }
+        if streaks.weekly.at_risk(now) {
+            states.push(LeaderboardState::WeeklyStreakAtRisk(week_start(now)));
+        }
+        for ao_streak in streaks.aos.iter() {
+            if let (Some(days), Some(start)) = (ao_streak.milestone(), ao_streak.start) {
+                states.push(LeaderboardState::HitAoStreak(
+                    ao_streak.ao.clone(),
+                    days,
+                    start,
+                ));
+            }
+        }
+        states
+    }
+
     pub fn icon(&self) -> Option<&str> {
         match self {
             LeaderboardState::Approaching(bds) => bds.icon(),
             LeaderboardState::Hit(bds) => bds.icon(),
             LeaderboardState::ApproachingBDBD(_) | LeaderboardState::HitBDBD(_) => Some("🎂"),
+            LeaderboardState::HitWeeklyStreak(..) | LeaderboardState::HitAoStreak(..) => Some("🔥"),
+            LeaderboardState::WeeklyStreakAtRisk(_) => Some("⚠️"),
             LeaderboardState::None => None,
         }
     }
 
-    pub fn message(&self) -> Option<String> {
+    pub fn message(&self, registry: &AoRegistry) -> Option<String> {
         let icon = self.icon().unwrap_or("");
         match self {
             LeaderboardState::Approaching(bds) => {
@@ -79,6 +111,18 @@ impl LeaderboardState {
             LeaderboardState::HitBDBD(year) => {
                 Some(format!("{} - Today is {} year anniversary", icon, year))
             }
+            LeaderboardState::HitWeeklyStreak(weeks, _) => {
+                Some(format!("{} - Hit {} week posting streak", icon, weeks))
+            }
+            LeaderboardState::WeeklyStreakAtRisk(_) => {
+                Some(format!("{} - Weekly posting streak at risk", icon))
+            }
+            LeaderboardState::HitAoStreak(ao, days, _) => Some(format!(
+                "{} - Hit {} day streak at {}",
+                icon,
+                days,
+                ao.friendly_name(registry)
+            )),
             LeaderboardState::None => None,
         }
     }
@@ -98,6 +142,16 @@ impl ProcessedType for LeaderboardState {
                 "approaching.bd.{}",
                 data.get_type_id().unwrap_or_default()
             )),
+            // start date included so a new streak gets called out again
+            LeaderboardState::HitWeeklyStreak(weeks, start) => {
+                Some(format!("hit.streak.weeks.{}.{}", weeks, start))
+            }
+            LeaderboardState::WeeklyStreakAtRisk(week) => {
+                Some(format!("risk.streak.weeks.{}", week))
+            }
+            LeaderboardState::HitAoStreak(ao, days, start) => {
+                Some(format!("hit.streak.ao.{}.{}.{}", ao, days, start))
+            }
         };
 
         id.map(|id| format!("{}.{}", prefix, id))
@@ -203,14 +257,14 @@ mod tests {
     #[test]
     fn approaching_bd_message() {
         let state = LeaderboardState::bds(199);
-        let message = state.message();
+        let message = state.message(&AoRegistry::default());
         assert_eq!(message.unwrap(), String::from("🔶 - Approaching 200 BDs"))
     }
 
     #[test]
     fn hit_bd_message() {
         let state = LeaderboardState::bds(300);
-        let message = state.message();
+        let message = state.message(&AoRegistry::default());
         assert_eq!(message.unwrap(), String::from("🌟 - Hit 300 BDs"))
     }
 
@@ -220,7 +274,7 @@ mod tests {
             NaiveDate::from_ymd_opt(2021, 3, 14).unwrap(),
             NaiveDate::from_ymd_opt(2024, 3, 12).unwrap(),
         );
-        let message = state.message();
+        let message = state.message(&AoRegistry::default());
         assert_eq!(
             message.unwrap(),
             String::from("🎂 - Approaching 3 year anniversary")
@@ -233,7 +287,7 @@ mod tests {
             NaiveDate::from_ymd_opt(2019, 3, 14).unwrap(),
             NaiveDate::from_ymd_opt(2024, 3, 14).unwrap(),
         );
-        let message = state.message();
+        let message = state.message(&AoRegistry::default());
         assert_eq!(
             message.unwrap(),
             String::from("🎂 - Today is 5 year anniversary")
@@ -243,14 +297,14 @@ mod tests {
     #[test]
     fn empty_message() {
         let state = LeaderboardState::bds(234);
-        let message = state.message();
+        let message = state.message(&AoRegistry::default());
         assert_eq!(message, None);
 
         let state = LeaderboardState::b_day(
             NaiveDate::from_ymd_opt(2019, 3, 5).unwrap(),
             NaiveDate::from_ymd_opt(2024, 3, 14).unwrap(),
         );
-        let message = state.message();
+        let message = state.message(&AoRegistry::default());
         assert_eq!(message, None);
     }
 
@@ -260,6 +314,45 @@ mod tests {
         assert_eq!(bds, NumberOfBeatDowns::Common(0));
     }
 
+    #[test]
+    fn streak_states() {
+        use crate::app_state::streaks::{AoStreak, WeeklyStreak};
+
+        let now = NaiveDate::from_ymd_opt(2026, 10, 30).unwrap();
+        let start = NaiveDate::from_ymd_opt(2026, 8, 3).unwrap();
+        let streaks = PaxStreaks {
+            weekly: WeeklyStreak {
+                weeks: 12,
+                start: Some(start),
+                posts_this_week: 1,
+                min_posts: 3,
+            },
+            aos: vec![AoStreak {
+                ao: AO::Bleach,
+                days: 9,
+                start: Some(start),
+            }],
+        };
+        let states = LeaderboardState::streaks(&streaks, &now);
+        assert_eq!(
+            states,
+            vec![
+                LeaderboardState::HitWeeklyStreak(12, start),
+                LeaderboardState::WeeklyStreakAtRisk(
+                    NaiveDate::from_ymd_opt(2026, 10, 26).unwrap()
+                ),
+            ]
+        );
+        assert_eq!(
+            states[0].get_type_id().unwrap(),
+            "leaderboard.state.hit.streak.weeks.12.2026-08-03"
+        );
+        assert_eq!(
+            states[0].message(&AoRegistry::default()).unwrap(),
+            String::from("🔥 - Hit 12 week posting streak")
+        );
+    }
+
     #[test]
     fn same_day_should_be_none() {
         let state = LeaderboardState::b_day(
@@ -267,6 +360,6 @@ mod tests {
             NaiveDate::from_ymd_opt(2024, 3, 14).unwrap(),
         );
 
-        assert_eq!(state.message(), None);
+        assert_eq!(state.message(&AoRegistry::default()), None);
     }
 }
@@ -1,7 +1,12 @@
 mod leaderboard_state;
 
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::streaks::PaxStreaks;
+use crate::app_state::MutableAppState;
 use crate::db::queries::all_back_blasts::pax_bd_stats;
-use crate::db::queries::all_back_blasts::pax_bd_stats::PaxBdStats;
+use crate::db::queries::all_back_blasts::pax_bd_stats::{PaxBdStats, PaxPost};
+use crate::db::queries::ao_closures::get_closure_calendar;
 use crate::db::queries::processed_items::{get_processed_items, process_items, ProcessedItem};
 use crate::shared::common_errors::AppError;
 use crate::shared::processed_type::{NewProcessItem, ProcessedType, ResolvingProcessedItems};
@@ -19,18 +24,20 @@ use std::collections::HashMap;
 
 pub async fn post_pax_leaderboards(
     db_pool: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
     web_state: web::Data<MutableWebState>,
     req: HttpRequest,
 ) -> impl Responder {
     if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_ok() {
-        let pax_data = get_pax_data(&db_pool).await;
+        let registry = app_state.get_ao_registry();
+        let pax_data = get_pax_data(&db_pool, &registry).await;
         let item_ids = pax_data.get_item_ids();
         let existing_processed = filter_processed_items(&db_pool, &item_ids, &pax_data).await;
 
         if existing_processed.is_empty() {
             HttpResponse::Ok().body("No leaderboards")
         } else {
-            match process_and_post(&db_pool, existing_processed, &web_state).await {
+            match process_and_post(&db_pool, existing_processed, &web_state, &registry).await {
                 Ok(_) => HttpResponse::Ok().body("success"),
                 Err(err) => {
                     println!("Err saving processing items: {:?}", err);
@@ -48,9 +55,10 @@ async fn process_and_post(
     db: &PgPool,
     leaderboard: LeaderboardItems,
     web_state: &MutableWebState,
+    registry: &AoRegistry,
 ) -> Result<(), AppError> {
     process_items(db, &leaderboard).await?;
-    let block_builder = get_blocks_for_leaderboard(&leaderboard);
+    let block_builder = get_blocks_for_leaderboard(&leaderboard, registry);
     web_state
         .post_message(PostMessageRequest::new(
             private_channels::ACHIEVEMENTS_CHANNEL_ID,
@@ -74,40 +82,80 @@ async fn filter_processed_items(
     }
 }
 
-async fn get_pax_data(db: &PgPool) -> LeaderboardItems {
+async fn get_pax_data(db: &PgPool, registry: &AoRegistry) -> LeaderboardItems {
     let now = local_boise_time().date_naive();
+    let streaks = match get_pax_streaks(db, registry, &now).await {
+        Ok(streaks) => streaks,
+        Err(err) => {
+            println!("Error getting pax streaks: {:?}", err);
+            HashMap::new()
+        }
+    };
     match pax_bd_stats::get_pax_bd_stats(db).await {
-        Ok(pax_data) => LeaderboardItems::new(pax_data, now),
+        Ok(pax_data) => LeaderboardItems::new(pax_data, &streaks, now),
         Err(err) => {
             println!("Error getting pax bd stats: {:?}", err);
-            LeaderboardItems::new(vec![], now)
+            LeaderboardItems::new(vec![], &streaks, now)
         }
     }
 }
 
-fn get_blocks_for_leaderboard(items: &LeaderboardItems) -> BlockBuilder {
+/// current streaks of each pax (key is slack id). Full post history is used so the start of a
+/// streak, which processed items are keyed on, stays the same from day to day.
+async fn get_pax_streaks(
+    db: &PgPool,
+    registry: &AoRegistry,
+    now: &NaiveDate,
+) -> Result<HashMap<String, PaxStreaks>, AppError> {
+    let posts = pax_bd_stats::get_pax_posts(db).await?;
+    let first_post = posts.iter().map(|post| post.date).min().unwrap_or(*now);
+    let closures = get_closure_calendar(db, &first_post, now).await?;
+    let mut per_pax = HashMap::<String, Vec<(AO, NaiveDate)>>::new();
+    for PaxPost {
+        slack_id,
+        channel_id,
+        date,
+    } in posts
+    {
+        per_pax
+            .entry(slack_id)
+            .or_default()
+            .push((AO::from_channel_id(channel_id.as_str(), registry), date));
+    }
+    Ok(per_pax
+        .into_iter()
+        .map(|(slack_id, posts)| (slack_id, PaxStreaks::new(&posts, &closures, registry, now)))
+        .collect())
+}
+
+/// one section per milestone (and AO for AO streaks). Streaks with different start dates are
+/// separate processed items but shown together.
+fn get_blocks_for_leaderboard(items: &LeaderboardItems, registry: &AoRegistry) -> BlockBuilder {
     let mut block_builder = BlockBuilder::new().header("Leaderboard Summary");
 
+    let mut sections = Vec::<(String, Vec<String>)>::new();
     for (_, pax_list) in items.full_map.iter() {
-        if let Some(msg) = pax_list.state.message() {
-            let pax = pax_list
-                .slack_ids
-                .iter()
-                .map(|(id, meta)| {
-                    let mapped_slack = map_slack_id_to_link(id);
-                    if let Some(meta) = meta {
-                        format!("{} {}", mapped_slack, meta)
-                    } else {
-                        mapped_slack
-                    }
-                })
-                .collect::<Vec<String>>()
-                .join(", ");
-            let msg = format!("{} - {}", msg, pax);
-            block_builder.add_section_markdown(&msg);
+        if let Some(msg) = pax_list.state.message(registry) {
+            let pax = pax_list.slack_ids.iter().map(|(id, meta)| {
+                let mapped_slack = map_slack_id_to_link(id);
+                if let Some(meta) = meta {
+                    format!("{} {}", mapped_slack, meta)
+                } else {
+                    mapped_slack
+                }
+            });
+            match sections.iter_mut().find(|(section, _)| section == &msg) {
+                Some((_, list)) => list.extend(pax),
+                None => sections.push((msg, pax.collect())),
+            }
         }
     }
 
+    for (msg, pax) in sections {
+        let msg = format!("{} - {}", msg, pax.join(", "));
+        block_builder.add_section_markdown(&msg);
+    }
+
     block_builder
 }
 
@@ -123,7 +171,11 @@ impl LeaderboardItems {
         }
     }
 
-    pub fn new(pax_data: Vec<PaxBdStats>, now: NaiveDate) -> Self {
+    pub fn new(
+        pax_data: Vec<PaxBdStats>,
+        streaks: &HashMap<String, PaxStreaks>,
+        now: NaiveDate,
+    ) -> Self {
         let mut full_map = HashMap::<String, LeaderboardPaxList>::new();
         for item in pax_data {
             let mut states = Vec::<leaderboard_state::LeaderboardState>::new();
@@ -133,15 +185,24 @@ impl LeaderboardItems {
                 let anni_state = leaderboard_state::LeaderboardState::b_day(anni, now);
                 states.push(anni_state);
             }
+            let pax_streaks = streaks.get(&item.slack_id);
+            if let Some(pax_streaks) = pax_streaks {
+                states.extend(leaderboard_state::LeaderboardState::streaks(
+                    pax_streaks,
+                    &now,
+                ));
+            }
 
             for state_item in states {
                 match (state_item.get_type_id(), state_item) {
                     (None, _) | (_, leaderboard_state::LeaderboardState::None) => {}
                     (Some(type_id), state) => {
-                        let entry = full_map
-                            .entry(type_id)
-                            .or_insert(LeaderboardPaxList::new(state, &item));
-                        entry.add_user(&item);
+                        let entry = full_map.entry(type_id).or_insert(LeaderboardPaxList::new(
+                            state,
+                            &item,
+                            pax_streaks,
+                        ));
+                        entry.add_user(&item, pax_streaks);
                     }
                 }
             }
@@ -206,16 +267,20 @@ struct LeaderboardPaxList {
 }
 
 impl LeaderboardPaxList {
-    pub fn new(state: leaderboard_state::LeaderboardState, pax_bd_stats: &PaxBdStats) -> Self {
-        let meta = get_meta_data(pax_bd_stats, &state);
+    pub fn new(
+        state: leaderboard_state::LeaderboardState,
+        pax_bd_stats: &PaxBdStats,
+        streaks: Option<&PaxStreaks>,
+    ) -> Self {
+        let meta = get_meta_data(pax_bd_stats, streaks, &state);
         LeaderboardPaxList {
             state,
             slack_ids: HashMap::from([(pax_bd_stats.slack_id.to_string(), meta)]),
         }
     }
 
-    pub fn add_user(&mut self, pax_bd_stats: &PaxBdStats) {
-        let meta = get_meta_data(pax_bd_stats, &self.state);
+    pub fn add_user(&mut self, pax_bd_stats: &PaxBdStats, streaks: Option<&PaxStreaks>) {
+        let meta = get_meta_data(pax_bd_stats, streaks, &self.state);
         self.slack_ids
             .insert(pax_bd_stats.slack_id.to_string(), meta);
     }
@@ -238,6 +303,7 @@ impl LeaderboardPaxList {
 
 fn get_meta_data(
     pax_bd_stats: &PaxBdStats,
+    streaks: Option<&PaxStreaks>,
     state: &leaderboard_state::LeaderboardState,
 ) -> Option<String> {
     match state {
@@ -247,6 +313,12 @@ fn get_meta_data(
         leaderboard_state::LeaderboardState::Approaching(_) => {
             Some(format!("({})", pax_bd_stats.bd_count))
         }
+        leaderboard_state::LeaderboardState::WeeklyStreakAtRisk(_) => streaks.map(|streaks| {
+            format!(
+                "({} weeks, {}/{} posts)",
+                streaks.weekly.weeks, streaks.weekly.posts_this_week, streaks.weekly.min_posts
+            )
+        }),
         _ => None,
     }
 }
@@ -310,9 +382,12 @@ mod tests {
             ),
         ];
         let now = NaiveDate::from_ymd_opt(2024, 3, 28).unwrap();
-        let items = LeaderboardItems::new(pax_data, now);
+        let items = LeaderboardItems::new(pax_data, &HashMap::new(), now);
 
-        println!("blocks: {:?}", get_blocks_for_leaderboard(&items));
+        println!(
+            "blocks: {:?}",
+            get_blocks_for_leaderboard(&items, &AoRegistry::default())
+        );
         assert_eq!(items.full_map.len(), 2);
         let processed_items: Vec<ProcessedItem> = vec![
             new_processed_item("U1", "leaderboard.state.approaching.bd.hnd.3"),
@@ -340,4 +415,46 @@ mod tests {
         let first = list.first().unwrap();
         assert_eq!(first, "U2");
     }
+
+    #[test]
+    fn streak_sections_grouped_by_milestone() {
+        use crate::app_state::streaks::{AoStreak, WeeklyStreak};
+
+        let now = NaiveDate::from_ymd_opt(2026, 10, 30).unwrap();
+        let pax_streaks = |start: NaiveDate| PaxStreaks {
+            weekly: WeeklyStreak {
+                weeks: 12,
+                start: Some(start),
+                posts_this_week: 3,
+                min_posts: 3,
+            },
+            aos: vec![AoStreak {
+                ao: AO::Bleach,
+                days: 9,
+                start: Some(start),
+            }],
+        };
+        let streaks = HashMap::from([
+            (
+                "U1".to_string(),
+                pax_streaks(NaiveDate::from_ymd_opt(2026, 8, 3).unwrap()),
+            ),
+            (
+                "U2".to_string(),
+                pax_streaks(NaiveDate::from_ymd_opt(2026, 8, 4).unwrap()),
+            ),
+        ]);
+        let pax_data = vec![
+            new_pax_stat("U1", "Stinger", 12, now),
+            new_pax_stat("U2", "Backslash", 12, now),
+        ];
+        let items = LeaderboardItems::new(pax_data, &streaks, now);
+        // processed per start date
+        assert_eq!(items.get_item_ids().len(), 2);
+        assert_eq!(items.full_map.len(), 2);
+
+        let blocks = get_blocks_for_leaderboard(&items, &AoRegistry::default()).blocks;
+        // header and one weekly streak section
+        assert_eq!(blocks.len(), 2);
+    }
 }
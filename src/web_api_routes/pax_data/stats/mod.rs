@@ -1,3 +1,6 @@
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::streaks::PaxStreaks;
+use crate::app_state::MutableAppState;
 use crate::db::queries::all_back_blasts::{
     get_dd_list_with_pax, get_list_with_pax, BackBlastJsonData,
 };
@@ -5,7 +8,9 @@ use crate::db::queries::users::get_user_by_name;
 use crate::shared::common_errors::AppError;
 use crate::users::f3_user::F3User;
 use crate::web_api_routes::pax_data::PaxInfoResponse;
-use crate::web_api_routes::slash_commands::my_stats::get_pax_info_from_bb_data;
+use crate::web_api_routes::slash_commands::my_stats::{
+    get_pax_info_from_bb_data, get_streaks_from_bb_data,
+};
 use actix_web::{web, HttpResponse, Responder};
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
@@ -23,6 +28,8 @@ pub struct PaxStatsResponse {
     pub post_count: usize,
     pub first_post: NaiveDate,
     pub pax_profile: Option<F3User>,
+    /// current weekly and AO streaks
+    pub streaks: PaxStreaks,
     /// full list of BD's for this pax
     pub bd_list: Vec<BackBlastJsonData>,
     /// full list of DD's for this pax
@@ -34,6 +41,7 @@ impl PaxStatsResponse {
     pub fn new(
         user: Option<F3User>,
         pax_info: PaxInfoResponse,
+        streaks: PaxStreaks,
         bd_list: Vec<BackBlastJsonData>,
         dd_list: Vec<BackBlastJsonData>,
     ) -> Self {
@@ -42,6 +50,7 @@ impl PaxStatsResponse {
             post_count: pax_info.post_count,
             first_post: pax_info.start_date,
             pax_profile: user,
+            streaks,
             bd_list,
             dd_list,
         }
@@ -51,20 +60,27 @@ impl PaxStatsResponse {
 /// route for getting some stats on a certain pax
 pub async fn pax_stats_route(
     db_pool: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
     route_data: web::Path<RouteData>,
 ) -> impl Responder {
-    match get_pax_stats(&db_pool, route_data.into_inner().name.as_str()).await {
+    let registry = app_state.get_ao_registry();
+    match get_pax_stats(&db_pool, route_data.into_inner().name.as_str(), &registry).await {
         Ok(response) => HttpResponse::Ok().json(response),
         Err(err) => HttpResponse::BadRequest().body(err.to_string()),
     }
 }
 
 /// get the stats for certain pax
-async fn get_pax_stats(db_pool: &PgPool, name: &str) -> Result<PaxStatsResponse, AppError> {
+async fn get_pax_stats(
+    db_pool: &PgPool,
+    name: &str,
+    registry: &AoRegistry,
+) -> Result<PaxStatsResponse, AppError> {
     let list = get_list_with_pax(db_pool, name).await?;
     let dd = get_dd_list_with_pax(db_pool, name).await?;
     let response = get_pax_info_from_bb_data(&list, &dd, name);
     let user = get_user_by_name(db_pool, name).await?;
-    let stats = PaxStatsResponse::new(user, response, list, dd);
+    let streaks = get_streaks_from_bb_data(db_pool, &list, registry).await?;
+    let stats = PaxStatsResponse::new(user, response, streaks, list, dd);
     Ok(stats)
 }
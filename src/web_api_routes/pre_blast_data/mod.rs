@@ -1,10 +1,19 @@
+use crate::app_state::backblast_data::{BackBlastData, BackBlastType};
+use crate::app_state::hard_commits::HcAttendance;
+use crate::db::queries::all_back_blasts::get_all_within_date_range;
+use crate::db::queries::ao_list::get_hc_emojis;
 use crate::db::queries::pre_blasts;
 use crate::db::queries::pre_blasts::PreBlastJsonFullData;
+use crate::db::queries::reactions_log::{get_pre_blast_hcs, PreBlastHcItem};
+use crate::shared::common_errors::AppError;
+use crate::shared::responses::failure;
 use actix_web::{web, HttpResponse, Responder};
 use chrono::{NaiveDate, NaiveTime};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 
+pub mod scheduled_pre_blasts;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct PreBlastRow {
     pub id: String,
@@ -66,3 +75,60 @@ pub async fn download_pre_blast_data_csv(db_pool: web::Data<PgPool>) -> impl Res
         Err(err) => HttpResponse::BadRequest().body(err.to_string()),
     }
 }
+
+#[derive(Deserialize)]
+pub struct PreBlastRouteData {
+    pub id: String,
+}
+
+/// HCs of preblast, with attendance compared once backblast is posted
+#[derive(Serialize)]
+pub struct PreBlastHcResponse {
+    pub pre_blast_id: String,
+    /// emojis counted as HC for AO
+    pub emojis: Vec<String>,
+    pub count: usize,
+    pub hcs: Vec<PreBlastHcItem>,
+    pub attendance: Option<HcAttendance>,
+}
+
+async fn get_pre_blast_hc_data(db_pool: &PgPool, id: &str) -> Result<PreBlastHcResponse, AppError> {
+    let pre_blast = pre_blasts::get_pre_blast_by_id(db_pool, id)
+        .await?
+        .ok_or_else(|| AppError::from("Missing Preblast in database"))?;
+    let emojis = get_hc_emojis(db_pool, &pre_blast.channel_id).await?;
+    let hcs = get_pre_blast_hcs(db_pool, id, &emojis).await?;
+
+    let back_blast = get_all_within_date_range(db_pool, &pre_blast.date, &pre_blast.date)
+        .await?
+        .iter()
+        .filter(|bb| bb.channel_id == pre_blast.channel_id)
+        .map(BackBlastData::from)
+        .find(|bb| bb.bb_type == BackBlastType::BackBlast);
+    let attendance = back_blast.map(|bb| {
+        let names = hcs
+            .iter()
+            .map(|hc| hc.name.to_string())
+            .collect::<Vec<String>>();
+        HcAttendance::new(&names, &bb.get_pax())
+    });
+
+    Ok(PreBlastHcResponse {
+        pre_blast_id: id.to_string(),
+        emojis,
+        count: hcs.len(),
+        hcs,
+        attendance,
+    })
+}
+
+/// route to get HCs of preblast, and projected versus actual attendance after the workout
+pub async fn pre_blast_hc_route(
+    db_pool: web::Data<PgPool>,
+    path: web::Path<PreBlastRouteData>,
+) -> impl Responder {
+    match get_pre_blast_hc_data(&db_pool, path.id.as_str()).await {
+        Ok(data) => HttpResponse::Ok().json(data),
+        Err(err) => failure(err),
+    }
+}
@@ -0,0 +1,73 @@
+//! Preblasts saved with a "post at" time stay pending until the scheduler posts them.
+
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::MutableAppState;
+use crate::db::queries::pre_blasts::{get_due_pre_blasts, PendingPreBlast};
+use crate::db::save_pre_blast;
+use crate::shared::common_errors::AppError;
+use crate::shared::responses::failure;
+use crate::shared::time::to_boise_time;
+use crate::web_api_routes::auth::internal_auth;
+use crate::web_api_routes::interactive_events::edit_pre_blast::get_pre_blast_post;
+use crate::web_api_routes::slash_commands::pre_blast::pre_blast_post;
+use crate::web_api_state::MutableWebState;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use sqlx::PgPool;
+
+/// post pending preblast to its AO channel (as Q that scheduled it) and save ts, so it can be edited and HC'd.
+pub async fn post_pending_pre_blast(
+    db_pool: &PgPool,
+    web_state: &MutableWebState,
+    pending: &PendingPreBlast,
+    registry: &AoRegistry,
+) -> Result<(), AppError> {
+    let id = pending.id.to_string();
+    let post = get_pre_blast_post(db_pool, &id, registry).await?;
+    let scheduled_by = pending.scheduled_by.clone().unwrap_or_default();
+    let message =
+        pre_blast_post::convert_to_message(db_pool, post, &id, &scheduled_by, registry).await;
+    let channel_id = message.channel.to_string();
+    match web_state.post_message(message).await? {
+        Some(ts) => save_pre_blast::update_pre_blast_ts(db_pool, &id, &channel_id, ts).await,
+        None => Err(AppError::from("Missing ts of posted preblast")),
+    }
+}
+
+/// post all pending preblasts that are due. Returns number posted.
+pub async fn post_due_pre_blasts(
+    db_pool: &PgPool,
+    web_state: &MutableWebState,
+    registry: &AoRegistry,
+) -> Result<usize, AppError> {
+    let due = get_due_pre_blasts(db_pool).await?;
+    let mut posted = 0;
+    for pending in due.iter() {
+        match post_pending_pre_blast(db_pool, web_state, pending, registry).await {
+            Ok(_) => posted += 1,
+            Err(err) => println!(
+                "Error posting scheduled preblast {} (due {}): {:?}",
+                pending.id,
+                to_boise_time(pending.post_at),
+                err
+            ),
+        }
+    }
+    Ok(posted)
+}
+
+/// route for scheduler to post preblasts that are due
+pub async fn post_scheduled_pre_blasts_route(
+    db_pool: web::Data<PgPool>,
+    web_state: web::Data<MutableWebState>,
+    app_state: web::Data<MutableAppState>,
+    req: HttpRequest,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    match post_due_pre_blasts(&db_pool, &web_state, &app_state.get_ao_registry()).await {
+        Ok(posted) => HttpResponse::Ok().body(format!("Posted {} preblasts", posted)),
+        Err(err) => failure(err),
+    }
+}
@@ -1,5 +1,7 @@
-use crate::app_state::ao_data::const_names::AO_LIST;
 use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::MutableAppState;
+use crate::db::queries::ao_closures::get_closure_calendar;
 use crate::db::queries::q_line_up::{
     get_q_line_up_between_dates, get_q_line_up_between_dates_for_ao, QLineUpDbData,
 };
@@ -10,6 +12,11 @@ use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use std::collections::HashMap;
 
+pub mod q_recurrences;
+pub mod q_reminders;
+pub mod q_swaps;
+pub mod q_vacancies;
+
 #[derive(Deserialize)]
 pub struct QLineUpQuery {
     start: NaiveDate,
@@ -60,9 +67,10 @@ impl QLineUpResponse {
 /// q line up list route.
 pub async fn q_line_up_route(
     db: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
     query: web::Query<QLineUpQuery>,
 ) -> impl Responder {
-    match q_line_up_results(&db, &query).await {
+    match q_line_up_results(&db, &query, &app_state.get_ao_registry()).await {
         Ok(response) => HttpResponse::Ok().json(response),
         Err(err) => HttpResponse::BadRequest().body(err.to_string()),
     }
@@ -93,8 +101,10 @@ pub async fn get_line_up_map(
 async fn q_line_up_results(
     db: &PgPool,
     req: &QLineUpQuery,
+    registry: &AoRegistry,
 ) -> Result<Vec<QLineUpResponse>, AppError> {
     let ao = validate_request(req)?;
+    let closures = get_closure_calendar(db, &req.start, &req.end).await?;
 
     let results = if let Some(ao) = ao {
         // for specific ao
@@ -105,10 +115,13 @@ async fn q_line_up_results(
         let end_date = req.end;
 
         while current_date <= end_date {
-            if ao.week_days().contains(&current_date.weekday()) {
+            if ao.week_days(registry).contains(&current_date.weekday()) {
                 let ao_string = ao.to_string();
-                let matching_entry =
-                    get_q_line_up_item_from_list(&results, current_date, &ao_string);
+                let matching_entry = if closures.is_closed(&ao, &current_date) {
+                    QLineUpResponse::new_closed(&ao_string, current_date)
+                } else {
+                    get_q_line_up_item_from_list(&results, current_date, &ao_string)
+                };
                 response.push(matching_entry);
             }
             current_date = current_date.succ_opt().unwrap();
@@ -125,11 +138,16 @@ async fn q_line_up_results(
 
         while current_date <= end_date {
             // loop through aos that are open on current date checked.
-            for ao in AO_LIST
+            for ao in registry
+                .active_aos()
                 .iter()
-                .filter(|ao| ao.week_days().contains(&current_date.weekday()))
+                .filter(|ao| ao.week_days(registry).contains(&current_date.weekday()))
             {
                 let ao_string = ao.to_string();
+                if closures.is_closed(ao, &current_date) {
+                    response.push(QLineUpResponse::new_closed(&ao_string, current_date));
+                    continue;
+                }
                 let filtered = results.get(ao_string.as_str());
                 let taken = filtered
                     .map(|list| {
@@ -0,0 +1,221 @@
+//! Recurring Q commitments added to the Q line up ahead of time. Closed days and dates
+//! someone already signed up for are skipped, and each recurrence date is only added once,
+//! so a Q removing themselves from the line up doesn't get signed back up on the next run.
+
+use crate::app_state::ao_closures::ClosureCalendar;
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::q_recurrence::QRecurrence;
+use crate::app_state::MutableAppState;
+use crate::db::queries::ao_closures::get_closure_calendar;
+use crate::db::queries::processed_items::{get_processed_items, process_items};
+use crate::db::queries::q_line_up::{get_q_line_up_between_dates, QLineUpDbData};
+use crate::db::queries::q_recurrences::get_active_recurrences;
+use crate::db::queries::users::get_slack_id_map;
+use crate::db::save_q_line_up::{save_list, NewQLineUpDbEntry};
+use crate::shared::common_errors::AppError;
+use crate::shared::processed_type::{NewProcessItem, ResolvingProcessedItems};
+use crate::shared::responses::failure;
+use crate::shared::time::local_boise_time;
+use crate::web_api_routes::auth::internal_auth;
+use crate::web_api_state::MutableWebState;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use chrono::{Duration, NaiveDate};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+
+/// days ahead to add recurring Qs, if not passed in
+const DEFAULT_HORIZON_DAYS: i64 = 28;
+/// processed item type of recurrence dates already added to line up
+const Q_RECURRENCE: &str = "q-recurrence";
+
+#[derive(Deserialize)]
+pub struct QRecurrenceQuery {
+    horizon_days: Option<i64>,
+}
+
+/// processed item id of recurrence date
+fn recurrence_item_id(recurrence_id: &str, date: &NaiveDate) -> String {
+    format!("{}.{}", recurrence_id, date)
+}
+
+/// recurrence dates added to line up, to mark as processed
+struct MaterializedRecurrences {
+    item_ids: Vec<String>,
+}
+
+impl ResolvingProcessedItems for MaterializedRecurrences {
+    fn get_processed_items(&self) -> Vec<NewProcessItem> {
+        vec![NewProcessItem::new(Q_RECURRENCE, self.item_ids.clone())]
+    }
+}
+
+/// q line up entries (with processed item id) for recurrences between dates, first recurrence
+/// winning when they overlap. Recurrence dates already materialized before are skipped.
+fn recurrence_sign_ups(
+    recurrences: &[QRecurrence],
+    line_up: &[QLineUpDbData],
+    closures: &ClosureCalendar,
+    registry: &AoRegistry,
+    names: &HashMap<String, String>,
+    materialized: &HashSet<String>,
+    (from, to): (&NaiveDate, &NaiveDate),
+) -> Vec<(String, NewQLineUpDbEntry)> {
+    let mut taken = line_up
+        .iter()
+        .map(|item| (item.ao.to_string(), item.date))
+        .collect::<HashSet<(String, NaiveDate)>>();
+    let mut entries = Vec::<(String, NewQLineUpDbEntry)>::new();
+    for recurrence in recurrences.iter() {
+        let Some(name) = names.get(&recurrence.slack_id) else {
+            continue;
+        };
+        for date in recurrence.dates_between(from, to) {
+            let item_id = recurrence_item_id(&recurrence.id, &date);
+            if materialized.contains(&item_id)
+                || !closures.is_open(&recurrence.ao, &date, registry)
+                || !taken.insert((recurrence.ao.to_string(), date))
+            {
+                continue;
+            }
+            entries.push((
+                item_id,
+                NewQLineUpDbEntry::new(
+                    vec![name.to_string()],
+                    &recurrence.ao,
+                    &date,
+                    recurrence.ao.channel_id(registry),
+                ),
+            ));
+        }
+    }
+    entries
+}
+
+/// add recurring Qs to line up up to horizon days ahead. Returns number of sign ups added.
+pub async fn materialize_q_recurrences(
+    db_pool: &PgPool,
+    registry: &AoRegistry,
+    horizon_days: i64,
+) -> Result<u64, AppError> {
+    let from = local_boise_time().date_naive();
+    let to = from + Duration::days(horizon_days);
+    let recurrences = get_active_recurrences(db_pool, &from).await?;
+    if recurrences.is_empty() {
+        return Ok(0);
+    }
+    let item_ids = recurrences
+        .iter()
+        .flat_map(|recurrence| {
+            recurrence
+                .dates_between(&from, &to)
+                .into_iter()
+                .map(|date| {
+                    format!(
+                        "{}.{}",
+                        Q_RECURRENCE,
+                        recurrence_item_id(&recurrence.id, &date)
+                    )
+                })
+        })
+        .collect::<Vec<String>>();
+    let materialized = get_processed_items(db_pool, &item_ids)
+        .await?
+        .into_iter()
+        .map(|item| item.item_id)
+        .collect::<HashSet<String>>();
+    let line_up = get_q_line_up_between_dates(db_pool, &from, &to).await?;
+    let closures = get_closure_calendar(db_pool, &from, &to).await?;
+    let names = get_slack_id_map(db_pool).await?;
+
+    let (item_ids, entries): (Vec<String>, Vec<NewQLineUpDbEntry>) = recurrence_sign_ups(
+        &recurrences,
+        &line_up,
+        &closures,
+        registry,
+        &names,
+        &materialized,
+        (&from, &to),
+    )
+    .into_iter()
+    .unzip();
+    let added = save_list(db_pool, &entries).await?;
+    process_items(db_pool, &MaterializedRecurrences { item_ids }).await?;
+    Ok(added)
+}
+
+/// route for scheduler to add recurring Qs to line up
+pub async fn materialize_q_recurrences_route(
+    db_pool: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
+    web_state: web::Data<MutableWebState>,
+    query: web::Query<QRecurrenceQuery>,
+    req: HttpRequest,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    let horizon_days = query.horizon_days.unwrap_or(DEFAULT_HORIZON_DAYS);
+    match materialize_q_recurrences(&db_pool, &app_state.get_ao_registry(), horizon_days).await {
+        Ok(added) => HttpResponse::Ok().body(format!("Added {} recurring Qs", added)),
+        Err(err) => failure(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::ao_data::AO;
+    use crate::app_state::q_recurrence::RecurrenceRule;
+    use chrono::Weekday;
+
+    #[test]
+    fn sign_ups_skip_taken_dates() {
+        let date = |day: u32| NaiveDate::from_ymd_opt(2026, 11, day).unwrap();
+        let recurrence = |slack_id: &str| QRecurrence {
+            id: slack_id.to_string(),
+            slack_id: slack_id.to_string(),
+            ao: AO::Bleach,
+            rule: RecurrenceRule::EveryNWeeks {
+                weeks: 1,
+                weekday: Weekday::Mon,
+            },
+            start_date: date(1),
+            end_date: None,
+        };
+        let recurrences = vec![recurrence("U1"), recurrence("U2"), recurrence("U3")];
+        let line_up = vec![QLineUpDbData {
+            ao: AO::Bleach.to_string(),
+            qs: vec!["someone".to_string()],
+            date: date(9),
+            closed: false,
+        }];
+        let names = HashMap::from([
+            ("U1".to_string(), "stinger".to_string()),
+            ("U2".to_string(), "backslash".to_string()),
+        ]);
+        let materialized = HashSet::from([recurrence_item_id("U1", &date(23))]);
+        let entries = recurrence_sign_ups(
+            &recurrences,
+            &line_up,
+            &ClosureCalendar::default(),
+            &AoRegistry::default(),
+            &names,
+            &materialized,
+            (&date(1), &date(23)),
+        );
+        let dates = entries
+            .iter()
+            .map(|(_, entry)| (entry.date, entry.qs.as_str()))
+            .collect::<Vec<(NaiveDate, &str)>>();
+        assert_eq!(
+            dates,
+            vec![
+                (date(2), "stinger"),
+                (date(16), "stinger"),
+                (date(23), "backslash")
+            ]
+        );
+    }
+}
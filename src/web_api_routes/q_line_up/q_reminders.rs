@@ -0,0 +1,281 @@
+//! DM reminders to Qs signed up on the Q line up: some days before their Q,
+//! and the morning after if the backblast still hasn't been posted.
+
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::MutableAppState;
+use crate::db::queries::all_back_blasts::get_all_within_date_range;
+use crate::db::queries::ao_closures::get_closure_calendar;
+use crate::db::queries::processed_items::{get_processed_items, process_items};
+use crate::db::queries::q_line_up::{get_q_line_up_between_dates, QLineUpDbData};
+use crate::db::queries::users::get_user_name_map;
+use crate::shared::common_errors::AppError;
+use crate::shared::processed_type::{NewProcessItem, ResolvingProcessedItems};
+use crate::shared::responses::failure;
+use crate::shared::time::local_boise_time;
+use crate::slack_api::block_kit::BlockBuilder;
+use crate::web_api_routes::auth::internal_auth;
+use crate::web_api_routes::interactive_events::interaction_types::InteractionTypes;
+use crate::web_api_state::MutableWebState;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+/// processed item type of reminder before Q
+const Q_REMINDER: &str = "q-reminder";
+/// processed item type of missing backblast reminder after Q
+const Q_BB_REMINDER: &str = "q-bb-reminder";
+/// days before Q to remind, if not passed in
+const DEFAULT_DAYS_BEFORE: i64 = 2;
+
+#[derive(Deserialize)]
+pub struct QReminderQuery {
+    days_before: Option<i64>,
+}
+
+/// signed up Q to DM
+#[derive(Debug, PartialEq)]
+struct QReminder {
+    ao: AO,
+    date: NaiveDate,
+    slack_id: String,
+}
+
+impl QReminder {
+    fn item_id(&self) -> String {
+        format!("{}.{}.{}", self.date, self.ao, self.slack_id)
+    }
+
+    /// DM before Q, with where / when and button to write preblast
+    fn upcoming_message(&self, registry: &AoRegistry) -> BlockBuilder {
+        let start_time = self
+            .ao
+            .default_time(&self.date.weekday(), registry)
+            .map(|time| time.format("%-I:%M %p").to_string())
+            .unwrap_or_else(|| String::from("Unknown"));
+        let address = self
+            .ao
+            .address(registry)
+            .unwrap_or_else(|| String::from("Unknown"));
+        let mut block_builder = BlockBuilder::new()
+            .section_markdown("*Q Reminder*")
+            .section_markdown(&format!(
+                "You're signed up to Q <#{}> on {}",
+                self.ao.channel_id(registry),
+                self.date.format("%A %b %-d")
+            ))
+            .section_markdown(&format!("*Start time*: {}", start_time))
+            .section_markdown(&format!("*Address*: {}", address))
+            .section_markdown(&format!("*Map*: {}", self.ao.google_maps_link(registry)));
+        let write_btn = InteractionTypes::new_write_pre_blast(&self.date, &self.ao);
+        block_builder.add_btn(
+            "Write Preblast",
+            write_btn.to_string().as_str(),
+            "write-preblast",
+        );
+        block_builder
+    }
+
+    /// DM morning after Q when backblast is missing
+    fn missing_back_blast_message(&self, registry: &AoRegistry) -> BlockBuilder {
+        BlockBuilder::new()
+            .section_markdown("*Backblast Reminder*")
+            .section_markdown(&format!(
+                "Thanks for Q'ing <#{}> yesterday! Don't forget to post the backblast with `/backblast`.",
+                self.ao.channel_id(registry)
+            ))
+    }
+}
+
+/// reminders that were sent, to mark as processed
+struct SentReminders {
+    item_type: &'static str,
+    item_ids: Vec<String>,
+}
+
+impl ResolvingProcessedItems for SentReminders {
+    fn get_processed_items(&self) -> Vec<NewProcessItem> {
+        vec![NewProcessItem::new(self.item_type, self.item_ids.clone())]
+    }
+}
+
+/// reminders for each Q (with slack id) of open sign ups on date
+fn reminders_for_date(
+    sign_ups: &[QLineUpDbData],
+    date: &NaiveDate,
+    users: &HashMap<String, String>,
+    registry: &AoRegistry,
+) -> Vec<QReminder> {
+    sign_ups
+        .iter()
+        .filter(|sign_up| &sign_up.date == date && !sign_up.closed)
+        .flat_map(|sign_up| {
+            sign_up.qs.iter().filter_map(|q| {
+                users.get(&q.to_lowercase()).map(|slack_id| QReminder {
+                    ao: AO::from_name(&sign_up.ao, registry),
+                    date: sign_up.date,
+                    slack_id: slack_id.to_string(),
+                })
+            })
+        })
+        .collect()
+}
+
+/// DM reminders not already sent, marking the ones that went out as processed.
+async fn send_reminders(
+    db_pool: &PgPool,
+    web_state: &MutableWebState,
+    item_type: &'static str,
+    reminders: Vec<QReminder>,
+    message: impl Fn(&QReminder) -> BlockBuilder,
+) -> Result<usize, AppError> {
+    let item_ids = reminders
+        .iter()
+        .map(|reminder| format!("{}.{}", item_type, reminder.item_id()))
+        .collect::<Vec<String>>();
+    let processed = get_processed_items(db_pool, &item_ids)
+        .await?
+        .into_iter()
+        .map(|item| item.item_id)
+        .collect::<Vec<String>>();
+
+    let mut sent = SentReminders {
+        item_type,
+        item_ids: Vec::new(),
+    };
+    for reminder in reminders
+        .iter()
+        .filter(|reminder| !processed.contains(&reminder.item_id()))
+    {
+        match web_state
+            .send_direct_message(&reminder.slack_id, message(reminder))
+            .await
+        {
+            Ok(_) => sent.item_ids.push(reminder.item_id()),
+            Err(err) => println!("Error sending {}: {:?}", item_type, err),
+        }
+    }
+    process_items(db_pool, &sent).await?;
+    Ok(sent.item_ids.len())
+}
+
+/// remind Qs signed up `days_before` from today, and Qs from yesterday with no backblast posted.
+pub async fn remind_qs(
+    db_pool: &PgPool,
+    web_state: &MutableWebState,
+    registry: &AoRegistry,
+    days_before: i64,
+) -> Result<usize, AppError> {
+    let today = local_boise_time().date_naive();
+    let yesterday = today - Duration::days(1);
+    let upcoming = today + Duration::days(days_before);
+    let users = get_user_name_map(db_pool).await?;
+    let sign_ups = get_q_line_up_between_dates(db_pool, &yesterday, &upcoming).await?;
+    let closures = get_closure_calendar(db_pool, &yesterday, &upcoming).await?;
+
+    let upcoming_reminders = reminders_for_date(&sign_ups, &upcoming, &users, registry)
+        .into_iter()
+        .filter(|reminder| !closures.is_closed(&reminder.ao, &reminder.date))
+        .collect::<Vec<QReminder>>();
+
+    let posted = get_all_within_date_range(db_pool, &yesterday, &today).await?;
+    let missing_reminders = reminders_for_date(&sign_ups, &yesterday, &users, registry)
+        .into_iter()
+        .filter(|reminder| {
+            !closures.is_closed(&reminder.ao, &reminder.date)
+                && !posted
+                    .iter()
+                    .any(|bb| bb.ao == reminder.ao.to_string() && bb.date == reminder.date)
+        })
+        .collect::<Vec<QReminder>>();
+
+    let upcoming_sent = send_reminders(
+        db_pool,
+        web_state,
+        Q_REMINDER,
+        upcoming_reminders,
+        |reminder| reminder.upcoming_message(registry),
+    )
+    .await?;
+    let missing_sent = send_reminders(
+        db_pool,
+        web_state,
+        Q_BB_REMINDER,
+        missing_reminders,
+        |reminder| reminder.missing_back_blast_message(registry),
+    )
+    .await?;
+    Ok(upcoming_sent + missing_sent)
+}
+
+/// route for scheduler to DM Q reminders
+pub async fn remind_qs_route(
+    db_pool: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
+    web_state: web::Data<MutableWebState>,
+    query: web::Query<QReminderQuery>,
+    req: HttpRequest,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    let days_before = query.days_before.unwrap_or(DEFAULT_DAYS_BEFORE);
+    match remind_qs(
+        &db_pool,
+        &web_state,
+        &app_state.get_ao_registry(),
+        days_before,
+    )
+    .await
+    {
+        Ok(sent) => HttpResponse::Ok().body(format!("Sent {} Q reminders", sent)),
+        Err(err) => failure(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reminders_for_signed_up_qs() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 22).unwrap();
+        let sign_ups = vec![
+            QLineUpDbData {
+                ao: "bleach".to_string(),
+                qs: vec!["stinger".to_string(), "no slack".to_string()],
+                date,
+                closed: false,
+            },
+            QLineUpDbData {
+                ao: "bleach".to_string(),
+                qs: vec!["stinger".to_string()],
+                date: date.succ_opt().unwrap(),
+                closed: false,
+            },
+            QLineUpDbData {
+                ao: "gem".to_string(),
+                qs: vec!["closed".to_string()],
+                date,
+                closed: true,
+            },
+        ];
+        let users = HashMap::from([
+            ("stinger".to_string(), "U1".to_string()),
+            ("closed".to_string(), "U2".to_string()),
+        ]);
+        let reminders = reminders_for_date(&sign_ups, &date, &users, &AoRegistry::default());
+        assert_eq!(
+            reminders,
+            vec![QReminder {
+                ao: AO::Bleach,
+                date,
+                slack_id: "U1".to_string(),
+            }]
+        );
+        assert_eq!(reminders[0].item_id(), "2026-10-22.bleach.U1");
+    }
+}
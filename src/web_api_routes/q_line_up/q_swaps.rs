@@ -0,0 +1,24 @@
+//! History of Q swap requests (who asked for a sub, who was asked and who took it)
+
+use crate::db::queries::q_swaps::get_q_swaps_between_dates;
+use actix_web::{web, HttpResponse, Responder};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use sqlx::PgPool;
+
+#[derive(Deserialize)]
+pub struct QSwapsQuery {
+    start: NaiveDate,
+    end: NaiveDate,
+}
+
+/// swap requests for q line up dates within range
+pub async fn q_swaps_route(
+    db: web::Data<PgPool>,
+    query: web::Query<QSwapsQuery>,
+) -> impl Responder {
+    match get_q_swaps_between_dates(&db, &query.start, &query.end).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(err) => HttpResponse::BadRequest().body(err.to_string()),
+    }
+}
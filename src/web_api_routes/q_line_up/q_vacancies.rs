@@ -0,0 +1,163 @@
+//! Weekly "Qs needed" message to each AO channel with its open Q dates. Rows use the same
+//! action ids as the Q sheet, so "Take it" is handled (and updated in place) like a Q sheet sign up.
+
+use crate::app_state::ao_closures::ClosureCalendar;
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::MutableAppState;
+use crate::db::queries::ao_closures::get_closure_calendar;
+use crate::db::queries::q_line_up::{get_q_line_up_between_dates, QLineUpDbData};
+use crate::shared::common_errors::AppError;
+use crate::shared::constants::Q_LINE_UP_BTN_TEXT;
+use crate::shared::responses::failure;
+use crate::shared::string_utils::{format_q_empty_row, format_q_line_up_date};
+use crate::shared::time::local_boise_time;
+use crate::slack_api::block_kit::{BlockBuilder, SectionBlock};
+use crate::slack_api::chat::post_message::request::PostMessageRequest;
+use crate::web_api_routes::auth::internal_auth;
+use crate::web_api_routes::interactive_events::interaction_types::InteractionTypes;
+use crate::web_api_routes::slash_commands::q_line_up::get_message_data_text;
+use crate::web_api_state::MutableWebState;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use chrono::{Duration, NaiveDate};
+use sqlx::PgPool;
+
+/// how far ahead to look for open Q dates
+const DAYS_AHEAD: i64 = 14;
+
+/// dates after start (up to and including end) that AO meets, isn't closed and nobody signed up for
+fn open_q_dates(
+    ao: &AO,
+    start_date: &NaiveDate,
+    end_date: &NaiveDate,
+    line_up: &[QLineUpDbData],
+    closures: &ClosureCalendar,
+    registry: &AoRegistry,
+) -> Vec<NaiveDate> {
+    let ao_name = ao.to_string();
+    let mut dates = Vec::<NaiveDate>::new();
+    let mut date = *start_date + Duration::days(1);
+    while &date <= end_date {
+        let taken = line_up
+            .iter()
+            .any(|item| item.ao == ao_name && item.date == date);
+        if closures.is_open(ao, &date, registry) && !taken {
+            dates.push(date);
+        }
+        date += Duration::days(1);
+    }
+    dates
+}
+
+/// "Qs needed" message with a "Take it" button per open date
+fn vacancy_message(
+    ao: &AO,
+    dates: &[NaiveDate],
+    start_date: &NaiveDate,
+    end_date: &NaiveDate,
+) -> BlockBuilder {
+    let mut block_builder = BlockBuilder::new()
+        .header(format!(":rotating_light: Qs needed for {} :rotating_light:", ao).as_str())
+        .context("*Next 2 weeks*  |  Take a spot!")
+        .divider();
+
+    for date in dates.iter() {
+        if block_builder.reached_max() {
+            break;
+        }
+        let friendly_date = format_q_line_up_date(date);
+        let text = format_q_empty_row(friendly_date.as_str(), None);
+        let action_id = InteractionTypes::new_q_line_up(date, ao).to_string();
+        block_builder.add_section(SectionBlock::new_markdown_with_btn(
+            text.as_str(),
+            Q_LINE_UP_BTN_TEXT,
+            action_id.as_str(),
+        ));
+    }
+
+    // message data needs to stay last, for q line up interactions
+    block_builder.add_divider();
+    block_builder.add_section_markdown(
+        get_message_data_text(start_date, end_date, Some(ao.clone())).as_str(),
+    );
+    block_builder
+}
+
+/// post open Q dates of next 2 weeks to each AO channel that has any. Returns number of AOs posted to.
+pub async fn post_q_vacancies(
+    db_pool: &PgPool,
+    web_state: &MutableWebState,
+    registry: &AoRegistry,
+) -> Result<usize, AppError> {
+    let start_date = local_boise_time().date_naive();
+    let end_date = start_date + Duration::days(DAYS_AHEAD);
+    let line_up = get_q_line_up_between_dates(db_pool, &start_date, &end_date).await?;
+    let closures = get_closure_calendar(db_pool, &start_date, &end_date).await?;
+
+    let mut posted = 0;
+    for ao in registry.active_aos() {
+        let dates = open_q_dates(&ao, &start_date, &end_date, &line_up, &closures, registry);
+        if dates.is_empty() {
+            continue;
+        }
+        let message = vacancy_message(&ao, &dates, &start_date, &end_date);
+        let request = PostMessageRequest::new(ao.channel_id(registry), message.blocks);
+        match web_state.post_message(request).await {
+            Ok(_) => posted += 1,
+            Err(err) => println!("Error posting Q vacancies for {}: {:?}", ao, err),
+        }
+    }
+    Ok(posted)
+}
+
+/// route for scheduler to post open Q dates
+pub async fn post_q_vacancies_route(
+    db_pool: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
+    web_state: web::Data<MutableWebState>,
+    req: HttpRequest,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    match post_q_vacancies(&db_pool, &web_state, &app_state.get_ao_registry()).await {
+        Ok(posted) => HttpResponse::Ok().body(format!("Posted Q vacancies to {} AOs", posted)),
+        Err(err) => failure(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_dates_skip_taken() {
+        // sunday
+        let start = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let end = start + Duration::days(DAYS_AHEAD);
+        let line_up = vec![QLineUpDbData {
+            ao: AO::Bleach.to_string(),
+            qs: vec!["stinger".to_string()],
+            date: NaiveDate::from_ymd_opt(2026, 10, 21).unwrap(),
+            closed: false,
+        }];
+        let dates = open_q_dates(
+            &AO::Bleach,
+            &start,
+            &end,
+            &line_up,
+            &ClosureCalendar::default(),
+            &AoRegistry::default(),
+        );
+        let expected = [19, 24, 26, 28, 31]
+            .iter()
+            .map(|day| NaiveDate::from_ymd_opt(2026, 10, *day).unwrap())
+            .collect::<Vec<NaiveDate>>();
+        assert_eq!(dates, expected);
+
+        let message = vacancy_message(&AO::Bleach, &dates, &start, &end);
+        // header, context, divider, rows, divider, message data
+        assert_eq!(message.blocks.len(), dates.len() + 5);
+    }
+}
@@ -1,5 +1,7 @@
 use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
 use crate::app_state::backblast_data::BackBlastData;
+use crate::app_state::MutableAppState;
 use crate::db::queries::reactions_log::PreBlastReactionLogItem;
 use crate::db::queries::{all_back_blasts, reactions_log};
 use crate::db::save_reaction_log::ReactionLogDbItem;
@@ -71,11 +73,12 @@ impl PreBlastPaxReactionData {
         date: NaiveDate,
         log_items: Vec<PreBlastReactionLogItem>,
         bb_list: Vec<BackBlastData>,
+        registry: &AoRegistry,
     ) -> Self {
         let mut bds = HashMap::<AO, HashMap<String, PaxReactionLogData>>::new();
 
         for item in log_items.into_iter() {
-            let ao = AO::from_channel_id(item.channel_id.as_str());
+            let ao = AO::from_channel_id(item.channel_id.as_str(), registry);
             bds.entry(ao.clone())
                 .and_modify(|pax_map| {
                     pax_map
@@ -100,11 +103,11 @@ impl PreBlastPaxReactionData {
         PreBlastPaxReactionData { date, bds }
     }
 
-    pub fn full_summary(&self) -> String {
+    pub fn full_summary(&self, registry: &AoRegistry) -> String {
         let mut summary = format!("Summary for expected BDs on {}.\n\n", self.date);
 
         for (ao, pax_data) in self.bds.iter() {
-            summary.push_str(ao.friendly_name());
+            summary.push_str(&ao.friendly_name(registry));
             self.add_dashed_new_line(&mut summary);
             self.add_new_line(&mut summary);
 
@@ -231,8 +234,10 @@ impl From<&PreBlastReactionLogItem> for PaxReactionLogItem {
 /// route for compiling some reaction data on a preblast date.
 pub async fn pre_blast_reaction_data_route(
     db: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
     req: web::Query<PreBlastReactionReq>,
 ) -> impl Responder {
+    let registry = app_state.get_ao_registry();
     match reactions_log::get_pre_blast_reaction_data(
         &db,
         req.date,
@@ -249,9 +254,10 @@ pub async fn pre_blast_reaction_data_route(
                     .map(BackBlastData::from)
                     .collect();
 
-            let data = PreBlastPaxReactionData::new(req.date, results, related_back_blasts);
+            let data =
+                PreBlastPaxReactionData::new(req.date, results, related_back_blasts, &registry);
 
-            HttpResponse::Ok().body(data.full_summary())
+            HttpResponse::Ok().body(data.full_summary(&registry))
         }
         Err(err) => HttpResponse::BadRequest().body(err.to_string()),
     }
@@ -1,8 +1,10 @@
 //! generic ao meta data for FE to consume
-use crate::app_state::ao_data::const_names::AO_LIST;
 use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
 use crate::app_state::backblast_data::BackBlastData;
+use crate::app_state::MutableAppState;
 use crate::db::queries::all_back_blasts::get_all_within_date_range;
+use crate::db::queries::ao_closures::get_closure_calendar;
 use crate::shared::common_errors::AppError;
 use crate::shared::time::local_boise_time;
 use crate::web_api_routes::q_line_up::get_line_up_map;
@@ -14,64 +16,75 @@ use sqlx::PgPool;
 use std::collections::HashMap;
 
 /// route to get work out meta data
-pub async fn ao_list_meta_data_route(db: web::Data<PgPool>) -> impl Responder {
-    match ao_list_data(&db).await {
+pub async fn ao_list_meta_data_route(
+    db: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
+) -> impl Responder {
+    match ao_list_data(&db, &app_state.get_ao_registry()).await {
         Ok(results) => HttpResponse::Ok().json(results),
         Err(err) => HttpResponse::BadRequest().body(err.to_string()),
     }
 }
 
 /// fetches general ao data along with tomorrow's q and avg pax info.
-async fn ao_list_data(db: &PgPool) -> Result<[AoMetaData; 21], AppError> {
+async fn ao_list_data(db: &PgPool, registry: &AoRegistry) -> Result<Vec<AoMetaData>, AppError> {
     let recent = get_recent_back_blasts(db).await?;
     let now = local_boise_time().date_naive();
     let two_days_later = now + Duration::days(2);
     let tomorrow_qs = get_line_up_map(db, &now, &two_days_later).await?;
     let tomorrow = now + Duration::days(1);
     let tomorrow_week_day = tomorrow.weekday().to_string();
-    let results = AO_LIST.map(|ao| {
-        let mut meta_data = AoMetaData::from(&ao);
-        let filtered_bb = recent
-            .iter()
-            .filter_map(|bb| {
-                if bb.ao == ao {
-                    Some(bb.total_pax())
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<usize>>();
-        let len = filtered_bb.len();
-        if len != 0 {
-            let sum = filtered_bb.iter().sum::<usize>();
-            let avg_pax = (sum as f32) / len as f32;
-            let avg_pax = avg_pax.ceil() as usize;
-            meta_data.avg_pax_count = avg_pax;
-        }
-
-        if meta_data
-            .workout_dates
-            .contains_key(tomorrow_week_day.as_str())
-        {
-            let q = tomorrow_qs
-                .get(ao.to_string().as_str())
+    let closures = get_closure_calendar(db, &tomorrow, &tomorrow).await?;
+    let results = registry
+        .active_aos()
+        .into_iter()
+        .map(|ao| {
+            let mut meta_data = AoMetaData::new(&ao, registry);
+            let filtered_bb = recent
                 .iter()
-                .find_map(|q_list| {
-                    q_list.iter().find_map(|item| {
-                        if item.date == tomorrow {
-                            Some(item.qs.join(", "))
-                        } else {
-                            None
-                        }
-                    })
-                });
+                .filter_map(|bb| {
+                    if bb.ao == ao {
+                        Some(bb.total_pax())
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<usize>>();
+            let len = filtered_bb.len();
+            if len != 0 {
+                let sum = filtered_bb.iter().sum::<usize>();
+                let avg_pax = (sum as f32) / len as f32;
+                let avg_pax = avg_pax.ceil() as usize;
+                meta_data.avg_pax_count = avg_pax;
+            }
 
-            meta_data.tomorrows_q = q;
-            meta_data.is_tomorrow = true;
-        }
+            let meets_tomorrow = meta_data
+                .workout_dates
+                .contains_key(tomorrow_week_day.as_str());
+            let tomorrow_closure = closures.closure_for(&ao, &tomorrow);
+            if let (true, Some(closure)) = (meets_tomorrow, tomorrow_closure) {
+                meta_data.tomorrow_closed_reason = Some(closure.reason.to_string());
+            } else if meets_tomorrow {
+                let q = tomorrow_qs
+                    .get(ao.to_string().as_str())
+                    .iter()
+                    .find_map(|q_list| {
+                        q_list.iter().find_map(|item| {
+                            if item.date == tomorrow {
+                                Some(item.qs.join(", "))
+                            } else {
+                                None
+                            }
+                        })
+                    });
+
+                meta_data.tomorrows_q = q;
+                meta_data.is_tomorrow = true;
+            }
 
-        meta_data
-    });
+            meta_data
+        })
+        .collect();
     Ok(results)
 }
 
@@ -92,6 +105,8 @@ pub struct AoMetaData {
     name: String,
     tomorrows_q: Option<String>,
     is_tomorrow: bool,
+    /// reason ao is closed tomorrow (if on closure calendar)
+    tomorrow_closed_reason: Option<String>,
     address: Option<String>,
     map_location_url: Option<String>,
     avg_pax_count: usize,
@@ -99,21 +114,22 @@ pub struct AoMetaData {
     workout_dates: HashMap<String, (NaiveTime, NaiveTime)>,
 }
 
-impl From<&AO> for AoMetaData {
-    fn from(value: &AO) -> Self {
+impl AoMetaData {
+    fn new(value: &AO, registry: &AoRegistry) -> Self {
         AoMetaData {
             id: value.to_string(),
-            name: value.friendly_name().to_string(),
+            name: value.friendly_name(registry),
             is_tomorrow: false,
+            tomorrow_closed_reason: None,
             tomorrows_q: None,
-            address: value.address().map(|address| address.to_string()),
-            map_location_url: value.real_map_url().map(|url| url.to_string()),
+            address: value.address(registry),
+            map_location_url: value.real_map_url(registry),
             avg_pax_count: 0,
-            workout_type: value.ao_type().to_string(),
-            workout_dates: value.week_days().iter().fold(
+            workout_type: value.ao_type(registry).to_string(),
+            workout_dates: value.week_days(registry).iter().fold(
                 HashMap::<String, (NaiveTime, NaiveTime)>::new(),
                 |mut acc, day| {
-                    if let Some(times) = value.start_end_times(day) {
+                    if let Some(times) = value.start_end_times(day, registry) {
                         acc.insert(day.to_string(), times);
                     }
                     acc
@@ -0,0 +1,141 @@
+//! admin routes for managing AOs in the ao_list registry.
+use crate::app_state::ao_registry::AoRegistryEntry;
+use crate::app_state::MutableAppState;
+use crate::db::insert_ao::{create_ao_record, retire_ao_record, update_ao_record};
+use crate::db::queries::ao_list::{get_ao_registry_entries, get_ao_registry_entry};
+use crate::shared::common_errors::AppError;
+use crate::shared::responses::{failure, success};
+use crate::web_api_routes::auth::internal_auth;
+use crate::web_api_state::MutableWebState;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+#[derive(Deserialize)]
+pub struct AoRouteData {
+    /// slug name of ao
+    name: String,
+}
+
+/// route to get full list of aos in registry (including retired)
+pub async fn get_ao_registry_route(db: web::Data<PgPool>) -> impl Responder {
+    match get_ao_registry_entries(&db).await {
+        Ok(entries) => HttpResponse::Ok().json(entries),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to get single ao in registry
+pub async fn get_single_ao_route(
+    db: web::Data<PgPool>,
+    path: web::Path<AoRouteData>,
+) -> impl Responder {
+    match get_ao_registry_entry(&db, path.name.as_str()).await {
+        Ok(Some(entry)) => HttpResponse::Ok().json(entry),
+        Ok(None) => HttpResponse::NotFound().body("AO not found"),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to launch new ao
+pub async fn create_ao_route(
+    db: web::Data<PgPool>,
+    web_state: web::Data<MutableWebState>,
+    app_state: web::Data<MutableAppState>,
+    req: HttpRequest,
+    body: web::Json<AoRegistryEntry>,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    match create_ao(&db, &app_state, body.into_inner()).await {
+        Ok(_) => success(),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to update existing ao (schedule, times, type, location etc).
+pub async fn update_ao_route(
+    db: web::Data<PgPool>,
+    web_state: web::Data<MutableWebState>,
+    app_state: web::Data<MutableAppState>,
+    req: HttpRequest,
+    path: web::Path<AoRouteData>,
+    body: web::Json<AoRegistryEntry>,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    match update_ao(&db, &app_state, path.name.as_str(), body.into_inner()).await {
+        Ok(_) => success(),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to retire ao. AO stays in db for history but is no longer active.
+pub async fn retire_ao_route(
+    db: web::Data<PgPool>,
+    web_state: web::Data<MutableWebState>,
+    app_state: web::Data<MutableAppState>,
+    req: HttpRequest,
+    path: web::Path<AoRouteData>,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    match retire_ao(&db, &app_state, path.name.as_str()).await {
+        Ok(_) => success(),
+        Err(err) => failure(err),
+    }
+}
+
+async fn create_ao(
+    db: &PgPool,
+    app_state: &MutableAppState,
+    entry: AoRegistryEntry,
+) -> Result<(), AppError> {
+    entry.validate()?;
+    let existing = get_ao_registry_entries(db).await?;
+    if existing
+        .iter()
+        .any(|item| item.name == entry.name || item.channel_id == entry.channel_id)
+    {
+        return Err(AppError::from("AO with name or channel already exists"));
+    }
+
+    create_ao_record(db, &entry).await?;
+    app_state.load_ao_registry(db).await?;
+    Ok(())
+}
+
+async fn update_ao(
+    db: &PgPool,
+    app_state: &MutableAppState,
+    name: &str,
+    mut entry: AoRegistryEntry,
+) -> Result<(), AppError> {
+    let existing = get_ao_registry_entry(db, name)
+        .await?
+        .ok_or_else(|| AppError::from("AO not found"))?;
+    // name and channel id identify ao, so can't be changed here.
+    entry.name = existing.name;
+    entry.channel_id = existing.channel_id;
+    entry.validate()?;
+
+    update_ao_record(db, &entry).await?;
+    app_state.load_ao_registry(db).await?;
+    Ok(())
+}
+
+async fn retire_ao(db: &PgPool, app_state: &MutableAppState, name: &str) -> Result<(), AppError> {
+    if get_ao_registry_entry(db, name).await?.is_none() {
+        return Err(AppError::from("AO not found"));
+    }
+
+    retire_ao_record(db, name).await?;
+    app_state.load_ao_registry(db).await?;
+    Ok(())
+}
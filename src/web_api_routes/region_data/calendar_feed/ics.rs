@@ -0,0 +1,178 @@
+//! minimal iCalendar (RFC 5545) writer for calendar feeds.
+use chrono::{DateTime, Utc};
+
+const LINE_BREAK: &str = "\r\n";
+/// max octets per content line before folding
+const MAX_LINE_LENGTH: usize = 75;
+
+#[derive(Debug, PartialEq)]
+pub enum EventStatus {
+    Confirmed,
+    Cancelled,
+}
+
+impl EventStatus {
+    fn as_str(&self) -> &str {
+        match self {
+            EventStatus::Confirmed => "CONFIRMED",
+            EventStatus::Cancelled => "CANCELLED",
+        }
+    }
+}
+
+/// single calendar event
+#[derive(Debug)]
+pub struct ICalEvent {
+    /// stable id so calendar apps update existing events
+    pub uid: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub summary: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub url: Option<String>,
+    pub status: EventStatus,
+}
+
+/// full calendar feed
+#[derive(Debug)]
+pub struct ICalendar {
+    pub name: String,
+    pub events: Vec<ICalEvent>,
+}
+
+impl ICalendar {
+    pub fn new(name: &str, events: Vec<ICalEvent>) -> Self {
+        ICalendar {
+            name: name.to_string(),
+            events,
+        }
+    }
+
+    /// serialize calendar to ics text
+    pub fn to_ics(&self, stamp: &DateTime<Utc>) -> String {
+        let mut lines = vec![
+            String::from("BEGIN:VCALENDAR"),
+            String::from("VERSION:2.0"),
+            String::from("PRODID:-//F3 Boise//Q Line Up//EN"),
+            String::from("CALSCALE:GREGORIAN"),
+            String::from("METHOD:PUBLISH"),
+            format!("X-WR-CALNAME:{}", escape_text(&self.name)),
+            String::from("X-WR-TIMEZONE:America/Boise"),
+        ];
+
+        for event in self.events.iter() {
+            lines.push(String::from("BEGIN:VEVENT"));
+            lines.push(format!("UID:{}", event.uid));
+            lines.push(format!("DTSTAMP:{}", format_date_time(stamp)));
+            lines.push(format!("DTSTART:{}", format_date_time(&event.start)));
+            lines.push(format!("DTEND:{}", format_date_time(&event.end)));
+            lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+            if let Some(description) = &event.description {
+                lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+            }
+            if let Some(location) = &event.location {
+                lines.push(format!("LOCATION:{}", escape_text(location)));
+            }
+            if let Some(url) = &event.url {
+                lines.push(format!("URL:{}", url));
+            }
+            lines.push(format!("STATUS:{}", event.status.as_str()));
+            // bump sequence for cancelled events so clients apply the change
+            let sequence = match event.status {
+                EventStatus::Confirmed => 0,
+                EventStatus::Cancelled => 1,
+            };
+            lines.push(format!("SEQUENCE:{}", sequence));
+            lines.push(String::from("END:VEVENT"));
+        }
+
+        lines.push(String::from("END:VCALENDAR"));
+
+        lines
+            .iter()
+            .map(|line| fold_line(line))
+            .collect::<Vec<String>>()
+            .join(LINE_BREAK)
+            + LINE_BREAK
+    }
+}
+
+fn format_date_time(date_time: &DateTime<Utc>) -> String {
+    date_time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// escape special characters in text values
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// fold content lines longer than 75 octets (continuation lines start with a space)
+fn fold_line(line: &str) -> String {
+    let mut result = String::new();
+    let mut current_length = 0;
+    for ch in line.chars() {
+        let ch_length = ch.len_utf8();
+        if current_length + ch_length > MAX_LINE_LENGTH {
+            result.push_str(LINE_BREAK);
+            result.push(' ');
+            current_length = 1;
+        }
+        result.push(ch);
+        current_length += ch_length;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_text() {
+        assert_eq!(
+            escape_text("Bleach; Q: backslash, stinger\nbring coupons"),
+            "Bleach\\; Q: backslash\\, stinger\\nbring coupons"
+        );
+    }
+
+    #[test]
+    fn folds_long_lines() {
+        let line = format!("DESCRIPTION:{}", "a".repeat(100));
+        let folded = fold_line(&line);
+        let lines = folded.split(LINE_BREAK).collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].len(), MAX_LINE_LENGTH);
+        assert!(lines[1].starts_with(' '));
+    }
+
+    #[test]
+    fn cancelled_event_output() {
+        let start = DateTime::parse_from_rfc3339("2024-07-04T11:15:00Z")
+            .unwrap()
+            .to_utc();
+        let calendar = ICalendar::new(
+            "Bleach",
+            vec![ICalEvent {
+                uid: String::from("bleach-20240704@f3boise"),
+                start,
+                end: start + chrono::Duration::minutes(45),
+                summary: String::from("Bleach - CLOSED"),
+                description: None,
+                location: None,
+                url: None,
+                status: EventStatus::Cancelled,
+            }],
+        );
+        let ics = calendar.to_ics(&start);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.contains("DTSTART:20240704T111500Z\r\n"));
+        assert!(ics.contains("DTEND:20240704T120000Z\r\n"));
+        assert!(ics.contains("STATUS:CANCELLED\r\nSEQUENCE:1\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    }
+}
@@ -0,0 +1,292 @@
+//! iCalendar feeds of AO schedules and the Q line-up.
+use crate::app_state::ao_closures::ClosureCalendar;
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::MutableAppState;
+use crate::db::queries::ao_closures::get_closure_calendar;
+use crate::db::queries::q_line_up::QLineUpDbData;
+use crate::shared::common_errors::AppError;
+use crate::shared::time::{boise_datetime, local_boise_time};
+use crate::web_api_routes::q_line_up::get_line_up_map;
+use actix_web::{web, HttpResponse, Responder};
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use ics::{EventStatus, ICalEvent, ICalendar};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+pub mod ics;
+
+/// how far back feed goes by default
+const DAYS_BEFORE: i64 = 14;
+/// how far ahead feed goes by default
+const DAYS_AFTER: i64 = 60;
+
+#[derive(Deserialize)]
+pub struct CalendarFeedQuery {
+    /// feed for single ao
+    ao: Option<String>,
+    /// personal feed of days pax is signed up to Q
+    pax: Option<String>,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+}
+
+/// which feed to generate
+#[derive(Debug, PartialEq)]
+enum CalendarFeed {
+    Region,
+    Ao(AO),
+    Pax(String),
+}
+
+impl CalendarFeed {
+    fn name(&self, registry: &AoRegistry) -> String {
+        match self {
+            CalendarFeed::Region => String::from("F3 Boise"),
+            CalendarFeed::Ao(ao) => format!("F3 Boise - {}", ao.friendly_name(registry)),
+            CalendarFeed::Pax(name) => format!("F3 Boise - {} Qs", name),
+        }
+    }
+
+    fn aos(&self, registry: &AoRegistry) -> Vec<AO> {
+        match self {
+            CalendarFeed::Ao(ao) => vec![ao.clone()],
+            _ => registry.active_aos(),
+        }
+    }
+}
+
+/// route for ics calendar feed (region-wide, per ao or per pax)
+pub async fn calendar_feed_route(
+    db: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
+    query: web::Query<CalendarFeedQuery>,
+) -> impl Responder {
+    match get_calendar_feed(&db, &app_state.get_ao_registry(), &query).await {
+        Ok(calendar) => HttpResponse::Ok()
+            .content_type("text/calendar; charset=utf-8")
+            .body(calendar),
+        Err(err) => HttpResponse::BadRequest().body(err.to_string()),
+    }
+}
+
+async fn get_calendar_feed(
+    db: &PgPool,
+    registry: &AoRegistry,
+    query: &CalendarFeedQuery,
+) -> Result<String, AppError> {
+    let feed = validate_query(query, registry)?;
+    let today = local_boise_time().date_naive();
+    let start = query.start.unwrap_or(today - Duration::days(DAYS_BEFORE));
+    let end = query.end.unwrap_or(today + Duration::days(DAYS_AFTER));
+    let diff_days = end.signed_duration_since(start).num_days();
+    if !(0..=365).contains(&diff_days) {
+        return Err(AppError::from("Date range cannot be more than a year."));
+    }
+
+    let line_up = get_line_up_map(db, &start, &end).await?;
+    let closures = get_closure_calendar(db, &start, &end).await?;
+    let events = build_events(&feed, &start, &end, &line_up, &closures, registry);
+    let calendar = ICalendar::new(feed.name(registry).as_str(), events);
+    Ok(calendar.to_ics(&Utc::now()))
+}
+
+fn validate_query(
+    query: &CalendarFeedQuery,
+    registry: &AoRegistry,
+) -> Result<CalendarFeed, AppError> {
+    match (&query.ao, &query.pax) {
+        (Some(_), Some(_)) => Err(AppError::from("Choose either ao or pax feed")),
+        (Some(ao), None) => match AO::from_name(ao, registry) {
+            AO::Unknown(_) | AO::DR => Err(AppError::from("Unknown AO")),
+            ao => Ok(CalendarFeed::Ao(ao)),
+        },
+        (None, Some(pax)) => Ok(CalendarFeed::Pax(pax.trim().to_lowercase())),
+        (None, None) => Ok(CalendarFeed::Region),
+    }
+}
+
+/// build events for each ao workout within date range (inclusive)
+fn build_events(
+    feed: &CalendarFeed,
+    start: &NaiveDate,
+    end: &NaiveDate,
+    line_up: &HashMap<String, Vec<QLineUpDbData>>,
+    closures: &ClosureCalendar,
+    registry: &AoRegistry,
+) -> Vec<ICalEvent> {
+    let aos = feed.aos(registry);
+    let mut events = Vec::<ICalEvent>::new();
+    let mut date = *start;
+    while &date <= end {
+        for ao in aos.iter() {
+            if !ao.week_days(registry).contains(&date.weekday()) {
+                continue;
+            }
+
+            let entry = line_up
+                .get(ao.to_string().as_str())
+                .and_then(|list| list.iter().find(|item| item.date == date));
+
+            if let CalendarFeed::Pax(name) = feed {
+                let is_q = entry
+                    .map(|item| item.qs.iter().any(|q| q.trim() == name.as_str()))
+                    .unwrap_or(false);
+                if !is_q {
+                    continue;
+                }
+            }
+
+            if let Some(event) = build_event(ao, &date, entry, closures, registry) {
+                events.push(event);
+            }
+        }
+        date += Duration::days(1);
+    }
+    events
+}
+
+fn build_event(
+    ao: &AO,
+    date: &NaiveDate,
+    entry: Option<&QLineUpDbData>,
+    closures: &ClosureCalendar,
+    registry: &AoRegistry,
+) -> Option<ICalEvent> {
+    let (start_time, end_time) = ao.start_end_times(&date.weekday(), registry)?;
+    let friendly_name = ao.friendly_name(registry);
+    let closure = closures.closure_for(ao, date);
+    let closed = entry.map(|item| item.closed).unwrap_or(false) || closure.is_some();
+
+    let (summary, description, status) = if closed {
+        let reason = closure.map(|closure| closure.reason.to_string());
+        (
+            format!("{} - CLOSED", friendly_name),
+            reason,
+            EventStatus::Cancelled,
+        )
+    } else {
+        let qs = entry
+            .map(|item| item.qs.join(", "))
+            .filter(|qs| !qs.is_empty());
+        let summary = match qs {
+            Some(qs) => format!("{} - Q: {}", friendly_name, qs),
+            None => format!("{} - Q needed", friendly_name),
+        };
+        (
+            summary,
+            Some(ao.ao_type(registry).to_string()),
+            EventStatus::Confirmed,
+        )
+    };
+
+    Some(ICalEvent {
+        uid: format!("{}-{}@f3boise", ao, date.format("%Y%m%d")),
+        start: boise_datetime(date.and_time(start_time)).to_utc(),
+        end: boise_datetime(date.and_time(end_time)).to_utc(),
+        summary,
+        description,
+        location: ao.address(registry),
+        url: ao.real_map_url(registry),
+        status,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::ao_closures::AoClosure;
+
+    fn line_up(
+        date: NaiveDate,
+        qs: Vec<&str>,
+        closed: bool,
+    ) -> HashMap<String, Vec<QLineUpDbData>> {
+        HashMap::from([(
+            AO::Bleach.to_string(),
+            vec![QLineUpDbData {
+                ao: AO::Bleach.to_string(),
+                qs: qs.into_iter().map(|q| q.to_string()).collect(),
+                date,
+                closed,
+            }],
+        )])
+    }
+
+    #[test]
+    fn ao_feed_names_q() {
+        // monday
+        let date = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+        let events = build_events(
+            &CalendarFeed::Ao(AO::Bleach),
+            &date,
+            &date,
+            &line_up(date, vec!["backslash"], false),
+            &ClosureCalendar::default(),
+            &AoRegistry::default(),
+        );
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].summary, "Bleach - Q: backslash");
+        assert_eq!(events[0].status, EventStatus::Confirmed);
+        assert_eq!(events[0].uid, "bleach-20240701@f3boise");
+    }
+
+    #[test]
+    fn closed_days_are_cancelled() {
+        let date = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+        let events = build_events(
+            &CalendarFeed::Ao(AO::Bleach),
+            &date,
+            &date,
+            &line_up(date, vec!["closed"], true),
+            &ClosureCalendar::default(),
+            &AoRegistry::default(),
+        );
+        assert_eq!(events[0].status, EventStatus::Cancelled);
+
+        let closures = ClosureCalendar::new(vec![AoClosure {
+            id: String::from("1"),
+            ao: None,
+            start_date: date,
+            end_date: date,
+            recurring: false,
+            reason: String::from("Smoke"),
+        }]);
+        let events = build_events(
+            &CalendarFeed::Ao(AO::Bleach),
+            &date,
+            &date,
+            &HashMap::new(),
+            &closures,
+            &AoRegistry::default(),
+        );
+        assert_eq!(events[0].status, EventStatus::Cancelled);
+        assert_eq!(events[0].description, Some(String::from("Smoke")));
+    }
+
+    #[test]
+    fn pax_feed_only_has_their_qs() {
+        let date = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+        let list = line_up(date, vec!["backslash"], false);
+        let events = build_events(
+            &CalendarFeed::Pax(String::from("backslash")),
+            &date,
+            &date,
+            &list,
+            &ClosureCalendar::default(),
+            &AoRegistry::default(),
+        );
+        assert_eq!(events.len(), 1);
+
+        let events = build_events(
+            &CalendarFeed::Pax(String::from("stinger")),
+            &date,
+            &date,
+            &list,
+            &ClosureCalendar::default(),
+            &AoRegistry::default(),
+        );
+        assert!(events.is_empty());
+    }
+}
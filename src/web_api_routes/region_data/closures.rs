@@ -0,0 +1,43 @@
+//! routes for the ao closure calendar.
+use crate::db::queries::ao_closures::get_closures_between_dates;
+use crate::db::save_ao_closure::delete_closure;
+use crate::shared::responses::{failure, success};
+use crate::shared::time::DateRange;
+use crate::web_api_routes::auth::internal_auth;
+use crate::web_api_state::MutableWebState;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+#[derive(Deserialize)]
+pub struct ClosureRouteData {
+    id: String,
+}
+
+/// route to get closures within date range
+pub async fn get_closures_route(
+    db: web::Data<PgPool>,
+    query: web::Query<DateRange>,
+) -> impl Responder {
+    match get_closures_between_dates(&db, &query.start, &query.end).await {
+        Ok(closures) => HttpResponse::Ok().json(closures),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to remove closure from calendar
+pub async fn delete_closure_route(
+    db: web::Data<PgPool>,
+    web_state: web::Data<MutableWebState>,
+    req: HttpRequest,
+    path: web::Path<ClosureRouteData>,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    match delete_closure(&db, path.id.as_str()).await {
+        Ok(_) => success(),
+        Err(err) => failure(err),
+    }
+}
@@ -0,0 +1,105 @@
+//! routes for the exicon (exercise dictionary) served to the website.
+use crate::app_state::exicon::{search_exicon, ExiconEntry};
+use crate::db::queries::exercises::get_exicon_entries;
+use crate::db::save_exercises::import_exicon;
+use crate::shared::common_errors::AppError;
+use crate::shared::responses::failure;
+use crate::web_api_routes::auth::internal_auth;
+use crate::web_api_state::MutableWebState;
+use actix_web::web::Bytes;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::io::Read;
+
+#[derive(Deserialize)]
+pub struct ExiconQuery {
+    /// optional search term
+    pub q: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ExiconImportResponse {
+    pub imported: usize,
+}
+
+/// csv row of exicon import. Aliases and tags are comma separated.
+#[derive(Deserialize, Debug)]
+struct ExiconCSVItem {
+    pub name: String,
+    pub aliases: Option<String>,
+    pub description: String,
+    pub category: Option<String>,
+    pub tags: Option<String>,
+}
+
+impl From<ExiconCSVItem> for ExiconEntry {
+    fn from(value: ExiconCSVItem) -> Self {
+        ExiconEntry::new(&value.name, &value.description)
+            .with_aliases(value.aliases.as_deref().unwrap_or_default())
+            .with_category(value.category)
+            .with_tags(value.tags.as_deref().unwrap_or_default())
+    }
+}
+
+fn extract_exicon_entries<R: Read>(mut rdr: csv::Reader<R>) -> Result<Vec<ExiconEntry>, AppError> {
+    let mut results: Vec<ExiconEntry> = vec![];
+    for record in rdr.deserialize() {
+        let record: ExiconCSVItem = record?;
+        results.push(ExiconEntry::from(record));
+    }
+    Ok(results)
+}
+
+/// route to get exicon, optionally searched by `q`
+pub async fn get_exicon_route(
+    db: web::Data<PgPool>,
+    query: web::Query<ExiconQuery>,
+) -> impl Responder {
+    match get_exicon_entries(&db).await {
+        Ok(entries) => match query.q.as_deref() {
+            Some(term) => HttpResponse::Ok().json(search_exicon(&entries, term)),
+            None => HttpResponse::Ok().json(entries),
+        },
+        Err(err) => failure(err),
+    }
+}
+
+/// route to seed / update exicon from csv body (name, aliases, description, category, tags)
+pub async fn import_exicon_route(
+    db: web::Data<PgPool>,
+    web_state: web::Data<MutableWebState>,
+    req: HttpRequest,
+    body: Bytes,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    let rdr = csv::ReaderBuilder::new().from_reader(body.as_ref());
+    let result = match extract_exicon_entries(rdr) {
+        Ok(entries) => import_exicon(&db, &entries).await,
+        Err(err) => Err(err),
+    };
+    match result {
+        Ok(imported) => HttpResponse::Ok().json(ExiconImportResponse { imported }),
+        Err(err) => failure(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_csv() {
+        let csv = "name,aliases,description,category,tags\nMerkin,\"pushup, push-up\",A pushup.,Upper body,chest\nSSH,,Jumping jack.,,\n";
+        let rdr = csv::ReaderBuilder::new().from_reader(csv.as_bytes());
+        let entries = extract_exicon_entries(rdr).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].aliases, vec!["pushup", "push-up"]);
+        assert_eq!(entries[0].category, Some(String::from("Upper body")));
+        assert_eq!(entries[1].category, None);
+        assert!(entries[1].tags.is_empty());
+    }
+}
@@ -0,0 +1,54 @@
+//! routes for managing emojis counted as HC on preblasts of AOs.
+use crate::app_state::ao_data::AO;
+use crate::app_state::hard_commits::hc_emoji_list;
+use crate::app_state::MutableAppState;
+use crate::db::insert_ao::update_ao_hc_emojis;
+use crate::db::queries::ao_list::get_hc_emojis;
+use crate::shared::responses::{failure, success};
+use crate::web_api_routes::auth::internal_auth;
+use crate::web_api_state::MutableWebState;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+#[derive(Deserialize)]
+pub struct AoRouteData {
+    /// slug name of ao
+    name: String,
+}
+
+/// route to get HC emojis of ao
+pub async fn get_hc_emojis_route(
+    db: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
+    path: web::Path<AoRouteData>,
+) -> impl Responder {
+    let registry = app_state.get_ao_registry();
+    let ao = AO::from_name(&path.into_inner().name, &registry);
+    match get_hc_emojis(&db, ao.channel_id(&registry)).await {
+        Ok(emojis) => HttpResponse::Ok().json(emojis),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to set HC emojis of ao (ie. `["hc", "muscle"]`). Empty list resets to default.
+pub async fn update_hc_emojis_route(
+    db: web::Data<PgPool>,
+    web_state: web::Data<MutableWebState>,
+    req: HttpRequest,
+    path: web::Path<AoRouteData>,
+    body: web::Json<Vec<String>>,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    let name = path.into_inner().name;
+    if let AO::Unknown(_) = AO::from(name.to_string()) {
+        return HttpResponse::NotFound().body("AO not found");
+    }
+    match update_ao_hc_emojis(&db, &name, &hc_emoji_list(&body)).await {
+        Ok(_) => success(),
+        Err(err) => failure(err),
+    }
+}
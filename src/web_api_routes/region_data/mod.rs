@@ -1 +1,9 @@
 pub mod ao_meta_data;
+pub mod ao_registry;
+pub mod calendar_feed;
+pub mod closures;
+pub mod exicon;
+pub mod hc_emojis;
+pub mod q_rules;
+pub mod roles;
+pub mod scoring;
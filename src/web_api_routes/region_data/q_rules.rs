@@ -0,0 +1,55 @@
+//! routes for managing Q line up sign up rules of AOs.
+use crate::app_state::ao_data::AO;
+use crate::app_state::q_line_up_rules::QLineUpRules;
+use crate::app_state::MutableAppState;
+use crate::db::insert_ao::update_ao_q_rules;
+use crate::db::queries::ao_list::get_q_line_up_rules;
+use crate::shared::responses::{failure, success};
+use crate::web_api_routes::auth::internal_auth;
+use crate::web_api_state::MutableWebState;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+#[derive(Deserialize)]
+pub struct AoRouteData {
+    /// slug name of ao
+    name: String,
+}
+
+/// route to get Q line up rules of ao
+pub async fn get_q_rules_route(
+    db: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
+    path: web::Path<AoRouteData>,
+) -> impl Responder {
+    let registry = app_state.get_ao_registry();
+    let ao = AO::from_name(&path.into_inner().name, &registry);
+    match get_q_line_up_rules(&db, ao.channel_id(&registry)).await {
+        Ok(rules) => HttpResponse::Ok().json(rules),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to set Q line up rules of ao (ie. `{"max_per_month": 2, "new_q_priority_days": 14}`).
+/// Fields left out use defaults.
+pub async fn update_q_rules_route(
+    db: web::Data<PgPool>,
+    web_state: web::Data<MutableWebState>,
+    req: HttpRequest,
+    path: web::Path<AoRouteData>,
+    body: web::Json<QLineUpRules>,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    let name = path.into_inner().name;
+    if let AO::Unknown(_) = AO::from(name.to_string()) {
+        return HttpResponse::NotFound().body("AO not found");
+    }
+    match update_ao_q_rules(&db, &name, &body).await {
+        Ok(_) => success(),
+        Err(err) => failure(err),
+    }
+}
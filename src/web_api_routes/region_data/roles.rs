@@ -0,0 +1,84 @@
+//! routes for managing roles of slack users.
+use crate::app_state::ao_data::AO;
+use crate::app_state::roles::Role;
+use crate::app_state::MutableAppState;
+use crate::db::queries::user_roles::get_all_user_roles;
+use crate::db::save_user_role::{add_user_role, delete_user_role};
+use crate::shared::responses::{failure, success};
+use crate::web_api_routes::auth::internal_auth;
+use crate::web_api_state::MutableWebState;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+#[derive(Deserialize)]
+pub struct RoleRouteData {
+    id: String,
+}
+
+/// role to give slack user (ie. `{"slack_id": "U123", "role": "site_q", "ao": "gem"}`)
+#[derive(Deserialize)]
+pub struct NewRoleRequest {
+    slack_id: String,
+    /// region_admin, site_q, comz or pax
+    role: String,
+    /// slug name of ao for site Qs
+    ao: Option<String>,
+}
+
+/// route to get all saved roles
+pub async fn get_roles_route(
+    db: web::Data<PgPool>,
+    web_state: web::Data<MutableWebState>,
+    req: HttpRequest,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    match get_all_user_roles(&db).await {
+        Ok(roles) => HttpResponse::Ok().json(roles),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to give slack user a role
+pub async fn add_role_route(
+    db: web::Data<PgPool>,
+    web_state: web::Data<MutableWebState>,
+    app_state: web::Data<MutableAppState>,
+    req: HttpRequest,
+    body: web::Json<NewRoleRequest>,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    let registry = app_state.get_ao_registry();
+    let ao = body.ao.as_ref().map(|ao| AO::from_name(ao, &registry));
+    let role = match Role::from_parts(&body.role, ao) {
+        Ok(role) => role,
+        Err(err) => return HttpResponse::BadRequest().body(err),
+    };
+    match add_user_role(&db, &body.slack_id, &role, &registry).await {
+        Ok(_) => success(),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to take role away from slack user
+pub async fn delete_role_route(
+    db: web::Data<PgPool>,
+    web_state: web::Data<MutableWebState>,
+    req: HttpRequest,
+    path: web::Path<RoleRouteData>,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    match delete_user_role(&db, path.id.as_str()).await {
+        Ok(_) => success(),
+        Err(err) => failure(err),
+    }
+}
@@ -0,0 +1,56 @@
+//! routes for managing scoring formula of AOs.
+use crate::app_state::ao_data::AO;
+use crate::app_state::scoring::ScoringFormula;
+use crate::app_state::MutableAppState;
+use crate::db::queries::scoring::get_scoring_formula;
+use crate::db::save_scoring::save_scoring_formula;
+use crate::shared::responses::{failure, success};
+use crate::web_api_routes::auth::internal_auth;
+use crate::web_api_state::MutableWebState;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+#[derive(Deserialize)]
+pub struct AoRouteData {
+    /// slug name of ao
+    name: String,
+}
+
+/// route to get scoring formula of ao
+pub async fn get_scoring_formula_route(
+    db: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
+    path: web::Path<AoRouteData>,
+) -> impl Responder {
+    let registry = app_state.get_ao_registry();
+    let ao = AO::from_name(&path.into_inner().name, &registry);
+    match get_scoring_formula(&db, ao.channel_id(&registry)).await {
+        Ok(Some(formula)) => HttpResponse::Ok().json(formula),
+        Ok(None) => HttpResponse::NotFound().body("No scoring formula for AO"),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to set scoring formula of ao (fields and formula are validated)
+pub async fn update_scoring_formula_route(
+    db: web::Data<PgPool>,
+    (web_state, app_state): (web::Data<MutableWebState>, web::Data<MutableAppState>),
+    req: HttpRequest,
+    path: web::Path<AoRouteData>,
+    body: web::Json<ScoringFormula>,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    let registry = app_state.get_ao_registry();
+    let ao = AO::from_name(&path.into_inner().name, &registry);
+    if let AO::Unknown(_) = ao {
+        return HttpResponse::NotFound().body("AO not found");
+    }
+    match save_scoring_formula(&db, ao.channel_id(&registry), &body).await {
+        Ok(_) => success(),
+        Err(err) => failure(err),
+    }
+}
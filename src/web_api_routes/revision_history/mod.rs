@@ -0,0 +1,261 @@
+//! routes for revision history of backblasts and preblasts (history, diff and rollback).
+use crate::app_state::revisions::{
+    diff_snapshots, FieldDiff, RevisionActor, RevisionEntity, RevisionSource,
+};
+use crate::app_state::MutableAppState;
+use crate::db::queries::revisions::{get_revisions, RevisionDbData};
+use crate::db::save_revision::{rollback_back_blast, rollback_pre_blast};
+use crate::shared::common_errors::AppError;
+use crate::shared::responses::{failure, success};
+use crate::web_api_routes::auth::internal_auth;
+use crate::web_api_routes::interactive_events::edit_backblast::update_posted_message;
+use crate::web_api_routes::interactive_events::edit_pre_blast::update_posted_pre_blast_message;
+use crate::web_api_state::MutableWebState;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub struct HistoryRouteData {
+    pub id: String,
+}
+
+#[derive(Deserialize)]
+pub struct RevisionRouteData {
+    pub id: String,
+    pub revision_id: Uuid,
+}
+
+#[derive(Deserialize)]
+pub struct RollbackQuery {
+    /// slack user doing the rollback
+    pub user: Option<String>,
+}
+
+/// revision with fields changed compared to previous revision
+#[derive(Serialize)]
+pub struct RevisionHistoryItem {
+    pub id: Uuid,
+    pub action: String,
+    pub source: String,
+    pub slack_user: Option<String>,
+    pub created: NaiveDateTime,
+    pub changes: Vec<FieldDiff>,
+}
+
+/// full snapshots of revision and the one before it
+#[derive(Serialize)]
+pub struct RevisionDiffResponse {
+    pub revision: RevisionDbData,
+    pub previous: Option<RevisionDbData>,
+    pub changes: Vec<FieldDiff>,
+}
+
+fn history_items(revisions: &[RevisionDbData]) -> Vec<RevisionHistoryItem> {
+    revisions
+        .iter()
+        .enumerate()
+        .map(|(index, revision)| {
+            let previous = index
+                .checked_sub(1)
+                .and_then(|previous| revisions.get(previous));
+            RevisionHistoryItem {
+                id: revision.id,
+                action: revision.action.to_string(),
+                source: revision.source.to_string(),
+                slack_user: revision.slack_user.clone(),
+                created: revision.created,
+                changes: diff_snapshots(
+                    previous.map(|previous| &previous.snapshot),
+                    &revision.snapshot,
+                ),
+            }
+        })
+        .collect()
+}
+
+fn revision_diff(
+    mut revisions: Vec<RevisionDbData>,
+    revision_id: &Uuid,
+) -> Result<RevisionDiffResponse, AppError> {
+    let index = revisions
+        .iter()
+        .position(|revision| &revision.id == revision_id)
+        .ok_or_else(|| AppError::from("Revision not found"))?;
+    revisions.truncate(index + 1);
+    let revision = revisions.pop().unwrap();
+    let previous = revisions.pop();
+    let changes = diff_snapshots(
+        previous.as_ref().map(|previous| &previous.snapshot),
+        &revision.snapshot,
+    );
+    Ok(RevisionDiffResponse {
+        revision,
+        previous,
+        changes,
+    })
+}
+
+async fn history(db_pool: &PgPool, entity: RevisionEntity, id: &str) -> HttpResponse {
+    match get_revisions(db_pool, entity, id).await {
+        Ok(revisions) => HttpResponse::Ok().json(history_items(&revisions)),
+        Err(err) => failure(err),
+    }
+}
+
+async fn diff(db_pool: &PgPool, entity: RevisionEntity, path: &RevisionRouteData) -> HttpResponse {
+    let result = match get_revisions(db_pool, entity, path.id.as_str()).await {
+        Ok(revisions) => revision_diff(revisions, &path.revision_id),
+        Err(err) => Err(err),
+    };
+    match result {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(err) => failure(err),
+    }
+}
+
+fn rollback_actor(query: &RollbackQuery) -> RevisionActor {
+    RevisionActor::new(RevisionSource::Admin, query.user.as_deref())
+}
+
+/// route to get revision history of backblast
+pub async fn back_blast_history_route(
+    db: web::Data<PgPool>,
+    path: web::Path<HistoryRouteData>,
+) -> impl Responder {
+    history(&db, RevisionEntity::BackBlast, path.id.as_str()).await
+}
+
+/// route to diff backblast revision against the revision before it
+pub async fn back_blast_revision_diff_route(
+    db: web::Data<PgPool>,
+    path: web::Path<RevisionRouteData>,
+) -> impl Responder {
+    diff(&db, RevisionEntity::BackBlast, &path).await
+}
+
+/// route to roll backblast back to revision (admins only), and update slack message.
+pub async fn rollback_back_blast_route(
+    db: web::Data<PgPool>,
+    web_state: web::Data<MutableWebState>,
+    app_state: web::Data<MutableAppState>,
+    req: HttpRequest,
+    path: web::Path<RevisionRouteData>,
+    query: web::Query<RollbackQuery>,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    let revision_id = path.revision_id.to_string();
+    let result = match rollback_back_blast(
+        &db,
+        path.id.as_str(),
+        revision_id.as_str(),
+        &rollback_actor(&query),
+    )
+    .await
+    {
+        Ok(_) => {
+            let registry = app_state.get_ao_registry();
+            update_posted_message(&db, &web_state, path.id.as_str(), None, &registry).await
+        }
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok(_) => success(),
+        Err(err) => failure(err),
+    }
+}
+
+/// route to get revision history of preblast
+pub async fn pre_blast_history_route(
+    db: web::Data<PgPool>,
+    path: web::Path<HistoryRouteData>,
+) -> impl Responder {
+    history(&db, RevisionEntity::PreBlast, path.id.as_str()).await
+}
+
+/// route to diff preblast revision against the revision before it
+pub async fn pre_blast_revision_diff_route(
+    db: web::Data<PgPool>,
+    path: web::Path<RevisionRouteData>,
+) -> impl Responder {
+    diff(&db, RevisionEntity::PreBlast, &path).await
+}
+
+/// route to roll preblast back to revision (admins only), and update slack message.
+pub async fn rollback_pre_blast_route(
+    db: web::Data<PgPool>,
+    web_state: web::Data<MutableWebState>,
+    app_state: web::Data<MutableAppState>,
+    req: HttpRequest,
+    path: web::Path<RevisionRouteData>,
+    query: web::Query<RollbackQuery>,
+) -> impl Responder {
+    if internal_auth::valid_internal_request(&req, &web_state.boise_key).is_err() {
+        return HttpResponse::Forbidden().body("Not authorized key");
+    }
+
+    let revision_id = path.revision_id.to_string();
+    let result = match rollback_pre_blast(
+        &db,
+        path.id.as_str(),
+        revision_id.as_str(),
+        &rollback_actor(&query),
+    )
+    .await
+    {
+        Ok(_) => {
+            let registry = app_state.get_ao_registry();
+            update_posted_pre_blast_message(&db, &web_state, path.id.as_str(), &registry).await
+        }
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok(_) => success(),
+        Err(err) => failure(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use serde_json::json;
+
+    fn revision(title: &str) -> RevisionDbData {
+        RevisionDbData {
+            id: Uuid::new_v4(),
+            entity_type: RevisionEntity::BackBlast.to_string(),
+            entity_id: Uuid::nil(),
+            action: String::from("edit"),
+            source: String::from("modal"),
+            slack_user: None,
+            snapshot: json!({ "title": title }),
+            created: NaiveDate::from_ymd_opt(2024, 7, 1)
+                .unwrap()
+                .and_hms_opt(6, 0, 0)
+                .unwrap(),
+        }
+    }
+
+    #[test]
+    fn diff_against_previous() {
+        let revisions = vec![revision("one"), revision("two"), revision("three")];
+        let second = revisions[1].id;
+        let items = history_items(&revisions);
+        assert_eq!(items[0].changes[0].before, None);
+        assert_eq!(items[2].changes[0].before, Some(json!("two")));
+
+        let response = revision_diff(revisions, &second).unwrap();
+        assert_eq!(response.revision.id, second);
+        assert_eq!(response.changes[0].before, Some(json!("one")));
+        assert_eq!(response.changes[0].after, Some(json!("two")));
+        assert!(revision_diff(vec![], &second).is_err());
+    }
+}
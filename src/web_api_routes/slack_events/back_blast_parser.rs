@@ -0,0 +1,445 @@
+//! Parser for backblasts typed by hand (`#backblast`) or posted by the old Slackblast app (`*slackblast*:`).
+//!
+//! Expected format is a header line followed by `Key: value` lines, for example:
+//! ```text
+//! #backblast Snarky title
+//! DATE: 2024-07-01
+//! AO: <#C123|bleach>
+//! Q: <@U123>
+//! PAX: <@U234> <@U345>, Non Slacker
+//! FNGs: 1 Bob
+//! COT: prayers for ...
+//! ```
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::backblast_data::{BackBlastData, BACK_BLAST_TAG, SLACK_BLAST_TAG};
+use chrono::{Datelike, NaiveDate};
+use std::collections::{HashMap, HashSet};
+
+/// result of parsing backblast text
+#[derive(Debug)]
+pub struct ParsedBackBlast {
+    pub data: BackBlastData,
+    /// what couldn't be parsed
+    pub problems: Vec<String>,
+    pub cot: Option<String>,
+}
+
+/// known header fields in backblast text
+#[derive(Debug, PartialEq)]
+enum BackBlastField {
+    Title,
+    Date,
+    Ao,
+    Qs,
+    Pax,
+    Fngs,
+    Cot,
+    /// any other section (warmup, thang, count etc) that gets saved to moleskine
+    Other,
+}
+
+impl From<&str> for BackBlastField {
+    fn from(key: &str) -> Self {
+        let key = key
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+        match key.as_str() {
+            "title" => BackBlastField::Title,
+            "date" | "when" => BackBlastField::Date,
+            "ao" | "where" => BackBlastField::Ao,
+            "q" | "qs" | "theq" | "coq" | "coqs" => BackBlastField::Qs,
+            "pax" | "thepax" => BackBlastField::Pax,
+            "fng" | "fngs" => BackBlastField::Fngs,
+            "cot" => BackBlastField::Cot,
+            _ => BackBlastField::Other,
+        }
+    }
+}
+
+/// whether message text starts like a backblast
+pub fn is_back_blast_text(text: &str) -> bool {
+    let first_line = text
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+    first_line.starts_with(BACK_BLAST_TAG) || first_line.starts_with(SLACK_BLAST_TAG)
+}
+
+/// parse backblast text.
+/// - `users` map of slack id to f3 name.
+/// - `channel_id` where message was posted, used when AO isn't listed.
+/// - `posted_date` local date message was posted, used when date isn't listed.
+/// - `registry` AOs from ao_list, for AOs without a dedicated variant.
+pub fn parse_back_blast(
+    text: &str,
+    users: &HashMap<String, String>,
+    channel_id: &str,
+    posted_date: NaiveDate,
+    registry: &AoRegistry,
+) -> ParsedBackBlast {
+    let mut problems = Vec::<String>::new();
+    let mut lines = text.lines();
+    let header = lines.next().unwrap_or_default();
+    let mut title = parse_title_from_header(header);
+
+    let mut date: Option<NaiveDate> = None;
+    let mut ao: Option<AO> = None;
+    let mut qs = HashSet::<String>::new();
+    let mut pax = HashSet::<String>::new();
+    let mut fngs = HashSet::<String>::new();
+    let mut cot: Option<String> = None;
+    let mut moleskine = Vec::<String>::new();
+    let mut current_field = BackBlastField::Other;
+
+    for line in lines {
+        let (field, value) = match split_field_line(line) {
+            Some((key, value)) => {
+                let field = BackBlastField::from(key);
+                if field == BackBlastField::Other {
+                    // keep unknown sections as part of moleskine
+                    (field, line.trim().to_string())
+                } else {
+                    (field, value)
+                }
+            }
+            // continuation of previous section
+            None => match current_field {
+                BackBlastField::Cot | BackBlastField::Other => (
+                    BackBlastField::from_continuation(&current_field),
+                    line.trim().to_string(),
+                ),
+                _ => (BackBlastField::Other, line.trim().to_string()),
+            },
+        };
+
+        match field {
+            BackBlastField::Title => {
+                if !value.is_empty() {
+                    title = Some(value);
+                }
+            }
+            BackBlastField::Date => match parse_date(&value, posted_date) {
+                Some(parsed) => date = Some(parsed),
+                None => problems.push(format!("Could not parse date `{}`", value)),
+            },
+            BackBlastField::Ao => match parse_ao(&value, registry) {
+                Some(parsed) => ao = Some(parsed),
+                None => problems.push(format!("Unknown AO `{}`", value)),
+            },
+            BackBlastField::Qs => {
+                let (names, unknown) = parse_names(&value, users);
+                qs.extend(names);
+                problems.extend(unknown);
+            }
+            BackBlastField::Pax => {
+                let (names, unknown) = parse_names(&value, users);
+                pax.extend(names);
+                problems.extend(unknown);
+            }
+            BackBlastField::Fngs => {
+                let (names, unknown) = parse_names(&strip_fng_count(&value), users);
+                fngs.extend(names);
+                problems.extend(unknown);
+            }
+            BackBlastField::Cot => {
+                let existing = cot.take().unwrap_or_default();
+                let combined = format!("{}\n{}", existing, value).trim().to_string();
+                cot = Some(combined);
+                moleskine.push(line.trim().to_string());
+            }
+            BackBlastField::Other => {
+                if !value.is_empty() {
+                    moleskine.push(value);
+                }
+            }
+        }
+        current_field = field;
+    }
+
+    let ao = ao.unwrap_or_else(|| AO::from_channel_id(channel_id, registry));
+    if matches!(ao, AO::Unknown(_) | AO::DR) {
+        problems.push(String::from("Missing AO"));
+    }
+
+    if qs.is_empty() {
+        problems.push(String::from("Missing Q"));
+    }
+
+    if pax.is_empty() && fngs.is_empty() {
+        problems.push(String::from("Missing PAX"));
+    }
+
+    pax.extend(fngs.clone());
+    let mut data = BackBlastData::new(ao, qs, pax, date.unwrap_or(posted_date));
+    data.title = title;
+    data.fngs = fngs;
+    let moleskine = moleskine.join("\n");
+    if !moleskine.is_empty() {
+        data.moleskine = Some(moleskine);
+    }
+
+    ParsedBackBlast {
+        data,
+        problems,
+        cot,
+    }
+}
+
+impl BackBlastField {
+    /// field that lines without a key belong to
+    fn from_continuation(previous: &BackBlastField) -> Self {
+        match previous {
+            BackBlastField::Cot => BackBlastField::Cot,
+            _ => BackBlastField::Other,
+        }
+    }
+}
+
+/// title is anything after the tag in the first line
+fn parse_title_from_header(header: &str) -> Option<String> {
+    let lower = header.to_lowercase();
+    let title = [BACK_BLAST_TAG, SLACK_BLAST_TAG]
+        .iter()
+        .find_map(|tag| lower.find(tag).map(|index| &header[index + tag.len()..]))
+        .unwrap_or_default();
+    let title = clean_value(title.trim_start_matches(':'));
+    if title.is_empty() {
+        None
+    } else {
+        Some(title)
+    }
+}
+
+/// split `Key: value` line. Keys can be bolded (`*AO*: value`)
+fn split_field_line(line: &str) -> Option<(&str, String)> {
+    let (key, value) = line.split_once(':')?;
+    let key_text = key.trim().trim_matches(|c| c == '*' || c == '_');
+    // keys are short labels, not sentences
+    if key_text.is_empty() || key_text.split_whitespace().count() > 3 || key_text.contains('<') {
+        return None;
+    }
+    Some((key_text, clean_value(value)))
+}
+
+/// remove markdown formatting around a value
+fn clean_value(value: &str) -> String {
+    value
+        .trim()
+        .trim_matches(|c| c == '*' || c == '_')
+        .trim()
+        .to_string()
+}
+
+/// parse date in common formats
+fn parse_date(value: &str, posted_date: NaiveDate) -> Option<NaiveDate> {
+    let value = value.trim();
+    // two digit year first, since `%Y` would also accept `24`
+    let full_formats = [
+        "%Y-%m-%d",
+        "%m/%d/%y",
+        "%m/%d/%Y",
+        "%m-%d-%Y",
+        "%B %d, %Y",
+        "%b %d, %Y",
+    ];
+    if let Some(date) = full_formats
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+    {
+        return Some(date);
+    }
+
+    // month and day only, assume year posted
+    let with_year = format!("{}/{}", value, posted_date.year());
+    NaiveDate::parse_from_str(&with_year, "%m/%d/%Y").ok()
+}
+
+/// parse AO from channel link (`<#C123|bleach>`) or name
+fn parse_ao(value: &str, registry: &AoRegistry) -> Option<AO> {
+    let value = value.trim();
+    let ao = if let Some(channel) = value.strip_prefix("<#") {
+        let channel = channel.trim_end_matches('>');
+        let (channel_id, _) = channel.split_once('|').unwrap_or((channel, ""));
+        AO::from_channel_id(channel_id, registry)
+    } else {
+        AO::from_name(&value.trim_start_matches('#').to_lowercase(), registry)
+    };
+
+    match ao {
+        AO::Unknown(_) => None,
+        ao => Some(ao),
+    }
+}
+
+/// slackblast prefixes fngs with count (ie. `2 Bob, Joe`). `None` means no fngs.
+fn strip_fng_count(value: &str) -> String {
+    let value = value.trim();
+    if matches!(
+        value.to_lowercase().as_str(),
+        "none" | "0" | "-" | "n/a" | ""
+    ) {
+        return String::new();
+    }
+    match value.split_once(' ') {
+        Some((count, rest)) if count.parse::<usize>().is_ok() => rest.to_string(),
+        _ => value.to_string(),
+    }
+}
+
+/// parse slack mentions (`<@U123>`) and comma separated plain names.
+/// returns names and list of mentions that couldn't be matched to a user.
+fn parse_names(value: &str, users: &HashMap<String, String>) -> (HashSet<String>, Vec<String>) {
+    let mut names = HashSet::<String>::new();
+    let mut unknown = Vec::<String>::new();
+    let mut remaining = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find("<@") {
+        remaining.push_str(&rest[..start]);
+        remaining.push(',');
+        let after = &rest[start + 2..];
+        match after.find('>') {
+            Some(end) => {
+                let mention = &after[..end];
+                let (slack_id, _) = mention.split_once('|').unwrap_or((mention, ""));
+                match users.get(slack_id) {
+                    Some(name) => {
+                        names.insert(name.to_string());
+                    }
+                    None => unknown.push(format!("Unknown user <@{}>", slack_id)),
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                rest = after;
+                break;
+            }
+        }
+    }
+    remaining.push_str(rest);
+
+    let known_names = users
+        .values()
+        .map(|name| (name.to_lowercase(), name.to_string()))
+        .collect::<HashMap<String, String>>();
+
+    for name in remaining.split(',') {
+        let name = name.trim().trim_start_matches('@').trim();
+        if name.is_empty() || matches!(name.to_lowercase().as_str(), "none" | "n/a") {
+            continue;
+        }
+        // use db name casing when it's a known pax
+        let name = known_names
+            .get(&name.to_lowercase())
+            .cloned()
+            .unwrap_or_else(|| name.to_string());
+        names.insert(name);
+    }
+
+    (names, unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::ao_data::const_names::BLEACH_CHANNEL_ID;
+
+    fn users() -> HashMap<String, String> {
+        HashMap::from([
+            (String::from("U01"), String::from("Backslash")),
+            (String::from("U02"), String::from("Stinger")),
+            (String::from("U03"), String::from("Deepdish")),
+        ])
+    }
+
+    fn posted() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 7, 2).unwrap()
+    }
+
+    #[test]
+    fn hand_typed_back_blast() {
+        let text = format!(
+            "#backblast Hot one\nDATE: 07/01/2024\nAO: <#{}|bleach>\nQ: <@U01>\nPAX: <@U02> <@U03>, Non Slacker\nFNGs: None\nCOT: prayers for the injured\nwelcome all",
+            BLEACH_CHANNEL_ID
+        );
+        let parsed = parse_back_blast(&text, &users(), "C999", posted(), &AoRegistry::default());
+        assert!(parsed.problems.is_empty(), "{:?}", parsed.problems);
+        assert_eq!(parsed.data.ao, AO::Bleach);
+        assert_eq!(
+            parsed.data.date,
+            NaiveDate::from_ymd_opt(2024, 7, 1).unwrap()
+        );
+        assert_eq!(parsed.data.title, Some(String::from("Hot one")));
+        assert_eq!(parsed.data.qs, HashSet::from([String::from("Backslash")]));
+        assert!(parsed.data.includes_pax("Stinger"));
+        assert!(parsed.data.includes_pax("Deepdish"));
+        assert!(parsed.data.includes_pax("Non Slacker"));
+        assert_eq!(parsed.data.total_pax(), 4);
+        assert!(parsed.data.fngs.is_empty());
+        assert_eq!(
+            parsed.cot,
+            Some(String::from("prayers for the injured\nwelcome all"))
+        );
+    }
+
+    #[test]
+    fn slackblast_format() {
+        let text = "*Slackblast*:\n*Title*: Murder bunnies\n*Date*: 2024-07-01\n*AO*: bleach\n*Q*: <@U01|backslash>\n*PAX*: <@U02>\n*FNGs*: 1 Bob\n*COUNT*: 3\n*WARMUP*: SSH";
+        let parsed = parse_back_blast(text, &users(), "C999", posted(), &AoRegistry::default());
+        assert!(parsed.problems.is_empty(), "{:?}", parsed.problems);
+        assert_eq!(parsed.data.title, Some(String::from("Murder bunnies")));
+        assert_eq!(parsed.data.ao, AO::Bleach);
+        assert_eq!(parsed.data.fngs, HashSet::from([String::from("Bob")]));
+        assert!(parsed.data.includes_pax("Bob"));
+        assert_eq!(parsed.data.total_pax(), 3);
+        assert_eq!(
+            parsed.data.moleskine,
+            Some(String::from("*COUNT*: 3\n*WARMUP*: SSH"))
+        );
+    }
+
+    #[test]
+    fn falls_back_to_channel_and_post_date() {
+        let text = "#backblast\nQ: stinger\nPAX: backslash";
+        let parsed = parse_back_blast(
+            text,
+            &users(),
+            BLEACH_CHANNEL_ID,
+            posted(),
+            &AoRegistry::default(),
+        );
+        assert!(parsed.problems.is_empty(), "{:?}", parsed.problems);
+        assert_eq!(parsed.data.ao, AO::Bleach);
+        assert_eq!(parsed.data.date, posted());
+        // matches db casing
+        assert!(parsed.data.qs.contains("Stinger"));
+    }
+
+    #[test]
+    fn reports_problems() {
+        let text = "#backblast\nDATE: someday\nQ: <@U99>\nPAX:";
+        let parsed = parse_back_blast(text, &users(), "C999", posted(), &AoRegistry::default());
+        assert_eq!(
+            parsed.problems,
+            vec![
+                String::from("Could not parse date `someday`"),
+                String::from("Unknown user <@U99>"),
+                String::from("Missing AO"),
+                String::from("Missing Q"),
+                String::from("Missing PAX"),
+            ]
+        );
+    }
+
+    #[test]
+    fn back_blast_text_detection() {
+        assert!(is_back_blast_text("#Backblast today\nQ: me"));
+        assert!(is_back_blast_text("*Slackblast*:\n*Title*: hi"));
+        assert!(!is_back_blast_text("Great workout today #backblast"));
+    }
+}
@@ -1,9 +1,24 @@
-use crate::app_state::backblast_data::{BACK_BLAST_TAG, SLACK_BLAST_TAG};
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::revisions::RevisionActor;
 use crate::app_state::MutableAppState;
+use crate::db::queries::all_back_blasts::back_blasts_by_ao::back_blasts_by_channel_id_and_date_range;
+use crate::db::queries::users::get_slack_id_map;
+use crate::db::save_back_blast;
+use crate::shared::common_errors::AppError;
+use crate::shared::time::convert_event_ts_to_boise;
+use crate::slack_api::block_kit::BlockBuilder;
+use crate::slack_api::chat::post_message::request::{PostMessageRequest, POSTED_AS_USER_SUFFIX};
+use crate::web_api_routes::slack_events::back_blast_parser::{
+    is_back_blast_text, parse_back_blast, ParsedBackBlast,
+};
+use crate::web_api_routes::slack_events::event_times::EventTimes;
 use crate::web_api_state::MutableWebState;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 
+/// message subtype for messages posted by bots or integrations (ie. Slackblast)
+const BOT_MESSAGE_SUBTYPE: &str = "bot_message";
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct ChannelMessageEvent {
     pub channel: String,
@@ -13,6 +28,8 @@ pub struct ChannelMessageEvent {
     pub user: Option<String>,
     /// when bot message
     pub username: Option<String>,
+    /// when bot message
+    pub bot_id: Option<String>,
     pub text: String,
     pub ts: String,
     pub event_ts: String,
@@ -20,38 +37,125 @@ pub struct ChannelMessageEvent {
     pub channel_type: String,
 }
 
+impl ChannelMessageEvent {
+    /// new messages from users or other integrations (not edits, deletes, joins etc).
+    /// Messages this app posted (directly or on behalf of a user) are ignored.
+    fn is_new_post(&self, self_bot_id: Option<&str>) -> bool {
+        match &self.subtype {
+            None => true,
+            Some(subtype) => subtype == BOT_MESSAGE_SUBTYPE && !self.is_own_post(self_bot_id),
+        }
+    }
+
+    fn is_own_post(&self, self_bot_id: Option<&str>) -> bool {
+        let own_bot = matches!(
+            (self.bot_id.as_deref(), self_bot_id),
+            (Some(bot_id), Some(self_bot_id)) if bot_id == self_bot_id
+        );
+        let as_user = self
+            .username
+            .as_deref()
+            .map(|username| username.ends_with(POSTED_AS_USER_SUFFIX))
+            .unwrap_or(false);
+        own_bot || as_user
+    }
+}
+
 pub async fn handle_channel_message(
     event: &ChannelMessageEvent,
-    _web_app: &MutableWebState,
-    _app_state: &MutableAppState,
-    _db_pool: &PgPool,
+    web_app: &MutableWebState,
+    app_state: &MutableAppState,
+    db_pool: &PgPool,
 ) {
-    let (first_line, _) = event.text.split_once('\n').unwrap_or(("", ""));
-    match first_line.to_lowercase() {
-        // is back-blast
-        line if line.starts_with(BACK_BLAST_TAG) || line.starts_with(SLACK_BLAST_TAG) => {
-            // let mut data = {
-            //     let app = app_state.app.lock().unwrap();
-            //     parse_back_blast(event.text.as_str(), &app.users, &app.channels)
-            // };
-            // data.set_event_times(EventTimes::new(
-            //     event.ts.to_string(),
-            //     event.event_ts.to_string(),
-            // ));
-            //
-            // let verified = data.is_valid_back_blast();
-            // let channel_request = channel_request(&data, verified, event.channel.as_str());
-            // web_app.back_blast_verified(channel_request).await;
-            // if verified {
-            //     if let Err(err) = save_back_blast::save(db_pool, &[data]).await {
-            //         println!("Error saving bb to db: {:?}", err);
-            //     }
-            // }
+    let self_bot_id = {
+        let app = app_state.app.lock().unwrap();
+        app.self_message_bot_id.to_owned()
+    };
+    // is back-blast
+    if event.is_new_post(self_bot_id.as_deref()) && is_back_blast_text(event.text.as_str()) {
+        let registry = app_state.get_ao_registry();
+        if let Err(err) = handle_back_blast_message(event, web_app, db_pool, &registry).await {
+            println!("Error handling back blast message: {:?}", err);
         }
-        _ => (),
     }
 }
 
+/// parse and save backblast from message text, then reply in thread with result.
+async fn handle_back_blast_message(
+    event: &ChannelMessageEvent,
+    web_app: &MutableWebState,
+    db_pool: &PgPool,
+    registry: &AoRegistry,
+) -> Result<(), AppError> {
+    let users = get_slack_id_map(db_pool).await?;
+    let posted_date = convert_event_ts_to_boise(event.ts.as_str()).date_naive();
+    let ParsedBackBlast {
+        mut data, problems, ..
+    } = parse_back_blast(
+        event.text.as_str(),
+        &users,
+        event.channel.as_str(),
+        posted_date,
+        registry,
+    );
+    data.set_event_times(EventTimes::new(
+        event.ts.to_string(),
+        event.event_ts.to_string(),
+    ));
+
+    let reply = if !problems.is_empty() || !data.is_valid_back_blast() {
+        let mut text = String::from(
+            ":warning: Could not save this backblast. Please fix the following and post again:",
+        );
+        if problems.is_empty() {
+            text.push_str("\n• Missing AO, Q, PAX or date");
+        }
+        for problem in problems.iter() {
+            text.push_str(format!("\n• {}", problem).as_str());
+        }
+        text
+    } else {
+        let existing = back_blasts_by_channel_id_and_date_range(
+            db_pool,
+            data.ao.channel_id(registry),
+            (data.date, data.date),
+        )
+        .await?;
+        if existing.is_empty() {
+            let actor = RevisionActor::parser(event.user.as_deref());
+            match save_back_blast::save_single(db_pool, &data, registry, &actor).await? {
+                Some(id) => {
+                    save_back_blast::update_back_blast_ts(db_pool, &id, event.ts.to_string())
+                        .await?;
+                    format!(
+                        ":white_check_mark: Saved backblast for {} on {} with {} pax.",
+                        data.ao.friendly_name(registry),
+                        data.date.format("%m/%d/%Y"),
+                        data.total_pax()
+                    )
+                }
+                None => format!(
+                    ":information_source: A backblast for {} on {} already exists, so this one was not saved.",
+                    data.ao.friendly_name(registry),
+                    data.date.format("%m/%d/%Y")
+                ),
+            }
+        } else {
+            format!(
+                ":information_source: A backblast for {} on {} already exists, so this one was not saved.",
+                data.ao.friendly_name(registry),
+                data.date.format("%m/%d/%Y")
+            )
+        }
+    };
+
+    let block_builder = BlockBuilder::new().section_markdown(reply.as_str());
+    let request = PostMessageRequest::new(event.channel.as_str(), block_builder.blocks)
+        .in_thread(event.ts.as_str());
+    web_app.post_message(request).await?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -72,4 +176,23 @@ mod tests {
         let my_s = serde_json::from_str::<S>(r#"{ "time": 1355517523.000005 }"#).unwrap();
         assert!(!my_s.time.to_string().is_empty());
     }
+
+    #[test]
+    fn ignores_own_bot_posts() {
+        let event = |bot_id: &str, username: &str| ChannelMessageEvent {
+            channel: String::from("C123"),
+            subtype: Some(BOT_MESSAGE_SUBTYPE.to_string()),
+            user: None,
+            username: Some(username.to_string()),
+            bot_id: Some(bot_id.to_string()),
+            text: String::from("Backblast! Bleach"),
+            ts: String::from("1689882778.307339"),
+            event_ts: String::from("1689882778.307339"),
+            channel_type: String::from("channel"),
+        };
+        assert!(event("B999", "Slackblast").is_new_post(Some("B123")));
+        assert!(!event("B123", "scraperrs").is_new_post(Some("B123")));
+        assert!(!event("B999", "Stinger (via BoiseBot)").is_new_post(Some("B123")));
+        assert!(!event("B999", "Stinger (via BoiseBot)").is_new_post(None));
+    }
 }
@@ -1,7 +1,11 @@
+use crate::app_state::hard_commits::is_hc_reaction;
 use crate::app_state::MutableAppState;
+use crate::db::queries::ao_list::get_hc_emojis;
 use crate::db::queries::match_reaction_items::{get_items_by_ts_and_channel, ReactionRelatedItem};
 use crate::db::save_reaction_log::{save_reaction_item, ReactionLogDbItem};
 use crate::shared::common_errors::AppError;
+use crate::web_api_routes::interactive_events::edit_pre_blast::update_posted_pre_blast_message;
+use crate::web_api_state::MutableWebState;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 
@@ -31,10 +35,11 @@ pub async fn handle_reaction_add(
     db: &PgPool,
     reaction: &ReactionData,
     app_state: &MutableAppState,
+    web_state: &MutableWebState,
 ) {
     if continue_with_emoji_handle(reaction, app_state) {
         println!("Reaction: {:?}", reaction);
-        if let Err(err) = get_related_entity(db, reaction, true).await {
+        if let Err(err) = get_related_entity(db, (web_state, app_state), reaction, true).await {
             println!("Error handling related entity: {:?}", err);
         }
     }
@@ -44,10 +49,11 @@ pub async fn handle_reaction_remove(
     db: &PgPool,
     reaction: &ReactionData,
     app_state: &MutableAppState,
+    web_state: &MutableWebState,
 ) {
     if continue_with_emoji_handle(reaction, app_state) {
         println!("Removed reaction: {:?}", reaction);
-        if let Err(err) = get_related_entity(db, reaction, false).await {
+        if let Err(err) = get_related_entity(db, (web_state, app_state), reaction, false).await {
             println!("Error handling related entity: {:?}", err);
         }
     }
@@ -68,6 +74,7 @@ fn continue_with_emoji_handle(reaction: &ReactionData, app_state: &MutableAppSta
 /// match different related entities based on reaction data coming from slack
 async fn get_related_entity(
     db: &PgPool,
+    (web_state, app_state): (&MutableWebState, &MutableAppState),
     reaction: &ReactionData,
     adding: bool,
 ) -> Result<(), AppError> {
@@ -77,10 +84,21 @@ async fn get_related_entity(
             get_items_by_ts_and_channel(db, &reaction.item.ts, &reaction.item.channel).await?;
         if let Some(data) = data {
             match data {
-                ReactionRelatedItem::PreBlast(id) => {
+                ReactionRelatedItem::PreBlast { id, channel_id } => {
                     let reaction_log_item =
                         ReactionLogDbItem::new(reaction, &id, adding).for_pre_blast();
                     save_reaction_item(db, reaction_log_item).await?;
+                    // live update headcount on preblast (preblast may be posted outside its AO channel)
+                    let hc_emojis = get_hc_emojis(db, &channel_id).await?;
+                    if is_hc_reaction(&reaction.reaction, &hc_emojis) {
+                        update_posted_pre_blast_message(
+                            db,
+                            web_state,
+                            &id,
+                            &app_state.get_ao_registry(),
+                        )
+                        .await?;
+                    }
                 }
             }
         }
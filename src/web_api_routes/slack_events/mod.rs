@@ -6,6 +6,7 @@ use sha2::Sha256;
 use sqlx::PgPool;
 
 mod app_rate_limited;
+pub mod back_blast_parser;
 pub mod channel_message;
 pub mod emoji_reactions;
 pub mod event_times;
@@ -88,10 +89,22 @@ pub async fn slack_events(
                 team_join::handle_new_user(&db_pool, &join_data.user, &app_state, &web_state).await;
             }
             event_wrapper::EventTypes::ReactionAdded(reaction_data) => {
-                emoji_reactions::handle_reaction_add(&db_pool, reaction_data, &app_state).await;
+                emoji_reactions::handle_reaction_add(
+                    &db_pool,
+                    reaction_data,
+                    &app_state,
+                    &web_state,
+                )
+                .await;
             }
             event_wrapper::EventTypes::ReactionRemoved(reaction_data) => {
-                emoji_reactions::handle_reaction_remove(&db_pool, reaction_data, &app_state).await;
+                emoji_reactions::handle_reaction_remove(
+                    &db_pool,
+                    reaction_data,
+                    &app_state,
+                    &web_state,
+                )
+                .await;
             }
             event_wrapper::EventTypes::AppRateLimited(rate_data) => {
                 app_rate_limited::handle_app_rate_limited(&web_state, rate_data).await;
@@ -0,0 +1,138 @@
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use crate::db::save_ao_closure::NewAoClosure;
+use crate::shared::common_errors::AppError;
+use crate::slack_api::block_kit::block_elements::OptionElement;
+use crate::web_api_routes::interactive_events::interaction_payload::BasicValue;
+use crate::web_api_routes::slash_commands::modal_utils::value_utils;
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+pub mod post_ids {
+    pub const AO_SELECT: &str = "closure_ao.select";
+    pub const START_DATE: &str = "closure_start_date.select";
+    pub const END_DATE: &str = "closure_end_date.select";
+    pub const REPEATS: &str = "closure_repeats.select";
+    pub const REASON: &str = "closure_reason.input";
+}
+
+const REPEAT_ONCE: &str = "once";
+const REPEAT_YEARLY: &str = "yearly";
+
+pub fn default_repeat_option() -> OptionElement {
+    OptionElement::new("Just this time", REPEAT_ONCE)
+}
+
+pub fn repeat_options() -> Vec<OptionElement> {
+    vec![
+        default_repeat_option(),
+        OptionElement::new("Every year", REPEAT_YEARLY),
+    ]
+}
+
+/// closure submitted from modal
+#[derive(Debug, PartialEq)]
+pub struct AoClosurePost {
+    /// selected channel (empty for region-wide)
+    pub channel_id: Option<String>,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub recurring: bool,
+    pub reason: String,
+}
+
+impl From<HashMap<String, BasicValue>> for AoClosurePost {
+    fn from(value: HashMap<String, BasicValue>) -> Self {
+        let channel_id =
+            value_utils::get_value(&value, post_ids::AO_SELECT, value_utils::get_single_string)
+                .filter(|channel_id| !channel_id.is_empty());
+
+        let start_date =
+            value_utils::get_value(&value, post_ids::START_DATE, value_utils::get_single_date)
+                .unwrap_or_default();
+
+        let end_date =
+            value_utils::get_value(&value, post_ids::END_DATE, value_utils::get_single_date)
+                .filter(|date| date != &NaiveDate::default());
+
+        let recurring =
+            value_utils::get_value(&value, post_ids::REPEATS, value_utils::get_single_string)
+                .map(|repeats| repeats == REPEAT_YEARLY)
+                .unwrap_or_default();
+
+        let reason =
+            value_utils::get_value(&value, post_ids::REASON, value_utils::get_single_string)
+                .unwrap_or_default();
+
+        AoClosurePost {
+            channel_id,
+            start_date,
+            end_date,
+            recurring,
+            reason: reason.trim().to_string(),
+        }
+    }
+}
+
+impl AoClosurePost {
+    /// ao for closure (`None` is region-wide)
+    pub fn ao(&self, registry: &AoRegistry) -> Result<Option<AO>, AppError> {
+        match &self.channel_id {
+            Some(channel_id) => match AO::from_channel_id(channel_id, registry) {
+                AO::Unknown(_) => Err(AppError::from("Selected channel is not an AO")),
+                ao => Ok(Some(ao)),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// convert to closure to save in db
+    pub fn to_new_closure(
+        &self,
+        user_id: &str,
+        registry: &AoRegistry,
+    ) -> Result<NewAoClosure, AppError> {
+        let closure = NewAoClosure {
+            ao: self.ao(registry)?.map(|ao| ao.to_string()),
+            start_date: self.start_date,
+            end_date: self.end_date.unwrap_or(self.start_date),
+            recurring: self.recurring,
+            reason: self.reason.to_string(),
+            created_by: Some(user_id.to_string()),
+        };
+        closure.validate()?;
+        Ok(closure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_day_region_wide() {
+        let post = AoClosurePost {
+            channel_id: None,
+            start_date: NaiveDate::from_ymd_opt(2024, 7, 4).unwrap(),
+            end_date: None,
+            recurring: true,
+            reason: String::from("Independence Day"),
+        };
+        let closure = post.to_new_closure("U123", &AoRegistry::default()).unwrap();
+        assert_eq!(closure.ao, None);
+        assert_eq!(closure.end_date, closure.start_date);
+        assert!(closure.recurring);
+    }
+
+    #[test]
+    fn missing_reason() {
+        let post = AoClosurePost {
+            channel_id: None,
+            start_date: NaiveDate::from_ymd_opt(2024, 7, 4).unwrap(),
+            end_date: None,
+            recurring: false,
+            reason: String::new(),
+        };
+        assert!(post.to_new_closure("U123", &AoRegistry::default()).is_err());
+    }
+}
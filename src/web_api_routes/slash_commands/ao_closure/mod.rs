@@ -0,0 +1,66 @@
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use crate::shared::common_errors::AppError;
+use crate::shared::time::local_boise_time;
+use crate::slack_api::block_kit::BlockBuilder;
+use crate::slack_api::views::payload::{ViewModal, ViewPayload};
+use crate::slack_api::views::request::ViewsOpenRequest;
+use crate::web_api_routes::slash_commands::modal_utils::view_ids::ViewIds;
+use crate::web_api_state::MutableWebState;
+
+pub mod ao_closure_post;
+
+pub async fn generate_modal(
+    trigger_id: &str,
+    web_app: &MutableWebState,
+    channel_id: &str,
+    registry: &AoRegistry,
+) -> Result<(), AppError> {
+    let modal = create_modal(channel_id, registry);
+    let view = ViewsOpenRequest::new(trigger_id, ViewPayload::Modal(modal));
+    web_app.open_view(view).await?;
+    Ok(())
+}
+
+fn create_modal(channel_id: &str, registry: &AoRegistry) -> ViewModal {
+    // default to current channel if it's an ao channel
+    let initial_channel = match AO::from_channel_id(channel_id, registry) {
+        AO::Unknown(_) => None,
+        _ => Some(channel_id.to_string()),
+    };
+    let today = local_boise_time().date_naive();
+
+    let block_builder = BlockBuilder::new()
+        .channel_select(
+            "AO",
+            ao_closure_post::post_ids::AO_SELECT,
+            initial_channel,
+            true,
+        )
+        .context("Leave AO empty to close all AOs in the region.")
+        .date_picker(
+            "Start Date",
+            ao_closure_post::post_ids::START_DATE,
+            Some(today.to_string()),
+            false,
+        )
+        .date_picker("End Date", ao_closure_post::post_ids::END_DATE, None, true)
+        .context("Leave end date empty for single day closure.")
+        .select(
+            "Repeats",
+            ao_closure_post::post_ids::REPEATS,
+            ao_closure_post::repeat_options(),
+            Some(ao_closure_post::default_repeat_option()),
+            false,
+        )
+        .plain_input(
+            "Reason",
+            ao_closure_post::post_ids::REASON,
+            Some("Holiday, convergence, smoke, construction...".to_string()),
+            None,
+            false,
+        );
+
+    ViewModal::new("AO Closure", block_builder, "Submit", ViewIds::AoClosure)
+        .with_private_meta(channel_id)
+}
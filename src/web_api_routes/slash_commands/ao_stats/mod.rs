@@ -1,4 +1,5 @@
 use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
 use crate::db::queries::all_back_blasts::back_blasts_by_ao::back_blasts_by_channel_id;
 use crate::shared::common_errors::AppError;
 use crate::slack_api::block_kit::BlockBuilder;
@@ -10,13 +11,14 @@ use sqlx::PgPool;
 pub async fn get_ao_stats_block(
     db_pool: &PgPool,
     form: &SlashCommandForm,
+    registry: &AoRegistry,
 ) -> Result<BlockBuilder, AppError> {
-    let possible_ao = AO::from_channel_id(form.channel_id.as_str());
+    let possible_ao = AO::from_channel_id(form.channel_id.as_str(), registry);
     if matches!(possible_ao, AO::Unknown(_) | AO::DR) {
         return Ok(error_block(":warning: Must be called within an AO channel"));
     }
 
-    let results = back_blasts_by_channel_id(db_pool, possible_ao.channel_id()).await?;
+    let results = back_blasts_by_channel_id(db_pool, possible_ao.channel_id(registry)).await?;
     let stats = AOBackBlastsStats::new(results);
 
     let block_builder = BlockBuilder::new()
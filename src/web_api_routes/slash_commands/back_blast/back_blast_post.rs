@@ -1,6 +1,11 @@
 use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
 use crate::app_state::backblast_data::{BackBlastData, BackBlastType};
+use crate::app_state::exercises::parse_exercise_list;
+use crate::app_state::ruck::{ruck_entries, RuckEntry};
 use crate::db::queries::users::get_user_by_slack_id;
+use crate::shared::constants;
+use crate::slack_api::block_kit::block_elements::OptionElement;
 use crate::slack_api::block_kit::BlockBuilder;
 use crate::slack_api::chat::post_message::request::PostMessageRequest;
 use crate::slack_api::chat::update_message::request::UpdateMessageRequest;
@@ -21,9 +26,13 @@ pub mod back_blast_post_action_ids {
     pub const UNTAGGABLE_PAX: &str = "untaggable-pax.input";
     pub const FNGS: &str = "fngs.input";
     pub const MOLESKINE: &str = "moleskine.textarea";
+    pub const THANG: &str = "thang.textarea";
     pub const WHERE_TO_POST: &str = "where-post.select";
     pub const BB_TYPE: &str = "back_blast_type.select";
     pub const FILE: &str = "file.input";
+    pub const RUCK_MILES: &str = "ruck-miles.input";
+    pub const RUCK_WEIGHT: &str = "ruck-weight.input";
+    pub const RUCK_PAX: &str = "ruck-pax.textarea";
 }
 
 #[derive(Debug)]
@@ -36,11 +45,27 @@ pub struct BackBlastPost {
     pub non_slack_pax: HashSet<String>,
     pub fngs: HashSet<String>,
     pub mole_skine: String,
+    /// structured exercises, one per line
+    pub thang: String,
+    /// ruck miles and weight for everyone (rucking AOs)
+    pub ruck: RuckEntry,
+    /// ruck lines for pax that differ from everyone, one per line
+    pub ruck_pax: String,
+    /// slack ids of uploaded route files (GPX or FIT)
+    pub route_file_ids: Vec<String>,
     pub blast_where: BlastWhere,
     pub bb_type: BackBlastType,
 }
 
 impl BackBlastPost {
+    /// channel the backblast is posted to
+    pub fn post_channel_id(&self, registry: &AoRegistry) -> String {
+        match &self.blast_where {
+            BlastWhere::AoChannel => self.ao.channel_id(registry).to_string(),
+            BlastWhere::CurrentChannel(id) => id.to_string(),
+        }
+    }
+
     /// parse to slack list of users
     pub fn qs_list(&self) -> String {
         self.qs
@@ -89,6 +114,26 @@ impl BackBlastPost {
         pax.len()
     }
 
+    /// ruck for everyone followed by per pax lines, if any entered
+    pub fn ruck_text(&self) -> Option<String> {
+        let mut lines = Vec::<String>::new();
+        if !self.ruck.is_empty() {
+            lines.push(format!("Everyone: {}", self.ruck));
+        }
+        lines.extend(
+            self.ruck_pax
+                .lines()
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty())
+                .map(|line| line.to_string()),
+        );
+        if lines.is_empty() {
+            None
+        } else {
+            Some(lines.join("\n"))
+        }
+    }
+
     pub fn saved_context_str(&self, is_valid: bool) -> String {
         let bb_type = self.bb_type.to_string();
         let valid_context_text = if is_valid { "Saved" } else { "Did not save" };
@@ -121,8 +166,9 @@ impl BackBlastPost {
     }
 }
 
-impl From<HashMap<String, BasicValue>> for BackBlastPost {
-    fn from(value: HashMap<String, BasicValue>) -> Self {
+impl BackBlastPost {
+    /// post from modal values, with AO of selected channel from registry
+    pub fn from_values(value: HashMap<String, BasicValue>, registry: &AoRegistry) -> Self {
         let title = value_utils::get_value(
             &value,
             back_blast_post_action_ids::TITLE,
@@ -137,11 +183,9 @@ impl From<HashMap<String, BasicValue>> for BackBlastPost {
         )
         .unwrap_or_default();
 
-        let ao = value_utils::get_value(
-            &value,
-            back_blast_post_action_ids::AO,
-            value_utils::get_ao_value,
-        )
+        let ao = value_utils::get_value(&value, back_blast_post_action_ids::AO, |value| {
+            value_utils::get_ao_value(value, registry)
+        })
         .unwrap_or_else(|| AO::Unknown("Not Parsed".to_string()));
 
         let qs = value
@@ -169,6 +213,31 @@ impl From<HashMap<String, BasicValue>> for BackBlastPost {
             .map(value_utils::get_single_string)
             .unwrap_or_default();
 
+        let thang = value
+            .get(back_blast_post_action_ids::THANG)
+            .map(value_utils::get_single_string)
+            .unwrap_or_default();
+
+        let ruck_value = |action_id: &str| {
+            value_utils::get_value(&value, action_id, value_utils::get_single_string)
+                .and_then(|value| value.trim().parse::<f32>().ok())
+                .filter(|value| value.is_finite() && *value >= 0.0)
+        };
+        let ruck = RuckEntry::new(
+            ruck_value(back_blast_post_action_ids::RUCK_MILES),
+            ruck_value(back_blast_post_action_ids::RUCK_WEIGHT),
+        );
+
+        let ruck_pax = value
+            .get(back_blast_post_action_ids::RUCK_PAX)
+            .map(value_utils::get_single_string)
+            .unwrap_or_default();
+
+        let route_file_ids = value
+            .get(back_blast_post_action_ids::FILE)
+            .and_then(BasicValue::get_multi_value)
+            .unwrap_or_default();
+
         let blast_where = value
             .get(back_blast_post_action_ids::WHERE_TO_POST)
             .map(value_utils::get_blast_where_value)
@@ -188,6 +257,10 @@ impl From<HashMap<String, BasicValue>> for BackBlastPost {
             non_slack_pax,
             fngs,
             mole_skine,
+            thang,
+            ruck,
+            ruck_pax,
+            route_file_ids,
             blast_where,
             bb_type,
         }
@@ -235,6 +308,8 @@ pub fn convert_to_bb_data(
     data.title = Some(request.title.to_string());
     data.moleskine = Some(request.mole_skine.to_string());
     data.fngs.clone_from(&request.fngs);
+    data.exercises = parse_exercise_list(&request.thang);
+    data.ruck = ruck_entries(data.get_pax().iter(), request.ruck, &request.ruck_pax);
     data
 }
 
@@ -244,13 +319,11 @@ pub fn convert_to_update_message(
     saved: bool,
     id: Option<String>,
     ts: &str,
+    registry: &AoRegistry,
 ) -> UpdateMessageRequest {
-    let channel_id = match &post.blast_where {
-        BlastWhere::AoChannel => post.ao.channel_id().to_string(),
-        BlastWhere::CurrentChannel(id) => id.to_string(),
-    };
+    let channel_id = post.post_channel_id(registry);
 
-    let block_builder = get_block_builder(post, id, saved);
+    let block_builder = get_block_builder(post, id, saved, registry);
 
     UpdateMessageRequest::new(&channel_id, ts, block_builder.blocks)
 }
@@ -262,17 +335,15 @@ pub async fn convert_to_message(
     is_valid: bool,
     id: Option<String>,
     action_user_id: &str,
+    registry: &AoRegistry,
 ) -> PostMessageRequest {
-    let channel_id = match &post.blast_where {
-        BlastWhere::AoChannel => post.ao.channel_id().to_string(),
-        BlastWhere::CurrentChannel(id) => id.to_string(),
-    };
+    let channel_id = post.post_channel_id(registry);
 
     let user = get_user_by_slack_id(db_pool, action_user_id)
         .await
         .unwrap_or_default();
 
-    let block_builder = get_block_builder(post, id, is_valid);
+    let block_builder = get_block_builder(post, id, is_valid, registry);
 
     if let Some(user) = user {
         PostMessageRequest::new_as_user(&channel_id, block_builder.blocks, user)
@@ -281,7 +352,12 @@ pub async fn convert_to_message(
     }
 }
 
-fn get_block_builder(post: BackBlastPost, id: Option<String>, is_valid: bool) -> BlockBuilder {
+fn get_block_builder(
+    post: BackBlastPost,
+    id: Option<String>,
+    is_valid: bool,
+    registry: &AoRegistry,
+) -> BlockBuilder {
     let first_section = format!(
         "*Slackblast*:\n
 {}\n
@@ -293,7 +369,7 @@ fn get_block_builder(post: BackBlastPost, id: Option<String>, is_valid: bool) ->
 *COUNT*: {}",
         post.title,
         post.date,
-        post.ao.channel_id(),
+        post.ao.channel_id(registry),
         post.qs_list(),
         post.pax_list(),
         post.fng_string_list(),
@@ -305,12 +381,28 @@ fn get_block_builder(post: BackBlastPost, id: Option<String>, is_valid: bool) ->
         .divider()
         .section_markdown(post.mole_skine.as_str());
 
+    if !post.thang.trim().is_empty() {
+        block_builder.add_section_markdown(format!("*The Thang*:\n{}", post.thang).as_str());
+    }
+
+    if let Some(ruck_text) = post.ruck_text() {
+        block_builder.add_section_markdown(format!("*Ruck*:\n{}", ruck_text).as_str());
+    }
+
     if let Some(id) = id {
         let interaction_btn = InteractionTypes::new_edit_back_blast(id.as_str());
-        block_builder.add_btn(
-            "Edit Backblast",
-            interaction_btn.to_string().as_str(),
-            "edit-backblast",
+        let options = InteractionTypes::new_back_blast_options(id.as_str());
+        block_builder.add_btn_with_overflow(
+            (
+                "Edit Backblast",
+                interaction_btn.to_string().as_str(),
+                "edit-backblast",
+            ),
+            options.to_string().as_str(),
+            vec![OptionElement::new(
+                constants::BACK_BLAST_DELETE_TEXT,
+                constants::BACK_BLAST_DELETE_TEXT,
+            )],
         );
     }
     let valid_context_text = post.saved_context_str(is_valid);
@@ -318,7 +410,7 @@ fn get_block_builder(post: BackBlastPost, id: Option<String>, is_valid: bool) ->
     block_builder
 }
 
-pub fn get_first_message_section(post: &BackBlastPost) -> String {
+pub fn get_first_message_section(post: &BackBlastPost, registry: &AoRegistry) -> String {
     format!(
         "*Slackblast*:\n
 {}\n
@@ -330,7 +422,7 @@ pub fn get_first_message_section(post: &BackBlastPost) -> String {
 *COUNT*: {}",
         post.title,
         post.date,
-        post.ao.channel_id(),
+        post.ao.channel_id(registry),
         post.qs_list(),
         post.pax_list(),
         post.fng_string_list(),
@@ -353,6 +445,10 @@ mod tests {
             non_slack_pax: HashSet::from([]),
             fngs: HashSet::from(["Fng".to_string(), "None".to_string()]),
             mole_skine: "The Thang".to_string(),
+            thang: String::new(),
+            ruck: RuckEntry::default(),
+            ruck_pax: String::new(),
+            route_file_ids: Vec::new(),
             blast_where: BlastWhere::AoChannel,
             bb_type: BackBlastType::BackBlast,
         };
@@ -362,6 +458,38 @@ mod tests {
         assert!(!post.pax_list().contains("None"));
     }
 
+    #[test]
+    fn ruck_per_pax() {
+        let post = BackBlastPost {
+            title: "Title".to_string(),
+            date: NaiveDate::from_ymd_opt(2023, 3, 2).unwrap(),
+            ao: AO::RuckershipWest,
+            qs: HashSet::from(["U1".to_string()]),
+            pax: HashSet::from(["U2".to_string()]),
+            non_slack_pax: HashSet::from(["Bob".to_string()]),
+            fngs: HashSet::from([]),
+            mole_skine: "Rucked".to_string(),
+            thang: String::new(),
+            ruck: RuckEntry::new(Some(4.), Some(30.)),
+            ruck_pax: "stinger 45lbs".to_string(),
+            route_file_ids: Vec::new(),
+            blast_where: BlastWhere::AoChannel,
+            bb_type: BackBlastType::BackBlast,
+        };
+        let users = HashMap::from([
+            ("U1".to_string(), "Stinger".to_string()),
+            ("U2".to_string(), "Backslash".to_string()),
+        ]);
+        let data = convert_to_bb_data(&post, users);
+        assert_eq!(data.ruck.len(), 3);
+        assert_eq!(data.ruck["stinger"], RuckEntry::new(Some(4.), Some(45.)));
+        assert_eq!(data.ruck["bob"], RuckEntry::new(Some(4.), Some(30.)));
+        assert_eq!(
+            post.ruck_text(),
+            Some("Everyone: 4mi 30lbs\nstinger 45lbs".to_string())
+        );
+    }
+
     #[test]
     fn double_down_context_str() {
         let post = BackBlastPost {
@@ -373,6 +501,10 @@ mod tests {
             non_slack_pax: HashSet::from([]),
             fngs: HashSet::from(["Fng".to_string(), "None".to_string()]),
             mole_skine: "The Thang".to_string(),
+            thang: String::new(),
+            ruck: RuckEntry::default(),
+            ruck_pax: String::new(),
+            route_file_ids: Vec::new(),
             blast_where: BlastWhere::AoChannel,
             bb_type: BackBlastType::DoubleDown,
         };
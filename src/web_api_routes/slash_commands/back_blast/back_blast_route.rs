@@ -0,0 +1,109 @@
+//! Route files (GPX or FIT) uploaded through backblast modal.
+//! Files are downloaded from slack, parsed and saved with the backblast, then a map thumbnail is posted.
+//! This runs in the background, so slack gets its response to the modal submission right away.
+
+use crate::app_state::route_track::{parse_route_file, ROUTE_FILE_TYPES};
+use crate::db::save_route::{save_back_blast_route, NewBackBlastRoute};
+use crate::shared::common_errors::AppError;
+use crate::web_api_routes::graphs::route_map::post_route_map;
+use crate::web_api_state::MutableWebState;
+use actix_web::web;
+use sqlx::PgPool;
+
+/// largest route file downloaded (in bytes)
+const MAX_ROUTE_FILE_SIZE: usize = 20 * 1024 * 1024;
+
+/// save and post map of each uploaded route file in the background.
+/// Files that fail are logged and skipped.
+pub fn attach_route_files(
+    db_pool: &PgPool,
+    web_state: &web::Data<MutableWebState>,
+    back_blast_id: &str,
+    file_ids: &[String],
+    channel_id: &str,
+    uploaded_by: &str,
+) {
+    if file_ids.is_empty() {
+        return;
+    }
+    let db_pool = db_pool.clone();
+    let web_state = web::Data::clone(web_state);
+    let back_blast_id = back_blast_id.to_string();
+    let file_ids = file_ids.to_vec();
+    let channel_id = channel_id.to_string();
+    let uploaded_by = uploaded_by.to_string();
+    actix_rt::spawn(async move {
+        for file_id in file_ids.iter() {
+            if let Err(err) = attach_route_file(
+                &db_pool,
+                &web_state,
+                &back_blast_id,
+                file_id,
+                &channel_id,
+                &uploaded_by,
+            )
+            .await
+            {
+                println!("Could not attach route file {}: {:?}", file_id, err);
+            }
+        }
+    });
+}
+
+async fn attach_route_file(
+    db_pool: &PgPool,
+    web_state: &MutableWebState,
+    back_blast_id: &str,
+    file_id: &str,
+    channel_id: &str,
+    uploaded_by: &str,
+) -> Result<(), AppError> {
+    let file = web_state.get_file_info(file_id).await?;
+    let file_type = route_file_type(&file.filetype, file.name.as_deref())
+        .ok_or_else(|| AppError::General(format!("Not a route file: {}", file.filetype)))?;
+    if file.size > MAX_ROUTE_FILE_SIZE {
+        return Err(AppError::General(format!(
+            "Route file too large: {} bytes",
+            file.size
+        )));
+    }
+    let bytes = web_state.download_file(&file).await?;
+    let summary = parse_route_file(&file_type, &bytes)?;
+    let route = NewBackBlastRoute {
+        file_id: file.id.to_string(),
+        file_name: file.name.clone(),
+        file_type,
+        summary,
+        uploaded_by: Some(uploaded_by.to_string()),
+    };
+    save_back_blast_route(db_pool, back_blast_id, &route).await?;
+    post_route_map(web_state, channel_id, &route.file_id, &route.summary).await
+}
+
+/// route file type from slack filetype, falling back to file extension
+/// (slack does not always recognize FIT files).
+fn route_file_type(filetype: &str, name: Option<&str>) -> Option<String> {
+    let extension = name
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, extension)| extension);
+    [Some(filetype), extension]
+        .into_iter()
+        .flatten()
+        .map(|file_type| file_type.to_lowercase())
+        .find(|file_type| ROUTE_FILE_TYPES.contains(&file_type.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_type_from_extension() {
+        assert_eq!(route_file_type("gpx", None), Some("gpx".to_string()));
+        assert_eq!(
+            route_file_type("binary", Some("Morning_Run.FIT")),
+            Some("fit".to_string())
+        );
+        assert_eq!(route_file_type("png", Some("map.png")), None);
+    }
+}
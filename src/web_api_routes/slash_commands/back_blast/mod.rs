@@ -1,44 +1,188 @@
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
 use crate::app_state::backblast_data::BackBlastType;
+use crate::app_state::route_track::ROUTE_FILE_TYPES;
+use crate::db::queries::ao_list::get_hc_emojis;
+use crate::db::queries::pre_blasts::get_matching_pre_blast_id;
+use crate::db::queries::reactions_log::get_pre_blast_hcs;
 use crate::shared::common_errors::AppError;
 use crate::shared::time::local_boise_time;
 use crate::slack_api::block_kit::BlockBuilder;
 use crate::slack_api::views::payload::{ViewModal, ViewPayload};
 use crate::slack_api::views::request::ViewsOpenRequest;
+use crate::web_api_routes::interactive_events::edit_pre_blast::{
+    get_pre_blast_data, get_pre_blast_user_data,
+};
 use crate::web_api_routes::slash_commands::modal_utils::view_ids::ViewIds;
 use crate::web_api_routes::slash_commands::modal_utils::{
     back_blast_types_list, default_back_blast_type, default_post_option, where_to_post_list,
 };
 use crate::web_api_state::MutableWebState;
+use chrono::NaiveDate;
+use sqlx::PgPool;
 
 pub mod back_blast_post;
+pub mod back_blast_route;
 
+/// open backblast modal, prefilled from preblast of AO channel for today (or yesterday) if one exists
 pub async fn generate_modal(
+    db_pool: &PgPool,
     trigger_id: &str,
     web_app: &MutableWebState,
     channel_id: &str,
     user_id: &str,
     back_blast_type: BackBlastType,
+    registry: &AoRegistry,
+) -> Result<(), AppError> {
+    let today = local_boise_time().date_naive();
+    let pre_blast_id = get_matching_pre_blast_id(db_pool, channel_id, &today)
+        .await
+        .unwrap_or_else(|err| {
+            println!("Error finding preblast for backblast: {:?}", err);
+            None
+        });
+    let prefill = match pre_blast_id {
+        Some(id) => get_pre_blast_prefill(db_pool, &id, registry)
+            .await
+            .unwrap_or_else(|err| {
+                println!("Error prefilling backblast from preblast: {:?}", err);
+                BackBlastPrefill::new(channel_id, user_id)
+            }),
+        None => BackBlastPrefill::new(channel_id, user_id),
+    };
+    let modal = create_modal(prefill, user_id, back_blast_type, registry);
+    let view = ViewsOpenRequest::new(trigger_id, ViewPayload::Modal(modal));
+    web_app.open_view(view).await?;
+    Ok(())
+}
+
+/// open backblast modal prefilled from saved preblast (ie. from button on preblast message)
+pub async fn generate_modal_from_pre_blast(
+    db_pool: &PgPool,
+    trigger_id: &str,
+    web_app: &MutableWebState,
+    pre_blast_id: &str,
+    channel_id: &str,
+    user_id: &str,
+    registry: &AoRegistry,
 ) -> Result<(), AppError> {
-    let modal = create_modal(channel_id, user_id, back_blast_type);
+    let prefill = get_pre_blast_prefill(db_pool, pre_blast_id, registry)
+        .await
+        .unwrap_or_else(|err| {
+            println!("Error prefilling backblast from preblast: {:?}", err);
+            BackBlastPrefill::new(channel_id, user_id)
+        });
+    let modal = create_modal(prefill, user_id, BackBlastType::BackBlast, registry);
     let view = ViewsOpenRequest::new(trigger_id, ViewPayload::Modal(modal));
     web_app.open_view(view).await?;
     Ok(())
 }
 
-fn create_modal(channel_id: &str, user_id: &str, back_blast_type: BackBlastType) -> ViewModal {
-    let default_date = local_boise_time().date_naive();
+/// initial values of backblast modal
+#[derive(Debug, PartialEq)]
+pub struct BackBlastPrefill {
+    /// preblast this backblast is written from, to link once saved
+    pub pre_blast_id: Option<String>,
+    pub title: Option<String>,
+    pub channel_id: String,
+    pub date: NaiveDate,
+    /// slack ids of Q(s)
+    pub qs: Vec<String>,
+    /// slack ids of PAX
+    pub pax: Vec<String>,
+}
+
+impl BackBlastPrefill {
+    /// defaults with user as Q of today's workout in channel
+    pub fn new(channel_id: &str, user_id: &str) -> Self {
+        BackBlastPrefill {
+            pre_blast_id: None,
+            title: None,
+            channel_id: channel_id.to_string(),
+            date: local_boise_time().date_naive(),
+            qs: vec![user_id.to_string()],
+            pax: Vec::new(),
+        }
+    }
+
+    /// prefill from preblast, with PAX being those that HC'd (besides the Q(s))
+    pub fn from_pre_blast(
+        pre_blast_id: &str,
+        title: &str,
+        channel_id: &str,
+        date: NaiveDate,
+        qs: Vec<String>,
+        hcs: Vec<String>,
+    ) -> Self {
+        let pax = hcs.into_iter().fold(Vec::<String>::new(), |mut acc, hc| {
+            if !qs.contains(&hc) && !acc.contains(&hc) {
+                acc.push(hc);
+            }
+            acc
+        });
+        BackBlastPrefill {
+            pre_blast_id: Some(pre_blast_id.to_string()),
+            title: Some(title.to_string()).filter(|title| !title.is_empty()),
+            channel_id: channel_id.to_string(),
+            date,
+            qs,
+            pax,
+        }
+    }
+}
+
+/// load preblast with its Q(s) and HCs as slack ids
+async fn get_pre_blast_prefill(
+    db_pool: &PgPool,
+    pre_blast_id: &str,
+    registry: &AoRegistry,
+) -> Result<BackBlastPrefill, AppError> {
+    let pre_blast = get_pre_blast_data(db_pool, pre_blast_id).await?;
+    let qs = get_pre_blast_user_data(db_pool, &pre_blast)
+        .await?
+        .convert_to_slack_ids(&pre_blast.qs);
+    let channel_id = pre_blast.ao.channel_id(registry);
+    let emojis = get_hc_emojis(db_pool, channel_id).await?;
+    let hcs = get_pre_blast_hcs(db_pool, pre_blast_id, &emojis)
+        .await?
+        .into_iter()
+        .map(|hc| hc.slack_user)
+        .collect();
+    Ok(BackBlastPrefill::from_pre_blast(
+        pre_blast_id,
+        &pre_blast.title,
+        channel_id,
+        pre_blast.date,
+        qs,
+        hcs,
+    ))
+}
+
+fn create_modal(
+    prefill: BackBlastPrefill,
+    user_id: &str,
+    back_blast_type: BackBlastType,
+    registry: &AoRegistry,
+) -> ViewModal {
+    let channel_id = prefill.channel_id.as_str();
+    let qs = if prefill.qs.is_empty() {
+        vec![user_id.to_string()]
+    } else {
+        prefill.qs
+    };
+    let pax = Some(prefill.pax).filter(|pax| !pax.is_empty());
     let default_moleskine = r#"*WARMUP:*
 *THE THANG:*
 *MARY:*
 *ANNOUNCEMENTS:*
 *COT:*"#;
     let default_moleskine = default_moleskine.to_string();
-    let block_builder = BlockBuilder::new()
+    let mut block_builder = BlockBuilder::new()
         .plain_input(
             "Title",
             back_blast_post::back_blast_post_action_ids::TITLE,
             Some("Snarky Title?".to_string()),
-            None,
+            prefill.title,
             false,
         )
         .channel_select(
@@ -50,19 +194,19 @@ fn create_modal(channel_id: &str, user_id: &str, back_blast_type: BackBlastType)
         .date_picker(
             "Workout Date",
             back_blast_post::back_blast_post_action_ids::DATE,
-            Some(default_date.to_string()),
+            Some(prefill.date.to_string()),
             false,
         )
         .multi_users_select(
             "The Q(s)",
             back_blast_post::back_blast_post_action_ids::QS,
-            Some(vec![user_id.to_string()]),
+            Some(qs),
             false,
         )
         .multi_users_select(
             "The PAX",
             back_blast_post::back_blast_post_action_ids::PAX,
-            None,
+            pax,
             false,
         )
         .plain_input(
@@ -86,6 +230,21 @@ fn create_modal(channel_id: &str, user_id: &str, back_blast_type: BackBlastType)
             Some(default_moleskine),
             false,
         ).context("If trying to tag PAX in here, substitute _ for spaces and do not include titles in parenthesis (ie, @Moneyball not @Moneyball_(F3_STC)). Spelling is important, capitalization is not!")
+        .text_box(
+            "The Thang",
+            back_blast_post::back_blast_post_action_ids::THANG,
+            Some("One exercise per line (ie. 20 Merkins (IC))".to_string()),
+            None,
+            true,
+        );
+    if AO::from_channel_id(channel_id, registry)
+        .ao_type(registry)
+        .tracks_ruck()
+    {
+        add_ruck_inputs(&mut block_builder, None);
+    }
+    add_route_file_input(&mut block_builder);
+    let block_builder = block_builder
         .select(
             "Backblast type",
             back_blast_post::back_blast_post_action_ids::BB_TYPE,
@@ -100,5 +259,67 @@ fn create_modal(channel_id: &str, user_id: &str, back_blast_type: BackBlastType)
             Some(default_post_option(Some(channel_id))),
             false,
         ).context("Do not hit Submit more than once! Even if you get a timeout error, the backblast has likely already been posted. If using email, this can take time and this form may not automatically close.");
-    ViewModal::new("Back Blast", block_builder, "Submit", ViewIds::BackBlast)
+    let modal = ViewModal::new("Back Blast", block_builder, "Submit", ViewIds::BackBlast);
+    match prefill.pre_blast_id {
+        Some(pre_blast_id) => modal.with_private_meta(&pre_blast_id),
+        None => modal,
+    }
+}
+
+/// inputs for ruck miles and weight (rucking AOs). Per pax lines can be prefilled when editing.
+pub fn add_ruck_inputs(block_builder: &mut BlockBuilder, per_pax: Option<String>) {
+    block_builder.add_plain_input(
+        "Ruck miles",
+        back_blast_post::back_blast_post_action_ids::RUCK_MILES,
+        Some("Miles everyone rucked".to_string()),
+        None,
+        true,
+    );
+    block_builder.add_plain_input(
+        "Ruck weight (lbs)",
+        back_blast_post::back_blast_post_action_ids::RUCK_WEIGHT,
+        Some("Weight everyone carried".to_string()),
+        None,
+        true,
+    );
+    block_builder.add_text_box(
+        "Ruck per PAX",
+        back_blast_post::back_blast_post_action_ids::RUCK_PAX,
+        Some("PAX that differ, one per line (ie. Stinger 4.2mi 45lbs)".to_string()),
+        per_pax,
+        true,
+    );
+}
+
+/// optional upload of route recorded on watch (GPX or FIT)
+pub fn add_route_file_input(block_builder: &mut BlockBuilder) {
+    block_builder.add_file_input(
+        "Route (GPX or FIT file)",
+        back_blast_post::back_blast_post_action_ids::FILE,
+        ROUTE_FILE_TYPES.to_vec(),
+        true,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefill_pax_from_hcs() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 20).unwrap();
+        let prefill = BackBlastPrefill::from_pre_blast(
+            "abc-123",
+            "Leg day",
+            "C123",
+            date,
+            vec!["U1".to_string()],
+            vec!["U2".to_string(), "U1".to_string(), "U3".to_string()],
+        );
+        assert_eq!(prefill.pre_blast_id, Some("abc-123".to_string()));
+        assert_eq!(prefill.title, Some("Leg day".to_string()));
+        assert_eq!(prefill.channel_id, "C123");
+        assert_eq!(prefill.qs, vec!["U1"]);
+        assert_eq!(prefill.pax, vec!["U2", "U3"]);
+    }
 }
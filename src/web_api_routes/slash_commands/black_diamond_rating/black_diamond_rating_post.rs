@@ -1,9 +1,15 @@
 use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use crate::db::queries::black_diamond_ratings::{BlackDiamondRatingDbData, RatingRank};
 use crate::db::queries::users::get_user_by_slack_id;
+use crate::db::save_black_diamond_rating::NewBlackDiamondRating;
+use crate::shared::common_errors::AppError;
+use crate::shared::time::local_boise_time;
 use crate::slack_api::block_kit::BlockBuilder;
 use crate::slack_api::chat::post_message::request::PostMessageRequest;
 use crate::web_api_routes::interactive_events::interaction_payload::BasicValue;
 use crate::web_api_routes::slash_commands::modal_utils::value_utils;
+use chrono::NaiveDate;
 use sqlx::PgPool;
 use std::collections::HashMap;
 
@@ -13,6 +19,7 @@ pub mod post_ids {
     pub const MILES: &str = "miles.input";
     pub const AVG_HR: &str = "avg-heart-rate.input";
     pub const WHERE_POST: &str = "where_to_post.select";
+    pub const DATE: &str = "bd-date.select";
 }
 
 #[derive(Debug)]
@@ -22,6 +29,8 @@ pub struct BlackDiamondRatingPost {
     miles: f32,
     avg_hr: f32,
     post_where: AO,
+    /// date of beatdown rated
+    date: NaiveDate,
 }
 
 mod calculation_consts {
@@ -66,17 +75,43 @@ impl BlackDiamondRatingPost {
         float_value_formatted(self.part_three())
     }
 
-    fn total(&self) -> f32 {
+    pub fn total(&self) -> f32 {
         self.part_one() + self.part_two() + self.part_three()
     }
 
     pub fn total_fmt(&self) -> String {
         float_value_formatted(self.total())
     }
+
+    /// convert to rating to save in db
+    pub fn to_new_rating(
+        &self,
+        user_id: &str,
+        registry: &AoRegistry,
+    ) -> Result<NewBlackDiamondRating, AppError> {
+        if self.pax_count == 0 {
+            return Err(AppError::from("Number of Pax is required"));
+        }
+        if self.vests_removed > self.pax_count {
+            return Err(AppError::from("Can't remove more vests than there are Pax"));
+        }
+
+        Ok(NewBlackDiamondRating {
+            channel_id: AO::BlackDiamond.channel_id(registry).to_string(),
+            date: self.date,
+            pax_count: self.pax_count as i32,
+            vests_removed: self.vests_removed as i32,
+            miles: self.miles,
+            avg_hr: self.avg_hr,
+            total: self.total(),
+            rated_by: Some(user_id.to_string()),
+        })
+    }
 }
 
-impl From<HashMap<String, BasicValue>> for BlackDiamondRatingPost {
-    fn from(value: HashMap<String, BasicValue>) -> Self {
+impl BlackDiamondRatingPost {
+    /// post from modal values, with AO of selected channel from registry
+    pub fn from_values(value: HashMap<String, BasicValue>, registry: &AoRegistry) -> Self {
         let pax_count =
             value_utils::get_value(&value, post_ids::PAX_COUNT, value_utils::get_single_usize)
                 .unwrap_or_default();
@@ -97,25 +132,74 @@ impl From<HashMap<String, BasicValue>> for BlackDiamondRatingPost {
 
         let post_where = value
             .get(post_ids::WHERE_POST)
-            .map(value_utils::get_ao_value)
+            .map(|value| value_utils::get_ao_value(value, registry))
             .unwrap_or_else(|| AO::Unknown("Missing AO".to_string()));
 
+        let date = value_utils::get_value(&value, post_ids::DATE, value_utils::get_single_date)
+            .unwrap_or_else(|| local_boise_time().date_naive());
+
         BlackDiamondRatingPost {
             pax_count,
             vests_removed,
             miles,
             avg_hr,
             post_where,
+            date,
         }
     }
 }
 
+/// text comparing total to previous ratings (newest first) and all time rank
+fn comparison_text(
+    total: f32,
+    previous: &[BlackDiamondRatingDbData],
+    rank: Option<&RatingRank>,
+) -> Option<String> {
+    let mut lines = Vec::<String>::new();
+    if let Some(rank) = rank {
+        lines.push(format!(
+            "Ranked *#{}* of {} rated beatdowns",
+            rank.rank, rank.total_ratings
+        ));
+    }
+    if let Some(last) = previous.first() {
+        lines.push(format!(
+            "{:+.2} compared to last rating ({})",
+            total - last.total,
+            float_value_formatted(last.total)
+        ));
+    }
+    for rating in previous {
+        let q = rating
+            .q
+            .as_deref()
+            .map(|q| format!(" - Q: {}", q))
+            .unwrap_or_default();
+        lines.push(format!(
+            "`{}` {}{}",
+            rating.date.format("%m/%d/%y"),
+            float_value_formatted(rating.total),
+            q
+        ));
+    }
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+/// convert to message. Pass in previous ratings (newest first) and rank of this rating to compare against.
 pub async fn convert_to_message(
     post: BlackDiamondRatingPost,
     db_pool: &PgPool,
     user_id: &str,
+    previous: &[BlackDiamondRatingDbData],
+    rank: Option<&RatingRank>,
+    registry: &AoRegistry,
 ) -> PostMessageRequest {
-    let channel_id = post.post_where.channel_id();
+    let channel_id = post.post_where.channel_id(registry);
 
     let user = get_user_by_slack_id(db_pool, user_id)
         .await
@@ -146,7 +230,7 @@ pub async fn convert_to_message(
 
     let total = format!("*Total*: {} :black-diamond-1:", post.total_fmt());
 
-    let block_builder = BlockBuilder::new()
+    let mut block_builder = BlockBuilder::new()
         .section_markdown("*BLACK DIAMOND GRADING*")
         .img_markdown(
             desc.as_str(),
@@ -157,6 +241,10 @@ pub async fn convert_to_message(
         .divider()
         .section_markdown(total.as_str());
 
+    if let Some(comparison) = comparison_text(post.total(), previous, rank) {
+        block_builder.add_context(comparison.as_str());
+    }
+
     if let Some(user) = user {
         PostMessageRequest::new_as_user(channel_id, block_builder.blocks, user)
     } else {
@@ -176,7 +264,56 @@ mod tests {
             miles: 3.72,
             avg_hr: 140.5,
             post_where: AO::BlackDiamond,
+            date: NaiveDate::from_ymd_opt(2024, 7, 3).unwrap(),
         };
         assert_eq!(post.total_fmt(), 3.86.to_string());
     }
+
+    #[test]
+    fn new_rating_validation() {
+        let mut post = BlackDiamondRatingPost {
+            pax_count: 0,
+            vests_removed: 0,
+            miles: 3.,
+            avg_hr: 140.,
+            post_where: AO::BlackDiamond,
+            date: NaiveDate::from_ymd_opt(2024, 7, 3).unwrap(),
+        };
+        let registry = AoRegistry::default();
+        assert!(post.to_new_rating("U123", &registry).is_err());
+        post.pax_count = 8;
+        post.vests_removed = 4;
+        let rating = post.to_new_rating("U123", &registry).unwrap();
+        assert_eq!(rating.channel_id, AO::BlackDiamond.channel_id(&registry));
+        assert_eq!(rating.total, post.total());
+    }
+
+    #[test]
+    fn compare_to_previous() {
+        let previous = BlackDiamondRatingDbData {
+            id: uuid::Uuid::nil(),
+            back_blast_id: None,
+            date: NaiveDate::from_ymd_opt(2024, 6, 26).unwrap(),
+            pax_count: 6,
+            vests_removed: 3,
+            miles: 3.,
+            avg_hr: 140.,
+            total: 3.,
+            rated_by: None,
+            created: NaiveDate::from_ymd_opt(2024, 6, 26)
+                .unwrap()
+                .and_hms_opt(6, 0, 0)
+                .unwrap(),
+            q: Some(String::from("stinger")),
+        };
+        let rank = RatingRank {
+            rank: 2,
+            total_ratings: 10,
+        };
+        let text = comparison_text(3.5, &[previous], Some(&rank)).unwrap();
+        assert!(text.contains("Ranked *#2* of 10"));
+        assert!(text.contains("+0.50 compared to last rating (3.00)"));
+        assert!(text.contains("`06/26/24` 3.00 - Q: stinger"));
+        assert_eq!(comparison_text(3.5, &[], None), None);
+    }
 }
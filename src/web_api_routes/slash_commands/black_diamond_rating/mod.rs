@@ -1,25 +1,51 @@
+use crate::db::queries::black_diamond_ratings::{get_recent_ratings, BlackDiamondRatingDbData};
 use crate::shared::common_errors::AppError;
+use crate::shared::time::local_boise_time;
 use crate::slack_api::block_kit::BlockBuilder;
 use crate::slack_api::views::payload::{ViewModal, ViewPayload};
 use crate::slack_api::views::request::ViewsOpenRequest;
 use crate::web_api_routes::slash_commands::modal_utils::view_ids::ViewIds;
 use crate::web_api_state::MutableWebState;
+use sqlx::PgPool;
 
 pub mod black_diamond_rating_post;
 
+/// number of previous ratings to show for comparison
+pub const PREVIOUS_RATINGS_COUNT: i64 = 3;
+
 pub async fn generate_modal(
+    db_pool: &PgPool,
     trigger_id: &str,
     web_app: &MutableWebState,
     channel_id: &str,
 ) -> Result<(), AppError> {
-    let modal = create_modal(channel_id);
+    let previous = get_recent_ratings(db_pool, PREVIOUS_RATINGS_COUNT).await?;
+    let modal = create_modal(channel_id, &previous);
     let view = ViewsOpenRequest::new(trigger_id, ViewPayload::Modal(modal));
     web_app.open_view(view).await?;
     Ok(())
 }
 
-fn create_modal(channel_id: &str) -> ViewModal {
-    let block_builder = BlockBuilder::new()
+fn previous_ratings_text(previous: &[BlackDiamondRatingDbData]) -> String {
+    let ratings = previous
+        .iter()
+        .map(|rating| format!("`{}` {:.2}", rating.date.format("%m/%d/%y"), rating.total))
+        .collect::<Vec<String>>();
+    format!("Previous ratings: {}", ratings.join(" | "))
+}
+
+fn create_modal(channel_id: &str, previous: &[BlackDiamondRatingDbData]) -> ViewModal {
+    let mut block_builder = BlockBuilder::new();
+    if !previous.is_empty() {
+        block_builder.add_context(previous_ratings_text(previous).as_str());
+    }
+    let block_builder = block_builder
+        .date_picker(
+            "Beatdown Date",
+            black_diamond_rating_post::post_ids::DATE,
+            Some(local_boise_time().date_naive().to_string()),
+            false,
+        )
         .plain_input(
             "Number of Pax",
             black_diamond_rating_post::post_ids::PAX_COUNT,
@@ -0,0 +1,26 @@
+use crate::app_state::exicon::ExiconEntry;
+use crate::web_api_routes::interactive_events::interaction_payload::BasicValue;
+use crate::web_api_routes::slash_commands::modal_utils::value_utils;
+use std::collections::HashMap;
+
+pub mod post_ids {
+    pub const NAME: &str = "exicon_name.input";
+    pub const ALIASES: &str = "exicon_aliases.input";
+    pub const CATEGORY: &str = "exicon_category.input";
+    pub const TAGS: &str = "exicon_tags.input";
+    pub const DESCRIPTION: &str = "exicon_description.textarea";
+}
+
+impl From<HashMap<String, BasicValue>> for ExiconEntry {
+    fn from(value: HashMap<String, BasicValue>) -> Self {
+        let text = |action_id: &str| {
+            value_utils::get_value(&value, action_id, value_utils::get_single_string)
+                .unwrap_or_default()
+        };
+
+        ExiconEntry::new(&text(post_ids::NAME), &text(post_ids::DESCRIPTION))
+            .with_aliases(&text(post_ids::ALIASES))
+            .with_category(Some(text(post_ids::CATEGORY)))
+            .with_tags(&text(post_ids::TAGS))
+    }
+}
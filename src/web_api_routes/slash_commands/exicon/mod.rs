@@ -0,0 +1,177 @@
+use crate::app_state::exicon::{search_exicon, ExiconEntry};
+use crate::db::queries::exercises::{get_exicon_entries, get_exicon_entry};
+use crate::shared::common_errors::AppError;
+use crate::slack_api::block_kit::BlockBuilder;
+use crate::slack_api::views::payload::{ViewModal, ViewPayload};
+use crate::slack_api::views::request::ViewsOpenRequest;
+use crate::web_api_routes::slash_commands::modal_utils::view_ids::ViewIds;
+use crate::web_api_state::MutableWebState;
+use sqlx::PgPool;
+
+pub mod exicon_post;
+
+/// max number of other matches to list under the top match
+const MAX_SIMILAR: usize = 5;
+
+/// `/exicon <term>` to look up exercise, or `/exicon edit <name>` for admins to add / edit one.
+#[derive(Debug, PartialEq)]
+pub enum ExiconCommand {
+    Search(String),
+    Edit(String),
+}
+
+impl From<&str> for ExiconCommand {
+    fn from(text: &str) -> Self {
+        let text = text.trim();
+        match text.split_once(' ') {
+            Some((command, name)) if command.eq_ignore_ascii_case("edit") => {
+                ExiconCommand::Edit(name.trim().to_string())
+            }
+            _ if text.eq_ignore_ascii_case("edit") => ExiconCommand::Edit(String::new()),
+            _ => ExiconCommand::Search(text.to_string()),
+        }
+    }
+}
+
+/// ephemeral card of best exicon match for term
+pub async fn get_exicon_card(db_pool: &PgPool, term: &str) -> Result<BlockBuilder, AppError> {
+    if term.trim().is_empty() {
+        return Ok(BlockBuilder::new()
+            .section_markdown("Look up an exercise with `/exicon <name>` (ie. `/exicon merkin`)"));
+    }
+    let entries = get_exicon_entries(db_pool).await?;
+    let matches = search_exicon(&entries, term);
+    Ok(exicon_card(term, &matches))
+}
+
+fn exicon_card(term: &str, matches: &[&ExiconEntry]) -> BlockBuilder {
+    let Some((entry, similar)) = matches.split_first() else {
+        return BlockBuilder::new().section_markdown(
+            format!("No exercise found for *{}* :man-shrugging:", term.trim()).as_str(),
+        );
+    };
+
+    let mut details = Vec::<String>::new();
+    if !entry.aliases.is_empty() {
+        details.push(format!("*Also known as*: {}", entry.aliases.join(", ")));
+    }
+    if let Some(category) = &entry.category {
+        details.push(format!("*Category*: {}", category));
+    }
+    if !entry.tags.is_empty() {
+        details.push(format!("*Tags*: {}", entry.tags.join(", ")));
+    }
+
+    let mut block_builder = BlockBuilder::new()
+        .header(entry.name.as_str())
+        .section_markdown(entry.description.as_str());
+    if !details.is_empty() {
+        block_builder.add_context(details.join(" | ").as_str());
+    }
+    if !similar.is_empty() {
+        let similar = similar
+            .iter()
+            .take(MAX_SIMILAR)
+            .map(|entry| entry.name.to_string())
+            .collect::<Vec<String>>();
+        block_builder.add_context(format!("Similar: {}", similar.join(", ")).as_str());
+    }
+    block_builder
+}
+
+/// open modal to add or edit exicon entry
+pub async fn generate_edit_modal(
+    db_pool: &PgPool,
+    trigger_id: &str,
+    web_app: &MutableWebState,
+    name: &str,
+) -> Result<(), AppError> {
+    let entry = if name.is_empty() {
+        None
+    } else {
+        get_exicon_entry(db_pool, name).await?
+    };
+    let modal = create_edit_modal(entry.as_ref(), name);
+    let view = ViewsOpenRequest::new(trigger_id, ViewPayload::Modal(modal));
+    web_app.open_view(view).await?;
+    Ok(())
+}
+
+fn create_edit_modal(entry: Option<&ExiconEntry>, name: &str) -> ViewModal {
+    let non_empty = |value: String| Some(value).filter(|value| !value.is_empty());
+    let block_builder = BlockBuilder::new()
+        .plain_input(
+            "Name",
+            exicon_post::post_ids::NAME,
+            Some("Merkin".to_string()),
+            entry
+                .map(|entry| entry.name.to_string())
+                .or_else(|| non_empty(name.to_string())),
+            false,
+        )
+        .plain_input(
+            "Also known as (separated by commas)",
+            exicon_post::post_ids::ALIASES,
+            Some("Pushup".to_string()),
+            entry.and_then(|entry| non_empty(entry.aliases.join(", "))),
+            true,
+        )
+        .plain_input(
+            "Category",
+            exicon_post::post_ids::CATEGORY,
+            Some("Upper body".to_string()),
+            entry.and_then(|entry| entry.category.clone()),
+            true,
+        )
+        .plain_input(
+            "Tags (separated by commas)",
+            exicon_post::post_ids::TAGS,
+            Some("chest, arms".to_string()),
+            entry.and_then(|entry| non_empty(entry.tags.join(", "))),
+            true,
+        )
+        .text_box(
+            "Description",
+            exicon_post::post_ids::DESCRIPTION,
+            Some("How to do it".to_string()),
+            entry.and_then(|entry| non_empty(entry.description.to_string())),
+            false,
+        );
+
+    let modal = ViewModal::new("Exicon", block_builder, "Save", ViewIds::ExiconEdit);
+    match entry {
+        // keep track of original name in case it gets renamed
+        Some(entry) => modal.with_private_meta(entry.name.as_str()),
+        None => modal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_command() {
+        assert_eq!(
+            ExiconCommand::from(" merkin "),
+            ExiconCommand::Search(String::from("merkin"))
+        );
+        assert_eq!(
+            ExiconCommand::from("Edit Big Boy Situp"),
+            ExiconCommand::Edit(String::from("Big Boy Situp"))
+        );
+        assert_eq!(
+            ExiconCommand::from("edit"),
+            ExiconCommand::Edit(String::new())
+        );
+    }
+
+    #[test]
+    fn card_with_similar() {
+        let merkin = ExiconEntry::new("Merkin", "A pushup.").with_aliases("pushup");
+        let diamond = ExiconEntry::new("Diamond Merkin", "Hands in diamond shape.");
+        let card = exicon_card("merkin", &[&merkin, &diamond]);
+        assert_eq!(card.blocks.len(), 4);
+        assert_eq!(exicon_card("burpee", &[]).blocks.len(), 1);
+    }
+}
@@ -1,13 +1,18 @@
 use crate::app_state::ao_data::AO;
 use crate::app_state::backblast_data::BackBlastType;
+use crate::app_state::roles::Permission;
 use crate::app_state::MutableAppState;
+use crate::db::queries::user_roles::{get_user_roles, user_can};
 use crate::db::queries::users::get_user_name_map;
 use crate::shared::time::local_boise_time;
 use crate::web_api_routes::graphs::ao_monthly_leaderboard::get_ao_monthly_stats_graph;
 use crate::web_api_routes::graphs::ao_pax_leaderboard::post_ao_pax_leaderboard_graph;
+use crate::web_api_routes::graphs::black_diamond_ratings::post_black_diamond_trend_graph;
 use crate::web_api_routes::graphs::overall_pax_leaderboard::{
     post_overall_pax_dd_leaderboard_graph, post_overall_pax_leaderboard_graph,
 };
+use crate::web_api_routes::graphs::ruck_leaderboard::post_ruck_leaderboard_graphs;
+use crate::web_api_routes::pax_data::merge_pax::handle_merge_pax_command;
 use crate::web_api_routes::slash_commands::ao_monthly_stats_graph::AOMonthlyStatsGraphCommand;
 use crate::web_api_routes::slash_commands::ao_stats::get_ao_stats_block;
 use crate::web_api_routes::slash_commands::invite_all::handle_invite_all;
@@ -24,18 +29,25 @@ use actix_web::{web, HttpResponse, Responder};
 use serde::Deserialize;
 use sqlx::PgPool;
 
+pub mod ao_closure;
 pub mod ao_monthly_stats_graph;
 pub mod ao_stats;
 pub mod back_blast;
 pub mod black_diamond_rating;
 mod check_name;
+pub mod exicon;
 pub mod invite_all;
 pub mod modal_utils;
+pub mod my_pre_blasts;
 pub mod my_stats;
 pub mod pre_blast;
 pub mod q_line_up;
+pub mod q_recurrences;
+pub mod roles;
+pub mod ruck_stats;
 pub mod top_pax;
 pub mod wheres_freighter;
+pub mod workout_score;
 
 /// respond to slash commands
 pub async fn slack_slash_commands_route(
@@ -50,19 +62,29 @@ pub async fn slack_slash_commands_route(
     }
 
     println!("form: {:?}", form);
+    let registry = app_state.get_ao_registry();
     match form.command.as_str() {
-        "/my-stats" => match handle_my_stats(&db_pool, &form).await {
+        "/my-stats" => match handle_my_stats(&db_pool, &form, &registry).await {
             Ok(response) => HttpResponse::Ok().json(response),
             Err(err) => {
                 println!("stats error: {}", err);
                 HttpResponse::BadRequest().body(err.to_string())
             }
         },
-        "/invite-all" => match handle_invite_all(&db_pool, &web_state, &form).await {
-            Ok(response) => HttpResponse::Ok().body(response),
-            Err(err) => HttpResponse::BadRequest().body(err.to_string()),
+        "/my-preblasts" => match my_pre_blasts::handle_my_pre_blasts(&db_pool, &form).await {
+            Ok(response) => HttpResponse::Ok().json(response),
+            Err(err) => HttpResponse::Ok().body(err.to_string()),
         },
-        "/q-sheet" | "/post-q-sheet" => match QLineUpCommand::from(form.text.as_str()) {
+        "/invite-all" => {
+            if !allowed(&db_pool, &form, &Permission::InviteAll).await {
+                return HttpResponse::Ok().body("Only comz and admins can invite all");
+            }
+            match handle_invite_all(&db_pool, &web_state, &form).await {
+                Ok(response) => HttpResponse::Ok().body(response),
+                Err(err) => HttpResponse::BadRequest().body(err.to_string()),
+            }
+        }
+        "/q-sheet" | "/post-q-sheet" => match QLineUpCommand::new(form.text.as_str(), &registry) {
             QLineUpCommand { ao: None, month } => {
                 let users = get_user_name_map(&db_pool).await.unwrap_or_default();
                 let start_date = month
@@ -71,7 +93,7 @@ pub async fn slack_slash_commands_route(
 
                 // see if request came from ao channel, then filter to ao data only, otherwise all
 
-                let possible_ao = AO::from_channel_id(form.channel_id.as_str());
+                let possible_ao = AO::from_channel_id(form.channel_id.as_str(), &registry);
                 let possible_ao = match possible_ao {
                     AO::Unknown(_) => None,
                     _ => Some(possible_ao),
@@ -88,6 +110,7 @@ pub async fn slack_slash_commands_route(
                                 &users,
                                 form.channel_id.as_str(),
                                 &web_state,
+                                &registry,
                             )
                             .await
                             {
@@ -101,6 +124,7 @@ pub async fn slack_slash_commands_route(
                                 &users,
                                 form.channel_id.as_str(),
                                 &web_state,
+                                &registry,
                             )
                             .await
                             {
@@ -112,12 +136,21 @@ pub async fn slack_slash_commands_route(
                     // this will be the silent response where only the requester will see.
                     "/q-sheet" => {
                         if let Some(ao) = possible_ao {
-                            match get_q_line_up_for_ao(&db_pool, ao, &start_date, &users).await {
+                            match get_q_line_up_for_ao(&db_pool, ao, &start_date, &users, &registry)
+                                .await
+                            {
                                 Ok(builder) => HttpResponse::Ok().json(builder),
                                 Err(err) => HttpResponse::BadRequest().body(err.to_string()),
                             }
                         } else {
-                            match get_q_line_up_message_all(&db_pool, &start_date, &users).await {
+                            match get_q_line_up_message_all(
+                                &db_pool,
+                                &start_date,
+                                &users,
+                                &registry,
+                            )
+                            .await
+                            {
                                 Ok(builder) => HttpResponse::Ok().json(builder),
                                 Err(err) => HttpResponse::BadRequest().body(err.to_string()),
                             }
@@ -141,6 +174,7 @@ pub async fn slack_slash_commands_route(
                     &users,
                     form.channel_id.as_str(),
                     &web_state,
+                    &registry,
                 )
                 .await
                 {
@@ -153,15 +187,20 @@ pub async fn slack_slash_commands_route(
             Ok(builder) => HttpResponse::Ok().json(builder),
             Err(err) => HttpResponse::BadRequest().body(err.to_string()),
         },
-        "/resync-bot" => match sync_data_to_state(&db_pool, &web_state, &app_state).await {
-            Ok(()) => HttpResponse::Ok().body("Re-synced Boise bot"),
-            Err(err) => HttpResponse::BadRequest().body(err.to_string()),
-        },
-        "/ao-stats" => match get_ao_stats_block(&db_pool, &form).await {
+        "/resync-bot" => {
+            if !allowed(&db_pool, &form, &Permission::Sync).await {
+                return HttpResponse::Ok().body("Only admins can re-sync the bot");
+            }
+            match sync_data_to_state(&db_pool, &web_state, &app_state).await {
+                Ok(()) => HttpResponse::Ok().body("Re-synced Boise bot"),
+                Err(err) => HttpResponse::BadRequest().body(err.to_string()),
+            }
+        }
+        "/ao-stats" => match get_ao_stats_block(&db_pool, &form, &registry).await {
             Ok(response) => HttpResponse::Ok().json(response),
             Err(err) => HttpResponse::BadRequest().body(err.to_string()),
         },
-        "/wheres-freighter" => match get_wheres_freighter_message(&db_pool).await {
+        "/wheres-freighter" => match get_wheres_freighter_message(&db_pool, &registry).await {
             Ok(response) => HttpResponse::Ok().json(response),
             Err(err) => HttpResponse::BadRequest().body(err.to_string()),
         },
@@ -171,6 +210,7 @@ pub async fn slack_slash_commands_route(
                 &db_pool,
                 &Some(command.month),
                 &web_state,
+                &registry,
                 form.channel_id.to_string(),
             )
             .await
@@ -180,8 +220,13 @@ pub async fn slack_slash_commands_route(
             }
         }
         "/top-pax-30-days" => {
-            match post_ao_pax_leaderboard_graph(&db_pool, &web_state, form.channel_id.to_string())
-                .await
+            match post_ao_pax_leaderboard_graph(
+                &db_pool,
+                &web_state,
+                &registry,
+                form.channel_id.to_string(),
+            )
+            .await
             {
                 Ok(_) => HttpResponse::Ok().body("Posting Top Pax stats"),
                 Err(err) => HttpResponse::Ok().body(err.to_string()),
@@ -219,6 +264,7 @@ pub async fn slack_slash_commands_route(
                 &web_state,
                 &form.channel_id,
                 &form.user_id,
+                &registry,
             )
             .await
             {
@@ -232,6 +278,7 @@ pub async fn slack_slash_commands_route(
                 &web_state,
                 &form.channel_id,
                 &form.user_id,
+                &registry,
             )
             .await
             {
@@ -247,11 +294,13 @@ pub async fn slack_slash_commands_route(
                 _ => BackBlastType::BackBlast,
             };
             match back_blast::generate_modal(
+                &db_pool,
                 form.trigger_id.as_str(),
                 &web_state,
                 &form.channel_id,
                 &form.user_id,
                 back_blast_type,
+                &registry,
             )
             .await
             {
@@ -259,14 +308,129 @@ pub async fn slack_slash_commands_route(
                 Err(err) => HttpResponse::Ok().body(err.to_string()),
             }
         }
-        "/black-diamond-grading" => match black_diamond_rating::generate_modal(
+        "/black-diamond-grading" => match form.text.trim().split_once("graph") {
+            // `graph [range]` posts trend of ratings to channel
+            Some(("", range)) => match post_black_diamond_trend_graph(
+                &db_pool,
+                &web_state,
+                form.channel_id.to_string(),
+                range.trim(),
+            )
+            .await
+            {
+                Ok(_) => HttpResponse::Ok().body("Posting Black Diamond ratings"),
+                Err(err) => HttpResponse::Ok().body(err.to_string()),
+            },
+            _ => match black_diamond_rating::generate_modal(
+                &db_pool,
+                form.trigger_id.as_str(),
+                &web_state,
+                &form.channel_id,
+            )
+            .await
+            {
+                Ok(_) => HttpResponse::Ok().body("Opening Black Diamond rating modal"),
+                Err(err) => HttpResponse::Ok().body(err.to_string()),
+            },
+        },
+        "/ruck-stats" => match ruck_stats::RuckStatsCommand::from(form.text.as_str()) {
+            ruck_stats::RuckStatsCommand::Graph(period) => match post_ruck_leaderboard_graphs(
+                &db_pool,
+                &web_state,
+                form.channel_id.to_string(),
+                period,
+            )
+            .await
+            {
+                Ok(_) => HttpResponse::Ok().body("Posting ruck leaderboard"),
+                Err(err) => HttpResponse::Ok().body(err.to_string()),
+            },
+            ruck_stats::RuckStatsCommand::Stats => {
+                match ruck_stats::handle_ruck_stats(&db_pool, &form).await {
+                    Ok(response) => HttpResponse::Ok().json(response),
+                    Err(err) => HttpResponse::Ok().body(err.to_string()),
+                }
+            }
+        },
+        "/q-recurring" => match q_recurrences::generate_modal(
+            &db_pool,
             form.trigger_id.as_str(),
             &web_state,
             &form.channel_id,
+            &form.user_id,
+            &registry,
         )
         .await
         {
-            Ok(_) => HttpResponse::Ok().body("Opening Black Diamond rating modal"),
+            Ok(_) => HttpResponse::Ok().body("Opening recurring Qs modal"),
+            Err(err) => HttpResponse::Ok().body(err.to_string()),
+        },
+        "/workout-score" => match workout_score::generate_modal(
+            &db_pool,
+            form.trigger_id.as_str(),
+            &web_state,
+            &form.channel_id,
+        )
+        .await
+        {
+            Ok(_) => HttpResponse::Ok().body("Opening workout score modal"),
+            Err(err) => HttpResponse::Ok().body(err.to_string()),
+        },
+        "/ao-closure" => {
+            let is_site_q = get_user_roles(&db_pool, &form.user_id)
+                .await
+                .map(|roles| roles.is_site_q())
+                .unwrap_or(false);
+            if !is_site_q {
+                return HttpResponse::Ok().body("Only site Qs can add closures");
+            }
+            match ao_closure::generate_modal(
+                form.trigger_id.as_str(),
+                &web_state,
+                &form.channel_id,
+                &registry,
+            )
+            .await
+            {
+                Ok(_) => HttpResponse::Ok().body("Opening AO closure modal"),
+                Err(err) => HttpResponse::Ok().body(err.to_string()),
+            }
+        }
+        "/merge-pax" => {
+            if !allowed(&db_pool, &form, &Permission::MergePax).await {
+                return HttpResponse::Ok().body("Only site Qs can merge pax");
+            }
+            match handle_merge_pax_command(&db_pool, &form).await {
+                Ok(response) => HttpResponse::Ok().json(response),
+                Err(err) => HttpResponse::Ok().body(err.to_string()),
+            }
+        }
+        "/exicon" => match exicon::ExiconCommand::from(form.text.as_str()) {
+            exicon::ExiconCommand::Edit(name) => {
+                if !allowed(&db_pool, &form, &Permission::EditExicon).await {
+                    return HttpResponse::Ok().body("Only site Qs can edit the exicon");
+                }
+                match exicon::generate_edit_modal(
+                    &db_pool,
+                    form.trigger_id.as_str(),
+                    &web_state,
+                    name.as_str(),
+                )
+                .await
+                {
+                    Ok(_) => HttpResponse::Ok().body("Opening Exicon modal"),
+                    Err(err) => HttpResponse::Ok().body(err.to_string()),
+                }
+            }
+            exicon::ExiconCommand::Search(term) => {
+                match exicon::get_exicon_card(&db_pool, term.as_str()).await {
+                    Ok(response) => HttpResponse::Ok().json(response),
+                    Err(err) => HttpResponse::Ok().body(err.to_string()),
+                }
+            }
+        },
+        "/roles" => match roles::handle_roles_command(&db_pool, &form, &registry).await {
+            Ok(response) => HttpResponse::Ok().json(response),
             Err(err) => HttpResponse::Ok().body(err.to_string()),
         },
         "/check-name" => match check_name::pax_name_taken(&db_pool, &form).await {
@@ -280,6 +444,13 @@ pub async fn slack_slash_commands_route(
     }
 }
 
+/// whether user running command is allowed to do action
+async fn allowed(db_pool: &PgPool, form: &SlashCommandForm, permission: &Permission) -> bool {
+    user_can(db_pool, &form.user_id, permission)
+        .await
+        .unwrap_or(false)
+}
+
 #[derive(Deserialize, Debug)]
 pub struct SlashCommandForm {
     pub token: String,
@@ -1,4 +1,5 @@
 use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
 use crate::app_state::backblast_data::BackBlastType;
 use crate::app_state::equipment::AoEquipment;
 use crate::web_api_routes::interactive_events::interaction_payload::BasicValue;
@@ -37,8 +38,9 @@ pub fn get_single_time(value: &BasicValue) -> NaiveTime {
     NaiveTime::parse_from_str(&value.get_single().unwrap_or_default(), "%H:%M").unwrap_or_default()
 }
 
-pub fn get_ao_value(value: &BasicValue) -> AO {
-    AO::from_channel_id(value.get_single().unwrap_or_default().as_str())
+pub fn get_ao_value(value: &BasicValue, registry: &AoRegistry) -> AO {
+    let channel_id = value.get_single().unwrap_or_default();
+    AO::from_channel_id(channel_id.as_str(), registry)
 }
 
 /// get hashset of strings from multi value
@@ -8,6 +8,11 @@ pub enum ViewIds {
     BackBlast,
     BackBlastEdit,
     BlackDiamondRating,
+    AoClosure,
+    ExiconEdit,
+    WorkoutScore,
+    QSwap,
+    QRecurrence,
     #[default]
     Unknown,
 }
@@ -22,6 +27,11 @@ impl Display for ViewIds {
             ViewIds::PreBlast => format!("{}::{uid}", PRE_BLAST_ID),
             ViewIds::PreBlastEdit => format!("{}::{uid}", PRE_BLAST_EDIT_ID),
             ViewIds::BlackDiamondRating => format!("{}::{uid}", BLACK_DIAMOND_RATING_ID),
+            ViewIds::AoClosure => format!("{}::{uid}", AO_CLOSURE_ID),
+            ViewIds::ExiconEdit => format!("{}::{uid}", EXICON_EDIT_ID),
+            ViewIds::WorkoutScore => format!("{}::{uid}", WORKOUT_SCORE_ID),
+            ViewIds::QSwap => format!("{}::{uid}", Q_SWAP_ID),
+            ViewIds::QRecurrence => format!("{}::{uid}", Q_RECURRENCE_ID),
             ViewIds::Unknown => "UNKNOWN".to_string(),
         };
         write!(f, "{}", str)
@@ -37,6 +47,11 @@ impl From<&str> for ViewIds {
             PRE_BLAST_ID => ViewIds::PreBlast,
             PRE_BLAST_EDIT_ID => ViewIds::PreBlastEdit,
             BLACK_DIAMOND_RATING_ID => ViewIds::BlackDiamondRating,
+            AO_CLOSURE_ID => ViewIds::AoClosure,
+            EXICON_EDIT_ID => ViewIds::ExiconEdit,
+            WORKOUT_SCORE_ID => ViewIds::WorkoutScore,
+            Q_SWAP_ID => ViewIds::QSwap,
+            Q_RECURRENCE_ID => ViewIds::QRecurrence,
             _ => ViewIds::Unknown,
         }
     }
@@ -47,6 +62,11 @@ const BACK_BLAST_EDIT_ID: &str = "back_blast_edit";
 const PRE_BLAST_ID: &str = "pre_blast";
 const PRE_BLAST_EDIT_ID: &str = "pre_blast_edit";
 const BLACK_DIAMOND_RATING_ID: &str = "black_diamond_rating";
+const AO_CLOSURE_ID: &str = "ao_closure";
+const EXICON_EDIT_ID: &str = "exicon_edit";
+const WORKOUT_SCORE_ID: &str = "workout_score";
+const Q_SWAP_ID: &str = "q_swap";
+const Q_RECURRENCE_ID: &str = "q_recurrence";
 
 #[cfg(test)]
 mod tests {
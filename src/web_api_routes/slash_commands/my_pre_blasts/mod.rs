@@ -0,0 +1,78 @@
+use crate::db::queries::pre_blasts::{get_pending_pre_blasts_by_user, PendingPreBlast};
+use crate::shared::common_errors::AppError;
+use crate::shared::time::to_boise_time;
+use crate::slack_api::block_kit::BlockBuilder;
+use crate::web_api_routes::interactive_events::interaction_types::InteractionTypes;
+use crate::web_api_routes::slash_commands::SlashCommandForm;
+use sqlx::PgPool;
+
+/// summary line of pending preblast (ie. `*Leg Day* - <#C123> Thu Oct 22 at 5:15 AM, posts Wed Oct 21 at 7:00 PM`)
+fn pending_text(pending: &PendingPreBlast) -> String {
+    format!(
+        "*{}* - <#{}> {} at {}, posts {}",
+        pending.title,
+        pending.channel_id,
+        pending.date.format("%a %b %-d"),
+        pending.start_time.format("%-I:%M %p"),
+        to_boise_time(pending.post_at).format("%a %b %-d at %-I:%M %p")
+    )
+}
+
+/// list preblasts user scheduled that haven't posted yet, with buttons to edit or cancel
+pub async fn handle_my_pre_blasts(
+    db_pool: &PgPool,
+    form: &SlashCommandForm,
+) -> Result<BlockBuilder, AppError> {
+    let pending = get_pending_pre_blasts_by_user(db_pool, &form.user_id).await?;
+    if pending.is_empty() {
+        return Ok(BlockBuilder::new().section_markdown(
+            "No scheduled preblasts. Pick a post at date or time in `/preblast` to schedule one.",
+        ));
+    }
+
+    let mut block_builder = BlockBuilder::new().section_markdown("*Your scheduled preblasts:*");
+    for item in pending.iter() {
+        let id = item.id.to_string();
+        block_builder.add_section_markdown(&pending_text(item));
+        block_builder.add_btn(
+            "Edit Preblast",
+            InteractionTypes::new_edit_pre_blast(&id)
+                .to_string()
+                .as_str(),
+            "edit-preblast",
+        );
+        block_builder.add_btn(
+            "Cancel Preblast",
+            InteractionTypes::new_cancel_pre_blast(&id)
+                .to_string()
+                .as_str(),
+            "cancel-preblast",
+        );
+    }
+    Ok(block_builder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveTime, TimeZone, Utc};
+    use uuid::Uuid;
+
+    #[test]
+    fn pending_summary() {
+        let pending = PendingPreBlast {
+            id: Uuid::nil(),
+            channel_id: "C123".to_string(),
+            title: "Leg Day".to_string(),
+            date: NaiveDate::from_ymd_opt(2026, 10, 22).unwrap(),
+            start_time: NaiveTime::from_hms_opt(5, 15, 0).unwrap(),
+            // 7pm MDT
+            post_at: Utc.with_ymd_and_hms(2026, 10, 22, 1, 0, 0).unwrap(),
+            scheduled_by: None,
+        };
+        assert_eq!(
+            pending_text(&pending),
+            "*Leg Day* - <#C123> Thu Oct 22 at 5:15 AM, posts Wed Oct 21 at 7:00 PM"
+        );
+    }
+}
@@ -1,12 +1,18 @@
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
 use crate::app_state::backblast_data::BackBlastData;
+use crate::app_state::streaks::PaxStreaks;
 use crate::db::queries::all_back_blasts::{
     get_dd_list_with_pax, get_list_with_pax, BackBlastJsonData,
 };
+use crate::db::queries::ao_closures::get_closure_calendar;
 use crate::db::queries::users::get_user_by_slack_id;
 use crate::shared::common_errors::AppError;
+use crate::shared::time::local_boise_time;
 use crate::slack_api::block_kit::BlockBuilder;
 use crate::web_api_routes::pax_data::PaxInfoResponse;
 use crate::web_api_routes::slash_commands::SlashCommandForm;
+use chrono::NaiveDate;
 use sqlx::PgPool;
 
 /// parse user backblast data to pax info (TODO should be converted to sql query)
@@ -46,10 +52,28 @@ pub fn get_pax_info_from_bb_data(
     result
 }
 
+/// current streaks of pax from their backblasts
+pub async fn get_streaks_from_bb_data(
+    db_pool: &PgPool,
+    list: &[BackBlastJsonData],
+    registry: &AoRegistry,
+) -> Result<PaxStreaks, AppError> {
+    let today = local_boise_time().date_naive();
+    let posts = list
+        .iter()
+        .map(BackBlastData::from)
+        .map(|bb| (bb.ao, bb.date))
+        .collect::<Vec<(AO, NaiveDate)>>();
+    let earliest = posts.iter().map(|(_, date)| *date).min().unwrap_or(today);
+    let closures = get_closure_calendar(db_pool, &earliest, &today).await?;
+    Ok(PaxStreaks::new(&posts, &closures, registry, &today))
+}
+
 /// handle getting response for my stats.
 pub async fn handle_my_stats(
     db_pool: &PgPool,
     form: &SlashCommandForm,
+    registry: &AoRegistry,
 ) -> Result<BlockBuilder, AppError> {
     let user_name = get_user_by_slack_id(db_pool, &form.user_id)
         .await
@@ -62,8 +86,11 @@ pub async fn handle_my_stats(
 
     let user_name = user_name.unwrap();
 
-    let response = get_user_stats_by_name(db_pool, user_name.as_str()).await?;
-    let block_builder = BlockBuilder::new()
+    let list = get_list_with_pax(db_pool, user_name.as_str()).await?;
+    let dd = get_dd_list_with_pax(db_pool, user_name.as_str()).await?;
+    let response = get_pax_info_from_bb_data(&list, &dd, user_name.as_str());
+    let streaks = get_streaks_from_bb_data(db_pool, &list, registry).await?;
+    let mut block_builder = BlockBuilder::new()
         .section_markdown(format!("*Here are your stats {}:*", response.name).as_str())
         .section_markdown(format!("*Total Posts*: {}", response.post_count).as_str())
         .section_markdown(format!("*Q Posts*: {}", response.q_count).as_str())
@@ -76,6 +103,9 @@ pub async fn handle_my_stats(
             )
             .as_str(),
         );
+    for line in streaks.display_lines(registry) {
+        block_builder.add_section_markdown(&line);
+    }
 
     Ok(block_builder)
 }
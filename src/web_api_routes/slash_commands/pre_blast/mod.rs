@@ -1,4 +1,5 @@
 use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
 use crate::app_state::equipment::AoEquipment;
 use crate::shared::common_errors::AppError;
 use crate::shared::time::local_boise_time;
@@ -9,7 +10,7 @@ use crate::slack_api::views::request::ViewsOpenRequest;
 use crate::web_api_routes::slash_commands::modal_utils::view_ids::ViewIds;
 use crate::web_api_routes::slash_commands::modal_utils::{default_post_option, where_to_post_list};
 use crate::web_api_state::MutableWebState;
-use chrono::{Datelike, Duration, NaiveDate};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
 
 pub mod pre_blast_post;
 
@@ -18,21 +19,42 @@ pub async fn generate_modal(
     web_app: &MutableWebState,
     channel_id: &str,
     user_id: &str,
+    registry: &AoRegistry,
 ) -> Result<(), AppError> {
-    let modal = create_pre_blast_modal(channel_id, user_id);
+    let modal = create_pre_blast_modal(channel_id, user_id, None, registry);
     let request = ViewsOpenRequest::new(trigger_id, ViewPayload::Modal(modal));
     web_app.open_view(request).await?;
     Ok(())
 }
 
-fn create_pre_blast_modal(channel_id: &str, user_id: &str) -> ViewModal {
-    let ao = AO::from_channel_id(channel_id);
-    let next_date = get_next_ao_date(&ao);
+/// open preblast modal for specific workout date (ie. from Q reminder)
+pub async fn generate_modal_for_date(
+    trigger_id: &str,
+    web_app: &MutableWebState,
+    channel_id: &str,
+    user_id: &str,
+    date: NaiveDate,
+    registry: &AoRegistry,
+) -> Result<(), AppError> {
+    let modal = create_pre_blast_modal(channel_id, user_id, Some(date), registry);
+    let request = ViewsOpenRequest::new(trigger_id, ViewPayload::Modal(modal));
+    web_app.open_view(request).await?;
+    Ok(())
+}
+
+fn create_pre_blast_modal(
+    channel_id: &str,
+    user_id: &str,
+    date: Option<NaiveDate>,
+    registry: &AoRegistry,
+) -> ViewModal {
+    let ao = AO::from_channel_id(channel_id, registry);
+    let next_date = date.unwrap_or_else(|| get_next_ao_date(&ao, registry));
     let default_time = ao
-        .default_time(&next_date.weekday())
+        .default_time(&next_date.weekday(), registry)
         .map(|time| time.format("%H:%M").to_string());
     let ao_equipment = ao
-        .ao_type()
+        .ao_type(registry)
         .equipment()
         .into_iter()
         .map(OptionElement::from)
@@ -45,7 +67,7 @@ fn create_pre_blast_modal(channel_id: &str, user_id: &str) -> ViewModal {
     };
 
     // build blocks
-    let block_builder = BlockBuilder::new()
+    let mut block_builder = BlockBuilder::new()
         .plain_input(
             "Title",
             pre_blast_post::pre_blast_action_ids::TITLE,
@@ -119,7 +141,9 @@ fn create_pre_blast_modal(channel_id: &str, user_id: &str) -> ViewModal {
             pre_blast_post::pre_blast_action_ids::FILE,
             vec!["jpg", "jpeg", "png", "gif"],
             true,
-        )
+        );
+    add_post_at_inputs(&mut block_builder, None);
+    let block_builder = block_builder
         .select(
             "Choose where to post this",
             pre_blast_post::pre_blast_action_ids::WHERE_POST,
@@ -132,12 +156,35 @@ fn create_pre_blast_modal(channel_id: &str, user_id: &str) -> ViewModal {
     ViewModal::new("Pre Blast", block_builder, "Submit", ViewIds::PreBlast)
 }
 
-fn get_next_ao_date(ao: &AO) -> NaiveDate {
+/// optional date / time to schedule preblast instead of posting right away.
+/// Prefilled with current schedule when editing pending preblast.
+pub fn add_post_at_inputs(block_builder: &mut BlockBuilder, post_at: Option<NaiveDateTime>) {
+    block_builder.add_date_picker(
+        "Post at (date)",
+        pre_blast_post::pre_blast_action_ids::POST_AT_DATE,
+        post_at.map(|post_at| post_at.date().to_string()),
+        true,
+    );
+    block_builder.add_time_picker(
+        "Post at (time)",
+        pre_blast_post::pre_blast_action_ids::POST_AT_TIME,
+        post_at.map(|post_at| post_at.format("%H:%M").to_string()),
+        true,
+    );
+    block_builder.add_context(
+        "Leave blank to post now. Only a time posts the day before the workout, only a date posts at 7pm. Scheduled preblasts post to the AO channel.",
+    );
+}
+
+fn get_next_ao_date(ao: &AO, registry: &AoRegistry) -> NaiveDate {
     let now = local_boise_time().date_naive();
     let mut current_date_check = now + Duration::days(1);
     // Only check up to a week
     while current_date_check.signed_duration_since(now).num_days() < 7 {
-        if ao.week_days().contains(&current_date_check.weekday()) {
+        if ao
+            .week_days(registry)
+            .contains(&current_date_check.weekday())
+        {
             return current_date_check;
         }
         current_date_check += Duration::days(1);
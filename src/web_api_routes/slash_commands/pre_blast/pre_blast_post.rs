@@ -1,6 +1,9 @@
 use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
 use crate::app_state::equipment::AoEquipment;
+use crate::app_state::hard_commits::hc_count_text;
 use crate::db::queries::users::get_user_by_slack_id;
+use crate::shared::time::{boise_datetime, local_boise_time};
 use crate::slack_api::block_kit::block_elements::OptionElement;
 use crate::slack_api::block_kit::BlockBuilder;
 use crate::slack_api::chat::post_message::request::PostMessageRequest;
@@ -8,7 +11,7 @@ use crate::slack_api::chat::update_message::request::UpdateMessageRequest;
 use crate::web_api_routes::interactive_events::interaction_payload::BasicValue;
 use crate::web_api_routes::interactive_events::interaction_types::InteractionTypes;
 use crate::web_api_routes::slash_commands::modal_utils::{value_utils, BlastWhere};
-use chrono::{NaiveDate, NaiveTime};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use sqlx::PgPool;
 use std::collections::{HashMap, HashSet};
 
@@ -25,6 +28,10 @@ pub struct PreBlastPost {
     pub mole_skin: Option<String>,
     pub post_where: BlastWhere,
     pub img_ids: HashSet<String>,
+    /// names of pax that HC'd (from reactions on posted preblast)
+    pub hcs: Vec<String>,
+    /// local time to post preblast at, if scheduled
+    pub post_at: Option<NaiveDateTime>,
 }
 
 impl PreBlastPost {
@@ -61,13 +68,22 @@ impl PreBlastPost {
     pub fn img_ids(&self) -> Vec<String> {
         self.img_ids.iter().map(|url| url.to_string()).collect()
     }
+
+    /// scheduled post time if still in the future, otherwise preblast gets posted now
+    pub fn future_post_at(&self) -> Option<DateTime<Utc>> {
+        self.post_at
+            .map(boise_datetime)
+            .filter(|post_at| *post_at > local_boise_time())
+            .map(|post_at| post_at.with_timezone(&Utc))
+    }
 }
 
-impl From<HashMap<String, BasicValue>> for PreBlastPost {
-    fn from(value: HashMap<String, BasicValue>) -> Self {
+impl PreBlastPost {
+    /// post from modal values, with AO of selected channel from registry
+    pub fn from_values(value: HashMap<String, BasicValue>, registry: &AoRegistry) -> Self {
         let ao = value
             .get(pre_blast_action_ids::AO_SELECT)
-            .map(value_utils::get_ao_value)
+            .map(|value| value_utils::get_ao_value(value, registry))
             .unwrap_or_else(|| AO::Unknown("Not Parsed".to_string()));
 
         let title = value
@@ -131,6 +147,16 @@ impl From<HashMap<String, BasicValue>> for PreBlastPost {
             .get(pre_blast_action_ids::MOLE_SKINE)
             .map(value_utils::get_single_string);
 
+        let post_at_date = value
+            .get(pre_blast_action_ids::POST_AT_DATE)
+            .and_then(|v| v.get_single())
+            .and_then(|v| NaiveDate::parse_from_str(&v, "%Y-%m-%d").ok());
+
+        let post_at_time = value
+            .get(pre_blast_action_ids::POST_AT_TIME)
+            .and_then(|v| v.get_single())
+            .and_then(|v| NaiveTime::parse_from_str(&v, "%H:%M").ok());
+
         let post_where = value
             .get(pre_blast_action_ids::WHERE_POST)
             .map(value_utils::get_blast_where_value)
@@ -148,10 +174,31 @@ impl From<HashMap<String, BasicValue>> for PreBlastPost {
             mole_skin,
             post_where,
             img_ids,
+            hcs: Vec::new(),
+            post_at: scheduled_post_at(&date, post_at_date, post_at_time),
         }
     }
 }
 
+/// default time to post scheduled preblast (evening before workout)
+const DEFAULT_POST_AT_TIME: (u32, u32) = (19, 0);
+
+/// when to post preblast from the optional "post at" inputs.
+/// Missing date means the day before workout, missing time means the evening.
+pub fn scheduled_post_at(
+    workout_date: &NaiveDate,
+    date: Option<NaiveDate>,
+    time: Option<NaiveTime>,
+) -> Option<NaiveDateTime> {
+    if date.is_none() && time.is_none() {
+        return None;
+    }
+    let (hour, min) = DEFAULT_POST_AT_TIME;
+    let date = date.unwrap_or(*workout_date - Duration::days(1));
+    let time = time.unwrap_or_else(|| NaiveTime::from_hms_opt(hour, min, 0).unwrap_or_default());
+    Some(date.and_time(time))
+}
+
 pub mod pre_blast_action_ids {
     pub const AO_SELECT: &str = "ao.select";
     pub const TIME_SELECT: &str = "time.select";
@@ -165,6 +212,8 @@ pub mod pre_blast_action_ids {
     pub const MOLE_SKINE: &str = "moleskin.textbox";
     pub const WHERE_POST: &str = "where_to_post.select";
     pub const FILE: &str = "file.input";
+    pub const POST_AT_DATE: &str = "post_at_date.select";
+    pub const POST_AT_TIME: &str = "post_at_time.select";
 }
 
 pub async fn convert_to_message(
@@ -172,9 +221,10 @@ pub async fn convert_to_message(
     post: PreBlastPost,
     id: &str,
     action_user_id: &str,
+    registry: &AoRegistry,
 ) -> PostMessageRequest {
     let channel_id = match &post.post_where {
-        BlastWhere::AoChannel => post.ao.channel_id().to_string(),
+        BlastWhere::AoChannel => post.ao.channel_id(registry).to_string(),
         BlastWhere::CurrentChannel(id) => id.to_string(),
     };
 
@@ -183,7 +233,7 @@ pub async fn convert_to_message(
         .await
         .unwrap_or_default();
 
-    let block_builder = get_block_builder(post, id);
+    let block_builder = get_block_builder(post, id, registry);
 
     if let Some(f3_user) = user {
         PostMessageRequest::new_as_user(&channel_id, block_builder.blocks, f3_user)
@@ -192,14 +242,18 @@ pub async fn convert_to_message(
     }
 }
 
-fn get_block_builder(post: PreBlastPost, id: &str) -> BlockBuilder {
+fn get_block_builder(post: PreBlastPost, id: &str, registry: &AoRegistry) -> BlockBuilder {
     let img_ids = post.img_ids();
     let mut block_builder = BlockBuilder::new()
         .section_markdown(&format!("*Preblast: {}*", post.title))
         .section_markdown(&format!("*Date*: {}", post.date))
         .section_markdown(&format!("*Time*: {}", post.start_time.format("%H:%M")))
-        .section_markdown(&format!("*Where*: <#{}>", post.ao.channel_id()))
-        .section_markdown(&format!("*Q(s)*: {}", post.qs_list()))
+        .section_markdown(&format!("*Where*: <#{}>", post.ao.channel_id(registry)))
+        .section_markdown(&format!("*Q(s)*: {}", post.qs_list()));
+    if !post.hcs.is_empty() {
+        block_builder.add_section_markdown(&format!("*HCs*: {}", hc_count_text(&post.hcs)));
+    }
+    block_builder = block_builder
         .divider()
         .section_markdown(&format!("*Why*: {}", post.why))
         .section_markdown(&format!("*Equipment*: {}", post.equipment_list()))
@@ -218,6 +272,12 @@ fn get_block_builder(post: PreBlastPost, id: &str) -> BlockBuilder {
             interaction_btn.to_string().as_str(),
             "edit-preblast",
         );
+        let write_btn = InteractionTypes::new_write_back_blast(id);
+        block_builder.add_btn(
+            "Write Backblast",
+            write_btn.to_string().as_str(),
+            "write-backblast",
+        );
         block_builder.add_context("Saved Preblast");
     }
 
@@ -228,13 +288,38 @@ pub fn convert_to_update_message(
     post: PreBlastPost,
     id: Option<String>,
     ts: &str,
+    registry: &AoRegistry,
 ) -> UpdateMessageRequest {
     let channel_id = match &post.post_where {
-        BlastWhere::AoChannel => post.ao.channel_id().to_string(),
+        BlastWhere::AoChannel => post.ao.channel_id(registry).to_string(),
         BlastWhere::CurrentChannel(id) => id.to_string(),
     };
 
-    let block_builder = get_block_builder(post, &id.unwrap_or_default());
+    let block_builder = get_block_builder(post, &id.unwrap_or_default(), registry);
 
     UpdateMessageRequest::new(&channel_id, ts, block_builder.blocks)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn post_at_defaults() {
+        let workout = NaiveDate::from_ymd_opt(2026, 10, 22).unwrap();
+        let evening_before = NaiveDate::from_ymd_opt(2026, 10, 21)
+            .unwrap()
+            .and_hms_opt(19, 0, 0);
+        assert_eq!(scheduled_post_at(&workout, None, None), None);
+        assert_eq!(
+            scheduled_post_at(&workout, NaiveDate::from_ymd_opt(2026, 10, 21), None),
+            evening_before
+        );
+        assert_eq!(
+            scheduled_post_at(&workout, None, NaiveTime::from_hms_opt(20, 30, 0)),
+            NaiveDate::from_ymd_opt(2026, 10, 21)
+                .unwrap()
+                .and_hms_opt(20, 30, 0)
+        );
+    }
+}
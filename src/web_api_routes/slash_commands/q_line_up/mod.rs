@@ -1,10 +1,11 @@
-use crate::app_state::ao_data::const_names::AO_LIST;
 use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use crate::db::queries::ao_closures::get_closure_calendar;
 use crate::db::queries::q_line_up::{get_q_line_up_between_dates_for_ao, QLineUpDbData};
 use crate::shared::common_errors::AppError;
 use crate::shared::constants::Q_LINE_UP_BTN_TEXT;
 use crate::shared::string_utils::{
-    format_q_empty_row, format_q_line_up_date, map_month_str_to_future_date,
+    format_q_closure_row, format_q_empty_row, format_q_line_up_date, map_month_str_to_future_date,
     map_q_line_up_existing, map_slack_id_to_link,
 };
 use crate::shared::time::local_boise_time;
@@ -25,12 +26,13 @@ pub struct QLineUpCommand {
     pub month: Option<NaiveDate>,
 }
 
-impl From<&str> for QLineUpCommand {
-    fn from(text: &str) -> Self {
+impl QLineUpCommand {
+    /// parse `[ao] [month]` text of command
+    pub fn new(text: &str, registry: &AoRegistry) -> Self {
         let (ao, month) = text.split_once(' ').unwrap_or((text, ""));
         let now = local_boise_time().date_naive();
         let month = map_month_str_to_future_date(month, &now);
-        let possible_ao = AO::from(ao.to_string());
+        let possible_ao = AO::from_name(ao, registry);
         let ao = if let AO::Unknown(_) = possible_ao {
             None
         } else {
@@ -48,8 +50,9 @@ pub async fn send_all_q_line_up_message(
     users: &HashMap<String, String>,
     channel_id: &str,
     web_app: &MutableWebState,
+    registry: &AoRegistry,
 ) -> Result<(), AppError> {
-    let message = get_q_line_up_message_all(db_pool, month_to_check, users).await?;
+    let message = get_q_line_up_message_all(db_pool, month_to_check, users, registry).await?;
     let request = PostMessageRequest::new(channel_id, message.blocks);
     web_app.post_message(request).await?;
     Ok(())
@@ -60,11 +63,13 @@ pub async fn get_q_line_up_message_all(
     db_pool: &PgPool,
     month_to_check: &NaiveDate,
     users: &HashMap<String, String>,
+    registry: &AoRegistry,
 ) -> Result<BlockBuilder, AppError> {
     let end_date = (*month_to_check)
         .checked_add_signed(Duration::days(5))
         .unwrap_or_else(|| (*month_to_check).succ_opt().unwrap());
-    let result = get_q_line_up_for_range(db_pool, month_to_check, end_date, users).await?;
+    let result =
+        get_q_line_up_for_range(db_pool, month_to_check, end_date, users, registry).await?;
     Ok(result)
 }
 
@@ -76,8 +81,9 @@ pub async fn send_ao_q_line_up_message(
     users: &HashMap<String, String>,
     channel_id: &str,
     web_app: &MutableWebState,
+    registry: &AoRegistry,
 ) -> Result<(), AppError> {
-    let message = get_q_line_up_for_ao(db_pool, ao, start_date, users).await?;
+    let message = get_q_line_up_for_ao(db_pool, ao, start_date, users, registry).await?;
     let request = PostMessageRequest::new(channel_id, message.blocks);
     web_app.post_message(request).await?;
     Ok(())
@@ -89,6 +95,7 @@ pub async fn get_q_line_up_for_ao(
     ao: AO,
     start_date: &NaiveDate,
     users: &HashMap<String, String>,
+    registry: &AoRegistry,
 ) -> Result<BlockBuilder, AppError> {
     let days_ahead: i64 = match ao {
         AO::RuckershipEast | AO::RuckershipWest => 30,
@@ -97,7 +104,8 @@ pub async fn get_q_line_up_for_ao(
     let end_date = (*start_date)
         .checked_add_signed(Duration::days(days_ahead))
         .unwrap_or_else(|| (*start_date).succ_opt().unwrap());
-    let result = get_q_line_up_for_range_for_ao(db_pool, ao, start_date, end_date, users).await?;
+    let result =
+        get_q_line_up_for_range_for_ao(db_pool, ao, start_date, end_date, users, registry).await?;
     Ok(result)
 }
 
@@ -107,6 +115,7 @@ async fn get_q_line_up_for_range_for_ao(
     start_date: &NaiveDate,
     end_date: NaiveDate,
     users: &HashMap<String, String>,
+    registry: &AoRegistry,
 ) -> Result<BlockBuilder, AppError> {
     let month_display = start_date.format("%B %Y").to_string();
     let mut block_builder = BlockBuilder::new()
@@ -116,13 +125,18 @@ async fn get_q_line_up_for_range_for_ao(
 
     let existing_line_up =
         get_q_line_up_between_dates_for_ao(db_pool, &ao, start_date, &end_date).await?;
+    let closures = get_closure_calendar(db_pool, start_date, &end_date).await?;
     let mut date_to_check = (*start_date).succ_opt().unwrap();
 
     while date_to_check < end_date && !block_builder.reached_max() {
-        if ao.week_days().contains(&date_to_check.weekday()) {
+        if ao.week_days(registry).contains(&date_to_check.weekday()) {
             let friendly_date = format_q_line_up_date(&date_to_check);
             let action_combo = InteractionTypes::new_q_line_up(&date_to_check, &ao);
-            if let Some(existing) =
+            if let Some(closure) = closures.closure_for(&ao, &date_to_check) {
+                // on closure calendar, so no sign ups
+                let text = format_q_closure_row(&friendly_date, None, &closure.reason);
+                block_builder.add_section_markdown(text.as_str());
+            } else if let Some(existing) =
                 someone_signed_up_for_ao(&existing_line_up, &date_to_check, users)
             {
                 // someone signed up
@@ -159,6 +173,7 @@ async fn get_q_line_up_for_range(
     start_date: &NaiveDate,
     end_date: NaiveDate,
     users: &HashMap<String, String>,
+    registry: &AoRegistry,
 ) -> Result<BlockBuilder, AppError> {
     let month_display = start_date.format("%B %Y").to_string();
     let mut block_builder = BlockBuilder::new()
@@ -167,19 +182,28 @@ async fn get_q_line_up_for_range(
         .divider();
 
     let existing_line_up = get_line_up_map(db_pool, start_date, &end_date).await?;
+    let closures = get_closure_calendar(db_pool, start_date, &end_date).await?;
 
     let mut date_to_check = (*start_date).succ_opt().unwrap_or_default();
 
     while date_to_check < end_date && !block_builder.reached_max() {
-        for ao in AO_LIST {
+        for ao in registry.active_aos() {
             if block_builder.reached_max() {
                 break;
             }
             let ao_string = ao.to_string();
-            if ao.week_days().contains(&date_to_check.weekday()) {
+            if ao.week_days(registry).contains(&date_to_check.weekday()) {
                 let friendly_date = format_q_line_up_date(&date_to_check);
                 let action_combo = InteractionTypes::new_q_line_up(&date_to_check, &ao);
-                if let Some(existing) =
+                if let Some(closure) = closures.closure_for(&ao, &date_to_check) {
+                    // on closure calendar, so no sign ups
+                    let text = format_q_closure_row(
+                        &friendly_date,
+                        Some(ao_string.as_str()),
+                        &closure.reason,
+                    );
+                    block_builder.add_section_markdown(text.as_str());
+                } else if let Some(existing) =
                     someone_signed_up(&existing_line_up, &date_to_check, ao_string.as_str(), users)
                 {
                     // someone signed up
@@ -211,7 +235,12 @@ async fn get_q_line_up_for_range(
     Ok(block_builder)
 }
 
-fn get_message_data_text(start_date: &NaiveDate, end_date: &NaiveDate, ao: Option<AO>) -> String {
+/// message data in last block, to know range and AO of q line up message when interacting with it.
+pub fn get_message_data_text(
+    start_date: &NaiveDate,
+    end_date: &NaiveDate,
+    ao: Option<AO>,
+) -> String {
     let ao_type = ao
         .map(|ao| ao.to_string())
         .unwrap_or_else(|| String::from("all"));
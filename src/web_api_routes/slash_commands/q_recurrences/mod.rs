@@ -0,0 +1,96 @@
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::q_recurrence::QRecurrence;
+use crate::db::queries::q_recurrences::get_recurrences_by_user;
+use crate::shared::common_errors::AppError;
+use crate::shared::time::local_boise_time;
+use crate::slack_api::block_kit::block_elements::OptionElement;
+use crate::slack_api::block_kit::BlockBuilder;
+use crate::slack_api::views::payload::{ViewModal, ViewPayload};
+use crate::slack_api::views::request::ViewsOpenRequest;
+use crate::web_api_routes::slash_commands::modal_utils::view_ids::ViewIds;
+use crate::web_api_state::MutableWebState;
+use sqlx::PgPool;
+
+pub mod q_recurrence_post;
+
+/// open modal for pax to add or remove their recurring Qs
+pub async fn generate_modal(
+    db_pool: &PgPool,
+    trigger_id: &str,
+    web_app: &MutableWebState,
+    channel_id: &str,
+    user_id: &str,
+    registry: &AoRegistry,
+) -> Result<(), AppError> {
+    let recurrences = get_recurrences_by_user(db_pool, user_id).await?;
+    let modal = create_modal(channel_id, &recurrences, registry);
+    let view = ViewsOpenRequest::new(trigger_id, ViewPayload::Modal(modal));
+    web_app.open_view(view).await?;
+    Ok(())
+}
+
+fn create_modal(channel_id: &str, recurrences: &[QRecurrence], registry: &AoRegistry) -> ViewModal {
+    // default to current channel if it's an ao channel
+    let initial_channel = match AO::from_channel_id(channel_id, registry) {
+        AO::Unknown(_) => None,
+        _ => Some(channel_id.to_string()),
+    };
+    let today = local_boise_time().date_naive();
+
+    let mut block_builder = BlockBuilder::new().section_markdown("*Your recurring Qs*");
+    if recurrences.is_empty() {
+        block_builder.add_context("None yet");
+    } else {
+        for recurrence in recurrences.iter() {
+            block_builder.add_context(&format!("• {}", recurrence.display_text(registry)));
+        }
+        let options = recurrences
+            .iter()
+            .map(|recurrence| {
+                OptionElement::new(&recurrence.display_text(registry), &recurrence.id)
+            })
+            .collect::<Vec<OptionElement>>();
+        block_builder.add_multi_select(
+            "Remove",
+            q_recurrence_post::post_ids::REMOVE,
+            options,
+            None,
+            true,
+        );
+    }
+
+    let block_builder = block_builder
+        .divider()
+        .section_markdown("*Add recurring Q*")
+        .channel_select(
+            "AO",
+            q_recurrence_post::post_ids::AO_SELECT,
+            initial_channel,
+            true,
+        )
+        .select(
+            "Repeats",
+            q_recurrence_post::post_ids::REPEATS,
+            q_recurrence_post::repeat_options(),
+            Some(q_recurrence_post::default_repeat_option()),
+            true,
+        )
+        .select(
+            "Day",
+            q_recurrence_post::post_ids::WEEKDAY,
+            q_recurrence_post::weekday_options(),
+            None,
+            true,
+        )
+        .date_picker(
+            "Starting",
+            q_recurrence_post::post_ids::START_DATE,
+            Some(today.to_string()),
+            true,
+        )
+        .date_picker("Until", q_recurrence_post::post_ids::END_DATE, None, true)
+        .context("Leave AO empty to only remove. Recurring Qs are added to the Q line up a few weeks ahead, skipping closed days and spots already taken.");
+
+    ViewModal::new("Recurring Qs", block_builder, "Save", ViewIds::QRecurrence)
+}
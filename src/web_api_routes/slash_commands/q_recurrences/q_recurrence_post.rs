@@ -0,0 +1,188 @@
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::q_recurrence::{weekday_key, weekday_name, RecurrenceRule};
+use crate::db::save_q_recurrence::NewQRecurrence;
+use crate::shared::common_errors::AppError;
+use crate::slack_api::block_kit::block_elements::OptionElement;
+use crate::web_api_routes::interactive_events::interaction_payload::BasicValue;
+use crate::web_api_routes::slash_commands::modal_utils::value_utils;
+use chrono::{NaiveDate, Weekday};
+use std::collections::HashMap;
+
+pub mod post_ids {
+    pub const REMOVE: &str = "q_recurrence_remove.select";
+    pub const AO_SELECT: &str = "q_recurrence_ao.select";
+    pub const REPEATS: &str = "q_recurrence_repeats.select";
+    pub const WEEKDAY: &str = "q_recurrence_weekday.select";
+    pub const START_DATE: &str = "q_recurrence_start_date.select";
+    pub const END_DATE: &str = "q_recurrence_end_date.select";
+}
+
+pub fn default_repeat_option() -> OptionElement {
+    OptionElement::new("1st of the month", "monthly:1")
+}
+
+pub fn repeat_options() -> Vec<OptionElement> {
+    vec![
+        default_repeat_option(),
+        OptionElement::new("2nd of the month", "monthly:2"),
+        OptionElement::new("3rd of the month", "monthly:3"),
+        OptionElement::new("4th of the month", "monthly:4"),
+        OptionElement::new("Last of the month", "monthly:-1"),
+        OptionElement::new("Every week", "weekly:1"),
+        OptionElement::new("Every 2 weeks", "weekly:2"),
+        OptionElement::new("Every 4 weeks", "weekly:4"),
+    ]
+}
+
+pub fn weekday_options() -> Vec<OptionElement> {
+    [
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+        Weekday::Sat,
+        Weekday::Sun,
+    ]
+    .iter()
+    .map(|weekday| OptionElement::new(weekday_name(weekday), &weekday_key(weekday)))
+    .collect()
+}
+
+/// recurrences submitted from modal
+#[derive(Debug, PartialEq)]
+pub struct QRecurrencePost {
+    /// ids of existing recurrences to remove
+    pub remove: Vec<String>,
+    /// selected channel of new recurrence. Nothing is added when empty.
+    pub channel_id: Option<String>,
+    pub repeats: String,
+    pub weekday: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+}
+
+impl From<HashMap<String, BasicValue>> for QRecurrencePost {
+    fn from(value: HashMap<String, BasicValue>) -> Self {
+        let mut remove = value_utils::get_value(
+            &value,
+            post_ids::REMOVE,
+            value_utils::get_hash_set_strings_from_multi,
+        )
+        .unwrap_or_default()
+        .into_iter()
+        .collect::<Vec<String>>();
+        remove.sort();
+
+        let channel_id =
+            value_utils::get_value(&value, post_ids::AO_SELECT, value_utils::get_single_string)
+                .filter(|channel_id| !channel_id.is_empty());
+
+        let repeats =
+            value_utils::get_value(&value, post_ids::REPEATS, value_utils::get_single_string)
+                .unwrap_or_default();
+
+        let weekday =
+            value_utils::get_value(&value, post_ids::WEEKDAY, value_utils::get_single_string)
+                .filter(|weekday| !weekday.is_empty());
+
+        let start_date =
+            value_utils::get_value(&value, post_ids::START_DATE, value_utils::get_single_date)
+                .filter(|date| date != &NaiveDate::default());
+
+        let end_date =
+            value_utils::get_value(&value, post_ids::END_DATE, value_utils::get_single_date)
+                .filter(|date| date != &NaiveDate::default());
+
+        QRecurrencePost {
+            remove,
+            channel_id,
+            repeats,
+            weekday,
+            start_date,
+            end_date,
+        }
+    }
+}
+
+impl QRecurrencePost {
+    /// convert to recurrence to save in db, if AO was picked
+    pub fn to_new_recurrence(
+        &self,
+        slack_id: &str,
+        today: NaiveDate,
+        registry: &AoRegistry,
+    ) -> Result<Option<NewQRecurrence>, AppError> {
+        let Some(channel_id) = &self.channel_id else {
+            return Ok(None);
+        };
+        let ao = AO::from_channel_id(channel_id, registry);
+        if let AO::Unknown(_) = ao {
+            return Err(AppError::from("Selected channel is not an AO"));
+        }
+        let weekday = self
+            .weekday
+            .as_ref()
+            .ok_or_else(|| AppError::from("Pick the day to Q"))?;
+        let rule = RecurrenceRule::try_from(format!("{}:{}", self.repeats, weekday).as_str())
+            .map_err(AppError::General)?;
+        if !ao.week_days(registry).contains(&rule.weekday()) {
+            return Err(AppError::General(format!(
+                "{} doesn't meet on {}",
+                ao.friendly_name(registry),
+                weekday_name(&rule.weekday())
+            )));
+        }
+        let start_date = self.start_date.unwrap_or(today);
+        if matches!(self.end_date, Some(end_date) if end_date < start_date) {
+            return Err(AppError::from("End date is before start date"));
+        }
+        Ok(Some(NewQRecurrence::new(
+            slack_id,
+            ao.channel_id(registry),
+            &rule,
+            start_date,
+            self.end_date,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post(weekday: &str) -> QRecurrencePost {
+        QRecurrencePost {
+            remove: Vec::new(),
+            channel_id: Some(AO::Bleach.channel_id(&AoRegistry::default()).to_string()),
+            repeats: String::from("monthly:1"),
+            weekday: Some(weekday.to_string()),
+            start_date: None,
+            end_date: None,
+        }
+    }
+
+    #[test]
+    fn new_recurrence_for_ao_day() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let recurrence = post("mon")
+            .to_new_recurrence("U1", today, &AoRegistry::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(recurrence.rule, "monthly:1:mon");
+        assert_eq!(recurrence.start_date, today);
+        // bleach doesn't meet on tuesdays
+        assert!(post("tue")
+            .to_new_recurrence("U1", today, &AoRegistry::default())
+            .is_err());
+        let remove_only = QRecurrencePost {
+            channel_id: None,
+            ..post("mon")
+        };
+        assert!(remove_only
+            .to_new_recurrence("U1", today, &AoRegistry::default())
+            .unwrap()
+            .is_none());
+    }
+}
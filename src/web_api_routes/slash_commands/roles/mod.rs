@@ -0,0 +1,167 @@
+use crate::app_state::ao_data::AO;
+use crate::app_state::ao_registry::AoRegistry;
+use crate::app_state::roles::{Permission, Role};
+use crate::db::queries::user_roles::{get_all_user_roles, get_user_roles};
+use crate::db::save_user_role::{add_user_role, remove_user_role};
+use crate::shared::common_errors::AppError;
+use crate::shared::string_utils::map_slack_id_to_link;
+use crate::slack_api::block_kit::BlockBuilder;
+use crate::web_api_routes::slash_commands::SlashCommandForm;
+use sqlx::PgPool;
+
+const USAGE: &str = "`/roles` to see your roles, `/roles list` to see everyone with a role, or `/roles add @pax <role> [ao]` / `/roles remove @pax <role> [ao]`. Roles are `region_admin`, `site_q` (needs AO), `comz` and `pax`.";
+
+/// `/roles` to see own roles, `/roles list` for all, or region admins adding / removing roles
+#[derive(Debug, PartialEq)]
+pub enum RolesCommand {
+    Mine,
+    List,
+    Add { slack_id: String, role: Role },
+    Remove { slack_id: String, role: Role },
+    Invalid(String),
+}
+
+impl RolesCommand {
+    /// parse command text, with AO of site Q roles from registry
+    pub fn new(text: &str, registry: &AoRegistry) -> Self {
+        let parts = text.split_whitespace().collect::<Vec<&str>>();
+        match parts.as_slice() {
+            [] => RolesCommand::Mine,
+            [command] if command.eq_ignore_ascii_case("list") => RolesCommand::List,
+            [command, user, role, rest @ ..] if rest.len() <= 1 => {
+                let Some(slack_id) = slack_id_from_mention(user) else {
+                    return RolesCommand::Invalid(format!("Could not find pax {}", user));
+                };
+                let ao = rest.first().map(|ao| AO::from_name(ao, registry));
+                let role = match Role::from_parts(role, ao) {
+                    Ok(role) => role,
+                    Err(err) => return RolesCommand::Invalid(err),
+                };
+                match command.to_lowercase().as_str() {
+                    "add" => RolesCommand::Add { slack_id, role },
+                    "remove" => RolesCommand::Remove { slack_id, role },
+                    _ => RolesCommand::Invalid(String::from(USAGE)),
+                }
+            }
+            _ => RolesCommand::Invalid(String::from(USAGE)),
+        }
+    }
+}
+
+/// slack id from escaped mention (ie. `<@U123|stinger>`)
+fn slack_id_from_mention(mention: &str) -> Option<String> {
+    let id = mention.strip_prefix("<@")?.strip_suffix('>')?;
+    let id = id.split('|').next().unwrap_or_default();
+    if id.is_empty() {
+        None
+    } else {
+        Some(id.to_string())
+    }
+}
+
+pub async fn handle_roles_command(
+    db_pool: &PgPool,
+    form: &SlashCommandForm,
+    registry: &AoRegistry,
+) -> Result<BlockBuilder, AppError> {
+    let command = RolesCommand::new(form.text.as_str(), registry);
+    if let RolesCommand::Add { .. } | RolesCommand::Remove { .. } = &command {
+        let roles = get_user_roles(db_pool, &form.user_id).await?;
+        if !roles.can(&Permission::ManageRoles) {
+            return Err(AppError::from("Only region admins can manage roles"));
+        }
+    }
+
+    let text = match command {
+        RolesCommand::Mine => {
+            let roles = get_user_roles(db_pool, &form.user_id).await?;
+            format!("*Your roles*: {}", roles.display_text(registry))
+        }
+        RolesCommand::List => {
+            let rows = get_all_user_roles(db_pool).await?;
+            let lines = rows
+                .iter()
+                .filter_map(|row| {
+                    Role::try_from(row).ok().map(|role| {
+                        format!(
+                            "• {} - {}",
+                            map_slack_id_to_link(&row.slack_id),
+                            role.display_text(registry)
+                        )
+                    })
+                })
+                .collect::<Vec<String>>();
+            if lines.is_empty() {
+                String::from("No roles saved yet")
+            } else {
+                format!("*Roles*\n{}", lines.join("\n"))
+            }
+        }
+        RolesCommand::Add { slack_id, role } => {
+            if add_user_role(db_pool, &slack_id, &role, registry).await? {
+                format!(
+                    "Added {} to {}",
+                    role.display_text(registry),
+                    map_slack_id_to_link(&slack_id)
+                )
+            } else {
+                format!(
+                    "{} already has {}",
+                    map_slack_id_to_link(&slack_id),
+                    role.display_text(registry)
+                )
+            }
+        }
+        RolesCommand::Remove { slack_id, role } => {
+            if remove_user_role(db_pool, &slack_id, &role, registry).await? {
+                format!(
+                    "Removed {} from {}",
+                    role.display_text(registry),
+                    map_slack_id_to_link(&slack_id)
+                )
+            } else {
+                format!(
+                    "{} doesn't have {}",
+                    map_slack_id_to_link(&slack_id),
+                    role.display_text(registry)
+                )
+            }
+        }
+        RolesCommand::Invalid(reason) => reason,
+    };
+    Ok(BlockBuilder::new().section_markdown(&text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_roles_command() {
+        let registry = AoRegistry::default();
+        assert_eq!(RolesCommand::new("", &registry), RolesCommand::Mine);
+        assert_eq!(RolesCommand::new("list", &registry), RolesCommand::List);
+        assert_eq!(
+            RolesCommand::new("add <@U123|stinger> site_q gem", &registry),
+            RolesCommand::Add {
+                slack_id: String::from("U123"),
+                role: Role::SiteQ(AO::Gem),
+            }
+        );
+        assert_eq!(
+            RolesCommand::new("remove <@U123> comz", &registry),
+            RolesCommand::Remove {
+                slack_id: String::from("U123"),
+                role: Role::Comz,
+            }
+        );
+        assert!(matches!(
+            RolesCommand::new("add stinger comz", &registry),
+            RolesCommand::Invalid(_)
+        ));
+        assert!(matches!(
+            RolesCommand::new("add <@U123> site_q", &registry),
+            RolesCommand::Invalid(_)
+        ));
+    }
+}
@@ -0,0 +1,106 @@
+use crate::db::queries::ruck_stats::{get_pax_ruck_stats, get_ruck_leaderboard, RuckStatsItem};
+use crate::shared::common_errors::AppError;
+use crate::shared::time::local_boise_time;
+use crate::slack_api::block_kit::BlockBuilder;
+use crate::web_api_routes::graphs::ruck_leaderboard::RuckPeriod;
+use crate::web_api_routes::slash_commands::SlashCommandForm;
+use sqlx::PgPool;
+
+/// number of pax listed on leaderboard
+const LEADERBOARD_COUNT: usize = 5;
+
+/// `/ruck-stats` shows your stats, `/ruck-stats graph [all]` posts leaderboard graphs
+#[derive(Debug, PartialEq)]
+pub enum RuckStatsCommand {
+    Stats,
+    Graph(RuckPeriod),
+}
+
+impl From<&str> for RuckStatsCommand {
+    fn from(text: &str) -> Self {
+        match text.trim().split_once("graph") {
+            Some(("", period)) => RuckStatsCommand::Graph(RuckPeriod::from(period)),
+            _ => RuckStatsCommand::Stats,
+        }
+    }
+}
+
+fn stats_text(stats: Option<&RuckStatsItem>) -> String {
+    match stats {
+        Some(stats) => format!(
+            "{:.1} miles | {:.0} pound-miles | {} rucks",
+            stats.miles, stats.pound_miles, stats.rucks
+        ),
+        None => String::from("No rucks yet"),
+    }
+}
+
+fn leaderboard_text(leaderboard: &[RuckStatsItem]) -> String {
+    leaderboard
+        .iter()
+        .take(LEADERBOARD_COUNT)
+        .enumerate()
+        .map(|(index, item)| {
+            format!(
+                "{}. {} - {:.0} pound-miles ({:.1} miles)",
+                index + 1,
+                item.name,
+                item.pound_miles,
+                item.miles
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// handle getting response for ruck stats of user, with top pax this month
+pub async fn handle_ruck_stats(
+    db_pool: &PgPool,
+    form: &SlashCommandForm,
+) -> Result<BlockBuilder, AppError> {
+    let today = local_boise_time().date_naive();
+    let (start, end) = RuckPeriod::Month.date_range(today);
+    let month = get_pax_ruck_stats(db_pool, &form.user_id, start.as_ref(), end.as_ref()).await?;
+    let all_time = get_pax_ruck_stats(db_pool, &form.user_id, None, None).await?;
+    let leaderboard = get_ruck_leaderboard(db_pool, start.as_ref(), end.as_ref()).await?;
+
+    let mut block_builder = BlockBuilder::new()
+        .section_markdown("*Here are your ruck stats:*")
+        .section_markdown(
+            format!(
+                "*{}*: {}",
+                RuckPeriod::Month.label(today),
+                stats_text(month.as_ref())
+            )
+            .as_str(),
+        )
+        .section_markdown(format!("*All Time*: {}", stats_text(all_time.as_ref())).as_str());
+    if !leaderboard.is_empty() {
+        block_builder.add_section_markdown(
+            format!(
+                "*Top rucking PAX this month:*\n{}",
+                leaderboard_text(&leaderboard)
+            )
+            .as_str(),
+        );
+    }
+    Ok(block_builder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_command() {
+        assert_eq!(RuckStatsCommand::from(""), RuckStatsCommand::Stats);
+        assert_eq!(
+            RuckStatsCommand::from("graph"),
+            RuckStatsCommand::Graph(RuckPeriod::Month)
+        );
+        assert_eq!(
+            RuckStatsCommand::from(" graph all"),
+            RuckStatsCommand::Graph(RuckPeriod::AllTime)
+        );
+    }
+}
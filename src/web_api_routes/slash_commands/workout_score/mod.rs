@@ -0,0 +1,61 @@
+use crate::app_state::scoring::{ScoringFieldType, ScoringFormula};
+use crate::db::queries::scoring::get_scoring_formula;
+use crate::shared::common_errors::AppError;
+use crate::shared::time::local_boise_time;
+use crate::slack_api::block_kit::block_elements::OptionElement;
+use crate::slack_api::block_kit::BlockBuilder;
+use crate::slack_api::views::payload::{ViewModal, ViewPayload};
+use crate::slack_api::views::request::ViewsOpenRequest;
+use crate::web_api_routes::slash_commands::modal_utils::view_ids::ViewIds;
+use crate::web_api_state::MutableWebState;
+use sqlx::PgPool;
+
+pub mod workout_score_post;
+
+/// open modal with inputs of scoring formula of the channel's ao
+pub async fn generate_modal(
+    db_pool: &PgPool,
+    trigger_id: &str,
+    web_app: &MutableWebState,
+    channel_id: &str,
+) -> Result<(), AppError> {
+    let formula = get_scoring_formula(db_pool, channel_id)
+        .await?
+        .ok_or_else(|| AppError::from("No scoring formula set up for this channel"))?;
+    let modal = create_modal(channel_id, &formula.formula);
+    let view = ViewsOpenRequest::new(trigger_id, ViewPayload::Modal(modal));
+    web_app.open_view(view).await?;
+    Ok(())
+}
+
+fn create_modal(channel_id: &str, formula: &ScoringFormula) -> ViewModal {
+    let mut block_builder = BlockBuilder::new()
+        .context(format!("Score: `{}`", formula.formula).as_str())
+        .date_picker(
+            "Beatdown Date",
+            workout_score_post::post_ids::DATE,
+            Some(local_boise_time().date_naive().to_string()),
+            false,
+        );
+    for field in &formula.fields {
+        let action_id = workout_score_post::field_action_id(&field.key);
+        match &field.field_type {
+            ScoringFieldType::Select { options } => {
+                let options = options
+                    .iter()
+                    .map(|option| OptionElement::new(&option.label, &option.value.to_string()))
+                    .collect::<Vec<OptionElement>>();
+                block_builder.add_select(&field.label, &action_id, options, None, false);
+            }
+            _ => block_builder.add_plain_input(&field.label, &action_id, None, None, false),
+        }
+    }
+
+    ViewModal::new(
+        &formula.name,
+        block_builder,
+        "Submit",
+        ViewIds::WorkoutScore,
+    )
+    .with_private_meta(channel_id)
+}
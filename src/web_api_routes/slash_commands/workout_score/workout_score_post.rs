@@ -0,0 +1,162 @@
+use crate::app_state::scoring::{ScoringFieldType, ScoringFormula};
+use crate::db::queries::scoring::ScoringFormulaDbData;
+use crate::db::save_scoring::NewWorkoutScore;
+use crate::shared::common_errors::AppError;
+use crate::slack_api::block_kit::BlockBuilder;
+use crate::slack_api::chat::post_message::request::PostMessageRequest;
+use crate::web_api_routes::interactive_events::interaction_payload::BasicValue;
+use crate::web_api_routes::slash_commands::modal_utils::value_utils;
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+pub mod post_ids {
+    pub const DATE: &str = "score-date.select";
+    /// prefix of action id for each formula field (ie. `score_field.miles`)
+    pub const FIELD_PREFIX: &str = "score_field.";
+}
+
+/// action id of formula field input
+pub fn field_action_id(key: &str) -> String {
+    format!("{}{}", post_ids::FIELD_PREFIX, key)
+}
+
+/// score submitted from modal, with raw field values by key
+#[derive(Debug, PartialEq)]
+pub struct WorkoutScorePost {
+    pub date: NaiveDate,
+    pub values: HashMap<String, String>,
+}
+
+impl From<HashMap<String, BasicValue>> for WorkoutScorePost {
+    fn from(value: HashMap<String, BasicValue>) -> Self {
+        let date = value_utils::get_value(&value, post_ids::DATE, value_utils::get_single_date)
+            .unwrap_or_default();
+        let values = value
+            .iter()
+            .filter_map(|(action_id, value)| {
+                action_id
+                    .strip_prefix(post_ids::FIELD_PREFIX)
+                    .map(|key| (key.to_string(), value_utils::get_single_string(value)))
+            })
+            .collect();
+        WorkoutScorePost { date, values }
+    }
+}
+
+impl WorkoutScorePost {
+    /// parse field values with formula of ao
+    pub fn inputs(&self, formula: &ScoringFormula) -> Result<HashMap<String, f64>, AppError> {
+        formula
+            .fields
+            .iter()
+            .map(|field| {
+                let value = self.values.get(&field.key).map(|value| value.as_str());
+                let value = field.parse_input(value.unwrap_or_default())?;
+                Ok((field.key.to_string(), value))
+            })
+            .collect()
+    }
+
+    /// compute score to save in db
+    pub fn to_new_score(
+        &self,
+        formula: &ScoringFormulaDbData,
+        user_id: &str,
+    ) -> Result<NewWorkoutScore, AppError> {
+        if self.date == NaiveDate::default() {
+            return Err(AppError::from("Date is required"));
+        }
+        let inputs = self.inputs(&formula.formula)?;
+        let score = formula.formula.score(&inputs)?;
+        Ok(NewWorkoutScore {
+            formula_id: formula.id,
+            channel_id: formula.channel_id.to_string(),
+            date: self.date,
+            inputs,
+            formula: formula.formula.formula.to_string(),
+            score,
+            scored_by: Some(user_id.to_string()),
+        })
+    }
+}
+
+/// text of each input value, using option label for selects
+fn inputs_text(formula: &ScoringFormula, inputs: &HashMap<String, f64>) -> String {
+    formula
+        .fields
+        .iter()
+        .filter_map(|field| {
+            let value = inputs.get(&field.key)?;
+            let value = match &field.field_type {
+                ScoringFieldType::Select { options } => options
+                    .iter()
+                    .find(|option| option.value == *value)
+                    .map(|option| option.label.to_string())
+                    .unwrap_or_else(|| value.to_string()),
+                _ => value.to_string(),
+            };
+            Some(format!("*{}*: {}", field.label, value))
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// message with score posted to ao channel
+pub fn convert_to_message(
+    formula: &ScoringFormula,
+    score: &NewWorkoutScore,
+    user_id: &str,
+) -> PostMessageRequest {
+    let block_builder = BlockBuilder::new()
+        .section_markdown(
+            format!(
+                ":trophy: *{}* for {}: *{}*",
+                formula.name,
+                score.date.format("%m/%d/%Y"),
+                formula.score_fmt(score.score)
+            )
+            .as_str(),
+        )
+        .section_markdown(inputs_text(formula, &score.inputs).as_str())
+        .context(format!("Scored by <@{}> | `{}`", user_id, score.formula).as_str());
+    PostMessageRequest::new(score.channel_id.as_str(), block_builder.blocks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::ao_data::AO;
+    use crate::app_state::ao_registry::AoRegistry;
+    use uuid::Uuid;
+
+    #[test]
+    fn ruck_score() {
+        let registry = AoRegistry::default();
+        let (ao, formula) = ScoringFormula::defaults().remove(0);
+        let formula = ScoringFormulaDbData {
+            id: Uuid::nil(),
+            channel_id: ao.channel_id(&registry).to_string(),
+            formula,
+        };
+        let post = WorkoutScorePost {
+            date: NaiveDate::from_ymd_opt(2024, 7, 5).unwrap(),
+            values: HashMap::from([
+                (String::from("weight"), String::from("30")),
+                (String::from("miles"), String::from("4.5")),
+            ]),
+        };
+        let score = post.to_new_score(&formula, "U123").unwrap();
+        assert_eq!(score.score, 135.);
+        assert_eq!(score.channel_id, AO::RuckershipWest.channel_id(&registry));
+        assert_eq!(
+            inputs_text(&formula.formula, &score.inputs),
+            "*Ruck weight (lbs)*: 30\n*Miles*: 4.5"
+        );
+
+        let missing = WorkoutScorePost {
+            date: post.date,
+            values: HashMap::new(),
+        };
+        assert!(missing.to_new_score(&formula, "U123").is_err());
+    }
+}
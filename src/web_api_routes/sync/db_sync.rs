@@ -1,4 +1,6 @@
+use crate::app_state::ao_registry::AoRegistry;
 use crate::app_state::backblast_data::BackBlastData;
+use crate::app_state::MutableAppState;
 use crate::db::pax_parent_tree;
 use crate::db::pax_parent_tree::{F3Parent, ParentPaxRelation};
 use crate::db::queries::{all_back_blasts, processed_items};
@@ -31,20 +33,25 @@ pub struct SyncProdReq {
 
 pub async fn sync_prod_back_blasts(
     db_pool: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
     req: web::Query<SyncProdReq>,
 ) -> impl Responder {
-    match fetch_and_sync_back_blasts(&req.url, &db_pool).await {
+    match fetch_and_sync_back_blasts(&req.url, &db_pool, &app_state.get_ao_registry()).await {
         Ok(_) => HttpResponse::Ok().body("Success"),
         Err(err) => HttpResponse::BadRequest().body(err.to_string()),
     }
 }
 
-async fn fetch_and_sync_back_blasts(url: &str, db: &PgPool) -> Result<(), AppError> {
+async fn fetch_and_sync_back_blasts(
+    url: &str,
+    db: &PgPool,
+    registry: &AoRegistry,
+) -> Result<(), AppError> {
     let rdr = get_data_bytes_to_reader(url).await?;
     let results = extract_back_blasts(rdr)?;
     let to_delete = get_to_delete_back_blasts(db, &results).await?;
     println!("ids to delete: {:?}", to_delete);
-    save_back_blast::sync_multiple(db, &results).await?;
+    save_back_blast::sync_multiple(db, &results, registry).await?;
     Ok(())
 }
 
@@ -111,18 +118,23 @@ fn extract_pax_parents<R: Read>(mut rdr: Reader<R>) -> Result<Vec<ParentPaxRelat
 
 pub async fn sync_prod_pre_blasts(
     db: web::Data<PgPool>,
+    app_state: web::Data<MutableAppState>,
     req: web::Query<SyncProdReq>,
 ) -> impl Responder {
-    match fetch_and_sync_pre_blasts(&req.url, &db).await {
+    match fetch_and_sync_pre_blasts(&req.url, &db, &app_state.get_ao_registry()).await {
         Ok(_) => HttpResponse::Ok().body("Success"),
         Err(err) => HttpResponse::BadRequest().body(err.to_string()),
     }
 }
 
-async fn fetch_and_sync_pre_blasts(url: &str, db: &PgPool) -> Result<(), AppError> {
+async fn fetch_and_sync_pre_blasts(
+    url: &str,
+    db: &PgPool,
+    registry: &AoRegistry,
+) -> Result<(), AppError> {
     let rdr = get_data_bytes_to_reader(url).await?;
     let results = extract_pre_blasts(rdr)?;
-    save_pre_blast::save_from_csv_rows(db, &results).await?;
+    save_pre_blast::save_from_csv_rows(db, &results, registry).await?;
     Ok(())
 }
 
@@ -8,6 +8,7 @@ use crate::app_state::backblast_data::{BackBlastData, BackBlastType};
 use crate::app_state::MutableAppState;
 use crate::db::init::sync_ao_list;
 use crate::db::queries::users::get_db_users;
+use crate::db::save_scoring::seed_scoring_formulas;
 use crate::migrate_old::{save_old_back_blasts, save_old_q_line_up};
 use crate::shared::common_errors::AppError;
 use crate::shared::string_utils::string_split_hash;
@@ -63,9 +64,12 @@ pub async fn sync_data_to_state(
     }
     println!("set slack users and bots");
 
-    let public_channels = web_state.get_public_channels().await?;
     sync_ao_list(db_pool).await?;
+    app_state.load_ao_registry(db_pool).await?;
     println!("synced ao list");
+    seed_scoring_formulas(db_pool, &app_state.get_ao_registry()).await?;
+    println!("seeded scoring formulas");
+    let public_channels = web_state.get_public_channels().await?;
     // scoped to limit lock
     {
         let mut app = app_state.app.lock().expect("Could not lock app state");
@@ -1,15 +1,34 @@
 use crate::web_api_routes::back_blast_data::ao_back_blast_stats::get_back_blast_stats_by_ao;
 use crate::web_api_routes::back_blast_data::ao_monthly_leaderboard::ao_monthly_leaderboard_route;
-use crate::web_api_routes::back_blast_data::back_blast_single::get_single_back_blast_data;
+use crate::web_api_routes::back_blast_data::back_blast_single::{
+    get_single_back_blast_data, get_single_back_blast_routes,
+};
+use crate::web_api_routes::back_blast_data::black_diamond_ratings::{
+    black_diamond_leaderboard_route, black_diamond_q_leaderboard_route,
+    black_diamond_ratings_route, black_diamond_trend_graph_route,
+};
 use crate::web_api_routes::back_blast_data::csv_download_all::{
     back_blasts_csv_html, download_back_blasts_csv_route,
 };
+use crate::web_api_routes::back_blast_data::deleted_back_blasts::{
+    delete_back_blast_route, deleted_back_blasts_route, restore_back_blast_route,
+};
+use crate::web_api_routes::back_blast_data::exercise_stats::{
+    ao_exercise_frequency_route, backfill_exercises_route, exercise_history_route,
+    q_exercise_frequency_route,
+};
 use crate::web_api_routes::back_blast_data::pax_leaderboard_graph::pax_leaderboard_route;
 use crate::web_api_routes::back_blast_data::remind_missing_back_blasts::remind_missing_back_blasts;
 use crate::web_api_routes::back_blast_data::test_png::test_png_route;
+use crate::web_api_routes::back_blast_data::workout_scores::{
+    ao_workout_score_leaderboard_route, ao_workout_scores_route,
+};
 use crate::web_api_routes::back_blast_data::{
     get_all_back_blasts_route, get_missing_back_blasts, get_top_pax_data_route,
 };
+use crate::web_api_routes::revision_history::{
+    back_blast_history_route, back_blast_revision_diff_route, rollback_back_blast_route,
+};
 use crate::web_api_routes::sync::db_sync::sync_prod_back_blasts;
 use actix_web::{web, Scope};
 
@@ -18,6 +37,7 @@ pub fn service() -> Scope {
     web::scope("/back_blasts")
         .route("/all", web::get().to(get_all_back_blasts_route))
         .route("/missing", web::get().to(get_missing_back_blasts))
+        .route("/deleted", web::get().to(deleted_back_blasts_route))
         .route("/top-pax", web::get().to(get_top_pax_data_route))
         .route(
             "/remind-missing-bb",
@@ -31,6 +51,22 @@ pub fn service() -> Scope {
             "/pax-leaderboard-graph",
             web::get().to(pax_leaderboard_route),
         )
+        .route(
+            "/black-diamond/ratings",
+            web::get().to(black_diamond_ratings_route),
+        )
+        .route(
+            "/black-diamond/leaderboard",
+            web::get().to(black_diamond_leaderboard_route),
+        )
+        .route(
+            "/black-diamond/q-leaderboard",
+            web::get().to(black_diamond_q_leaderboard_route),
+        )
+        .route(
+            "/black-diamond/trend-graph",
+            web::get().to(black_diamond_trend_graph_route),
+        )
         .route("/test-png", web::get().to(test_png_route))
         .route("/download", web::get().to(back_blasts_csv_html))
         .route(
@@ -39,5 +75,50 @@ pub fn service() -> Scope {
         )
         .route("/sync-via-url", web::get().to(sync_prod_back_blasts))
         .route("/single/{id}", web::get().to(get_single_back_blast_data))
+        .route(
+            "/single/{id}/routes",
+            web::get().to(get_single_back_blast_routes),
+        )
+        .route(
+            "/single/{id}/history",
+            web::get().to(back_blast_history_route),
+        )
+        .route(
+            "/single/{id}/history/{revision_id}/diff",
+            web::get().to(back_blast_revision_diff_route),
+        )
+        .route(
+            "/single/{id}/delete",
+            web::post().to(delete_back_blast_route),
+        )
+        .route(
+            "/single/{id}/restore",
+            web::post().to(restore_back_blast_route),
+        )
+        .route(
+            "/single/{id}/rollback/{revision_id}",
+            web::post().to(rollback_back_blast_route),
+        )
+        .route(
+            "/exercises/ao/{ao_name}",
+            web::get().to(ao_exercise_frequency_route),
+        )
+        .route(
+            "/exercises/q/{name}",
+            web::get().to(q_exercise_frequency_route),
+        )
+        .route(
+            "/exercises/history/{exercise}",
+            web::get().to(exercise_history_route),
+        )
+        .route(
+            "/exercises/backfill",
+            web::post().to(backfill_exercises_route),
+        )
+        .route("/scores/{ao_name}", web::get().to(ao_workout_scores_route))
+        .route(
+            "/scores/{ao_name}/leaderboard",
+            web::get().to(ao_workout_score_leaderboard_route),
+        )
         .route("/{ao_name}", web::get().to(get_back_blast_stats_by_ao))
 }
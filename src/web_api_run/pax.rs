@@ -4,6 +4,9 @@ use crate::web_api_routes::pax_data::direct_message::{
 use crate::web_api_routes::pax_data::get_pax_tree::{
     download_pax_relationship_csv_route, get_pax_tree,
 };
+use crate::web_api_routes::pax_data::merge_pax::{
+    pax_aliases_route, pax_merge_route, preview_pax_merge_route, undo_pax_merge_route,
+};
 use crate::web_api_routes::pax_data::pax_leaderboards::post_pax_leaderboards;
 use crate::web_api_routes::pax_data::set_pax_parent::set_pax_parent_tree_route;
 use crate::web_api_routes::pax_data::stats::pax_stats_route;
@@ -41,4 +44,8 @@ pub fn service() -> Scope {
             web::get().to(download_pax_relationship_csv_route),
         )
         .route("/stats/{name}", web::get().to(pax_stats_route))
+        .route("/merge/preview", web::post().to(preview_pax_merge_route))
+        .route("/merge", web::post().to(pax_merge_route))
+        .route("/merge/{id}/undo", web::post().to(undo_pax_merge_route))
+        .route("/aliases", web::get().to(pax_aliases_route))
 }
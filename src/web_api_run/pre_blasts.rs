@@ -1,4 +1,7 @@
 use crate::web_api_routes::pre_blast_data;
+use crate::web_api_routes::revision_history::{
+    pre_blast_history_route, pre_blast_revision_diff_route, rollback_pre_blast_route,
+};
 use crate::web_api_routes::sync::db_sync::sync_prod_pre_blasts;
 use actix_web::{web, Scope};
 
@@ -9,4 +12,24 @@ pub fn service() -> Scope {
             web::get().to(pre_blast_data::download_pre_blast_data_csv),
         )
         .route("/sync-db-via-url", web::get().to(sync_prod_pre_blasts))
+        .route(
+            "/post-scheduled",
+            web::get().to(pre_blast_data::scheduled_pre_blasts::post_scheduled_pre_blasts_route),
+        )
+        .route(
+            "/single/{id}/history",
+            web::get().to(pre_blast_history_route),
+        )
+        .route(
+            "/single/{id}/history/{revision_id}/diff",
+            web::get().to(pre_blast_revision_diff_route),
+        )
+        .route(
+            "/single/{id}/rollback/{revision_id}",
+            web::post().to(rollback_pre_blast_route),
+        )
+        .route(
+            "/{id}/hc",
+            web::get().to(pre_blast_data::pre_blast_hc_route),
+        )
 }
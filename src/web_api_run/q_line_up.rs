@@ -1,4 +1,8 @@
 use crate::web_api_routes::q_line_up::q_line_up_route;
+use crate::web_api_routes::q_line_up::q_recurrences::materialize_q_recurrences_route;
+use crate::web_api_routes::q_line_up::q_reminders::remind_qs_route;
+use crate::web_api_routes::q_line_up::q_swaps::q_swaps_route;
+use crate::web_api_routes::q_line_up::q_vacancies::post_q_vacancies_route;
 use crate::web_api_routes::sync::db_sync::sync_q_line_up_db;
 use crate::web_api_routes::sync::download_q_line_up_csv;
 use actix_web::{web, Scope};
@@ -9,4 +13,11 @@ pub fn service() -> Scope {
         .route("/list", web::get().to(q_line_up_route))
         .route("/download-csv", web::get().to(download_q_line_up_csv))
         .route("/sync-items-via-url", web::get().to(sync_q_line_up_db))
+        .route("/remind-qs", web::get().to(remind_qs_route))
+        .route("/post-vacancies", web::get().to(post_q_vacancies_route))
+        .route("/swaps", web::get().to(q_swaps_route))
+        .route(
+            "/materialize-recurrences",
+            web::get().to(materialize_q_recurrences_route),
+        )
 }
@@ -1,6 +1,20 @@
 use crate::web_api_routes::back_blast_data::yearly_stats::get_yearly_stats_route;
 use crate::web_api_routes::files::get_files_test;
 use crate::web_api_routes::region_data::ao_meta_data::ao_list_meta_data_route;
+use crate::web_api_routes::region_data::ao_registry::{
+    create_ao_route, get_ao_registry_route, get_single_ao_route, retire_ao_route, update_ao_route,
+};
+use crate::web_api_routes::region_data::calendar_feed::calendar_feed_route;
+use crate::web_api_routes::region_data::closures::{delete_closure_route, get_closures_route};
+use crate::web_api_routes::region_data::exicon::{get_exicon_route, import_exicon_route};
+use crate::web_api_routes::region_data::hc_emojis::{get_hc_emojis_route, update_hc_emojis_route};
+use crate::web_api_routes::region_data::q_rules::{get_q_rules_route, update_q_rules_route};
+use crate::web_api_routes::region_data::roles::{
+    add_role_route, delete_role_route, get_roles_route,
+};
+use crate::web_api_routes::region_data::scoring::{
+    get_scoring_formula_route, update_scoring_formula_route,
+};
 use actix_web::{web, Scope};
 
 /// service and routes related to region
@@ -9,4 +23,32 @@ pub fn service() -> Scope {
         .route("/workouts", web::get().to(ao_list_meta_data_route))
         .route("/stats", web::get().to(get_yearly_stats_route))
         .route("/test-files", web::get().to(get_files_test))
+        .route("/aos", web::get().to(get_ao_registry_route))
+        .route("/aos", web::post().to(create_ao_route))
+        .route("/aos/{name}", web::get().to(get_single_ao_route))
+        .route("/aos/{name}", web::put().to(update_ao_route))
+        .route("/aos/{name}", web::delete().to(retire_ao_route))
+        .route(
+            "/aos/{name}/scoring",
+            web::get().to(get_scoring_formula_route),
+        )
+        .route(
+            "/aos/{name}/scoring",
+            web::put().to(update_scoring_formula_route),
+        )
+        .route("/aos/{name}/hc-emojis", web::get().to(get_hc_emojis_route))
+        .route(
+            "/aos/{name}/hc-emojis",
+            web::put().to(update_hc_emojis_route),
+        )
+        .route("/aos/{name}/q-rules", web::get().to(get_q_rules_route))
+        .route("/aos/{name}/q-rules", web::put().to(update_q_rules_route))
+        .route("/calendar.ics", web::get().to(calendar_feed_route))
+        .route("/closures", web::get().to(get_closures_route))
+        .route("/closures/{id}", web::delete().to(delete_closure_route))
+        .route("/exicon", web::get().to(get_exicon_route))
+        .route("/exicon/import", web::post().to(import_exicon_route))
+        .route("/roles", web::get().to(get_roles_route))
+        .route("/roles", web::post().to(add_role_route))
+        .route("/roles/{id}", web::delete().to(delete_role_route))
 }
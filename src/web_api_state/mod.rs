@@ -3,7 +3,9 @@ use crate::oauth_client::get_oauth_client;
 use crate::shared::common_errors::AppError;
 use crate::slack_api::block_kit::BlockBuilder;
 use crate::slack_api::channels::history::request::ChannelHistoryRequest;
-use crate::slack_api::channels::history::response::ChannelsHistoryResponse;
+use crate::slack_api::channels::history::response::{
+    ChannelMessageResponse, ChannelsHistoryResponse,
+};
 use crate::slack_api::channels::invite::request::InviteToConvoRequest;
 use crate::slack_api::channels::invite::response::InviteToConvoResponse;
 use crate::slack_api::channels::kick::request::KickFromChannelRequest;
@@ -16,11 +18,15 @@ use crate::slack_api::channels::open::request::OpenConversationRequest;
 use crate::slack_api::channels::open::response::OpenConversationResponse;
 use crate::slack_api::channels::public_channels::PublicChannels;
 use crate::slack_api::channels::types::ChannelTypes;
+use crate::slack_api::chat::post_ephemeral::request::PostEphemeralRequest;
+use crate::slack_api::chat::post_ephemeral::response::PostEphemeralResponse;
 use crate::slack_api::chat::post_message::request::PostMessageRequest;
 use crate::slack_api::chat::post_message::response::PostMessageResponse;
 use crate::slack_api::chat::update_message::request::UpdateMessageRequest;
 use crate::slack_api::chat::update_message::response::UpdateMessageResponse;
 use crate::slack_api::files::complete_upload_url_external;
+use crate::slack_api::files::files_info::request::FilesInfoRequest;
+use crate::slack_api::files::files_info::response::{FileInfo, FilesInfoResponse};
 use crate::slack_api::files::files_list::request::FilesListRequest;
 use crate::slack_api::files::files_list::response::{FilesListItem, FilesListResponse};
 use crate::slack_api::files::get_upload_url_external::request::GetUploadUrlExternalRequest;
@@ -32,6 +38,7 @@ use crate::slack_api::users::users_list::response::UsersListResponse;
 use crate::slack_api::views::request::ViewsOpenRequest;
 use crate::slack_api::views::response::ViewsOpenResponse;
 use crate::users::f3_user::F3User;
+use crate::web_api_routes::interactive_events::interaction_payload::InteractionMessage;
 use http::header::CONTENT_TYPE;
 use oauth2::basic::BasicClient;
 use reqwest::header::HeaderMap;
@@ -145,6 +152,20 @@ impl MutableWebState {
         }
     }
 
+    /// post message in channel only visible to user
+    pub async fn post_ephemeral(&self, request: PostEphemeralRequest) -> Result<(), AppError> {
+        let url = request.get_plain_url_request(&self.base_api_url);
+        let body = serde_json::to_vec(&request)?;
+        let response = self.make_post_request(url, body).await;
+        let bytes = response.bytes().await?;
+        let response: PostEphemeralResponse = serde_json::from_slice(&bytes)?;
+        if let Some(err) = response.error {
+            Err(AppError::General(err))
+        } else {
+            Ok(())
+        }
+    }
+
     pub async fn send_direct_message(
         &self,
         user: &str,
@@ -219,6 +240,43 @@ impl MutableWebState {
         }
     }
 
+    /// get info of single file
+    pub async fn get_file_info(&self, file_id: &str) -> Result<FileInfo, AppError> {
+        let request = FilesInfoRequest::new(file_id);
+        let url = request.get_url_request(&self.base_api_url);
+        let response = self.make_get_url_request(url).await;
+        let bytes = response.bytes().await?;
+        let response: FilesInfoResponse = serde_json::from_slice(&bytes)?;
+        match response {
+            FilesInfoResponse {
+                error: Some(err), ..
+            } => Err(AppError::General(err)),
+            FilesInfoResponse {
+                file: Some(file), ..
+            } => Ok(file),
+            _ => Err(AppError::General(
+                "Missing data from file info response".to_string(),
+            )),
+        }
+    }
+
+    /// download contents of private file (ie. uploaded through modal file input)
+    pub async fn download_file(&self, file: &FileInfo) -> Result<Vec<u8>, AppError> {
+        let url = file
+            .url_private_download
+            .as_ref()
+            .ok_or_else(|| AppError::from("File has no download url"))?;
+        let url = url::Url::parse(url)?;
+        let response = self.make_get_url_request(url).await;
+        if !response.status().is_success() {
+            return Err(AppError::General(format!(
+                "Error downloading file: {}",
+                response.status()
+            )));
+        }
+        Ok(response.bytes().await?.to_vec())
+    }
+
     pub async fn upload_file(&self, request: FileUpload) -> Result<(), AppError> {
         let step_1 = GetUploadUrlExternalRequest::new(&request.filename, request.file.len());
         let url = step_1.get_url_request(&self.base_api_url);
@@ -327,6 +385,26 @@ impl MutableWebState {
         }
     }
 
+    /// bot message posted in channel at ts, with its blocks
+    pub async fn get_message(
+        &self,
+        channel_id: &str,
+        ts: &str,
+    ) -> Result<Option<InteractionMessage>, AppError> {
+        let request = ChannelHistoryRequest::new(channel_id).with_message(ts);
+        let url = request.get_url_request(&self.base_api_url);
+        let response = self.make_get_url_request(url).await;
+        let bytes = response.bytes().await?;
+        let response: ChannelMessageResponse = serde_json::from_slice(&bytes)?;
+        if let Some(err) = response.error {
+            Err(AppError::General(err))
+        } else {
+            Ok(response
+                .messages
+                .and_then(|messages| messages.into_iter().find(|message| message.ts == ts)))
+        }
+    }
+
     fn get_auth_header(&self) -> HeaderMap {
         let mut header_map = HeaderMap::new();
         let bearer = format!("Bearer {}", self.token);